@@ -1,5 +1,6 @@
 use num_bigint::{BigInt,Sign};
 
+#[allow(unused_imports)]
 use crate::*;
 use crate::consts::*;
 use crate::randop::*;
@@ -34,7 +35,7 @@ impl std::default::Default for BnGf2m {
 impl BnGf2m {
 
 	fn _check_self(&self) {
-		if self.data.len() == 0 {
+		if self.data.is_empty() {
 			panic!("self len 0");
 		}
 	}
@@ -45,12 +46,12 @@ impl BnGf2m {
 
 	pub fn one() -> BnGf2m {
 		let v :Vec<u8> = vec![1];
-		return BnGf2m::new_from_be(&v);
+		BnGf2m::new_from_be(&v)
 	}
 
 	pub fn zero() -> BnGf2m {
 		let v :Vec<u8> = vec![0];
-		return BnGf2m::new_from_be(&v);
+		BnGf2m::new_from_be(&v)
 	}
 
 	pub fn new_from_le(varr :&[u8]) -> BnGf2m {
@@ -75,7 +76,7 @@ impl BnGf2m {
 			}
 			rdata.push(curval);
 		}
-		if rdata.len() == 0 {
+		if rdata.is_empty() {
 			rdata.push(0);
 		}
 
@@ -95,14 +96,14 @@ impl BnGf2m {
 		if bmax < 0 {
 			retv = true;
 		}
-		return retv;
+		retv
 
 	}
 
 	pub fn new_from_bigint(bn :&BigInt) -> BnGf2m {
 		let varr :Vec<u8>;
 		(_, varr) = bn.to_bytes_be();
-		return BnGf2m::new_from_be(&varr);
+		BnGf2m::new_from_be(&varr)
 	}
 
 	pub fn new_from_be(varr :&[u8]) -> BnGf2m {
@@ -113,9 +114,10 @@ impl BnGf2m {
 		if (varr.len() % BVALUE_SIZE) != 0 {
 			leftlen = varr.len() % BVALUE_SIZE;
 			curval = 0;
-			for i in 0..leftlen {
-				curval |= (varr[i] as BValue) << ((leftlen - i - 1) * 8);
+			for (i,b) in varr.iter().enumerate().take(leftlen) {
+				curval |= (*b as BValue) << ((leftlen - i - 1) * 8);
 			}
+
 			rdata.insert(0,curval);
 			passlen += leftlen;
 		}
@@ -130,7 +132,7 @@ impl BnGf2m {
 			passlen += BVALUE_SIZE;
 		}
 
-		if rdata.len() == 0 {
+		if rdata.is_empty() {
 			rdata.push(0);
 		}
 
@@ -158,7 +160,7 @@ impl BnGf2m {
 	}
 
 	pub fn sub_op(&self,other :&BnGf2m) -> BnGf2m {
-		return self.add_op(other);
+		self.add_op(other)
 	}
 
 	pub fn add_op(&self, other :&BnGf2m) -> BnGf2m {
@@ -185,7 +187,7 @@ impl BnGf2m {
 				bval = 0;
 			}
 
-			aval = aval ^ bval;
+			aval ^= bval;
 			retv.push(aval);
 		}
 
@@ -204,9 +206,7 @@ impl BnGf2m {
 		a2 = a1 << 1;
 		a4 = a2 << 1;
 		a8 = a4 << 1;
-		for _ in 0..16 {
-			tab.push(0);
-		}
+		tab.resize(16,0);
 
 		tab[0] = 0;
 		tab[1] = a1;
@@ -335,9 +335,7 @@ impl BnGf2m {
 		let mut retv :Vec<BValue> = Vec::new();
 		let mut resv :Vec<BValue>;
 		//ecsimple_log_trace!("x0 0x{:x} x1 0x{:x} y0 0x{:x} y1 0x{:x}",x0,x1,y0,y1);
-		for _ in 0..4 {
-			retv.push(0);
-		}
+		retv.resize(4,0);
 		resv = self._mul_1x1(x1,y1);
 		retv[2] = resv[0];
 		retv[3] = resv[1];
@@ -360,7 +358,7 @@ impl BnGf2m {
 
 
 	pub fn mul_op(&self, other :&BnGf2m) -> BnGf2m {
-		let maxlen :usize;
+		
 		let alen :usize = self.data.len();
 		let olen :usize = other.data.len();
 		let r8 :Vec<u8> = vec![0];
@@ -371,10 +369,8 @@ impl BnGf2m {
 		let (mut i, mut j) : (usize,usize);
 		self._check_other(other);
 
-		maxlen = alen + olen + 4;
-		for _ in 0..maxlen {
-			retv.push(0);
-		}
+		let maxlen :usize = alen + olen + 4;
+		retv.resize(maxlen,0);
 
 		i = 0;
 		while i < alen {
@@ -408,10 +404,9 @@ impl BnGf2m {
 	}
 
 	fn _check_mod_val(&self) {
-		if self.polyarr.len() == 0 || self.polyarr[self.polyarr.len() - 1] != 0 {
+		if self.polyarr.is_empty() || self.polyarr[self.polyarr.len() - 1] != 0 {
 			panic!("0x{:x} not odd for mod", self);
 		}
-		return;
 	}
 
 	fn _extend_poly(&mut self) {
@@ -431,7 +426,6 @@ impl BnGf2m {
 			}
 			jdx -= 1;
 		}
-		return
 	}
 
 	fn _fixup_length(&mut self) {
@@ -454,25 +448,25 @@ impl BnGf2m {
 				self.data.push(rdata[0]);
 			}			
 		}
-		return;
 	}
 
 	pub fn extend_poly(&mut self) {
-		if self.polyarr.len() > 0 {
+		if !self.polyarr.is_empty() {
 			return;
 		}
-		return self._extend_poly();
+		self._extend_poly()
 	}
 
+	#[allow(clippy::while_immutable_condition)]
 	pub fn mod_op(&self,other :&BnGf2m) -> BnGf2m {
 		let mut retv :BnGf2m = self.clone();
-		let modptr :&BnGf2m;
-		modptr = other;
+		
+		let modptr :&BnGf2m = other;
 		modptr._check_mod_val();
 
 		//ecsimple_debug_buffer_trace!(modptr.polyarr.as_ptr(), modptr.polyarr.len() * std::mem::size_of::<i32>(),"polyarr {}",modptr.polyarr.len());
 
-		let dn :usize = ((modptr.polyarr[0] as usize) / BVALUE_BITS ) as usize;
+		let dn :usize = (modptr.polyarr[0] as usize) / BVALUE_BITS ;
 		let mut jdx :usize = retv.data.len() - 1;
 		let mut n :i32;
 		let mut d0 :i32;
@@ -493,12 +487,12 @@ impl BnGf2m {
 				//ecsimple_log_trace!("p[0] {} - p[{kidx}] {} = {}", modptr.polyarr[0],modptr.polyarr[kidx],n);
 				d0 = n % (BVALUE_BITS as i32);
 				d1 = (BVALUE_BITS as i32) - d0;				
-				n = n / (BVALUE_BITS as i32);
+				n /= BVALUE_BITS as i32 ;
 				//ecsimple_log_trace!("z[{}] (0x{:x}) ^ (0x{:x} >> {}) = 0x{:x}", jdx-(n as usize),retv.data[jdx-(n as usize)],zz,d0,retv.data[jdx-(n as usize)] ^ (zz >> d0));
-				retv.data[(jdx - (n as usize))] ^= ( zz >> d0) as BValue;
+				retv.data[jdx - (n as usize)] ^= ( zz >> d0) as BValue;
 				if d0 != 0 {
 					//ecsimple_log_trace!("z[{}] (0x{:x}) ^ (0x{:x} << {}) = 0x{:x}", jdx-(n as usize)-1,retv.data[jdx-(n as usize)-1],zz,d1,retv.data[jdx-(n as usize) - 1] ^ (zz << d1));
-					retv.data[(jdx - (n as usize) - 1)] ^=  zz << d1;
+					retv.data[jdx - (n as usize) - 1] ^=  zz << d1;
 				}
 				kidx += 1;
 				if kidx < modptr.polyarr.len() {
@@ -539,15 +533,15 @@ impl BnGf2m {
 
 			kidx = 1;
 			while kidx < modptr.polyarr.len() && modptr.polyarr[kidx] != 0 {
-				let tmp_ulong :BValue;
+				
 
 				n = modptr.polyarr[kidx] / (BVALUE_BITS as i32);
 				d0 = modptr.polyarr[kidx] % (BVALUE_BITS  as i32);
 				d1 = (BVALUE_BITS as i32)- d0;
 				//ecsimple_log_trace!("p[{}] 0x{:x} n {} d0 {} d1 {}",kidx,modptr.polyarr[kidx],n,d0,d1);
-				//ecsimple_log_trace!("z[{}] 0x{:x} ^ (zz 0x{:x} << d0 {}) = 0x{:x}", n,retv.data[(n as usize)],zz,d0,retv.data[(n as usize)] ^ (zz << d0));
-				retv.data[(n as usize)] ^= zz << d0;
-				tmp_ulong = zz >> d1;
+				//ecsimple_log_trace!("z[{}] 0x{:x} ^ (zz 0x{:x} << d0 {}) = 0x{:x}", n,retv.data[n as usize],zz,d0,retv.data[(n as usize)] ^ (zz << d0));
+				retv.data[n as usize] ^= zz << d0;
+				let tmp_ulong :BValue = zz >> d1;
 				if d0 != 0 && tmp_ulong != 0 {
 					//ecsimple_log_trace!("z[{}] 0x{:x} ^ tmp_ulong 0x{:x} = 0x{:x}", n+1,retv.data[(n as usize)+1],tmp_ulong,retv.data[(n as usize)+1]^tmp_ulong);
 					retv.data[(n as usize)+1] ^= tmp_ulong;
@@ -593,23 +587,21 @@ impl BnGf2m {
 			idx -= 1;
 		}
 
-		return (findidx,bitidx);
+		(findidx,bitidx)
 	}
 
 	fn _poly_to_bngf2m(&self,poly :&[i32]) -> BnGf2m {
-		if poly.len() == 0 {
+		if poly.is_empty() {
 			let r8 :Vec<u8> = vec![0];
 			return BnGf2m::new_from_be(&r8);
 		}
 		let mut rdata :Vec<BValue> = Vec::new();
 		let maxbytes : usize = ((poly[0]  as usize)/ BVALUE_BITS ) + 1;
-		for _ in 0..maxbytes {
-			rdata.push(0);
-		}
+		rdata.resize(maxbytes,0);
 
-		for k in 0..poly.len() {
-			let bs = (poly[k] as usize) / BVALUE_BITS  ;
-			let bb = (poly[k] as usize)% BVALUE_BITS;
+		for k in poly.iter() {
+			let bs = (*k as usize) / BVALUE_BITS  ;
+			let bb = (*k as usize)% BVALUE_BITS;
 			rdata[bs] |= 1 << bb;
 		}
 
@@ -631,9 +623,7 @@ impl BnGf2m {
 		let maxbytes :i32 = shnum / (BVALUE_BITS as i32) + mbs + 2;
 		let addi :i32 = shnum / (BVALUE_BITS as i32);
 		let addb :i32 = shnum % (BVALUE_BITS as i32);
-		for _ in 0..maxbytes {
-			retvdata.push(0);
-		}
+		retvdata.resize(maxbytes as usize,0);
 		let mut i :usize = self.data.len() - 1;
 		//ecsimple_log_trace!("retvdata len {}" ,retvdata.len());
 
@@ -653,7 +643,7 @@ impl BnGf2m {
 		retv.data = retvdata;
 		retv._fixup_length();
 		retv._extend_poly();
-		return retv; 
+		retv
 	}
 
 	pub fn right_shift(&self,shnum :i32) -> BnGf2m {
@@ -667,9 +657,7 @@ impl BnGf2m {
 		let addi :i32 = shnum / (BVALUE_BITS as i32);
 		let addb :i32 = shnum % (BVALUE_BITS as i32);
 		let maxbytes :i32 = mbs + 2;
-		for _ in 0..maxbytes {
-			retvdata.push(0);
-		}
+		retvdata.resize(maxbytes as usize,0);
 		//ecsimple_log_trace!("addi {} addb {}", addi, addb);
 
 		let mut kidx :usize = self.data.len() - 1;
@@ -681,13 +669,13 @@ impl BnGf2m {
 			if addb > 0  {
 				if kidx > addi as usize {
 					//ecsimple_log_trace!("kidx {} [{}]", kidx,kidx - addi as usize);
-					retvdata[(kidx - addi as usize)] |= self.data[kidx] >> addb;
-					retvdata[(kidx - addi as usize - 1)] |= self.data[kidx] << ( BVALUE_BITS - addb as usize);
+					retvdata[kidx - addi as usize] |= self.data[kidx] >> addb;
+					retvdata[kidx - addi as usize - 1] |= self.data[kidx] << ( BVALUE_BITS - addb as usize);
 				} else {
 					retvdata[0] |= self.data[kidx] >> addb;
 				}
 			} else {
-				retvdata[(kidx- addi as usize)] |= self.data[kidx];
+				retvdata[kidx- addi as usize] |= self.data[kidx];
 			}
 			if kidx == 0 {
 				break;
@@ -698,7 +686,7 @@ impl BnGf2m {
 		retv.data = retvdata;
 		retv._fixup_length();
 		retv._extend_poly();
-		return retv;
+		retv
 	}
 
 	pub fn div_op(&self,other :&BnGf2m) -> BnGf2m {
@@ -720,7 +708,7 @@ impl BnGf2m {
 			d1 = d;
 		}
 
-		return self._poly_to_bngf2m(&polyarr);
+		self._poly_to_bngf2m(&polyarr)
 
 	}
 
@@ -728,15 +716,15 @@ impl BnGf2m {
 		if self.max_bits() == 1 {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	pub fn is_odd(&self) -> bool {
 		let mut retv :bool = false;
-		if self.data.len() > 0 && (self.data[0] & 0x1) != 0 {
+		if !self.data.is_empty() && (self.data[0] & 0x1) != 0 {
 			retv = true;
 		}
-		return retv;
+		retv
 	}
 
 
@@ -756,24 +744,23 @@ impl BnGf2m {
 					retv = false;
 					break;
 				}
-			} else if jdx < v.data.len() {
-				if v.data[jdx] != 0 {
+			} else if jdx < v.data.len()
+				&& v.data[jdx] != 0 {
 					retv = false;
 					break;
 				}
-			}
 			idx += 1;
 			jdx += 1;
 		}
 
-		return retv;
+		retv
 	}
 
 	pub fn max_bits(&self) -> i32 {
-		if self.polyarr.len() == 0 {
+		if self.polyarr.is_empty() {
 			return 0;
 		}
-		return (self.polyarr[0] + 1) as i32;
+		self.polyarr[0] + 1 
 	}
 
 	pub fn inv_op(&self, p :&BnGf2m) -> Result<BnGf2m,Box<dyn Error>> {
@@ -830,14 +817,14 @@ impl BnGf2m {
 			//ecsimple_log_trace!("u 0x{:X} b 0x{:X}", u,b);
 		}
 
-		return Ok(b);
+		Ok(b)
 	}
 
 	///  to find retv ^ 2 + retv = self % pnum values
 	/// 
 	/// 
 	pub fn sqrt_quad_op(&self,pnum :&BnGf2m) -> Result<BnGf2m,Box<dyn Error>> {
-		if pnum.polyarr.len() == 0 {
+		if pnum.polyarr.is_empty() {
 			ecsimple_new_error!{BnGf2mError,"pnum zero"}
 		}
 
@@ -858,9 +845,9 @@ impl BnGf2m {
 			z = a.clone();
 			jdx = 1;
 			while jdx <= ((pnum.polyarr[0]-1) >> 1) {
-				z = &(&z * &z) % &pnum;
+				z = &(&z * &z) % pnum;
 				//ecsimple_log_trace!("[{}] z 0x{:X}",jdx,z);
-				z = &(&z * &z) % &pnum;
+				z = &(&z * &z) % pnum;
 				//ecsimple_log_trace!("[{}] z 0x{:X}",jdx,z);
 				z = z.add_op(&a);
 				//ecsimple_log_trace!("[{}] z 0x{:X} a 0x{:X}",jdx,z,a);
@@ -871,17 +858,17 @@ impl BnGf2m {
 			while count < MAX_ITERATIONS {
 				let rhob :BigInt = ecsimple_rand_bits(pnum.polyarr[0] as u64,-1,0);
 				let mut rho :BnGf2m = BnGf2m::new_from_bigint(&rhob);
-				rho = rho.mod_op(&pnum);
+				rho = rho.mod_op(pnum);
 				//ecsimple_log_trace!("rho 0x{:X}",rho);
 				z = BnGf2m::zero();
 				w = rho.clone();
 				jdx = 1;
 				while jdx <= (pnum.polyarr[0] - 1) {
-					z = &(&z * &z) % &pnum;
+					z = &(&z * &z) % pnum;
 					//ecsimple_log_trace!("[{}] z 0x{:X}",jdx,z);
-					w2 = &(&w * &w) % &pnum;
+					w2 = &(&w * &w) % pnum;
 					//ecsimple_log_trace!("[{}] w2 0x{:X} w 0x{:X}",jdx,w2,w);
-					tmp = &(&w2 * &a) % &pnum;
+					tmp = &(&w2 * &a) % pnum;
 					//ecsimple_log_trace!("[{}] tmp 0x{:X} = ( w2 0x{:X} * a 0x{:X} )",jdx,tmp,w2,a);
 					z = z.add_op(&tmp);
 					//ecsimple_log_trace!("[{}] z 0x{:X}",jdx,z);
@@ -901,7 +888,7 @@ impl BnGf2m {
 			}
 		}
 
-		w = &(&z * &z) % &pnum;
+		w = &(&z * &z) % pnum;
 		//ecsimple_log_trace!("w 0x{:X} z 0x{:X}",w,z);
 		w = w.add_op(&z);
 		//ecsimple_log_trace!("w 0x{:X} z 0x{:X} a 0x{:X}",w,z,a);
@@ -909,7 +896,7 @@ impl BnGf2m {
 			ecsimple_new_error!{BnGf2mError,"can not resolv 0x{:X} pnum 0x{:X}",self,pnum}
 		}
 		r = z.clone();
-		return Ok(r);
+		Ok(r)
 	}
 
 }
@@ -960,28 +947,28 @@ impl core::fmt::UpperHex for BnGf2m {
 impl Add for BnGf2m {
 	type Output = BnGf2m;
 	fn add (self,rhs :BnGf2m) -> Self::Output {
-		return( &self).add_op(&rhs);
+		self.add_op(&rhs)
 	}
 }
 
 impl Add for &BnGf2m {
 	type Output = BnGf2m;
 	fn add(self, rhs :&BnGf2m) -> Self::Output {
-		return self.add_op(rhs);
+		self.add_op(rhs)
 	}
 }
 
 impl Sub for BnGf2m {
 	type Output = BnGf2m;
 	fn sub (self, rhs :BnGf2m) -> Self::Output {
-		return (&self).sub_op(&rhs);
+		self.sub_op(&rhs)
 	}
 }
 
 impl Sub for &BnGf2m {
 	type Output = BnGf2m;
 	fn sub (self, rhs :&BnGf2m) -> Self::Output {
-		return (self).sub_op(rhs);
+		(self).sub_op(rhs)
 	}
 }
 
@@ -989,14 +976,14 @@ impl Sub for &BnGf2m {
 impl Mul for BnGf2m {
 	type Output = BnGf2m;
 	fn mul(self, rhs :Self) -> Self::Output {
-		return (&self).mul_op(&rhs);
+		self.mul_op(&rhs)
 	}
 }
 
 impl Mul for &BnGf2m {
 	type Output = BnGf2m;
 	fn mul(self, rhs :&BnGf2m) -> Self::Output {
-		return (self).mul_op(rhs);
+		(self).mul_op(rhs)
 	}
 }
 
@@ -1004,28 +991,28 @@ impl Mul for &BnGf2m {
 impl Div for BnGf2m {
 	type Output = BnGf2m;
 	fn div(self, rhs :Self) -> Self::Output {
-		return (&self).div_op(&rhs);
+		self.div_op(&rhs)
 	}
 }
 
 impl Div for &BnGf2m {
 	type Output = BnGf2m;
 	fn div(self, rhs :&BnGf2m) -> Self::Output {
-		return (self).div_op(rhs);
+		(self).div_op(rhs)
 	}
 }
 
 impl Rem for BnGf2m {
 	type Output = BnGf2m;
 	fn rem(self, rhs :Self) -> Self::Output {
-		return (&self).mod_op(&rhs);
+		self.mod_op(&rhs)
 	}
 }
 
 impl Rem for &BnGf2m {
 	type Output = BnGf2m;
 	fn rem(self, rhs :&BnGf2m) -> Self::Output {
-		return (self).mod_op(rhs);
+		(self).mod_op(rhs)
 	}
 }
 
@@ -1033,28 +1020,28 @@ impl Rem for &BnGf2m {
 impl Shl<i32> for BnGf2m {
 	type Output = BnGf2m;
 	fn shl(self, rhs :i32) -> Self::Output {
-		return (&self).left_shift(rhs);
+		self.left_shift(rhs)
 	}
 }
 
 impl Shl<i32> for &BnGf2m {
 	type Output = BnGf2m;
 	fn shl(self, rhs :i32) -> Self::Output {
-		return (self).left_shift(rhs);
+		(self).left_shift(rhs)
 	}
 }
 
 impl Shr<i32> for BnGf2m {
 	type Output = BnGf2m;
 	fn shr(self, rhs :i32) -> Self::Output {
-		return (&self).right_shift(rhs);
+		self.right_shift(rhs)
 	}
 }
 
 impl Shr<i32> for &BnGf2m {
 	type Output = BnGf2m;
 	fn shr(self, rhs :i32) -> Self::Output {
-		return (self).right_shift(rhs);
+		(self).right_shift(rhs)
 	}
 }
 
@@ -0,0 +1,390 @@
+#[allow(unused_imports)]
+use crate::logger::*;
+use std::error::Error;
+
+ecsimple_error_class!{ECCborError}
+
+/*nested arrays maps and tags deeper than this are rejected*/
+const CBOR_MAX_DEPTH :usize = 64;
+
+const CBOR_MAJOR_UNSIGNED :u8 = 0;
+const CBOR_MAJOR_NEGATIVE :u8 = 1;
+const CBOR_MAJOR_BYTES :u8 = 2;
+const CBOR_MAJOR_TEXT :u8 = 3;
+const CBOR_MAJOR_ARRAY :u8 = 4;
+const CBOR_MAJOR_MAP :u8 = 5;
+const CBOR_MAJOR_TAG :u8 = 6;
+const CBOR_MAJOR_SIMPLE :u8 = 7;
+
+const CBOR_SIMPLE_FALSE :u8 = 20;
+const CBOR_SIMPLE_TRUE :u8 = 21;
+const CBOR_SIMPLE_NULL :u8 = 22;
+const CBOR_SIMPLE_UNDEFINED :u8 = 23;
+
+/*
+	the subset COSE needs : no floats and no indefinite length ,
+	integer covers the whole major type 0 and 1 range
+*/
+#[derive(Clone,Debug,PartialEq)]
+pub enum CborValue {
+	Integer(i128),
+	Bytes(Vec<u8>),
+	Text(String),
+	Array(Vec<CborValue>),
+	Map(Vec<(CborValue,CborValue)>),
+	Tag(u64,Box<CborValue>),
+	Bool(bool),
+	Null,
+	Undefined,
+}
+
+impl CborValue {
+	pub fn as_integer(&self) -> Option<i128> {
+		match self {
+			CborValue::Integer(v) => Some(*v),
+			_ => None,
+		}
+	}
+
+	pub fn as_bytes(&self) -> Option<&Vec<u8>> {
+		match self {
+			CborValue::Bytes(v) => Some(v),
+			_ => None,
+		}
+	}
+
+	pub fn as_text(&self) -> Option<&str> {
+		match self {
+			CborValue::Text(v) => Some(v),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&Vec<CborValue>> {
+		match self {
+			CborValue::Array(v) => Some(v),
+			_ => None,
+		}
+	}
+
+	pub fn as_map(&self) -> Option<&Vec<(CborValue,CborValue)>> {
+		match self {
+			CborValue::Map(v) => Some(v),
+			_ => None,
+		}
+	}
+
+	/*the value of key in a map , None when not map or not found*/
+	pub fn map_get(&self,key :&CborValue) -> Option<&CborValue> {
+		match self {
+			CborValue::Map(v) => {
+				for (k,kv) in v.iter() {
+					if k == key {
+						return Some(kv);
+					}
+				}
+				None
+			},
+			_ => None,
+		}
+	}
+
+	pub fn map_get_int(&self,key :i128) -> Option<&CborValue> {
+		self.map_get(&CborValue::Integer(key))
+	}
+}
+
+fn cbor_encode_head(major :u8,val :u64,retv :&mut Vec<u8>) {
+	let mt :u8 = major << 5;
+	if val < 24 {
+		retv.push(mt | (val as u8));
+	} else if val <= 0xff {
+		retv.push(mt | 24);
+		retv.push(val as u8);
+	} else if val <= 0xffff {
+		retv.push(mt | 25);
+		retv.extend_from_slice(&(val as u16).to_be_bytes());
+	} else if val <= 0xffffffff {
+		retv.push(mt | 26);
+		retv.extend_from_slice(&(val as u32).to_be_bytes());
+	} else {
+		retv.push(mt | 27);
+		retv.extend_from_slice(&val.to_be_bytes());
+	}
+}
+
+fn cbor_encode_inner(val :&CborValue,retv :&mut Vec<u8>) -> Result<(),Box<dyn Error>> {
+	match val {
+		CborValue::Integer(v) => {
+			if *v >= 0 {
+				if *v > (u64::MAX as i128) {
+					ecsimple_new_error!{ECCborError,"integer {} too large",v}
+				}
+				cbor_encode_head(CBOR_MAJOR_UNSIGNED,*v as u64,retv);
+			} else {
+				let nv :i128 = -1 - *v;
+				if nv > (u64::MAX as i128) {
+					ecsimple_new_error!{ECCborError,"integer {} too small",v}
+				}
+				cbor_encode_head(CBOR_MAJOR_NEGATIVE,nv as u64,retv);
+			}
+		},
+		CborValue::Bytes(v) => {
+			cbor_encode_head(CBOR_MAJOR_BYTES,v.len() as u64,retv);
+			retv.extend_from_slice(v);
+		},
+		CborValue::Text(v) => {
+			cbor_encode_head(CBOR_MAJOR_TEXT,v.len() as u64,retv);
+			retv.extend_from_slice(v.as_bytes());
+		},
+		CborValue::Array(v) => {
+			cbor_encode_head(CBOR_MAJOR_ARRAY,v.len() as u64,retv);
+			for c in v.iter() {
+				cbor_encode_inner(c,retv)?;
+			}
+		},
+		CborValue::Map(v) => {
+			/*RFC 8949 4.2.1 , keys sorted by the bytewise order of their encoding*/
+			let mut pairs :Vec<(Vec<u8>,Vec<u8>)> = Vec::new();
+			for (k,kv) in v.iter() {
+				let mut kenc :Vec<u8> = Vec::new();
+				let mut venc :Vec<u8> = Vec::new();
+				cbor_encode_inner(k,&mut kenc)?;
+				cbor_encode_inner(kv,&mut venc)?;
+				pairs.push((kenc,venc));
+			}
+			pairs.sort_by(|a,b| a.0.cmp(&b.0));
+			for i in 1..pairs.len() {
+				if pairs[i].0 == pairs[i-1].0 {
+					ecsimple_new_error!{ECCborError,"duplicate map key"}
+				}
+			}
+			cbor_encode_head(CBOR_MAJOR_MAP,pairs.len() as u64,retv);
+			for (k,kv) in pairs.iter() {
+				retv.extend_from_slice(k);
+				retv.extend_from_slice(kv);
+			}
+		},
+		CborValue::Tag(t,v) => {
+			cbor_encode_head(CBOR_MAJOR_TAG,*t,retv);
+			cbor_encode_inner(v,retv)?;
+		},
+		CborValue::Bool(v) => {
+			if *v {
+				cbor_encode_head(CBOR_MAJOR_SIMPLE,CBOR_SIMPLE_TRUE as u64,retv);
+			} else {
+				cbor_encode_head(CBOR_MAJOR_SIMPLE,CBOR_SIMPLE_FALSE as u64,retv);
+			}
+		},
+		CborValue::Null => {
+			cbor_encode_head(CBOR_MAJOR_SIMPLE,CBOR_SIMPLE_NULL as u64,retv);
+		},
+		CborValue::Undefined => {
+			cbor_encode_head(CBOR_MAJOR_SIMPLE,CBOR_SIMPLE_UNDEFINED as u64,retv);
+		},
+	}
+	Ok(())
+}
+
+/*deterministic encoding of RFC 8949 section 4.2.1*/
+pub fn cbor_encode(val :&CborValue) -> Result<Vec<u8>,Box<dyn Error>> {
+	let mut retv :Vec<u8> = Vec::new();
+	cbor_encode_inner(val,&mut retv)?;
+	Ok(retv)
+}
+
+/*return major type , argument and the bytes used*/
+fn cbor_decode_head(data :&[u8]) -> Result<(u8,u64,usize),Box<dyn Error>> {
+	if data.is_empty() {
+		ecsimple_new_error!{ECCborError,"no data for head"}
+	}
+	let major :u8 = data[0] >> 5;
+	let info :u8 = data[0] & 0x1f;
+	if info < 24 {
+		return Ok((major,info as u64,1));
+	}
+	let size :usize = match info {
+		24 => 1,
+		25 => 2,
+		26 => 4,
+		27 => 8,
+		31 => {
+			ecsimple_new_error!{ECCborError,"indefinite length not supported"}
+		},
+		_ => {
+			ecsimple_new_error!{ECCborError,"reserved additional info {}",info}
+		}
+	};
+	if data.len() < 1 + size {
+		ecsimple_new_error!{ECCborError,"head needs {} bytes only {}",1 + size,data.len()}
+	}
+	let mut val :u64 = 0;
+	for i in 0..size {
+		val = (val << 8) | (data[1 + i] as u64);
+	}
+	/*RFC 8949 4.2.1 , the argument in the shortest form , major 7 is refused by the caller*/
+	let minval :u64 = match size {
+		1 => 24,
+		2 => 0x100,
+		4 => 0x10000,
+		_ => 0x100000000,
+	};
+	if major != CBOR_MAJOR_SIMPLE && val < minval {
+		ecsimple_new_error!{ECCborError,"argument {} not in shortest form of head 0x{:02x}",val,data[0]}
+	}
+	Ok((major,val,1 + size))
+}
+
+fn cbor_decode_inner(data :&[u8],depth :usize) -> Result<(CborValue,usize),Box<dyn Error>> {
+	if depth > CBOR_MAX_DEPTH {
+		ecsimple_new_error!{ECCborError,"nested deeper than {}",CBOR_MAX_DEPTH}
+	}
+	let (major,val,mut off) = cbor_decode_head(data)?;
+	match major {
+		CBOR_MAJOR_UNSIGNED => {
+			Ok((CborValue::Integer(val as i128),off))
+		},
+		CBOR_MAJOR_NEGATIVE => {
+			Ok((CborValue::Integer(-1 - (val as i128)),off))
+		},
+		CBOR_MAJOR_BYTES | CBOR_MAJOR_TEXT => {
+			if ((data.len() - off) as u64) < val {
+				ecsimple_new_error!{ECCborError,"string needs {} bytes only {}",val,data.len() - off}
+			}
+			let bv :Vec<u8> = data[off..(off + val as usize)].to_vec();
+			off += val as usize;
+			if major == CBOR_MAJOR_BYTES {
+				return Ok((CborValue::Bytes(bv),off));
+			}
+			let s :String = match String::from_utf8(bv) {
+				Ok(v) => v,
+				Err(e) => {
+					ecsimple_new_error!{ECCborError,"text not utf8 {:?}",e}
+				}
+			};
+			Ok((CborValue::Text(s),off))
+		},
+		CBOR_MAJOR_ARRAY => {
+			/*each item at least one byte , so a bad length fails before allocation*/
+			if ((data.len() - off) as u64) < val {
+				ecsimple_new_error!{ECCborError,"array of {} items only {} bytes",val,data.len() - off}
+			}
+			let mut arr :Vec<CborValue> = Vec::new();
+			for _ in 0..val {
+				let (c,used) = cbor_decode_inner(&data[off..],depth + 1)?;
+				arr.push(c);
+				off += used;
+			}
+			Ok((CborValue::Array(arr),off))
+		},
+		CBOR_MAJOR_MAP => {
+			if ((data.len() - off) as u64) < val.saturating_mul(2) {
+				ecsimple_new_error!{ECCborError,"map of {} pairs only {} bytes",val,data.len() - off}
+			}
+			let mut m :Vec<(CborValue,CborValue)> = Vec::new();
+			let mut lastkey :&[u8] = &[];
+			for i in 0..val {
+				let (k,kused) = cbor_decode_inner(&data[off..],depth + 1)?;
+				/*keys strictly in the bytewise order of the encoding as cbor_encode gives , so no duplicate*/
+				let kenc :&[u8] = &data[off..(off + kused)];
+				if i > 0 && kenc <= lastkey {
+					if kenc == lastkey {
+						ecsimple_new_error!{ECCborError,"duplicate map key {:?}",k}
+					}
+					ecsimple_new_error!{ECCborError,"map key {:?} not in order",k}
+				}
+				lastkey = kenc;
+				off += kused;
+				let (v,vused) = cbor_decode_inner(&data[off..],depth + 1)?;
+				off += vused;
+				m.push((k,v));
+			}
+			Ok((CborValue::Map(m),off))
+		},
+		CBOR_MAJOR_TAG => {
+			let (c,used) = cbor_decode_inner(&data[off..],depth + 1)?;
+			off += used;
+			Ok((CborValue::Tag(val,Box::new(c)),off))
+		},
+		_ => {
+			if off != 1 {
+				ecsimple_new_error!{ECCborError,"float or simple value 0x{:02x} not supported",data[0]}
+			}
+			match val as u8 {
+				CBOR_SIMPLE_FALSE => Ok((CborValue::Bool(false),off)),
+				CBOR_SIMPLE_TRUE => Ok((CborValue::Bool(true),off)),
+				CBOR_SIMPLE_NULL => Ok((CborValue::Null,off)),
+				CBOR_SIMPLE_UNDEFINED => Ok((CborValue::Undefined,off)),
+				_ => {
+					ecsimple_new_error!{ECCborError,"simple value {} not supported",val}
+				}
+			}
+		}
+	}
+}
+
+/*one item that must use all the data , and in the deterministic encoding of RFC 8949 4.2.1*/
+pub fn cbor_decode(data :&[u8]) -> Result<CborValue,Box<dyn Error>> {
+	let (retv,used) = cbor_decode_inner(data,0)?;
+	if used != data.len() {
+		ecsimple_new_error!{ECCborError,"{} bytes left after item",data.len() - used}
+	}
+	Ok(retv)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	#[test]
+	fn test_rfc8949_appendix_a() {
+		let cases :Vec<(&str,CborValue)> = vec![
+			("00",CborValue::Integer(0)),
+			("17",CborValue::Integer(23)),
+			("1818",CborValue::Integer(24)),
+			("1903e8",CborValue::Integer(1000)),
+			("1bffffffffffffffff",CborValue::Integer(18446744073709551615)),
+			("3bffffffffffffffff",CborValue::Integer(-18446744073709551616)),
+			("3863",CborValue::Integer(-100)),
+			("4401020304",CborValue::Bytes(vec![1,2,3,4])),
+			("6449455446",CborValue::Text("IETF".to_string())),
+			("8301820203820405",CborValue::Array(vec![CborValue::Integer(1),
+				CborValue::Array(vec![CborValue::Integer(2),CborValue::Integer(3)]),
+				CborValue::Array(vec![CborValue::Integer(4),CborValue::Integer(5)])])),
+			("a201020304",CborValue::Map(vec![(CborValue::Integer(1),CborValue::Integer(2)),(CborValue::Integer(3),CborValue::Integer(4))])),
+			("c074323031332d30332d32315432303a30343a30305a",CborValue::Tag(0,Box::new(CborValue::Text("2013-03-21T20:04:00Z".to_string())))),
+			("f4",CborValue::Bool(false)),
+			("f6",CborValue::Null),
+		];
+		for (h,v) in cases.iter() {
+			let data :Vec<u8> = Vec::from_hex(h).unwrap();
+			assert_eq!(cbor_decode(&data).unwrap(),*v);
+			assert_eq!(cbor_encode(v).unwrap(),data);
+		}
+	}
+
+	#[test]
+	fn test_non_minimal_head() {
+		for h in ["1817","190017","1a000000ff","1b00000000ffffffff","3817","5801ff","780161","9800","b800","d81700"].iter() {
+			assert!(cbor_decode(&Vec::from_hex(h).unwrap()).is_err(),"{}",h);
+		}
+		/*the simple values in two bytes and the floats stay refused*/
+		assert!(cbor_decode(&Vec::from_hex("f814").unwrap()).is_err());
+		assert!(cbor_decode(&Vec::from_hex("f93c00").unwrap()).is_err());
+	}
+
+	#[test]
+	fn test_map_key_order() {
+		/*{3:4,1:2} out of order , {1:2,1:3} duplicate*/
+		assert!(cbor_decode(&Vec::from_hex("a203040102").unwrap()).is_err());
+		assert!(cbor_decode(&Vec::from_hex("a201020103").unwrap()).is_err());
+		/*-1 encodes as 0x20 so it goes after 1 and 2*/
+		assert!(cbor_decode(&Vec::from_hex("a2200101f5").unwrap()).is_err());
+		let v :CborValue = cbor_decode(&Vec::from_hex("a201012002").unwrap()).unwrap();
+		assert_eq!(v.map_get_int(-1),Some(&CborValue::Integer(2)));
+		/*shorter key first in the bytewise order*/
+		assert!(cbor_decode(&Vec::from_hex("a2186401626161f5").unwrap()).is_ok());
+		assert!(cbor_decode(&Vec::from_hex("a2626161f5186401").unwrap()).is_err());
+	}
+}
@@ -0,0 +1,652 @@
+use crate::consts::*;
+use crate::ecasn1::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::x509::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Any,Asn1Object,Asn1OctData,Asn1Time};
+use asn1obj::complex::{Asn1Set,Asn1ImpSet,Asn1Ndef};
+use asn1obj::asn1impl::Asn1Op;
+use chrono::{DateTime,Utc};
+use num_bigint::BigInt;
+use std::error::Error;
+
+ecsimple_error_class!{ECCmsError}
+
+pub const PEM_CMS :&str = "CMS";
+pub const PEM_PKCS7 :&str = "PKCS7";
+
+const ASN1_INTEGER_TAG :u64 = 0x02;
+const ASN1_SEQUENCE_TAG :u64 = 0x30;
+const ASN1_SET_TAG :u8 = 0x31;
+const CMS_SKI_TAG :u64 = 0x80;
+const CMS_SIGNED_ATTRS_TAG :u64 = 0xa0;
+
+/*the signer certificate from issuerAndSerialNumber or subjectKeyIdentifier*/
+#[derive(Clone,Debug,PartialEq)]
+pub enum ECCmsSignerId {
+	IssuerSerial(ECX509Name,BigInt),
+	KeyId(Vec<u8>),
+}
+
+impl ECCmsSignerId {
+	pub fn matches(&self,cert :&ECX509Cert) -> bool {
+		match self {
+			ECCmsSignerId::IssuerSerial(n,s) => {
+				cert.issuer == *n && cert.serial == *s
+			},
+			ECCmsSignerId::KeyId(k) => {
+				cert.subject_key_id().unwrap_or(None).as_ref() == Some(k)
+			}
+		}
+	}
+}
+
+#[derive(Clone,Debug)]
+pub struct ECCmsSignParams {
+	/*None for SM3 with SM2 keys and the sha of the curve size otherwise , SM2 keys take only SM3*/
+	pub digest :Option<ECHashType>,
+	/*subjectKeyIdentifier signer id instead of issuerAndSerialNumber*/
+	pub use_key_id :bool,
+	pub detached :bool,
+	pub include_cert :bool,
+	/*None for now*/
+	pub signing_time :Option<DateTime<Utc>>,
+}
+
+/*detached with the signer certificate , as openssl cms -sign*/
+impl Default for ECCmsSignParams {
+	fn default() -> Self {
+		ECCmsSignParams {
+			digest : None,
+			use_key_id : false,
+			detached : true,
+			include_cert : true,
+			signing_time : None,
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct ECCmsSignerInfo {
+	pub sid :ECCmsSignerId,
+	pub digest :ECHashType,
+	pub sigalg :String,
+	/*the signedAttrs bytes with the SET tag as signed , None when absent*/
+	pub signed_attrs :Option<Vec<u8>>,
+	/*oid and the DER of the values SET*/
+	pub attrs :Vec<(String,Vec<u8>)>,
+	pub signature :Vec<u8>,
+}
+
+impl ECCmsSignerInfo {
+	fn get_attr(&self,oid :&str) -> Option<&Vec<u8>> {
+		for (k,v) in self.attrs.iter() {
+			if k == oid {
+				return Some(v);
+			}
+		}
+		None
+	}
+
+	pub fn content_type(&self) -> Result<Option<String>,Box<dyn Error>> {
+		let a = self.get_attr(PKCS9_CONTENT_TYPE_OID);
+		if a.is_none() {
+			return Ok(None);
+		}
+		let mut setv :Asn1Set<Asn1Object> = Asn1Set::init_asn1();
+		let _ = setv.decode_asn1(a.unwrap())?;
+		if setv.val.len() != 1 {
+			ecsimple_new_error!{ECCmsError,"contentType {} values",setv.val.len()}
+		}
+		Ok(Some(setv.val[0].get_value()))
+	}
+
+	pub fn message_digest(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		let a = self.get_attr(PKCS9_MESSAGE_DIGEST_OID);
+		if a.is_none() {
+			return Ok(None);
+		}
+		let mut setv :Asn1Set<Asn1OctData> = Asn1Set::init_asn1();
+		let _ = setv.decode_asn1(a.unwrap())?;
+		if setv.val.len() != 1 {
+			ecsimple_new_error!{ECCmsError,"messageDigest {} values",setv.val.len()}
+		}
+		Ok(Some(setv.val[0].data.clone()))
+	}
+
+	pub fn signing_time(&self) -> Result<Option<DateTime<Utc>>,Box<dyn Error>> {
+		let a = self.get_attr(PKCS9_SIGNING_TIME_OID);
+		if a.is_none() {
+			return Ok(None);
+		}
+		let mut setv :Asn1Set<Asn1Time> = Asn1Set::init_asn1();
+		let _ = setv.decode_asn1(a.unwrap())?;
+		if setv.val.len() != 1 {
+			ecsimple_new_error!{ECCmsError,"signingTime {} values",setv.val.len()}
+		}
+		Ok(Some(setv.val[0].get_value_time()?))
+	}
+}
+
+/*RFC 5753 ecdsa-with-* or id-ecPublicKey with the digest , SM2 by its oids and only with SM3*/
+fn cms_sig_alg(sigoid :&str,digest :ECHashType) -> Result<ECX509SigAlg,Box<dyn Error>> {
+	if sigoid == SM2_WITH_SM3_OID || sigoid == SM2_OID {
+		if digest != ECHashType::Sm3 {
+			ecsimple_new_error!{ECCmsError,"signature [{}] with digest [{}]",sigoid,digest.name()}
+		}
+		return Ok(ECX509SigAlg::Sm2Sm3);
+	}
+	if sigoid == EC_PUBLIC_KEY_OID {
+		return cms_ecdsa_alg(digest);
+	}
+	let alg :ECX509SigAlg = ECX509SigAlg::from_oid(sigoid)?;
+	if alg == ECX509SigAlg::Sm2Sm3 || alg.hash_type() != digest {
+		ecsimple_new_error!{ECCmsError,"signature [{}] with digest [{}]",sigoid,digest.name()}
+	}
+	Ok(alg)
+}
+
+fn cms_ecdsa_alg(digest :ECHashType) -> Result<ECX509SigAlg,Box<dyn Error>> {
+	match digest {
+		ECHashType::Sha1 => Ok(ECX509SigAlg::EcdsaSha1),
+		ECHashType::Sha224 => Ok(ECX509SigAlg::EcdsaSha224),
+		ECHashType::Sha256 => Ok(ECX509SigAlg::EcdsaSha256),
+		ECHashType::Sha384 => Ok(ECX509SigAlg::EcdsaSha384),
+		ECHashType::Sha512 => Ok(ECX509SigAlg::EcdsaSha512),
+		ECHashType::Sm3 => {
+			ecsimple_new_error!{ECCmsError,"no ecdsa with sm3"}
+		}
+	}
+}
+
+fn cms_form_attribute(oid :&str,setder :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let mut attr :Asn1X509Attribute = Asn1X509Attribute::init_asn1();
+	let mut attrelem :Asn1X509AttributeElem = Asn1X509AttributeElem::init_asn1();
+	attrelem.object.set_value(oid)?;
+	attrelem.set = der_to_any(setder)?;
+	attr.elem.val.push(attrelem);
+	attr.encode_asn1()
+}
+
+/*contentType , signingTime and messageDigest in DER SET OF order*/
+fn cms_form_signed_attrs(digest :&[u8],t :&DateTime<Utc>) -> Result<Asn1ImpSet<Asn1X509Attribute,0>,Box<dyn Error>> {
+	let mut encs :Vec<Vec<u8>> = Vec::new();
+	let mut ctset :Asn1Set<Asn1Object> = Asn1Set::init_asn1();
+	let mut ct :Asn1Object = Asn1Object::init_asn1();
+	ct.set_value(PKCS7_DATA_OID)?;
+	ctset.val.push(ct);
+	encs.push(cms_form_attribute(PKCS9_CONTENT_TYPE_OID,&ctset.encode_asn1()?)?);
+	let mut tset :Asn1Set<Asn1Time> = Asn1Set::init_asn1();
+	tset.val.push(x509_time_asn1(t)?);
+	encs.push(cms_form_attribute(PKCS9_SIGNING_TIME_OID,&tset.encode_asn1()?)?);
+	let mut mdset :Asn1Set<Asn1OctData> = Asn1Set::init_asn1();
+	let mut md :Asn1OctData = Asn1OctData::init_asn1();
+	md.data = digest.to_vec();
+	mdset.val.push(md);
+	encs.push(cms_form_attribute(PKCS9_MESSAGE_DIGEST_OID,&mdset.encode_asn1()?)?);
+	encs.sort();
+
+	let mut retv :Asn1ImpSet<Asn1X509Attribute,0> = Asn1ImpSet::init_asn1();
+	for e in encs.iter() {
+		let mut attr :Asn1X509Attribute = Asn1X509Attribute::init_asn1();
+		let _ = attr.decode_asn1(e)?;
+		retv.val.push(attr);
+	}
+	Ok(retv)
+}
+
+/*RFC 5652 5.4 , the signature is over the EXPLICIT SET OF encoding*/
+fn cms_signed_attrs_der(attrs :&Asn1ImpSet<Asn1X509Attribute,0>) -> Result<Vec<u8>,Box<dyn Error>> {
+	let mut retv :Vec<u8> = attrs.encode_asn1()?;
+	retv[0] = ASN1_SET_TAG;
+	Ok(retv)
+}
+
+/*the [0] signedAttrs of the SignerInfo bytes as received , only the tag made SET*/
+fn cms_signed_attrs_raw(si :&Asn1Any) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+	let mut off :usize = 0;
+	while off < si.content.len() {
+		let mut elem :Asn1Any = Asn1Any::init_asn1();
+		let used :usize = elem.decode_asn1(&si.content[off..])?;
+		if used == 0 {
+			ecsimple_new_error!{ECCmsError,"SignerInfo element at {} not parsed",off}
+		}
+		if elem.tag == CMS_SIGNED_ATTRS_TAG {
+			let mut retv :Vec<u8> = si.content[off..(off + used)].to_vec();
+			retv[0] = ASN1_SET_TAG;
+			return Ok(Some(retv));
+		}
+		off += used;
+	}
+	Ok(None)
+}
+
+/*RFC 5652 SignedData in a ContentInfo*/
+#[derive(Clone)]
+pub struct ECCmsSignedData {
+	pub der :Vec<u8>,
+	pub content_type :String,
+	/*None for detached content*/
+	pub content :Option<Vec<u8>>,
+	/*the certificates with EC keys , others are skipped*/
+	pub certs :Vec<ECX509Cert>,
+	pub signers :Vec<ECCmsSignerInfo>,
+}
+
+impl ECCmsSignedData {
+	pub fn sign(cert :&ECX509Cert,privkey :&ECPrivateKey,content :&[u8],params :&ECCmsSignParams) -> Result<ECCmsSignedData,Box<dyn Error>> {
+		let pubkey :ECPublicKey = privkey.export_pubkey();
+		if pubkey.to_bin(EC_UNCOMPRESSED)? != cert.pubkey.to_bin(EC_UNCOMPRESSED)? {
+			ecsimple_new_error!{ECCmsError,"private key not for [{}]",cert.subject}
+		}
+		let mut sigalg :ECX509SigAlg = ECX509SigAlg::from_key(&pubkey);
+		if let Some(d) = params.digest {
+			if sigalg == ECX509SigAlg::Sm2Sm3 {
+				if d != ECHashType::Sm3 {
+					ecsimple_new_error!{ECCmsError,"SM2 with digest [{}]",d.name()}
+				}
+			} else {
+				sigalg = cms_ecdsa_alg(d)?;
+			}
+		}
+		let digest :ECHashType = sigalg.hash_type();
+		let t :DateTime<Utc> = match params.signing_time {
+			Some(v) => v,
+			None => Utc::now(),
+		};
+		let attrs :Asn1ImpSet<Asn1X509Attribute,0> = cms_form_signed_attrs(&digest.digest(content),&t)?;
+		let tbs :Vec<u8> = cms_signed_attrs_der(&attrs)?;
+
+		let mut sielem :Asn1CmsSignerInfoElem = Asn1CmsSignerInfoElem::init_asn1();
+		if params.use_key_id {
+			let mut keyid :Option<Vec<u8>> = cert.subject_key_id()?;
+			if keyid.is_none() {
+				keyid = Some(x509_key_id(&cert.pubkey)?);
+			}
+			sielem.version.val = 3;
+			sielem.sid.tag = CMS_SKI_TAG;
+			sielem.sid.content = keyid.unwrap();
+		} else {
+			let mut iselem :Asn1CmsIssuerSerialElem = Asn1CmsIssuerSerialElem::init_asn1();
+			iselem.issuer = der_to_any(&cert.issuer.der)?;
+			iselem.serial.tag = ASN1_INTEGER_TAG;
+			iselem.serial.content = cert.serial.to_signed_bytes_be();
+			let mut isv :Asn1CmsIssuerSerial = Asn1CmsIssuerSerial::init_asn1();
+			isv.elem.val.push(iselem);
+			sielem.version.val = 1;
+			sielem.sid = der_to_any(&isv.encode_asn1()?)?;
+		}
+		sielem.digestalg = form_algor(digest.oid(),None)?;
+		sielem.signedattrs.val = Some(attrs);
+		sielem.sigalg = form_algor(sigalg.oid(),None)?;
+		sielem.signature.data = x509_sign_data(sigalg,privkey,&tbs)?;
+		let mut si :Asn1CmsSignerInfo = Asn1CmsSignerInfo::init_asn1();
+		si.elem.val.push(sielem);
+
+		let mut sdelem :Asn1CmsSignedDataElem = Asn1CmsSignedDataElem::init_asn1();
+		sdelem.version.val = if params.use_key_id {3} else {1};
+		sdelem.digestalgs.val.push(form_algor(digest.oid(),None)?);
+		let mut ecelem :Asn1CmsEncapContentInfoElem = Asn1CmsEncapContentInfoElem::init_asn1();
+		ecelem.econtenttype.set_value(PKCS7_DATA_OID)?;
+		if !params.detached {
+			let mut ndef :Asn1Ndef<Asn1OctData,0> = Asn1Ndef::init_asn1();
+			let mut oct :Asn1OctData = Asn1OctData::init_asn1();
+			oct.data = content.to_vec();
+			ndef.val = Some(oct);
+			ecelem.econtent.val = Some(ndef);
+		}
+		sdelem.encapcontent.elem.val.push(ecelem);
+		if params.include_cert {
+			let mut certs :Asn1ImpSet<Asn1Any,0> = Asn1ImpSet::init_asn1();
+			certs.val.push(der_to_any(&cert.der)?);
+			sdelem.certificates.val = Some(certs);
+		}
+		sdelem.signerinfos.val.push(der_to_any(&si.encode_asn1()?)?);
+		let mut sd :Asn1CmsSignedData = Asn1CmsSignedData::init_asn1();
+		sd.elem.val.push(sdelem);
+
+		let mut info :Asn1Pkcs7ContentInfo = Asn1Pkcs7ContentInfo::init_asn1();
+		let mut infoelem :Asn1Pkcs7ContentInfoElem = Asn1Pkcs7ContentInfoElem::init_asn1();
+		infoelem.contenttype.set_value(PKCS7_SIGNED_DATA_OID)?;
+		infoelem.content.val = Some(der_to_any(&sd.encode_asn1()?)?);
+		info.elem.val.push(infoelem);
+		return ECCmsSignedData::from_der(&info.encode_asn1()?);
+	}
+
+	pub fn from_der(der :&[u8]) -> Result<ECCmsSignedData,Box<dyn Error>> {
+		let mut info :Asn1Pkcs7ContentInfo = Asn1Pkcs7ContentInfo::init_asn1();
+		let used :usize = info.decode_asn1(der)?;
+		if used != der.len() || info.elem.val.len() != 1 {
+			ecsimple_new_error!{ECCmsError,"not one ContentInfo in {} bytes",der.len()}
+		}
+		let infoelem :&Asn1Pkcs7ContentInfoElem = &info.elem.val[0];
+		if infoelem.contenttype.get_value() != PKCS7_SIGNED_DATA_OID {
+			ecsimple_new_error!{ECCmsError,"content type [{}] not signedData",infoelem.contenttype.get_value()}
+		}
+		if infoelem.content.val.is_none() {
+			ecsimple_new_error!{ECCmsError,"no SignedData"}
+		}
+		let mut sd :Asn1CmsSignedData = Asn1CmsSignedData::init_asn1();
+		let _ = sd.decode_asn1(&infoelem.content.val.as_ref().unwrap().encode_asn1()?)?;
+		if sd.elem.val.len() != 1 {
+			ecsimple_new_error!{ECCmsError,"SignedData elem {} != 1",sd.elem.val.len()}
+		}
+		let sdelem :&Asn1CmsSignedDataElem = &sd.elem.val[0];
+		let ecelem :&Asn1CmsEncapContentInfoElem = &sdelem.encapcontent.elem.val[0];
+		let mut content :Option<Vec<u8>> = None;
+		if let Some(ndef) = ecelem.econtent.val.as_ref() {
+			if let Some(oct) = ndef.val.as_ref() {
+				content = Some(oct.data.clone());
+			}
+		}
+
+		let mut certs :Vec<ECX509Cert> = Vec::new();
+		if let Some(cset) = sdelem.certificates.val.as_ref() {
+			for c in cset.val.iter() {
+				if c.tag != ASN1_SEQUENCE_TAG {
+					/*attribute or other certificate formats*/
+					continue;
+				}
+				match ECX509Cert::from_der(&c.encode_asn1()?) {
+					Ok(v) => {
+						certs.push(v);
+					},
+					Err(_e) => {
+						ecsimple_log_trace!("certificate skipped {:?}",_e);
+					}
+				}
+			}
+		}
+
+		let mut signers :Vec<ECCmsSignerInfo> = Vec::new();
+		for siany in sdelem.signerinfos.val.iter() {
+			let mut si :Asn1CmsSignerInfo = Asn1CmsSignerInfo::init_asn1();
+			let _ = si.decode_asn1(&siany.encode_asn1()?)?;
+			let sielem :&Asn1CmsSignerInfoElem = &si.elem.val[0];
+			let sid :ECCmsSignerId;
+			if sielem.sid.tag == CMS_SKI_TAG {
+				sid = ECCmsSignerId::KeyId(sielem.sid.content.clone());
+			} else if sielem.sid.tag == ASN1_SEQUENCE_TAG {
+				let mut isv :Asn1CmsIssuerSerial = Asn1CmsIssuerSerial::init_asn1();
+				let _ = isv.decode_asn1(&sielem.sid.encode_asn1()?)?;
+				let iselem :&Asn1CmsIssuerSerialElem = &isv.elem.val[0];
+				if iselem.serial.tag != ASN1_INTEGER_TAG || iselem.serial.content.is_empty() {
+					ecsimple_new_error!{ECCmsError,"signer serial not INTEGER"}
+				}
+				sid = ECCmsSignerId::IssuerSerial(ECX509Name::from_der(&iselem.issuer.encode_asn1()?)?,BigInt::from_signed_bytes_be(&iselem.serial.content));
+			} else {
+				ecsimple_new_error!{ECCmsError,"signer id tag 0x{:x}",sielem.sid.tag}
+			}
+			let (doid,_) = get_algor_elem(&sielem.digestalg)?;
+			let (soid,_) = get_algor_elem(&sielem.sigalg)?;
+			let mut signed_attrs :Option<Vec<u8>> = None;
+			let mut attrs :Vec<(String,Vec<u8>)> = Vec::new();
+			if let Some(sa) = sielem.signedattrs.val.as_ref().filter(|s| !s.val.is_empty()) {
+				signed_attrs = cms_signed_attrs_raw(siany)?;
+				for a in sa.val.iter() {
+					let aelem :&Asn1X509AttributeElem = &a.elem.val[0];
+					attrs.push((aelem.object.get_value(),aelem.set.encode_asn1()?));
+				}
+			}
+			signers.push(ECCmsSignerInfo {
+				sid,
+				digest : ECHashType::from_oid(&doid)?,
+				sigalg : soid,
+				signed_attrs,
+				attrs,
+				signature : sielem.signature.data.clone(),
+			});
+		}
+
+		Ok(ECCmsSignedData {
+			der : der.to_vec(),
+			content_type : ecelem.econtenttype.get_value(),
+			content,
+			certs,
+			signers,
+		})
+	}
+
+	pub fn from_pem(ins :&str) -> Result<ECCmsSignedData,Box<dyn Error>> {
+		let b :PemBlock = pem_find_block(ins,&[PEM_CMS,PEM_PKCS7])?;
+		ECCmsSignedData::from_der(&b.data)
+	}
+
+	pub fn to_der(&self) -> Vec<u8> {
+		self.der.clone()
+	}
+
+	pub fn to_pem(&self) -> String {
+		der_to_pem(&self.der,PEM_CMS)
+	}
+
+	pub fn signer_cert(&self,idx :usize) -> Option<&ECX509Cert> {
+		if idx >= self.signers.len() {
+			return None;
+		}
+		self.certs.iter().find(|&c| self.signers[idx].sid.matches(c)).map(|v| v as _)
+	}
+
+	/*detached is the content when not carried , false on any mismatch*/
+	pub fn verify_signer(&self,idx :usize,pubkey :&ECPublicKey,detached :Option<&[u8]>) -> Result<bool,Box<dyn Error>> {
+		if idx >= self.signers.len() {
+			ecsimple_new_error!{ECCmsError,"signer {} of {}",idx,self.signers.len()}
+		}
+		let content :&[u8] = match (&self.content,detached) {
+			(Some(c),_) => c,
+			(None,Some(d)) => d,
+			(None,None) => {
+				ecsimple_new_error!{ECCmsError,"no content to verify"}
+			}
+		};
+		let si :&ECCmsSignerInfo = &self.signers[idx];
+		let alg :ECX509SigAlg = cms_sig_alg(&si.sigalg,si.digest)?;
+		let tbs :Vec<u8> = if let Some(sattrs) = si.signed_attrs.as_ref() {
+			if si.content_type()? != Some(self.content_type.clone()) {
+				ecsimple_log_trace!("contentType attribute not [{}]",self.content_type);
+				return Ok(false);
+			}
+			if si.message_digest()? != Some(si.digest.digest(content)) {
+				ecsimple_log_trace!("messageDigest mismatch");
+				return Ok(false);
+			}
+			sattrs.clone()
+		} else {
+			if self.content_type != PKCS7_DATA_OID {
+				ecsimple_new_error!{ECCmsError,"no signedAttrs for [{}]",self.content_type}
+			}
+			content.to_vec()
+		};
+		x509_verify_data(alg,pubkey,&tbs,&si.signature)
+	}
+
+	/*every signer with its certificate in the message , no path validation*/
+	pub fn verify(&self,detached :Option<&[u8]>) -> Result<bool,Box<dyn Error>> {
+		if self.signers.is_empty() {
+			ecsimple_new_error!{ECCmsError,"no signer"}
+		}
+		for i in 0..self.signers.len() {
+			let cert = self.signer_cert(i);
+			if cert.is_none() {
+				ecsimple_new_error!{ECCmsError,"no certificate for signer {} {:?}",i,self.signers[i].sid}
+			}
+			if !self.verify_signer(i,&cert.unwrap().pubkey,detached)? {
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::group::*;
+
+	const CMS_CONTENT :&[u8] = b"hello ecsimple\n";
+
+	/*openssl cms -sign -binary , detached with sha256*/
+	const OPENSSL_CMS_P256 :&str = "
+-----BEGIN CMS-----
+MIIDTQYJKoZIhvcNAQcCoIIDPjCCAzoCAQExDTALBglghkgBZQMEAgEwCwYJKoZI
+hvcNAQcBoIIBoDCCAZwwggFBoAMCAQICBQCKGyw9MAoGCCqGSM49BAMCMBgxFjAU
+BgNVBAMMDWVjc2ltcGxlIHAyNTYwHhcNMjYxMDE5MDk0NTA4WhcNMzYxMDE2MDk0
+NTA4WjAYMRYwFAYDVQQDDA1lY3NpbXBsZSBwMjU2MFkwEwYHKoZIzj0CAQYIKoZI
+zj0DAQcDQgAEyDT5ukeEPiqdsGt8S+l8Mc+/NJVpZguKf0xZVav4WUnt+ldEN6fk
+850FvzQcsxa9UtNw7qLOgKt/kMAU0r4Ze6N4MHYwHQYDVR0OBBYEFKIPghkRw16B
+QU1QMD6IIPBPn7xSMA8GA1UdEwEB/wQFMAMBAf8wRAYDVR0jBD0wO4AUog+CGRHD
+XoFBTVAwPogg8E+fvFKhHKQaMBgxFjAUBgNVBAMMDWVjc2ltcGxlIHAyNTaCBQCK
+Gyw9MAoGCCqGSM49BAMCA0kAMEYCIQDNGJYxpIweu77tf2bsIJPBaXHe5oabz0c9
+hTpA+Gi5zAIhAPutd0Os8wJ69BTpUu3aja9XJeetIIrEsB0fb1TBecxrMYIBczCC
+AW8CAQEwITAYMRYwFAYDVQQDDA1lY3NpbXBsZSBwMjU2AgUAihssPTALBglghkgB
+ZQMEAgGggeQwGAYJKoZIhvcNAQkDMQsGCSqGSIb3DQEHATAcBgkqhkiG9w0BCQUx
+DxcNMjYxMDE5MDk1MDM3WjAvBgkqhkiG9w0BCQQxIgQgcIptL1JO2NmFYIyVbb1X
+B6CgbahYDSO1wVUA6ni6CyQweQYJKoZIhvcNAQkPMWwwajALBglghkgBZQMEASow
+CwYJYIZIAWUDBAEWMAsGCWCGSAFlAwQBAjAKBggqhkiG9w0DBzAOBggqhkiG9w0D
+AgICAIAwDQYIKoZIhvcNAwICAUAwBwYFKw4DAgcwDQYIKoZIhvcNAwICASgwCgYI
+KoZIzj0EAwIERzBFAiEAhCe50tYvIKKRj1hRyO6HLogRj9kS0jCIJYg1gnwygPQC
+IGevWvVKxoUgkQov/OCINeQ8KUEOKVxDJnq+56ullOsp
+-----END CMS-----
+";
+
+	/*openssl cms -sign -binary -nodetach -md sha384*/
+	const OPENSSL_CMS_P256_SHA384 :&str = "
+-----BEGIN CMS-----
+MIIDcAYJKoZIhvcNAQcCoIIDYTCCA10CAQExDTALBglghkgBZQMEAgIwHgYJKoZI
+hvcNAQcBoBEED2hlbGxvIGVjc2ltcGxlCqCCAaAwggGcMIIBQaADAgECAgUAihss
+PTAKBggqhkjOPQQDAjAYMRYwFAYDVQQDDA1lY3NpbXBsZSBwMjU2MB4XDTI2MTAx
+OTA5NDUwOFoXDTM2MTAxNjA5NDUwOFowGDEWMBQGA1UEAwwNZWNzaW1wbGUgcDI1
+NjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABMg0+bpHhD4qnbBrfEvpfDHPvzSV
+aWYLin9MWVWr+FlJ7fpXRDen5POdBb80HLMWvVLTcO6izoCrf5DAFNK+GXujeDB2
+MB0GA1UdDgQWBBSiD4IZEcNegUFNUDA+iCDwT5+8UjAPBgNVHRMBAf8EBTADAQH/
+MEQGA1UdIwQ9MDuAFKIPghkRw16BQU1QMD6IIPBPn7xSoRykGjAYMRYwFAYDVQQD
+DA1lY3NpbXBsZSBwMjU2ggUAihssPTAKBggqhkjOPQQDAgNJADBGAiEAzRiWMaSM
+Hru+7X9m7CCTwWlx3uaGm89HPYU6QPhoucwCIQD7rXdDrPMCevQU6VLt2o2vVyXn
+rSCKxLAdH29UwXnMazGCAYMwggF/AgEBMCEwGDEWMBQGA1UEAwwNZWNzaW1wbGUg
+cDI1NgIFAIobLD0wCwYJYIZIAWUDBAICoIH0MBgGCSqGSIb3DQEJAzELBgkqhkiG
+9w0BBwEwHAYJKoZIhvcNAQkFMQ8XDTI2MTAxOTA5NTAzN1owPwYJKoZIhvcNAQkE
+MTIEMC0TudeuoGqhEYASGr9RAj03qI6Wam/P+rx0aeU7b4OYpfo0lkJ2vhoMGvo2
+I70ryTB5BgkqhkiG9w0BCQ8xbDBqMAsGCWCGSAFlAwQBKjALBglghkgBZQMEARYw
+CwYJYIZIAWUDBAECMAoGCCqGSIb3DQMHMA4GCCqGSIb3DQMCAgIAgDANBggqhkiG
+9w0DAgIBQDAHBgUrDgMCBzANBggqhkiG9w0DAgIBKDAKBggqhkjOPQQDAwRHMEUC
+IQDm32mNh6MWPf7LQAvo5ZS2OzNpMstBDHu1GBJq7u4kgwIgIihGHdLZJDPL6lNS
+gcMDpSk/DcY0cjSOF2gGOEZlSHA=
+-----END CMS-----
+";
+
+	/*openssl cms -sign -binary -keyopt distid:1234567812345678 with an SM2 key*/
+	const OPENSSL_CMS_SM2 :&str = "
+-----BEGIN CMS-----
+MIIDIAYJKoZIhvcNAQcCoIIDETCCAw0CAQExDjAMBggqgRzPVQGDEQUAMAsGCSqG
+SIb3DQEHAaCCAXQwggFwMIIBF6ADAgECAgISNDAKBggqgRzPVQGDdTAXMRUwEwYD
+VQQDDAxlY3NpbXBsZSBzbTIwHhcNMjYxMDE5MDk0NTA4WhcNMzYxMDE2MDk0NTA4
+WjAXMRUwEwYDVQQDDAxlY3NpbXBsZSBzbTIwWTATBgcqhkjOPQIBBggqgRzPVQGC
+LQNCAAQVYQlup651LHxvyCBqVy+rca2ywkL8V13DpXD/DuNYBCraHHTjhEgqHAwY
+rbvHg7arcWvWf5lgQ/OyIJbUSeH1o1MwUTAdBgNVHQ4EFgQUy7aVpvp5gc7VWokn
+nI5rhFxXuUkwHwYDVR0jBBgwFoAUy7aVpvp5gc7VWoknnI5rhFxXuUkwDwYDVR0T
+AQH/BAUwAwEB/zAKBggqgRzPVQGDdQNHADBEAiAVTVxBHYAfQEgX2NOPBqVpaItJ
+wWiO3BI+dOY4eggu1QIgCp7ENixEgYJAknSk5WUSs1u8Jp+UBJeHOhNRZiGOItwx
+ggFxMIIBbQIBATAdMBcxFTATBgNVBAMMDGVjc2ltcGxlIHNtMgICEjQwDAYIKoEc
+z1UBgxEFAKCB5DAYBgkqhkiG9w0BCQMxCwYJKoZIhvcNAQcBMBwGCSqGSIb3DQEJ
+BTEPFw0yNjEwMTkwOTUxNTRaMC8GCSqGSIb3DQEJBDEiBCBXuEhwsXkWy3hemH9r
+s6vetxXxD/3QqCWDeK+GQM0MfjB5BgkqhkiG9w0BCQ8xbDBqMAsGCWCGSAFlAwQB
+KjALBglghkgBZQMEARYwCwYJYIZIAWUDBAECMAoGCCqGSIb3DQMHMA4GCCqGSIb3
+DQMCAgIAgDANBggqhkiG9w0DAgIBQDAHBgUrDgMCBzANBggqhkiG9w0DAgIBKDAK
+BggqgRzPVQGDdQRIMEYCIQDdLSGV//xQavRvfXX3MKwaz+C50HFfDNVs/u8xtFbK
+gwIhAPiCkCQsaU67jg/pBrggowA5VoxmNXDMHNrxFa5QtaqK
+-----END CMS-----
+";
+
+	/*openssl cms -sign -binary without distid , openssl hashes Z of an empty id*/
+	const OPENSSL_CMS_SM2_EMPTY_ID :&str = "
+-----BEGIN CMS-----
+MIIDHwYJKoZIhvcNAQcCoIIDEDCCAwwCAQExDjAMBggqgRzPVQGDEQUAMAsGCSqG
+SIb3DQEHAaCCAXQwggFwMIIBF6ADAgECAgISNDAKBggqgRzPVQGDdTAXMRUwEwYD
+VQQDDAxlY3NpbXBsZSBzbTIwHhcNMjYxMDE5MDk0NTA4WhcNMzYxMDE2MDk0NTA4
+WjAXMRUwEwYDVQQDDAxlY3NpbXBsZSBzbTIwWTATBgcqhkjOPQIBBggqgRzPVQGC
+LQNCAAQVYQlup651LHxvyCBqVy+rca2ywkL8V13DpXD/DuNYBCraHHTjhEgqHAwY
+rbvHg7arcWvWf5lgQ/OyIJbUSeH1o1MwUTAdBgNVHQ4EFgQUy7aVpvp5gc7VWokn
+nI5rhFxXuUkwHwYDVR0jBBgwFoAUy7aVpvp5gc7VWoknnI5rhFxXuUkwDwYDVR0T
+AQH/BAUwAwEB/zAKBggqgRzPVQGDdQNHADBEAiAVTVxBHYAfQEgX2NOPBqVpaItJ
+wWiO3BI+dOY4eggu1QIgCp7ENixEgYJAknSk5WUSs1u8Jp+UBJeHOhNRZiGOItwx
+ggFwMIIBbAIBATAdMBcxFTATBgNVBAMMDGVjc2ltcGxlIHNtMgICEjQwDAYIKoEc
+z1UBgxEFAKCB5DAYBgkqhkiG9w0BCQMxCwYJKoZIhvcNAQcBMBwGCSqGSIb3DQEJ
+BTEPFw0yNjEwMTkwOTUwMzdaMC8GCSqGSIb3DQEJBDEiBCBXuEhwsXkWy3hemH9r
+s6vetxXxD/3QqCWDeK+GQM0MfjB5BgkqhkiG9w0BCQ8xbDBqMAsGCWCGSAFlAwQB
+KjALBglghkgBZQMEARYwCwYJYIZIAWUDBAECMAoGCCqGSIb3DQMHMA4GCCqGSIb3
+DQMCAgIAgDANBggqhkiG9w0DAgIBQDAHBgUrDgMCBzANBggqhkiG9w0DAgIBKDAK
+BggqgRzPVQGDdQRHMEUCIEDKLRv9Duq38gDa5pyVstsogwhdVDn5lShJp2jI4FYh
+AiEAo+kAG3k/T/XH5auJscnlKQ43gwHm5+/MNv4RsLUFam8=
+-----END CMS-----
+";
+
+	#[test]
+	fn test_openssl_cms_p256() {
+		let sd :ECCmsSignedData = ECCmsSignedData::from_pem(OPENSSL_CMS_P256).unwrap();
+		assert_eq!(sd.content_type,PKCS7_DATA_OID);
+		assert!(sd.content.is_none());
+		assert_eq!(sd.signers.len(),1);
+		assert_eq!(sd.signers[0].digest,ECHashType::Sha256);
+		assert_eq!(sd.signers[0].sid,ECCmsSignerId::IssuerSerial(sd.certs[0].issuer.clone(),BigInt::from(0x8a1b2c3du64)));
+		/*openssl adds smimeCapabilities after contentType signingTime and messageDigest*/
+		assert_eq!(sd.signers[0].attrs.len(),4);
+		assert_eq!(sd.signers[0].message_digest().unwrap(),Some(ECHashType::Sha256.digest(CMS_CONTENT)));
+		assert!(sd.verify(Some(CMS_CONTENT)).unwrap());
+		assert!(!sd.verify(Some(b"hello ecsimple")).unwrap());
+		assert!(sd.verify(None).is_err());
+	}
+
+	#[test]
+	fn test_openssl_cms_p256_sha384() {
+		let sd :ECCmsSignedData = ECCmsSignedData::from_pem(OPENSSL_CMS_P256_SHA384).unwrap();
+		assert_eq!(sd.content,Some(CMS_CONTENT.to_vec()));
+		assert_eq!(sd.signers[0].digest,ECHashType::Sha384);
+		assert!(sd.verify(None).unwrap());
+	}
+
+	#[test]
+	fn test_openssl_cms_sm2() {
+		let sd :ECCmsSignedData = ECCmsSignedData::from_pem(OPENSSL_CMS_SM2).unwrap();
+		assert_eq!(sd.signers[0].digest,ECHashType::Sm3);
+		assert_eq!(sd.signers[0].sigalg,SM2_WITH_SM3_OID);
+		assert!(sd.verify(Some(CMS_CONTENT)).unwrap());
+		assert!(!sd.verify(Some(b"hello ecsimple")).unwrap());
+		let sd :ECCmsSignedData = ECCmsSignedData::from_pem(OPENSSL_CMS_SM2_EMPTY_ID).unwrap();
+		assert!(!sd.verify(Some(CMS_CONTENT)).unwrap());
+	}
+
+	#[test]
+	fn test_sm2_only_sm3() {
+		assert!(cms_sig_alg(SM2_WITH_SM3_OID,ECHashType::Sha256).is_err());
+		assert!(cms_sig_alg(SM2_OID,ECHashType::Sha256).is_err());
+		assert_eq!(cms_sig_alg(SM2_WITH_SM3_OID,ECHashType::Sm3).unwrap(),ECX509SigAlg::Sm2Sm3);
+
+		let grp :ECGroup = ecc_get_curve_group(SM2_NAME).unwrap();
+		let key :ECPrivateKey = ECPrivateKey::generate(&grp);
+		let cert :ECX509Cert = ECX509CertBuilder::new(&ECX509Name::parse("CN=ecsimple sm2").unwrap(),&key.export_pubkey()).sign(&key).unwrap();
+		let mut params :ECCmsSignParams = ECCmsSignParams {
+			digest : Some(ECHashType::Sha256),
+			..Default::default()
+		};
+		assert!(ECCmsSignedData::sign(&cert,&key,CMS_CONTENT,&params).is_err());
+
+		params.digest = Some(ECHashType::Sm3);
+		let sd :ECCmsSignedData = ECCmsSignedData::sign(&cert,&key,CMS_CONTENT,&params).unwrap();
+		assert!(sd.verify(Some(CMS_CONTENT)).unwrap());
+	}
+
+	#[test]
+	fn test_serial_high_bit_signer() {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let key :ECPrivateKey = ECPrivateKey::generate(&grp);
+		let mut certb :ECX509CertBuilder = ECX509CertBuilder::new(&ECX509Name::parse("CN=ecsimple").unwrap(),&key.export_pubkey());
+		certb.serial = BigInt::from(0x80);
+		let cert :ECX509Cert = certb.sign(&key).unwrap();
+		let sd :ECCmsSignedData = ECCmsSignedData::sign(&cert,&key,CMS_CONTENT,&ECCmsSignParams::default()).unwrap();
+		assert_eq!(sd.signers[0].sid,ECCmsSignerId::IssuerSerial(cert.issuer.clone(),BigInt::from(0x80)));
+		assert!(sd.verify(Some(CMS_CONTENT)).unwrap());
+	}
+}
@@ -157,11 +157,83 @@ pub const SM2_OID :&str = "1.2.156.10197.1.301";
 pub const EC_PUBLIC_KEY_OID :&str = "1.2.840.10045.2.1";
 pub const ID_PRIME_FIELD_OID :&str = "1.2.840.10045.1.1";
 
+pub const PBES2_OID :&str = "1.2.840.113549.1.5.13";
+pub const PBKDF2_OID :&str = "1.2.840.113549.1.5.12";
+pub const SCRYPT_OID :&str = "1.3.6.1.4.1.11591.4.11";
+pub const HMAC_SHA1_OID :&str = "1.2.840.113549.2.7";
+pub const HMAC_SHA224_OID :&str = "1.2.840.113549.2.8";
+pub const HMAC_SHA256_OID :&str = "1.2.840.113549.2.9";
+pub const HMAC_SHA384_OID :&str = "1.2.840.113549.2.10";
+pub const HMAC_SHA512_OID :&str = "1.2.840.113549.2.11";
+pub const HMAC_SM3_OID :&str = "1.2.156.10197.1.401.3.1";
+pub const AES128_CBC_OID :&str = "2.16.840.1.101.3.4.1.2";
+pub const AES192_CBC_OID :&str = "2.16.840.1.101.3.4.1.22";
+pub const AES256_CBC_OID :&str = "2.16.840.1.101.3.4.1.42";
+pub const AES128_GCM_OID :&str = "2.16.840.1.101.3.4.1.6";
+pub const AES192_GCM_OID :&str = "2.16.840.1.101.3.4.1.26";
+pub const AES256_GCM_OID :&str = "2.16.840.1.101.3.4.1.46";
+pub const SM4_CBC_OID :&str = "1.2.156.10197.1.104.2";
+pub const DES_EDE3_CBC_OID :&str = "1.2.840.113549.3.7";
+
+pub const SHA1_OID :&str = "1.3.14.3.2.26";
+pub const SHA224_OID :&str = "2.16.840.1.101.3.4.2.4";
+pub const SHA256_OID :&str = "2.16.840.1.101.3.4.2.1";
+pub const SHA384_OID :&str = "2.16.840.1.101.3.4.2.2";
+pub const SHA512_OID :&str = "2.16.840.1.101.3.4.2.3";
+pub const SM3_OID :&str = "1.2.156.10197.1.401";
+
+pub const PKCS7_DATA_OID :&str = "1.2.840.113549.1.7.1";
+pub const PKCS7_SIGNED_DATA_OID :&str = "1.2.840.113549.1.7.2";
+pub const PKCS7_ENCRYPTED_DATA_OID :&str = "1.2.840.113549.1.7.6";
+pub const PKCS12_KEY_BAG_OID :&str = "1.2.840.113549.1.12.10.1.1";
+pub const PKCS12_SHROUDED_KEY_BAG_OID :&str = "1.2.840.113549.1.12.10.1.2";
+pub const PKCS12_CERT_BAG_OID :&str = "1.2.840.113549.1.12.10.1.3";
+pub const PKCS9_CONTENT_TYPE_OID :&str = "1.2.840.113549.1.9.3";
+pub const PKCS9_MESSAGE_DIGEST_OID :&str = "1.2.840.113549.1.9.4";
+pub const PKCS9_SIGNING_TIME_OID :&str = "1.2.840.113549.1.9.5";
+pub const PKCS9_FRIENDLY_NAME_OID :&str = "1.2.840.113549.1.9.20";
+pub const PKCS9_LOCAL_KEY_ID_OID :&str = "1.2.840.113549.1.9.21";
+pub const PKCS9_X509_CERTIFICATE_OID :&str = "1.2.840.113549.1.9.22.1";
+
+pub const ECDSA_WITH_SHA1_OID :&str = "1.2.840.10045.4.1";
+pub const ECDSA_WITH_SHA224_OID :&str = "1.2.840.10045.4.3.1";
+pub const ECDSA_WITH_SHA256_OID :&str = "1.2.840.10045.4.3.2";
+pub const ECDSA_WITH_SHA384_OID :&str = "1.2.840.10045.4.3.3";
+pub const ECDSA_WITH_SHA512_OID :&str = "1.2.840.10045.4.3.4";
+pub const SM2_WITH_SM3_OID :&str = "1.2.156.10197.1.501";
+
+pub const X509_COMMON_NAME_OID :&str = "2.5.4.3";
+pub const X509_SERIAL_NUMBER_OID :&str = "2.5.4.5";
+pub const X509_COUNTRY_OID :&str = "2.5.4.6";
+pub const X509_LOCALITY_OID :&str = "2.5.4.7";
+pub const X509_STATE_OID :&str = "2.5.4.8";
+pub const X509_ORGANIZATION_OID :&str = "2.5.4.10";
+pub const X509_ORG_UNIT_OID :&str = "2.5.4.11";
+pub const X509_EMAIL_OID :&str = "1.2.840.113549.1.9.1";
+pub const X509_DOMAIN_COMPONENT_OID :&str = "0.9.2342.19200300.100.1.25";
+
+pub const X509_SUBJECT_KEY_ID_OID :&str = "2.5.29.14";
+pub const X509_KEY_USAGE_OID :&str = "2.5.29.15";
+pub const X509_SUBJECT_ALT_NAME_OID :&str = "2.5.29.17";
+pub const X509_BASIC_CONSTRAINTS_OID :&str = "2.5.29.19";
+pub const X509_AUTHORITY_KEY_ID_OID :&str = "2.5.29.35";
+pub const X509_EXT_KEY_USAGE_OID :&str = "2.5.29.37";
+
+pub const X509_EKU_SERVER_AUTH_OID :&str = "1.3.6.1.5.5.7.3.1";
+pub const X509_EKU_CLIENT_AUTH_OID :&str = "1.3.6.1.5.5.7.3.2";
+pub const X509_EKU_CODE_SIGNING_OID :&str = "1.3.6.1.5.5.7.3.3";
+pub const X509_EKU_EMAIL_PROTECTION_OID :&str = "1.3.6.1.5.5.7.3.4";
+pub const X509_EKU_TIME_STAMPING_OID :&str = "1.3.6.1.5.5.7.3.8";
+pub const X509_EKU_OCSP_SIGNING_OID :&str = "1.3.6.1.5.5.7.3.9";
+pub const PKCS9_EXTENSION_REQUEST_OID :&str = "1.2.840.113549.1.9.14";
+
 
 pub const EC_COMPRESSED :&str = "compressed";
 pub const EC_UNCOMPRESSED :&str = "uncompressed";
 pub const EC_HYBRID :&str = "hybrid";
 pub const EC_PARAMS_EXLICIT :&str = "explicit";
+/*explicit with the brainpool seed of RFC 5639 , which openssl does not give*/
+pub const EC_PARAMS_EXLICIT_SEED :&str = "explicit_seed";
 
 pub const EC_SSLEAY_TYPE :&str = "ssleay";
 pub const EC_PKCS8_TYPE :&str = "pkcs8";
@@ -0,0 +1,497 @@
+use crate::cbor::*;
+use crate::consts::*;
+use crate::curve::*;
+use crate::group::*;
+use crate::jws::*;
+use crate::keys::*;
+use crate::signature::*;
+use crate::utils::bigint_to_fixed_bytes;
+#[allow(unused_imports)]
+use crate::logger::*;
+use num_bigint::{BigInt,Sign};
+use num_traits::{zero};
+use std::error::Error;
+
+ecsimple_error_class!{ECCoseError}
+
+/*RFC 9052 7.1 and RFC 9053 7.1.1 labels*/
+const COSE_KEY_KTY :i128 = 1;
+const COSE_KEY_KID :i128 = 2;
+const COSE_KEY_ALG :i128 = 3;
+const COSE_KEY_CRV :i128 = -1;
+const COSE_KEY_X :i128 = -2;
+const COSE_KEY_Y :i128 = -3;
+const COSE_KEY_D :i128 = -4;
+pub const COSE_KTY_EC2 :i128 = 2;
+
+/*RFC 9052 3.1 common header labels*/
+pub const COSE_HEADER_ALG :i128 = 1;
+pub const COSE_HEADER_CRIT :i128 = 2;
+pub const COSE_HEADER_KID :i128 = 4;
+
+pub const COSE_SIGN1_TAG :u64 = 18;
+const COSE_SIGN1_CONTEXT :&str = "Signature1";
+
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECCoseAlg {
+	Es256,
+	Es384,
+	Es512,
+	Es256k,
+}
+
+impl ECCoseAlg {
+	/*IANA COSE Algorithms value , ES256K from RFC 8812*/
+	pub fn value(&self) -> i64 {
+		match self {
+			ECCoseAlg::Es256 => -7,
+			ECCoseAlg::Es384 => -35,
+			ECCoseAlg::Es512 => -36,
+			ECCoseAlg::Es256k => -47,
+		}
+	}
+
+	pub fn from_value(val :i64) -> Result<ECCoseAlg,Box<dyn Error>> {
+		for a in [ECCoseAlg::Es256,ECCoseAlg::Es384,ECCoseAlg::Es512,ECCoseAlg::Es256k].iter() {
+			if a.value() == val {
+				return Ok(*a);
+			}
+		}
+		ecsimple_new_error!{ECCoseError,"unsupported cose alg {}",val}
+	}
+
+	/*same hash curve and signature size as the JOSE one*/
+	pub fn jws_alg(&self) -> ECJwsAlg {
+		match self {
+			ECCoseAlg::Es256 => ECJwsAlg::Es256,
+			ECCoseAlg::Es384 => ECJwsAlg::Es384,
+			ECCoseAlg::Es512 => ECJwsAlg::Es512,
+			ECCoseAlg::Es256k => ECJwsAlg::Es256k,
+		}
+	}
+
+	pub fn from_group(grp :&ECGroup) -> Result<ECCoseAlg,Box<dyn Error>> {
+		for a in [ECCoseAlg::Es256,ECCoseAlg::Es384,ECCoseAlg::Es512,ECCoseAlg::Es256k].iter() {
+			if a.jws_alg().check_group(grp).is_ok() {
+				return Ok(*a);
+			}
+		}
+		ecsimple_new_error!{ECCoseError,"no cose alg for curve [{}]",grp.info().curvename}
+	}
+}
+
+#[derive(Clone)]
+pub struct ECCoseKey {
+	pub pubkey :ECPublicKey,
+	pub privkey :Option<ECPrivateKey>,
+	pub kid :Option<Vec<u8>>,
+	pub alg :Option<i64>,
+}
+
+fn cose_get_int(m :&CborValue,label :i128) -> Result<Option<i64>,Box<dyn Error>> {
+	match m.map_get_int(label) {
+		None => Ok(None),
+		Some(v) => {
+			let iv :Option<i128> = v.as_integer();
+			if iv.is_none() || iv.unwrap() < (i64::MIN as i128) || iv.unwrap() > (i64::MAX as i128) {
+				ecsimple_new_error!{ECCoseError,"label {} not int",label}
+			}
+			Ok(Some(iv.unwrap() as i64))
+		}
+	}
+}
+
+fn cose_get_bytes(m :&CborValue,label :i128) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+	match m.map_get_int(label) {
+		None => Ok(None),
+		Some(v) => {
+			if v.as_bytes().is_none() {
+				ecsimple_new_error!{ECCoseError,"label {} not bstr",label}
+			}
+			Ok(Some(v.as_bytes().unwrap().clone()))
+		}
+	}
+}
+
+impl ECCoseKey {
+	pub fn new_public(pubkey :&ECPublicKey) -> ECCoseKey {
+		ECCoseKey {
+			pubkey : pubkey.clone(),
+			privkey : None,
+			kid : None,
+			alg : None,
+		}
+	}
+
+	pub fn new_private(privkey :&ECPrivateKey) -> ECCoseKey {
+		ECCoseKey {
+			pubkey : privkey.export_pubkey(),
+			privkey : Some(privkey.clone()),
+			kid : None,
+			alg : None,
+		}
+	}
+
+	pub fn from_cbor_value(m :&CborValue) -> Result<ECCoseKey,Box<dyn Error>> {
+		if m.as_map().is_none() {
+			ecsimple_new_error!{ECCoseError,"COSE_Key not map"}
+		}
+		let kty :Option<i64> = cose_get_int(m,COSE_KEY_KTY)?;
+		if kty != Some(COSE_KTY_EC2 as i64) {
+			ecsimple_new_error!{ECCoseError,"kty {:?} not EC2",kty}
+		}
+		let crv :Option<i64> = cose_get_int(m,COSE_KEY_CRV)?;
+		if crv.is_none() {
+			ecsimple_new_error!{ECCoseError,"no crv in COSE_Key"}
+		}
+		let grp :ECGroup = CurveId::from_cose_crv(crv.unwrap())?.group()?;
+		let fsize :usize = grp.info().field_bytes();
+		let x :Option<Vec<u8>> = cose_get_bytes(m,COSE_KEY_X)?;
+		if x.is_none() || x.as_ref().unwrap().len() != fsize {
+			ecsimple_new_error!{ECCoseError,"x not of {} bytes",fsize}
+		}
+		let mut pubbin :Vec<u8> = Vec::new();
+		/*y is bstr or the sign bit of the compressed point*/
+		match m.map_get_int(COSE_KEY_Y) {
+			Some(CborValue::Bytes(y)) => {
+				if y.len() != fsize {
+					ecsimple_new_error!{ECCoseError,"y {} bytes not {}",y.len(),fsize}
+				}
+				pubbin.push(0x04);
+				pubbin.extend_from_slice(x.as_ref().unwrap());
+				pubbin.extend_from_slice(y);
+			},
+			Some(CborValue::Bool(b)) => {
+				pubbin.push(if *b { 0x03 } else { 0x02 });
+				pubbin.extend_from_slice(x.as_ref().unwrap());
+			},
+			_ => {
+				ecsimple_new_error!{ECCoseError,"y not bstr or bool"}
+			}
+		}
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&pubbin)?;
+		let mut privkey :Option<ECPrivateKey> = None;
+		let d :Option<Vec<u8>> = cose_get_bytes(m,COSE_KEY_D)?;
+		if let Some(d) = d.as_ref() {
+			let osize :usize = grp.info().order_bytes();
+			if d.len() != osize {
+				ecsimple_new_error!{ECCoseError,"d {} bytes not {}",d.len(),osize}
+			}
+			let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,d);
+			if privnum == zero() || privnum >= grp.info().order {
+				ecsimple_new_error!{ECCoseError,"d out of range"}
+			}
+			let pk :ECPrivateKey = ECPrivateKey::new(&grp,&privnum);
+			if pk.export_pubkey().to_bin(EC_UNCOMPRESSED)? != pubkey.to_bin(EC_UNCOMPRESSED)? {
+				ecsimple_new_error!{ECCoseError,"x y not match d"}
+			}
+			privkey = Some(pk);
+		}
+		Ok(ECCoseKey {
+			pubkey,
+			privkey,
+			kid : cose_get_bytes(m,COSE_KEY_KID)?,
+			alg : cose_get_int(m,COSE_KEY_ALG)?,
+		})
+	}
+
+	pub fn from_cbor(data :&[u8]) -> Result<ECCoseKey,Box<dyn Error>> {
+		let m :CborValue = cbor_decode(data)?;
+		ECCoseKey::from_cbor_value(&m)
+	}
+
+	pub fn to_cbor_value(&self,withpriv :bool) -> Result<CborValue,Box<dyn Error>> {
+		let grp :ECGroup = self.pubkey.get_group();
+		let cid :Option<CurveId> = grp.curve_id();
+		let crv :i64 = match cid.and_then(|c| c.cose_crv()) {
+			Some(c) => c,
+			None => {
+				ecsimple_new_error!{ECCoseError,"no cose crv for [{}]",grp.info().curvename}
+			},
+		};
+		let fsize :usize = grp.info().field_bytes();
+		let pubbin :Vec<u8> = self.pubkey.to_bin(EC_UNCOMPRESSED)?;
+		let mut m :Vec<(CborValue,CborValue)> = vec![
+			(CborValue::Integer(COSE_KEY_KTY),CborValue::Integer(COSE_KTY_EC2)),
+			(CborValue::Integer(COSE_KEY_CRV),CborValue::Integer(crv as i128)),
+			(CborValue::Integer(COSE_KEY_X),CborValue::Bytes(pubbin[1..(1 + fsize)].to_vec())),
+			(CborValue::Integer(COSE_KEY_Y),CborValue::Bytes(pubbin[(1 + fsize)..].to_vec())),
+		];
+		if withpriv {
+			if self.privkey.is_none() {
+				ecsimple_new_error!{ECCoseError,"no private key"}
+			}
+			let osize :usize = grp.info().order_bytes();
+			let d :Vec<u8> = bigint_to_fixed_bytes(&self.privkey.as_ref().unwrap().get_privnum(),osize)?;
+			m.push((CborValue::Integer(COSE_KEY_D),CborValue::Bytes(d)));
+		}
+		if let Some(kid) = self.kid.as_ref() {
+			m.push((CborValue::Integer(COSE_KEY_KID),CborValue::Bytes(kid.clone())));
+		}
+		if let Some(alg) = self.alg {
+			m.push((CborValue::Integer(COSE_KEY_ALG),CborValue::Integer(alg as i128)));
+		}
+		Ok(CborValue::Map(m))
+	}
+
+	pub fn to_cbor(&self,withpriv :bool) -> Result<Vec<u8>,Box<dyn Error>> {
+		return cbor_encode(&self.to_cbor_value(withpriv)?);
+	}
+}
+
+impl ECPublicKey {
+	pub fn to_cose_key(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		ECCoseKey::new_public(self).to_cbor(false)
+	}
+
+	pub fn from_cose_key(data :&[u8]) -> Result<ECPublicKey,Box<dyn Error>> {
+		Ok(ECCoseKey::from_cbor(data)?.pubkey)
+	}
+}
+
+impl ECPrivateKey {
+	pub fn to_cose_key(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		ECCoseKey::new_private(self).to_cbor(true)
+	}
+
+	pub fn from_cose_key(data :&[u8]) -> Result<ECPrivateKey,Box<dyn Error>> {
+		let k :ECCoseKey = ECCoseKey::from_cbor(data)?;
+		if k.privkey.is_none() {
+			ecsimple_new_error!{ECCoseError,"no d in COSE_Key"}
+		}
+		Ok(k.privkey.unwrap())
+	}
+}
+
+/*COSE_Sign1 of RFC 9052 4.2*/
+#[derive(Clone,Debug)]
+pub struct ECCoseSign1 {
+	/*the serialized protected header as carried , empty for zero length*/
+	pub protected :Vec<u8>,
+	pub unprotected :CborValue,
+	/*None for detached content*/
+	pub payload :Option<Vec<u8>>,
+	pub signature :Vec<u8>,
+}
+
+fn cose_sig_structure(protected :&[u8],external_aad :&[u8],payload :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let sigs :CborValue = CborValue::Array(vec![
+		CborValue::Text(COSE_SIGN1_CONTEXT.to_string()),
+		CborValue::Bytes(protected.to_vec()),
+		CborValue::Bytes(external_aad.to_vec()),
+		CborValue::Bytes(payload.to_vec()),
+	]);
+	cbor_encode(&sigs)
+}
+
+impl ECCoseSign1 {
+	/*alg put in the protected header from the key curve , kid unprotected*/
+	pub fn sign(privkey :&ECPrivateKey,kid :Option<&[u8]>,payload :&[u8],external_aad :&[u8],detached :bool) -> Result<ECCoseSign1,Box<dyn Error>> {
+		let alg :ECCoseAlg = ECCoseAlg::from_group(&privkey.get_group())?;
+		let protmap :CborValue = CborValue::Map(vec![(CborValue::Integer(COSE_HEADER_ALG),CborValue::Integer(alg.value() as i128))]);
+		let protected :Vec<u8> = cbor_encode(&protmap)?;
+		let mut unprot :Vec<(CborValue,CborValue)> = Vec::new();
+		if let Some(kid) = kid {
+			unprot.push((CborValue::Integer(COSE_HEADER_KID),CborValue::Bytes(kid.to_vec())));
+		}
+
+		let tobesigned :Vec<u8> = cose_sig_structure(&protected,external_aad,payload)?;
+		let jalg :ECJwsAlg = alg.jws_alg();
+		let hashv :Vec<u8> = jalg.hash_type().digest(&tobesigned);
+		let sig :ECSignature = privkey.sign_base(&hashv)?;
+		let mut retv :ECCoseSign1 = ECCoseSign1 {
+			protected,
+			unprotected : CborValue::Map(unprot),
+			payload : None,
+			signature : sig.to_fixed_bytes(jalg.sig_part_size())?,
+		};
+		if !detached {
+			retv.payload = Some(payload.to_vec());
+		}
+		Ok(retv)
+	}
+
+	/*tag 18 is optional*/
+	pub fn decode(data :&[u8]) -> Result<ECCoseSign1,Box<dyn Error>> {
+		let mut v :CborValue = cbor_decode(data)?;
+		if let CborValue::Tag(t,inner) = v.clone() {
+			if t != COSE_SIGN1_TAG {
+				ecsimple_new_error!{ECCoseError,"tag {} not COSE_Sign1",t}
+			}
+			v = *inner;
+		}
+		let arr = match v.as_array() {
+			Some(a) => a.clone(),
+			None => {
+				ecsimple_new_error!{ECCoseError,"COSE_Sign1 not array"}
+			}
+		};
+		if arr.len() != 4 {
+			ecsimple_new_error!{ECCoseError,"COSE_Sign1 {} items != 4",arr.len()}
+		}
+		if arr[0].as_bytes().is_none() || arr[1].as_map().is_none() || arr[3].as_bytes().is_none() {
+			ecsimple_new_error!{ECCoseError,"COSE_Sign1 item type invalid"}
+		}
+		let payload :Option<Vec<u8>> = match &arr[2] {
+			CborValue::Bytes(b) => Some(b.clone()),
+			CborValue::Null => None,
+			_ => {
+				ecsimple_new_error!{ECCoseError,"payload not bstr or nil"}
+			}
+		};
+		Ok(ECCoseSign1 {
+			protected : arr[0].as_bytes().unwrap().clone(),
+			unprotected : arr[1].clone(),
+			payload,
+			signature : arr[3].as_bytes().unwrap().clone(),
+		})
+	}
+
+	pub fn encode(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let payload :CborValue = match &self.payload {
+			Some(b) => CborValue::Bytes(b.clone()),
+			None => CborValue::Null,
+		};
+		let v :CborValue = CborValue::Tag(COSE_SIGN1_TAG,Box::new(CborValue::Array(vec![
+			CborValue::Bytes(self.protected.clone()),
+			self.unprotected.clone(),
+			payload,
+			CborValue::Bytes(self.signature.clone()),
+		])));
+		cbor_encode(&v)
+	}
+
+	pub fn protected_map(&self) -> Result<CborValue,Box<dyn Error>> {
+		if self.protected.is_empty() {
+			return Ok(CborValue::Map(Vec::new()));
+		}
+		let m :CborValue = cbor_decode(&self.protected)?;
+		if m.as_map().is_none() {
+			ecsimple_new_error!{ECCoseError,"protected header not map"}
+		}
+		Ok(m)
+	}
+
+	/*alg is only taken from the protected bucket*/
+	pub fn alg(&self) -> Result<ECCoseAlg,Box<dyn Error>> {
+		let m :CborValue = self.protected_map()?;
+		if m.map_get_int(COSE_HEADER_CRIT).is_some() {
+			ecsimple_new_error!{ECCoseError,"crit header not supported"}
+		}
+		let alg :Option<i64> = cose_get_int(&m,COSE_HEADER_ALG)?;
+		if alg.is_none() {
+			ecsimple_new_error!{ECCoseError,"no alg in protected header"}
+		}
+		ECCoseAlg::from_value(alg.unwrap())
+	}
+
+	pub fn kid(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		let m :CborValue = self.protected_map()?;
+		let kid :Option<Vec<u8>> = cose_get_bytes(&m,COSE_HEADER_KID)?;
+		if kid.is_some() {
+			return Ok(kid);
+		}
+		cose_get_bytes(&self.unprotected,COSE_HEADER_KID)
+	}
+
+	fn verify_inner(&self,pubkey :&ECPublicKey,payload :&[u8],external_aad :&[u8]) -> Result<bool,Box<dyn Error>> {
+		let alg :ECCoseAlg = self.alg()?;
+		let jalg :ECJwsAlg = alg.jws_alg();
+		jalg.check_group(&pubkey.get_group())?;
+		if self.signature.len() != 2 * jalg.sig_part_size() {
+			ecsimple_log_trace!("signature {} bytes != {}",self.signature.len(),2 * jalg.sig_part_size());
+			return Ok(false);
+		}
+		let tobesigned :Vec<u8> = cose_sig_structure(&self.protected,external_aad,payload)?;
+		let hashv :Vec<u8> = jalg.hash_type().digest(&tobesigned);
+		let sig :ECSignature = ECSignature::from_fixed_bytes(&self.signature)?;
+		pubkey.verify_base(&sig,&hashv)
+	}
+
+	pub fn verify(&self,pubkey :&ECPublicKey,external_aad :&[u8]) -> Result<bool,Box<dyn Error>> {
+		if self.payload.is_none() {
+			ecsimple_new_error!{ECCoseError,"detached payload , use verify_detached"}
+		}
+		self.verify_inner(pubkey,self.payload.as_ref().unwrap(),external_aad)
+	}
+
+	pub fn verify_detached(&self,pubkey :&ECPublicKey,payload :&[u8],external_aad :&[u8]) -> Result<bool,Box<dyn Error>> {
+		if self.payload.is_some() {
+			ecsimple_new_error!{ECCoseError,"payload not detached"}
+		}
+		self.verify_inner(pubkey,payload,external_aad)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	/*RFC 9052 C.2.1 , the cose-wg Examples ecdsa-sig-01 , signed by the key "11" of C.7.2*/
+	const COSE_EXAMPLE_ECDSA_SIG_01 :&str = "d28443a10126a10442313154546869732069732074686520636f6e74656e742e58408eb33e4ca31d1c465ab05aac34cc6b23d58fef5c083106c4d25a91aef0b0117e2af9a291aa32e14ab834dc56ed2a223444547e01f11d3b0916e5a4c345cacb36";
+	const COSE_KEY_11_D :&str = "57c92077664146e876760c9520d054aa93c3afb04e306705db6090308507b4d3";
+	const COSE_KEY_11_X :&str = "bac5b11cad8f99f9c72b05cf4b9e26d244dc189f745228255a219a86d6a09eff";
+	/*the P-521 key "bilbo.baggins@hobbiton.example" of RFC 9052 C.7.2 , d has a leading zero byte*/
+	const COSE_KEY_BILBO_D :&str = "00085138ddabf5ca975f5860f91a08e91d6d5f9a76ad4018766a476680b55cd339e8ab6c72b5facdb2a2a50ac25bd086647dd3e2e6e99e84ca2c3609fdf177feb26d";
+	/*ES384 and ES512 in the same layout as ecdsa-sig-01 , signed by python cryptography*/
+	const COSE_ES384_X :&str = "0867b085a136878f0c4405abf3af92e15da63af849b66179cc2e87dd3ba793c17394c1754c8abae8989007bdb78576e6";
+	const COSE_ES384_Y :&str = "a702b69b96f6e48e216cef1106ccf401e464d74193e402d3b768572024364df5f6083985f2981d34b56e0b011d5947ea";
+	const COSE_ES384_SIGN1 :&str = "d28444a1013822a104445033383454546869732069732074686520636f6e74656e742e5860ed8020507472e49b089c206e828558431a621ea0fa79a9c561ecdcfdbb25546931f5f3b6ec4769141eabdf45c5e74943a890dad0fb1c6adde46b9a253496583ae1f03bfaa5cd710b2f820e8502987dbec787c91b9746c20544d1107536220a75";
+	const COSE_ES512_SIGN1 :&str = "d28444a1013823a104581e62696c626f2e62616767696e7340686f626269746f6e2e6578616d706c6554546869732069732074686520636f6e74656e742e588400903012ee19ef693fa00eba680ef469f6138615e024ece596fed2a26bc3b3d6617d02ddb0ac321d0f4ada7cb2f708a8838acbfe426f1117778bb6eb9e8669488e4f01671354194a7807b23fed264e5be3668e00b73532e0c7c6d4334c98b9927bba5b2cec68655404f817ba24d23e4097b54be11c944866b0b34c5dd3ad7ca957313fe5";
+
+	fn privkey_from_hex(name :&str,d :&str) -> ECPrivateKey {
+		let grp :ECGroup = ecc_get_curve_group(name).unwrap();
+		let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(d).unwrap());
+		ECPrivateKey::new(&grp,&privnum)
+	}
+
+	#[test]
+	fn test_ecdsa_sig_01() {
+		let privkey :ECPrivateKey = privkey_from_hex(PRIME256v1_NAME,COSE_KEY_11_D);
+		let pubkey :ECPublicKey = privkey.export_pubkey();
+		assert_eq!(&pubkey.to_bin(EC_UNCOMPRESSED).unwrap()[1..33],&Vec::from_hex(COSE_KEY_11_X).unwrap()[..]);
+		let data :Vec<u8> = Vec::from_hex(COSE_EXAMPLE_ECDSA_SIG_01).unwrap();
+		let s1 :ECCoseSign1 = ECCoseSign1::decode(&data).unwrap();
+		assert_eq!(s1.alg().unwrap(),ECCoseAlg::Es256);
+		assert_eq!(s1.kid().unwrap(),Some(b"11".to_vec()));
+		assert_eq!(s1.payload,Some(b"This is the content.".to_vec()));
+		assert!(s1.verify(&pubkey,&[]).unwrap());
+		assert!(!s1.verify(&pubkey,b"aad").unwrap());
+		assert_eq!(s1.encode().unwrap(),data);
+		/*the failure case , one bit of the signature changed*/
+		let mut bad :ECCoseSign1 = s1.clone();
+		bad.signature[0] ^= 0x1;
+		assert!(!bad.verify(&pubkey,&[]).unwrap());
+	}
+
+	#[test]
+	fn test_es384_es512_sign1() {
+		let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME).unwrap();
+		let mut pubbin :Vec<u8> = vec![0x04];
+		pubbin.extend_from_slice(&Vec::from_hex(COSE_ES384_X).unwrap());
+		pubbin.extend_from_slice(&Vec::from_hex(COSE_ES384_Y).unwrap());
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&pubbin).unwrap();
+		let s1 :ECCoseSign1 = ECCoseSign1::decode(&Vec::from_hex(COSE_ES384_SIGN1).unwrap()).unwrap();
+		assert_eq!(s1.alg().unwrap(),ECCoseAlg::Es384);
+		assert!(s1.verify(&pubkey,&[]).unwrap());
+
+		let privkey :ECPrivateKey = privkey_from_hex(SECP521r1_NAME,COSE_KEY_BILBO_D);
+		let s1 :ECCoseSign1 = ECCoseSign1::decode(&Vec::from_hex(COSE_ES512_SIGN1).unwrap()).unwrap();
+		assert_eq!(s1.alg().unwrap(),ECCoseAlg::Es512);
+		assert_eq!(s1.kid().unwrap(),Some(b"bilbo.baggins@hobbiton.example".to_vec()));
+		assert!(s1.verify(&privkey.export_pubkey(),&[]).unwrap());
+		/*the wrong curve for the alg is an error*/
+		assert!(s1.verify(&pubkey,&[]).is_err());
+	}
+
+	#[test]
+	fn test_cose_key_fixed_width() {
+		let privkey :ECPrivateKey = privkey_from_hex(SECP521r1_NAME,COSE_KEY_BILBO_D);
+		let data :Vec<u8> = privkey.to_cose_key().unwrap();
+		let m :CborValue = cbor_decode(&data).unwrap();
+		assert_eq!(m.map_get_int(COSE_KEY_D),Some(&CborValue::Bytes(Vec::from_hex(COSE_KEY_BILBO_D).unwrap())));
+		let back :ECPrivateKey = ECPrivateKey::from_cose_key(&data).unwrap();
+		assert_eq!(back.get_privnum(),privkey.get_privnum());
+	}
+}
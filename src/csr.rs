@@ -0,0 +1,216 @@
+use crate::consts::*;
+use crate::ecasn1::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::x509::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Any,Asn1BitDataFlag};
+use asn1obj::asn1impl::Asn1Op;
+use std::error::Error;
+
+ecsimple_error_class!{ECCsrError}
+
+pub const PEM_CERTIFICATE_REQUEST :&str = "CERTIFICATE REQUEST";
+/*the label of old netscape and openssl versions*/
+pub const PEM_NEW_CERTIFICATE_REQUEST :&str = "NEW CERTIFICATE REQUEST";
+
+const ASN1_SET_TAG :u64 = 0x31;
+
+/*PKCS#10 CertificationRequest , extensions from the extensionRequest attribute*/
+#[derive(Clone)]
+pub struct ECX509Csr {
+	pub der :Vec<u8>,
+	/*the CertificationRequestInfo bytes as signed*/
+	pub info :Vec<u8>,
+	pub subject :ECX509Name,
+	pub spki :Vec<u8>,
+	pub pubkey :ECPublicKey,
+	pub extensions :Vec<ECX509Extension>,
+	pub sigalg :ECX509SigAlg,
+	pub signature :Vec<u8>,
+}
+
+impl ECX509Csr {
+	pub fn from_der(der :&[u8]) -> Result<ECX509Csr,Box<dyn Error>> {
+		let mut req :Asn1X509Req = Asn1X509Req::init_asn1();
+		let used :usize = req.decode_asn1(der)?;
+		if used != der.len() || req.elem.val.len() != 1 {
+			ecsimple_new_error!{ECCsrError,"not one certificate request in {} bytes",der.len()}
+		}
+		let relem :&Asn1X509ReqElem = &req.elem.val[0];
+		let info :Vec<u8> = relem.info.encode_asn1()?;
+		let mut infoasn1 :Asn1X509ReqInfo = Asn1X509ReqInfo::init_asn1();
+		let iused :usize = infoasn1.decode_asn1(&info)?;
+		if iused != info.len() || infoasn1.elem.val.len() != 1 {
+			ecsimple_new_error!{ECCsrError,"CertificationRequestInfo not parsed"}
+		}
+		let ielem :&Asn1X509ReqInfoElem = &infoasn1.elem.val[0];
+		if ielem.version.val != 0 {
+			ecsimple_new_error!{ECCsrError,"request version {} not 0",ielem.version.val}
+		}
+		let sigalg :ECX509SigAlg = ECX509SigAlg::from_oid(&relem.sigalg.elem.val[0].algorithm.get_value())?;
+		let spki :Vec<u8> = ielem.spki.encode_asn1()?;
+		let pubkey :ECPublicKey = ECPublicKey::from_der(&spki)?;
+
+		let mut extensions :Vec<ECX509Extension> = Vec::new();
+		for a in ielem.attributes.val.iter() {
+			let aelem :&Asn1X509AttributeElem = &a.elem.val[0];
+			if aelem.object.get_value() != PKCS9_EXTENSION_REQUEST_OID {
+				ecsimple_log_trace!("skip attribute [{}]",aelem.object.get_value());
+				continue;
+			}
+			if aelem.set.tag != ASN1_SET_TAG {
+				ecsimple_new_error!{ECCsrError,"extensionRequest values tag 0x{:x} not SET",aelem.set.tag}
+			}
+			let mut exts :Asn1X509Extensions = Asn1X509Extensions::init_asn1();
+			let eused :usize = exts.decode_asn1(&aelem.set.content)?;
+			if eused != aelem.set.content.len() {
+				ecsimple_new_error!{ECCsrError,"not one Extensions in extensionRequest"}
+			}
+			extensions = x509_extensions_from_asn1(&exts)?;
+		}
+
+		Ok(ECX509Csr {
+			der : der.to_vec(),
+			info : info.clone(),
+			subject : ECX509Name::from_der(&ielem.subject.encode_asn1()?)?,
+			spki,
+			pubkey,
+			extensions,
+			sigalg,
+			signature : relem.signature.data.clone(),
+		})
+	}
+
+	pub fn from_pem(ins :&str) -> Result<ECX509Csr,Box<dyn Error>> {
+		let b :PemBlock = pem_find_block(ins,&[PEM_CERTIFICATE_REQUEST,PEM_NEW_CERTIFICATE_REQUEST])?;
+		ECX509Csr::from_der(&b.data)
+	}
+
+	pub fn to_der(&self) -> Vec<u8> {
+		self.der.clone()
+	}
+
+	pub fn to_pem(&self) -> String {
+		der_to_pem(&self.der,PEM_CERTIFICATE_REQUEST)
+	}
+
+	/*proof of possession , signed by the key it carries*/
+	pub fn verify(&self) -> Result<bool,Box<dyn Error>> {
+		x509_verify_data(self.sigalg,&self.pubkey,&self.info,&self.signature)
+	}
+
+	pub fn get_extension(&self,oid :&str) -> Option<&ECX509Extension> {
+		self.extensions.iter().find(|&e| e.oid == oid).map(|v| v as _)
+	}
+}
+
+#[derive(Clone)]
+pub struct ECX509CsrBuilder {
+	pub subject :ECX509Name,
+	/*put in an extensionRequest attribute when not empty*/
+	pub extensions :Vec<ECX509Extension>,
+	/*None for ECX509SigAlg::from_key*/
+	pub sigalg :Option<ECX509SigAlg>,
+}
+
+impl ECX509CertBuilder {
+	/*subject and key of a verified request , of the requested extensions only those with oid in allowed are copied*/
+	pub fn from_csr(csr :&ECX509Csr,allowed :&[&str]) -> Result<ECX509CertBuilder,Box<dyn Error>> {
+		if !csr.verify()? {
+			ecsimple_new_error!{ECCsrError,"certificate request signature not valid"}
+		}
+		let mut retv :ECX509CertBuilder = ECX509CertBuilder::new(&csr.subject,&csr.pubkey);
+		for e in csr.extensions.iter() {
+			if allowed.iter().any(|o| *o == e.oid) {
+				retv.add_extension(e.clone());
+			} else {
+				ecsimple_log_trace!("requested extension [{}] not copied",e.oid);
+			}
+		}
+		Ok(retv)
+	}
+}
+
+impl ECX509CsrBuilder {
+	pub fn new(subject :&ECX509Name) -> ECX509CsrBuilder {
+		ECX509CsrBuilder {
+			subject : subject.clone(),
+			extensions : Vec::new(),
+			sigalg : None,
+		}
+	}
+
+	pub fn add_extension(&mut self,ext :ECX509Extension) {
+		x509_put_extension(&mut self.extensions,ext);
+	}
+
+	pub fn sign(&self,privkey :&ECPrivateKey) -> Result<ECX509Csr,Box<dyn Error>> {
+		let pubkey :ECPublicKey = privkey.export_pubkey();
+		let sigalg :ECX509SigAlg = match self.sigalg {
+			Some(a) => a,
+			None => ECX509SigAlg::from_key(&pubkey),
+		};
+		let mut ielem :Asn1X509ReqInfoElem = Asn1X509ReqInfoElem::init_asn1();
+		ielem.version.val = 0;
+		ielem.subject = der_to_any(&self.subject.der)?;
+		ielem.spki = der_to_any(&pubkey.to_der(EC_UNCOMPRESSED,"")?)?;
+		if !self.extensions.is_empty() {
+			let mut aelem :Asn1X509AttributeElem = Asn1X509AttributeElem::init_asn1();
+			let _ = aelem.object.set_value(PKCS9_EXTENSION_REQUEST_OID)?;
+			let mut values :Asn1Any = Asn1Any::init_asn1();
+			values.tag = ASN1_SET_TAG;
+			values.content = x509_extensions_to_asn1(&self.extensions)?.encode_asn1()?;
+			aelem.set = values;
+			let mut attr :Asn1X509Attribute = Asn1X509Attribute::init_asn1();
+			attr.elem.val.push(aelem);
+			ielem.attributes.val.push(attr);
+		}
+		let mut infoasn1 :Asn1X509ReqInfo = Asn1X509ReqInfo::init_asn1();
+		infoasn1.elem.val.push(ielem);
+		let info :Vec<u8> = infoasn1.encode_asn1()?;
+
+		let mut relem :Asn1X509ReqElem = Asn1X509ReqElem::init_asn1();
+		relem.info = der_to_any(&info)?;
+		relem.sigalg = form_algor(sigalg.oid(),None)?;
+		relem.signature = Asn1BitDataFlag::init_asn1();
+		relem.signature.data = x509_sign_data(sigalg,privkey,&info)?;
+		let mut req :Asn1X509Req = Asn1X509Req::init_asn1();
+		req.elem.val.push(relem);
+		return ECX509Csr::from_der(&req.encode_asn1()?);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::group::*;
+
+	#[test]
+	fn test_from_csr_allowed() {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let key :ECPrivateKey = ECPrivateKey::generate(&grp);
+		let mut reqb :ECX509CsrBuilder = ECX509CsrBuilder::new(&ECX509Name::parse("CN=www.example.com").unwrap());
+		reqb.add_extension(ECX509Extension::subject_alt_name(&[ECX509GeneralName::Dns("www.example.com".to_string())]).unwrap());
+		reqb.add_extension(ECX509Extension::basic_constraints(true,None).unwrap());
+		let req :ECX509Csr = reqb.sign(&key).unwrap();
+		assert!(req.verify().unwrap());
+		assert_eq!(req.extensions.len(),2);
+
+		let none :ECX509CertBuilder = ECX509CertBuilder::from_csr(&req,&[]).unwrap();
+		assert_eq!(none.extensions.len(),0);
+		let san :ECX509CertBuilder = ECX509CertBuilder::from_csr(&req,&[X509_SUBJECT_ALT_NAME_OID]).unwrap();
+		assert_eq!(san.extensions.len(),1);
+		assert_eq!(san.extensions[0].oid,X509_SUBJECT_ALT_NAME_OID);
+		let cert :ECX509Cert = san.sign(&key).unwrap();
+		assert_eq!(cert.dns_names().unwrap(),vec!["www.example.com".to_string()]);
+		assert!(cert.basic_constraints().unwrap().is_none());
+
+		let mut der :Vec<u8> = req.der.clone();
+		let l :usize = der.len();
+		der[l - 1] ^= 1;
+		let bad :ECX509Csr = ECX509Csr::from_der(&der).unwrap();
+		assert!(ECX509CertBuilder::from_csr(&bad,&[X509_SUBJECT_ALT_NAME_OID]).is_err());
+	}
+}
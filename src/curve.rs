@@ -1 +1,622 @@
+use crate::group::*;
+use crate::consts::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::error::Error;
 
+ecsimple_error_class!{ECCurveIdError}
+
+/*the built-in named curves , registered ones are Custom*/
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub enum CurveId {
+	Sect163k1,
+	Sect163r1,
+	Sect239k1,
+	Sect113r1,
+	Sect113r2,
+	Secp112r1,
+	Secp112r2,
+	Secp160r1,
+	Secp160k1,
+	Secp256k1,
+	Sect163r2,
+	Sect283k1,
+	Sect283r1,
+	Sect131r1,
+	Sect131r2,
+	Sect193r1,
+	Sect193r2,
+	Sect233k1,
+	Sect233r1,
+	Secp128r1,
+	Secp128r2,
+	Secp160r2,
+	Secp192k1,
+	Secp224k1,
+	Secp224r1,
+	Secp384r1,
+	Secp521r1,
+	Sect409k1,
+	Sect409r1,
+	Sect571k1,
+	Sect571r1,
+	Prime192v1,
+	Prime192v2,
+	Prime192v3,
+	Prime239v1,
+	Prime239v2,
+	Prime239v3,
+	Prime256v1,
+	Wtls8,
+	Wtls9,
+	Wtls12,
+	C2pnb163v1,
+	C2pnb163v2,
+	C2pnb163v3,
+	C2pnb176v1,
+	C2tnb191v1,
+	C2tnb191v2,
+	C2tnb191v3,
+	C2pnb208w1,
+	C2tnb239v1,
+	C2tnb239v2,
+	C2tnb239v3,
+	C2pnb272w1,
+	C2pnb304w1,
+	C2tnb359v1,
+	C2pnb368w1,
+	C2tnb431r1,
+	Wtls1,
+	BrainpoolP160r1,
+	BrainpoolP160t1,
+	BrainpoolP192r1,
+	BrainpoolP192t1,
+	BrainpoolP224r1,
+	BrainpoolP224t1,
+	BrainpoolP256r1,
+	BrainpoolP256t1,
+	BrainpoolP320r1,
+	BrainpoolP320t1,
+	BrainpoolP384r1,
+	BrainpoolP384t1,
+	BrainpoolP512r1,
+	BrainpoolP512t1,
+	Sm2,
+	Custom(String),
+}
+
+fn curve_id_table() -> Vec<(CurveId,&'static str)> {
+	vec![
+		(CurveId::Sect163k1,SECT163k1_NAME),
+		(CurveId::Sect163r1,SECT163r1_NAME),
+		(CurveId::Sect239k1,SECT239k1_NAME),
+		(CurveId::Sect113r1,SECT113r1_NAME),
+		(CurveId::Sect113r2,SECT113r2_NAME),
+		(CurveId::Secp112r1,SECP112r1_NAME),
+		(CurveId::Secp112r2,SECP112r2_NAME),
+		(CurveId::Secp160r1,SECP160r1_NAME),
+		(CurveId::Secp160k1,SECP160k1_NAME),
+		(CurveId::Secp256k1,SECP256k1_NAME),
+		(CurveId::Sect163r2,SECT163r2_NAME),
+		(CurveId::Sect283k1,SECT283k1_NAME),
+		(CurveId::Sect283r1,SECT283r1_NAME),
+		(CurveId::Sect131r1,SECT131r1_NAME),
+		(CurveId::Sect131r2,SECT131r2_NAME),
+		(CurveId::Sect193r1,SECT193r1_NAME),
+		(CurveId::Sect193r2,SECT193r2_NAME),
+		(CurveId::Sect233k1,SECT233k1_NAME),
+		(CurveId::Sect233r1,SECT233r1_NAME),
+		(CurveId::Secp128r1,SECP128r1_NAME),
+		(CurveId::Secp128r2,SECP128r2_NAME),
+		(CurveId::Secp160r2,SECP160r2_NAME),
+		(CurveId::Secp192k1,SECP192k1_NAME),
+		(CurveId::Secp224k1,SECP224k1_NAME),
+		(CurveId::Secp224r1,SECP224r1_NAME),
+		(CurveId::Secp384r1,SECP384r1_NAME),
+		(CurveId::Secp521r1,SECP521r1_NAME),
+		(CurveId::Sect409k1,SECT409k1_NAME),
+		(CurveId::Sect409r1,SECT409r1_NAME),
+		(CurveId::Sect571k1,SECT571k1_NAME),
+		(CurveId::Sect571r1,SECT571r1_NAME),
+		(CurveId::Prime192v1,PRIME192v1_NAME),
+		(CurveId::Prime192v2,PRIME192v2_NAME),
+		(CurveId::Prime192v3,PRIME192v3_NAME),
+		(CurveId::Prime239v1,PRIME239v1_NAME),
+		(CurveId::Prime239v2,PRIME239v2_NAME),
+		(CurveId::Prime239v3,PRIME239v3_NAME),
+		(CurveId::Prime256v1,PRIME256v1_NAME),
+		(CurveId::Wtls8,WTLS8_NAME),
+		(CurveId::Wtls9,WTLS9_NAME),
+		(CurveId::Wtls12,WTLS12_NAME),
+		(CurveId::C2pnb163v1,C2PNB163v1_NAME),
+		(CurveId::C2pnb163v2,C2PNB163v2_NAME),
+		(CurveId::C2pnb163v3,C2PNB163v3_NAME),
+		(CurveId::C2pnb176v1,C2PNB176v1_NAME),
+		(CurveId::C2tnb191v1,C2TNB191v1_NAME),
+		(CurveId::C2tnb191v2,C2TNB191v2_NAME),
+		(CurveId::C2tnb191v3,C2TNB191v3_NAME),
+		(CurveId::C2pnb208w1,C2PNB208w1_NAME),
+		(CurveId::C2tnb239v1,C2TNB239v1_NAME),
+		(CurveId::C2tnb239v2,C2TNB239v2_NAME),
+		(CurveId::C2tnb239v3,C2TNB239v3_NAME),
+		(CurveId::C2pnb272w1,C2PNB272w1_NAME),
+		(CurveId::C2pnb304w1,C2PNB304w1_NAME),
+		(CurveId::C2tnb359v1,C2TNB359v1_NAME),
+		(CurveId::C2pnb368w1,C2PNB368w1_NAME),
+		(CurveId::C2tnb431r1,C2TNB431r1_NAME),
+		(CurveId::Wtls1,WTLS1_NAME),
+		(CurveId::BrainpoolP160r1,BRAINPOOLP160r1_NAME),
+		(CurveId::BrainpoolP160t1,BRAINPOOLP160t1_NAME),
+		(CurveId::BrainpoolP192r1,BRAINPOOLP192r1_NAME),
+		(CurveId::BrainpoolP192t1,BRAINPOOLP192t1_NAME),
+		(CurveId::BrainpoolP224r1,BRAINPOOLP224r1_NAME),
+		(CurveId::BrainpoolP224t1,BRAINPOOLP224t1_NAME),
+		(CurveId::BrainpoolP256r1,BRAINPOOLP256r1_NAME),
+		(CurveId::BrainpoolP256t1,BRAINPOOLP256t1_NAME),
+		(CurveId::BrainpoolP320r1,BRAINPOOLP320r1_NAME),
+		(CurveId::BrainpoolP320t1,BRAINPOOLP320t1_NAME),
+		(CurveId::BrainpoolP384r1,BRAINPOOLP384r1_NAME),
+		(CurveId::BrainpoolP384t1,BRAINPOOLP384t1_NAME),
+		(CurveId::BrainpoolP512r1,BRAINPOOLP512r1_NAME),
+		(CurveId::BrainpoolP512t1,BRAINPOOLP512t1_NAME),
+		(CurveId::Sm2,SM2_NAME),
+	]
+}
+/*alias in lower case to the openssl short name*/
+fn create_curve_aliases() -> HashMap<String,String> {
+	let mut retv :HashMap<String,String> = HashMap::new();
+	let aliases :Vec<(&str,&str)> = vec![
+		/*NIST FIPS 186*/
+		("P-192",PRIME192v1_NAME),
+		("P-224",SECP224r1_NAME),
+		("P-256",PRIME256v1_NAME),
+		("P-384",SECP384r1_NAME),
+		("P-521",SECP521r1_NAME),
+		("K-163",SECT163k1_NAME),
+		("B-163",SECT163r2_NAME),
+		("K-233",SECT233k1_NAME),
+		("B-233",SECT233r1_NAME),
+		("K-283",SECT283k1_NAME),
+		("B-283",SECT283r1_NAME),
+		("K-409",SECT409k1_NAME),
+		("B-409",SECT409r1_NAME),
+		("K-571",SECT571k1_NAME),
+		("B-571",SECT571r1_NAME),
+		/*SECG names which openssl gives X9.62 names*/
+		("secp192r1",PRIME192v1_NAME),
+		("secp256r1",PRIME256v1_NAME),
+		/*SSH RFC 5656*/
+		("nistp192",PRIME192v1_NAME),
+		("nistp224",SECP224r1_NAME),
+		("nistp256",PRIME256v1_NAME),
+		("nistp384",SECP384r1_NAME),
+		("nistp521",SECP521r1_NAME),
+		("nistk163",SECT163k1_NAME),
+		("nistk233",SECT233k1_NAME),
+		("nistb233",SECT233r1_NAME),
+		("nistk283",SECT283k1_NAME),
+		("nistk409",SECT409k1_NAME),
+		("nistb409",SECT409r1_NAME),
+		("nistt571",SECT571k1_NAME),
+		/*JOSE algorithm names*/
+		("ES256",PRIME256v1_NAME),
+		("ES384",SECP384r1_NAME),
+		("ES512",SECP521r1_NAME),
+		("ES256K",SECP256k1_NAME),
+		/*SM2*/
+		("curveSM2",SM2_NAME),
+		("sm2p256v1",SM2_NAME),
+	];
+	for (k,v) in aliases.iter() {
+		retv.insert(k.to_lowercase(),v.to_string());
+	}
+	for (_,v) in curve_id_table().iter() {
+		retv.insert(v.to_lowercase(),v.to_string());
+	}
+	retv
+}
+
+/*TLS NamedGroup RFC 8422 , RFC 7027 and RFC 8998*/
+fn create_tls_groups() -> Vec<(u16,&'static str)> {
+	vec![
+		(1,SECT163k1_NAME),
+		(2,SECT163r1_NAME),
+		(3,SECT163r2_NAME),
+		(4,SECT193r1_NAME),
+		(5,SECT193r2_NAME),
+		(6,SECT233k1_NAME),
+		(7,SECT233r1_NAME),
+		(8,SECT239k1_NAME),
+		(9,SECT283k1_NAME),
+		(10,SECT283r1_NAME),
+		(11,SECT409k1_NAME),
+		(12,SECT409r1_NAME),
+		(13,SECT571k1_NAME),
+		(14,SECT571r1_NAME),
+		(15,SECP160k1_NAME),
+		(16,SECP160r1_NAME),
+		(17,SECP160r2_NAME),
+		(18,SECP192k1_NAME),
+		(19,PRIME192v1_NAME),
+		(20,SECP224k1_NAME),
+		(21,SECP224r1_NAME),
+		(22,SECP256k1_NAME),
+		(23,PRIME256v1_NAME),
+		(24,SECP384r1_NAME),
+		(25,SECP521r1_NAME),
+		(26,BRAINPOOLP256r1_NAME),
+		(27,BRAINPOOLP384r1_NAME),
+		(28,BRAINPOOLP512r1_NAME),
+		/*TLS 1.3 codepoints for brainpool , RFC 8734*/
+		(31,BRAINPOOLP256r1_NAME),
+		(32,BRAINPOOLP384r1_NAME),
+		(33,BRAINPOOLP512r1_NAME),
+		(41,SM2_NAME),
+	]
+}
+
+/*COSE Elliptic Curves registry , with the JOSE crv name when defined*/
+fn create_cose_curves() -> Vec<(i64,&'static str,&'static str)> {
+	vec![
+		(1,PRIME256v1_NAME,"P-256"),
+		(2,SECP384r1_NAME,"P-384"),
+		(3,SECP521r1_NAME,"P-521"),
+		(8,SECP256k1_NAME,"secp256k1"),
+		(256,BRAINPOOLP256r1_NAME,""),
+		(257,BRAINPOOLP320r1_NAME,""),
+		(258,BRAINPOOLP384r1_NAME,""),
+		(259,BRAINPOOLP512r1_NAME,""),
+	]
+}
+
+lazy_static ! {
+	static ref ECC_CURVE_ALIASES :HashMap<String,String> = {
+		create_curve_aliases()
+	};
+	static ref ECC_CURVE_IDS :Vec<(CurveId,&'static str)> = {
+		curve_id_table()
+	};
+	static ref ECC_TLS_GROUPS :Vec<(u16,&'static str)> = {
+		create_tls_groups()
+	};
+	static ref ECC_COSE_CURVES :Vec<(i64,&'static str,&'static str)> = {
+		create_cose_curves()
+	};
+}
+
+/*
+	resolve NIST , SECG , X9.62 , JOSE , SSH and the registered names
+	case insensitive to the name used in ecc_get_curve_group
+*/
+pub fn ecc_resolve_curve_name(name :&str) -> Result<String,Box<dyn Error>> {
+	let names :Vec<String> = ecc_get_curve_names();
+	for k in names.iter() {
+		if k == name {
+			return Ok(k.to_string());
+		}
+	}
+	let lname :String = name.to_lowercase();
+	if let Some(v) = ECC_CURVE_ALIASES.get(&lname) {
+ 			return Ok(v.to_string());
+ 		}
+	for k in names.iter() {
+		if k.to_lowercase() == lname {
+			return Ok(k.to_string());
+		}
+	}
+	ecsimple_new_error!{ECCurveIdError,"can not resolve curve name [{}]",name}
+}
+
+impl CurveId {
+	/*the openssl short name*/
+	pub fn name(&self) -> String {
+		if let CurveId::Custom(n) = self {
+  				return n.to_string();
+  			}
+		for (k,v) in ECC_CURVE_IDS.iter() {
+			if k == self {
+				return v.to_string();
+			}
+		}
+		"".to_string()
+	}
+
+	pub fn from_name(name :&str) -> Result<CurveId,Box<dyn Error>> {
+		let cname :String = ecc_resolve_curve_name(name)?;
+		for (k,v) in ECC_CURVE_IDS.iter() {
+			if *v == cname {
+				return Ok(k.clone());
+			}
+		}
+		Ok(CurveId::Custom(cname))
+	}
+
+	pub fn oid(&self) -> Result<String,Box<dyn Error>> {
+		ecc_get_oid_from_name(&self.name())
+	}
+
+	pub fn from_oid(oid :&str) -> Result<CurveId,Box<dyn Error>> {
+		let name :String = ecc_get_name_from_oid(oid)?;
+		CurveId::from_name(&name)
+	}
+
+	/*for brainpool the TLS 1.2 codepoint is given*/
+	pub fn tls_group(&self) -> Option<u16> {
+		let name :String = self.name();
+		for (k,v) in ECC_TLS_GROUPS.iter() {
+			if *v == name {
+				return Some(*k);
+			}
+		}
+		None
+	}
+
+	pub fn from_tls_group(val :u16) -> Result<CurveId,Box<dyn Error>> {
+		for (k,v) in ECC_TLS_GROUPS.iter() {
+			if *k == val {
+				return CurveId::from_name(v);
+			}
+		}
+		ecsimple_new_error!{ECCurveIdError,"unsupported tls group [0x{:04x}]",val}
+	}
+
+	pub fn cose_crv(&self) -> Option<i64> {
+		let name :String = self.name();
+		for (k,v,_) in ECC_COSE_CURVES.iter() {
+			if *v == name {
+				return Some(*k);
+			}
+		}
+		None
+	}
+
+	pub fn from_cose_crv(val :i64) -> Result<CurveId,Box<dyn Error>> {
+		for (k,v,_) in ECC_COSE_CURVES.iter() {
+			if *k == val {
+				return CurveId::from_name(v);
+			}
+		}
+		ecsimple_new_error!{ECCurveIdError,"unsupported cose crv [{}]",val}
+	}
+
+	/*JOSE crv value , RFC 7518 and RFC 8812*/
+	pub fn jose_crv(&self) -> Option<String> {
+		let name :String = self.name();
+		for (_,v,j) in ECC_COSE_CURVES.iter() {
+			if *v == name && !j.is_empty() {
+				return Some(j.to_string());
+			}
+		}
+		None
+	}
+
+	pub fn from_jose_crv(crv :&str) -> Result<CurveId,Box<dyn Error>> {
+		for (_,v,j) in ECC_COSE_CURVES.iter() {
+			if !j.is_empty() && *j == crv {
+				return CurveId::from_name(v);
+			}
+		}
+		ecsimple_new_error!{ECCurveIdError,"unsupported jose crv [{}]",crv}
+	}
+
+	pub fn group(&self) -> Result<ECGroup,Box<dyn Error>> {
+		ecc_get_curve_group(&self.name())
+	}
+}
+
+impl std::fmt::Display for CurveId {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f,"{}",self.name())
+	}
+}
+
+impl std::str::FromStr for CurveId {
+	type Err = Box<dyn Error>;
+	fn from_str(s :&str) -> Result<CurveId,Box<dyn Error>> {
+		CurveId::from_name(s)
+	}
+}
+
+impl ECGroup {
+	/*None for the anonymous explicit parameters*/
+	pub fn curve_id(&self) -> Option<CurveId> {
+		let name :String = if self.is_prime_group() {
+			self.get_prime_group().curvename.clone()
+		} else {
+			self.get_bn_group().curvename.clone()
+		};
+		if name.is_empty() {
+			return None;
+		}
+		CurveId::from_name(&name).ok()
+
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/*every variant with its tls group , cose crv and jose crv , the brainpool tls groups are the TLS 1.2 ones*/
+	fn test_curve_id_expect(id :&CurveId) -> (Option<u16>,Option<i64>,Option<&'static str>) {
+		match id {
+			CurveId::Sect163k1 => (Some(1),None,None),
+			CurveId::Sect163r1 => (Some(2),None,None),
+			CurveId::Sect239k1 => (Some(8),None,None),
+			CurveId::Sect113r1 => (None,None,None),
+			CurveId::Sect113r2 => (None,None,None),
+			CurveId::Secp112r1 => (None,None,None),
+			CurveId::Secp112r2 => (None,None,None),
+			CurveId::Secp160r1 => (Some(16),None,None),
+			CurveId::Secp160k1 => (Some(15),None,None),
+			CurveId::Secp256k1 => (Some(22),Some(8),Some("secp256k1")),
+			CurveId::Sect163r2 => (Some(3),None,None),
+			CurveId::Sect283k1 => (Some(9),None,None),
+			CurveId::Sect283r1 => (Some(10),None,None),
+			CurveId::Sect131r1 => (None,None,None),
+			CurveId::Sect131r2 => (None,None,None),
+			CurveId::Sect193r1 => (Some(4),None,None),
+			CurveId::Sect193r2 => (Some(5),None,None),
+			CurveId::Sect233k1 => (Some(6),None,None),
+			CurveId::Sect233r1 => (Some(7),None,None),
+			CurveId::Secp128r1 => (None,None,None),
+			CurveId::Secp128r2 => (None,None,None),
+			CurveId::Secp160r2 => (Some(17),None,None),
+			CurveId::Secp192k1 => (Some(18),None,None),
+			CurveId::Secp224k1 => (Some(20),None,None),
+			CurveId::Secp224r1 => (Some(21),None,None),
+			CurveId::Secp384r1 => (Some(24),Some(2),Some("P-384")),
+			CurveId::Secp521r1 => (Some(25),Some(3),Some("P-521")),
+			CurveId::Sect409k1 => (Some(11),None,None),
+			CurveId::Sect409r1 => (Some(12),None,None),
+			CurveId::Sect571k1 => (Some(13),None,None),
+			CurveId::Sect571r1 => (Some(14),None,None),
+			CurveId::Prime192v1 => (Some(19),None,None),
+			CurveId::Prime192v2 => (None,None,None),
+			CurveId::Prime192v3 => (None,None,None),
+			CurveId::Prime239v1 => (None,None,None),
+			CurveId::Prime239v2 => (None,None,None),
+			CurveId::Prime239v3 => (None,None,None),
+			CurveId::Prime256v1 => (Some(23),Some(1),Some("P-256")),
+			CurveId::Wtls8 => (None,None,None),
+			CurveId::Wtls9 => (None,None,None),
+			CurveId::Wtls12 => (None,None,None),
+			CurveId::C2pnb163v1 => (None,None,None),
+			CurveId::C2pnb163v2 => (None,None,None),
+			CurveId::C2pnb163v3 => (None,None,None),
+			CurveId::C2pnb176v1 => (None,None,None),
+			CurveId::C2tnb191v1 => (None,None,None),
+			CurveId::C2tnb191v2 => (None,None,None),
+			CurveId::C2tnb191v3 => (None,None,None),
+			CurveId::C2pnb208w1 => (None,None,None),
+			CurveId::C2tnb239v1 => (None,None,None),
+			CurveId::C2tnb239v2 => (None,None,None),
+			CurveId::C2tnb239v3 => (None,None,None),
+			CurveId::C2pnb272w1 => (None,None,None),
+			CurveId::C2pnb304w1 => (None,None,None),
+			CurveId::C2tnb359v1 => (None,None,None),
+			CurveId::C2pnb368w1 => (None,None,None),
+			CurveId::C2tnb431r1 => (None,None,None),
+			CurveId::Wtls1 => (None,None,None),
+			CurveId::BrainpoolP160r1 => (None,None,None),
+			CurveId::BrainpoolP160t1 => (None,None,None),
+			CurveId::BrainpoolP192r1 => (None,None,None),
+			CurveId::BrainpoolP192t1 => (None,None,None),
+			CurveId::BrainpoolP224r1 => (None,None,None),
+			CurveId::BrainpoolP224t1 => (None,None,None),
+			CurveId::BrainpoolP256r1 => (Some(26),Some(256),None),
+			CurveId::BrainpoolP256t1 => (None,None,None),
+			CurveId::BrainpoolP320r1 => (None,Some(257),None),
+			CurveId::BrainpoolP320t1 => (None,None,None),
+			CurveId::BrainpoolP384r1 => (Some(27),Some(258),None),
+			CurveId::BrainpoolP384t1 => (None,None,None),
+			CurveId::BrainpoolP512r1 => (Some(28),Some(259),None),
+			CurveId::BrainpoolP512t1 => (None,None,None),
+			CurveId::Sm2 => (Some(41),None,None),
+			CurveId::Custom(_) => (None,None,None),
+		}
+	}
+
+	#[test]
+	fn test_curve_id_table() {
+		let table :Vec<(CurveId,&'static str)> = curve_id_table();
+		let mut oids :Vec<String> = Vec::new();
+		for (id,name) in table.iter() {
+			assert_eq!(id.name(),*name);
+			assert_eq!(format!("{}",id),*name);
+			assert_eq!(CurveId::from_name(name).unwrap(),*id);
+			assert_eq!(CurveId::from_name(&name.to_uppercase()).unwrap(),*id);
+			assert_eq!(name.parse::<CurveId>().unwrap(),*id);
+			let oid :String = id.oid().unwrap();
+			assert_eq!(CurveId::from_oid(&oid).unwrap(),*id,"{}",oid);
+			assert!(!oids.contains(&oid),"{} oid {}",name,oid);
+			oids.push(oid);
+			assert_eq!(id.group().unwrap().curve_id(),Some(id.clone()));
+
+			let (tls,cose,jose) = test_curve_id_expect(id);
+			assert_eq!(id.tls_group(),tls,"{}",name);
+			if let Some(t) = tls {
+				assert_eq!(CurveId::from_tls_group(t).unwrap(),*id);
+			}
+			assert_eq!(id.cose_crv(),cose,"{}",name);
+			if let Some(c) = cose {
+				assert_eq!(CurveId::from_cose_crv(c).unwrap(),*id);
+			}
+			assert_eq!(id.jose_crv(),jose.map(|j| j.to_string()),"{}",name);
+			if let Some(j) = jose {
+				assert_eq!(CurveId::from_jose_crv(j).unwrap(),*id);
+			}
+		}
+		/*each built-in name is a variant*/
+		let builtin :HashMap<String,String> = create_name_to_oid();
+		assert_eq!(builtin.len(),table.len());
+		for (name,oid) in builtin.iter() {
+			assert!(table.iter().any(|(_,v)| v == name),"{}",name);
+			assert!(oids.contains(oid),"{}",name);
+		}
+
+		/*TLS 1.3 brainpool codepoints*/
+		assert_eq!(CurveId::from_tls_group(31).unwrap(),CurveId::BrainpoolP256r1);
+		assert_eq!(CurveId::from_tls_group(32).unwrap(),CurveId::BrainpoolP384r1);
+		assert_eq!(CurveId::from_tls_group(33).unwrap(),CurveId::BrainpoolP512r1);
+		assert!(CurveId::from_tls_group(29).is_err());
+		assert!(CurveId::from_cose_crv(4).is_err());
+		assert!(CurveId::from_jose_crv("Ed25519").is_err());
+		assert!(CurveId::from_name("no-such-curve").is_err());
+		assert!(CurveId::from_oid("1.3.6.1.4.1.99999.1").is_err());
+		let custom :CurveId = CurveId::Custom("no-such-curve".to_string());
+		assert!(custom.oid().is_err());
+		assert!(custom.tls_group().is_none());
+	}
+
+	#[test]
+	fn test_curve_id_aliases() {
+		let aliases :Vec<(&str,CurveId)> = vec![
+			("P-192",CurveId::Prime192v1),
+			("P-224",CurveId::Secp224r1),
+			("P-256",CurveId::Prime256v1),
+			("P-384",CurveId::Secp384r1),
+			("P-521",CurveId::Secp521r1),
+			("K-163",CurveId::Sect163k1),
+			("B-163",CurveId::Sect163r2),
+			("K-233",CurveId::Sect233k1),
+			("B-233",CurveId::Sect233r1),
+			("K-283",CurveId::Sect283k1),
+			("B-283",CurveId::Sect283r1),
+			("K-409",CurveId::Sect409k1),
+			("B-409",CurveId::Sect409r1),
+			("K-571",CurveId::Sect571k1),
+			("B-571",CurveId::Sect571r1),
+			("secp192r1",CurveId::Prime192v1),
+			("secp256r1",CurveId::Prime256v1),
+			("nistp192",CurveId::Prime192v1),
+			("nistp224",CurveId::Secp224r1),
+			("nistp256",CurveId::Prime256v1),
+			("nistp384",CurveId::Secp384r1),
+			("nistp521",CurveId::Secp521r1),
+			("nistk163",CurveId::Sect163k1),
+			("nistk233",CurveId::Sect233k1),
+			("nistb233",CurveId::Sect233r1),
+			("nistk283",CurveId::Sect283k1),
+			("nistk409",CurveId::Sect409k1),
+			("nistb409",CurveId::Sect409r1),
+			("nistt571",CurveId::Sect571k1),
+			("ES256",CurveId::Prime256v1),
+			("ES384",CurveId::Secp384r1),
+			("ES512",CurveId::Secp521r1),
+			("ES256K",CurveId::Secp256k1),
+			("curveSM2",CurveId::Sm2),
+			("sm2p256v1",CurveId::Sm2),
+		];
+		for (alias,id) in aliases.iter() {
+			assert_eq!(CurveId::from_name(alias).unwrap(),*id,"{}",alias);
+			assert_eq!(alias.to_lowercase().parse::<CurveId>().unwrap(),*id,"{}",alias);
+			assert_eq!(CurveId::from_name(&alias.to_uppercase()).unwrap(),*id,"{}",alias);
+			let grp :ECGroup = ecc_get_curve_group(alias).unwrap();
+			assert!(grp.eq_params(&id.group().unwrap()),"{}",alias);
+			assert_eq!(grp.curve_id(),Some(id.clone()),"{}",alias);
+		}
+	}
+}
@@ -0,0 +1,789 @@
+use crate::consts::*;
+use crate::group::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::signature::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use chrono::{NaiveDateTime,TimeZone,Utc};
+use std::error::Error;
+
+ecsimple_error_class!{ECDnssecError}
+
+pub const DNS_TYPE_A :u16 = 1;
+pub const DNS_TYPE_NS :u16 = 2;
+pub const DNS_TYPE_CNAME :u16 = 5;
+pub const DNS_TYPE_SOA :u16 = 6;
+pub const DNS_TYPE_PTR :u16 = 12;
+pub const DNS_TYPE_MX :u16 = 15;
+pub const DNS_TYPE_TXT :u16 = 16;
+pub const DNS_TYPE_AAAA :u16 = 28;
+pub const DNS_TYPE_SRV :u16 = 33;
+pub const DNS_TYPE_NAPTR :u16 = 35;
+pub const DNS_TYPE_DNAME :u16 = 39;
+pub const DNS_TYPE_DS :u16 = 43;
+pub const DNS_TYPE_RRSIG :u16 = 46;
+pub const DNS_TYPE_NSEC :u16 = 47;
+pub const DNS_TYPE_DNSKEY :u16 = 48;
+pub const DNS_CLASS_IN :u16 = 1;
+
+pub const DNS_KEY_FLAG_ZONE :u16 = 0x0100;
+pub const DNS_KEY_FLAG_SEP :u16 = 0x0001;
+/*RFC 4034 2.1.2*/
+pub const DNS_KEY_PROTOCOL :u8 = 3;
+
+const DNS_NAME_MAX :usize = 255;
+const DNS_LABEL_MAX :usize = 63;
+
+/*RFC 6605 2*/
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECDnsAlg {
+	EcdsaP256Sha256,
+	EcdsaP384Sha384,
+}
+
+impl ECDnsAlg {
+	pub fn number(&self) -> u8 {
+		match self {
+			ECDnsAlg::EcdsaP256Sha256 => 13,
+			ECDnsAlg::EcdsaP384Sha384 => 14,
+		}
+	}
+
+	pub fn from_number(v :u8) -> Result<ECDnsAlg,Box<dyn Error>> {
+		for a in [ECDnsAlg::EcdsaP256Sha256,ECDnsAlg::EcdsaP384Sha384].iter() {
+			if a.number() == v {
+				return Ok(*a);
+			}
+		}
+		ecsimple_new_error!{ECDnssecError,"unsupported dnssec algorithm {}",v}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			ECDnsAlg::EcdsaP256Sha256 => "ECDSAP256SHA256",
+			ECDnsAlg::EcdsaP384Sha384 => "ECDSAP384SHA384",
+		}
+	}
+
+	pub fn hash_type(&self) -> ECHashType {
+		match self {
+			ECDnsAlg::EcdsaP256Sha256 => ECHashType::Sha256,
+			ECDnsAlg::EcdsaP384Sha384 => ECHashType::Sha384,
+		}
+	}
+
+	pub fn curve_name(&self) -> &'static str {
+		match self {
+			ECDnsAlg::EcdsaP256Sha256 => PRIME256v1_NAME,
+			ECDnsAlg::EcdsaP384Sha384 => SECP384r1_NAME,
+		}
+	}
+
+	/*bytes of r and of s , and of x and of y in the key*/
+	pub fn sig_part_size(&self) -> usize {
+		match self {
+			ECDnsAlg::EcdsaP256Sha256 => 32,
+			ECDnsAlg::EcdsaP384Sha384 => 48,
+		}
+	}
+
+	pub fn from_group(grp :&ECGroup) -> Result<ECDnsAlg,Box<dyn Error>> {
+		for a in [ECDnsAlg::EcdsaP256Sha256,ECDnsAlg::EcdsaP384Sha384].iter() {
+			let algrp :ECGroup = ecc_get_curve_group(a.curve_name())?;
+			if grp.eq_params(&algrp) {
+				return Ok(*a);
+			}
+		}
+		ecsimple_new_error!{ECDnssecError,"no dnssec algorithm for curve [{}]",grp.info().curvename}
+	}
+}
+
+/*RFC 4509 and RFC 6605 DS digest types*/
+pub fn dns_ds_digest_id(htype :ECHashType) -> Result<u8,Box<dyn Error>> {
+	match htype {
+		ECHashType::Sha1 => Ok(1),
+		ECHashType::Sha256 => Ok(2),
+		ECHashType::Sha384 => Ok(4),
+		_ => {
+			ecsimple_new_error!{ECDnssecError,"no DS digest type for {}",htype.name()}
+		}
+	}
+}
+
+pub fn dns_ds_digest_from_id(id :u8) -> Result<ECHashType,Box<dyn Error>> {
+	for h in [ECHashType::Sha1,ECHashType::Sha256,ECHashType::Sha384].iter() {
+		if dns_ds_digest_id(*h)? == id {
+			return Ok(*h);
+		}
+	}
+	ecsimple_new_error!{ECDnssecError,"DS digest type {} not supported",id}
+}
+
+fn dns_type_table() -> [(u16,&'static str);15] {
+	[
+		(DNS_TYPE_A,"A"),
+		(DNS_TYPE_NS,"NS"),
+		(DNS_TYPE_CNAME,"CNAME"),
+		(DNS_TYPE_SOA,"SOA"),
+		(DNS_TYPE_PTR,"PTR"),
+		(DNS_TYPE_MX,"MX"),
+		(DNS_TYPE_TXT,"TXT"),
+		(DNS_TYPE_AAAA,"AAAA"),
+		(DNS_TYPE_SRV,"SRV"),
+		(DNS_TYPE_NAPTR,"NAPTR"),
+		(DNS_TYPE_DNAME,"DNAME"),
+		(DNS_TYPE_DS,"DS"),
+		(DNS_TYPE_RRSIG,"RRSIG"),
+		(DNS_TYPE_NSEC,"NSEC"),
+		(DNS_TYPE_DNSKEY,"DNSKEY"),
+	]
+}
+
+/*TYPEnnn of RFC 3597 for the ones not in the table*/
+pub fn dns_type_name(rtype :u16) -> String {
+	for (t,n) in dns_type_table().iter() {
+		if *t == rtype {
+			return n.to_string();
+		}
+	}
+	format!("TYPE{}",rtype)
+}
+
+pub fn dns_type_from_name(name :&str) -> Result<u16,Box<dyn Error>> {
+	let uname :String = name.to_uppercase();
+	for (t,n) in dns_type_table().iter() {
+		if *n == uname {
+			return Ok(*t);
+		}
+	}
+	if let Some(tnum) = uname.strip_prefix("TYPE") {
+		if let Ok(v) = tnum.parse::<u16>() {
+			return Ok(v);
+		}
+	}
+
+	ecsimple_new_error!{ECDnssecError,"unknown rr type [{}]",name}
+}
+
+/*labels of a presentation name with \. and \DDD escapes , root gives none*/
+fn dns_name_split(name :&str) -> Result<Vec<Vec<u8>>,Box<dyn Error>> {
+	let mut retv :Vec<Vec<u8>> = Vec::new();
+	let b :&[u8] = name.as_bytes();
+	if name == "." {
+		return Ok(retv);
+	}
+	let mut cur :Vec<u8> = Vec::new();
+	let mut idx :usize = 0;
+	while idx < b.len() {
+		let c :u8 = b[idx];
+		if c == b'\\' {
+			if idx + 3 < b.len() && b[idx+1..idx+4].iter().all(|d| d.is_ascii_digit()) {
+				let v :u32 = std::str::from_utf8(&b[idx+1..idx+4])?.parse::<u32>()?;
+				if v > 255 {
+					ecsimple_new_error!{ECDnssecError,"escape \\{} in [{}]",v,name}
+				}
+				cur.push(v as u8);
+				idx += 4;
+				continue;
+			}
+			if idx + 1 >= b.len() {
+				ecsimple_new_error!{ECDnssecError,"trailing escape in [{}]",name}
+			}
+			cur.push(b[idx+1]);
+			idx += 2;
+			continue;
+		}
+		if c == b'.' {
+			if cur.is_empty() {
+				ecsimple_new_error!{ECDnssecError,"empty label in [{}]",name}
+			}
+			retv.push(cur.clone());
+			cur = Vec::new();
+		} else {
+			cur.push(c);
+		}
+		idx += 1;
+	}
+	if !cur.is_empty() {
+		retv.push(cur);
+	}
+	for l in retv.iter() {
+		if l.len() > DNS_LABEL_MAX {
+			ecsimple_new_error!{ECDnssecError,"label of {} bytes in [{}]",l.len(),name}
+		}
+	}
+	Ok(retv)
+}
+
+/*RFC 4034 6.2 canonical wire form , lowercase and uncompressed , relative names taken as absolute*/
+pub fn dns_name_to_wire(name :&str) -> Result<Vec<u8>,Box<dyn Error>> {
+	let mut retv :Vec<u8> = Vec::new();
+	for l in dns_name_split(name)?.iter() {
+		retv.push(l.len() as u8);
+		retv.extend(l.iter().map(|c| c.to_ascii_lowercase()));
+	}
+	retv.push(0);
+	if retv.len() > DNS_NAME_MAX {
+		ecsimple_new_error!{ECDnssecError,"[{}] of {} bytes",name,retv.len()}
+	}
+	Ok(retv)
+}
+
+/*RFC 4034 3.1.3 , root and a leading * not counted*/
+pub fn dns_name_labels(name :&str) -> Result<u8,Box<dyn Error>> {
+	let labels :Vec<Vec<u8>> = dns_name_split(name)?;
+	if !labels.is_empty() && labels[0] == b"*".to_vec() {
+		return Ok((labels.len() - 1) as u8);
+	}
+	Ok(labels.len() as u8)
+}
+
+/*wire name at off lowercased into v , the offset after it*/
+fn dns_wire_name_lower(rdata :&[u8],off :usize,v :&mut Vec<u8>) -> Result<usize,Box<dyn Error>> {
+	let mut idx :usize = off;
+	loop {
+		if idx >= rdata.len() {
+			ecsimple_new_error!{ECDnssecError,"name at {} past rdata",off}
+		}
+		let l :usize = rdata[idx] as usize;
+		if l > DNS_LABEL_MAX {
+			ecsimple_new_error!{ECDnssecError,"compressed or bad label 0x{:02x} in rdata",l}
+		}
+		if idx + 1 + l > rdata.len() {
+			ecsimple_new_error!{ECDnssecError,"label at {} past rdata",idx}
+		}
+		v.push(rdata[idx]);
+		v.extend(rdata[(idx+1)..(idx+1+l)].iter().map(|c| c.to_ascii_lowercase()));
+		idx += 1 + l;
+		if l == 0 {
+			return Ok(idx);
+		}
+	}
+}
+
+/*RFC 4034 6.2 item 3 as updated by RFC 6840 5.1 , (octets before , names)*/
+fn dns_rdata_names(rtype :u16) -> Option<(usize,usize)> {
+	match rtype {
+		2 | 3 | 4 | 5 | 7 | 8 | 9 | 12 | 39 => Some((0,1)),
+		6 | 14 | 17 => Some((0,2)),
+		15 | 18 | 21 | 36 => Some((2,1)),
+		26 => Some((2,2)),
+		33 => Some((6,1)),
+		35 => Some((4,1)),
+		_ => None,
+	}
+}
+
+/*names embedded in the rdata of the types listed by RFC 4034 lowercased*/
+pub fn dns_canonical_rdata(rtype :u16,rdata :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let (mut pre,names) = match dns_rdata_names(rtype) {
+		Some(v) => v,
+		None => {
+			return Ok(rdata.to_vec());
+		}
+	};
+	if rtype == DNS_TYPE_NAPTR {
+		/*RFC 3403 4.1 , flags services and regexp character strings before the replacement*/
+		for _ in 0..3 {
+			if pre >= rdata.len() {
+				ecsimple_new_error!{ECDnssecError,"NAPTR rdata of {} bytes",rdata.len()}
+			}
+			pre += 1 + rdata[pre] as usize;
+		}
+	}
+	if rdata.len() < pre {
+		ecsimple_new_error!{ECDnssecError,"{} rdata of {} bytes",dns_type_name(rtype),rdata.len()}
+	}
+	let mut retv :Vec<u8> = rdata[..pre].to_vec();
+	let mut off :usize = pre;
+	for _ in 0..names {
+		off = dns_wire_name_lower(rdata,off,&mut retv)?;
+	}
+	retv.extend_from_slice(&rdata[off..]);
+	Ok(retv)
+}
+
+/*YYYYMMDDHHmmSS in UTC*/
+pub fn dns_time_to_text(t :u32) -> String {
+	match Utc.timestamp_opt(t as i64,0).single() {
+		Some(v) => v.format("%Y%m%d%H%M%S").to_string(),
+		None => format!("{}",t),
+	}
+}
+
+/*RFC 4034 3.2 , YYYYMMDDHHmmSS or plain seconds*/
+pub fn dns_time_from_text(s :&str) -> Result<u32,Box<dyn Error>> {
+	if !s.bytes().all(|c| c.is_ascii_digit()) || s.is_empty() {
+		ecsimple_new_error!{ECDnssecError,"time [{}] not valid",s}
+	}
+	if s.len() != 14 {
+		return Ok(s.parse::<u32>()?);
+	}
+	let ndt :NaiveDateTime = match NaiveDateTime::parse_from_str(s,"%Y%m%d%H%M%S") {
+		Ok(v) => v,
+		Err(e) => {
+			ecsimple_new_error!{ECDnssecError,"time [{}] not valid {:?}",s,e}
+		}
+	};
+	Ok(Utc.from_utc_datetime(&ndt).timestamp() as u32)
+}
+
+/*one resource record with wire rdata*/
+#[derive(Clone,Debug)]
+pub struct ECDnsRecord {
+	pub name :String,
+	pub rtype :u16,
+	pub class :u16,
+	pub ttl :u32,
+	pub rdata :Vec<u8>,
+}
+
+impl ECDnsRecord {
+	pub fn new(name :&str,rtype :u16,ttl :u32,rdata :&[u8]) -> ECDnsRecord {
+		ECDnsRecord {
+			name : name.to_string(),
+			rtype,
+			class : DNS_CLASS_IN,
+			ttl,
+			rdata : rdata.to_vec(),
+		}
+	}
+}
+
+/*DNSKEY RDATA with the RFC 6605 4 public key X || Y*/
+#[derive(Clone)]
+pub struct ECDnsKey {
+	pub flags :u16,
+	pub protocol :u8,
+	pub alg :ECDnsAlg,
+	pub pubkey :ECPublicKey,
+}
+
+impl ECDnsKey {
+	/*DNS_KEY_FLAG_ZONE for a ZSK , with DNS_KEY_FLAG_SEP for a KSK*/
+	pub fn new(pubkey :&ECPublicKey,flags :u16) -> Result<ECDnsKey,Box<dyn Error>> {
+		Ok(ECDnsKey {
+			flags,
+			protocol : DNS_KEY_PROTOCOL,
+			alg : ECDnsAlg::from_group(&pubkey.get_group())?,
+			pubkey : pubkey.clone(),
+		})
+	}
+
+	pub fn public_key_bytes(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let bin :Vec<u8> = self.pubkey.to_bin(EC_UNCOMPRESSED)?;
+		if bin.len() != 1 + 2 * self.alg.sig_part_size() {
+			ecsimple_new_error!{ECDnssecError,"point of {} bytes for {}",bin.len(),self.alg.name()}
+		}
+		Ok(bin[1..].to_vec())
+	}
+
+	pub fn to_rdata(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = self.flags.to_be_bytes().to_vec();
+		retv.push(self.protocol);
+		retv.push(self.alg.number());
+		retv.extend_from_slice(&self.public_key_bytes()?);
+		Ok(retv)
+	}
+
+	pub fn from_rdata(rdata :&[u8]) -> Result<ECDnsKey,Box<dyn Error>> {
+		if rdata.len() < 4 {
+			ecsimple_new_error!{ECDnssecError,"DNSKEY rdata of {} bytes",rdata.len()}
+		}
+		let flags :u16 = u16::from_be_bytes([rdata[0],rdata[1]]);
+		if rdata[2] != DNS_KEY_PROTOCOL {
+			ecsimple_new_error!{ECDnssecError,"DNSKEY protocol {} not {}",rdata[2],DNS_KEY_PROTOCOL}
+		}
+		let alg :ECDnsAlg = ECDnsAlg::from_number(rdata[3])?;
+		if rdata.len() != 4 + 2 * alg.sig_part_size() {
+			ecsimple_new_error!{ECDnssecError,"{} key of {} bytes",alg.name(),rdata.len() - 4}
+		}
+		let grp :ECGroup = ecc_get_curve_group(alg.curve_name())?;
+		let mut bin :Vec<u8> = vec![0x04];
+		bin.extend_from_slice(&rdata[4..]);
+		Ok(ECDnsKey {
+			flags,
+			protocol : rdata[2],
+			alg,
+			pubkey : ECPublicKey::from_bin(&grp,&bin)?,
+		})
+	}
+
+	/*flags protocol algorithm base64 , the DNSKEY presentation rdata*/
+	pub fn to_text(&self) -> Result<String,Box<dyn Error>> {
+		Ok(format!("{} {} {} {}",self.flags,self.protocol,self.alg.number(),base64_encode(&self.public_key_bytes()?)))
+	}
+
+	/*the key may be split by blanks as zone files do*/
+	pub fn from_text(s :&str) -> Result<ECDnsKey,Box<dyn Error>> {
+		let toks :Vec<&str> = s.split_whitespace().filter(|t| *t != "(" && *t != ")").collect();
+		if toks.len() < 4 {
+			ecsimple_new_error!{ECDnssecError,"DNSKEY [{}] not flags protocol algorithm key",s}
+		}
+		let mut rdata :Vec<u8> = toks[0].parse::<u16>()?.to_be_bytes().to_vec();
+		rdata.push(toks[1].parse::<u8>()?);
+		rdata.push(toks[2].parse::<u8>()?);
+		rdata.extend_from_slice(&base64_decode(&toks[3..].concat())?);
+		ECDnsKey::from_rdata(&rdata)
+	}
+
+	/*RFC 4034 appendix B*/
+	pub fn key_tag(&self) -> Result<u16,Box<dyn Error>> {
+		let rdata :Vec<u8> = self.to_rdata()?;
+		let mut ac :u32 = 0;
+		for (i,b) in rdata.iter().enumerate() {
+			if (i & 1) != 0 {
+				ac += *b as u32;
+			} else {
+				ac += (*b as u32) << 8;
+			}
+		}
+		ac += (ac >> 16) & 0xffff;
+		Ok((ac & 0xffff) as u16)
+	}
+
+	/*RFC 4034 5.1.4 digest over owner name and DNSKEY rdata*/
+	pub fn ds(&self,owner :&str,digest :ECHashType) -> Result<ECDnsDs,Box<dyn Error>> {
+		let mut data :Vec<u8> = dns_name_to_wire(owner)?;
+		data.extend_from_slice(&self.to_rdata()?);
+		Ok(ECDnsDs {
+			key_tag : self.key_tag()?,
+			alg : self.alg.number(),
+			digest_type : dns_ds_digest_id(digest)?,
+			digest : digest.digest(&data),
+		})
+	}
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub struct ECDnsDs {
+	pub key_tag :u16,
+	pub alg :u8,
+	pub digest_type :u8,
+	pub digest :Vec<u8>,
+}
+
+impl ECDnsDs {
+	pub fn to_rdata(&self) -> Vec<u8> {
+		let mut retv :Vec<u8> = self.key_tag.to_be_bytes().to_vec();
+		retv.push(self.alg);
+		retv.push(self.digest_type);
+		retv.extend_from_slice(&self.digest);
+		retv
+	}
+
+	pub fn from_rdata(rdata :&[u8]) -> Result<ECDnsDs,Box<dyn Error>> {
+		if rdata.len() < 4 {
+			ecsimple_new_error!{ECDnssecError,"DS rdata of {} bytes",rdata.len()}
+		}
+		Ok(ECDnsDs {
+			key_tag : u16::from_be_bytes([rdata[0],rdata[1]]),
+			alg : rdata[2],
+			digest_type : rdata[3],
+			digest : rdata[4..].to_vec(),
+		})
+	}
+
+	pub fn to_text(&self) -> String {
+		format!("{} {} {} {}",self.key_tag,self.alg,self.digest_type,hex::encode_upper(&self.digest))
+	}
+
+	/*the DS made for owner and key equals this one*/
+	pub fn matches(&self,owner :&str,key :&ECDnsKey) -> Result<bool,Box<dyn Error>> {
+		let htype :ECHashType = dns_ds_digest_from_id(self.digest_type)?;
+		return Ok(key.ds(owner,htype)? == *self);
+	}
+}
+
+/*RRSIG RDATA , signature as RFC 6605 4 r || s*/
+#[derive(Clone,Debug)]
+pub struct ECDnsRrsig {
+	pub type_covered :u16,
+	pub alg :u8,
+	pub labels :u8,
+	pub original_ttl :u32,
+	pub expiration :u32,
+	pub inception :u32,
+	pub key_tag :u16,
+	pub signer :String,
+	pub signature :Vec<u8>,
+}
+
+impl ECDnsRrsig {
+	/*the rdata up to the signature with the signer name in canonical form*/
+	fn rdata_head(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = self.type_covered.to_be_bytes().to_vec();
+		retv.push(self.alg);
+		retv.push(self.labels);
+		retv.extend_from_slice(&self.original_ttl.to_be_bytes());
+		retv.extend_from_slice(&self.expiration.to_be_bytes());
+		retv.extend_from_slice(&self.inception.to_be_bytes());
+		retv.extend_from_slice(&self.key_tag.to_be_bytes());
+		retv.extend_from_slice(&dns_name_to_wire(&self.signer)?);
+		Ok(retv)
+	}
+
+	pub fn to_rdata(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = self.rdata_head()?;
+		retv.extend_from_slice(&self.signature);
+		Ok(retv)
+	}
+
+	pub fn from_rdata(rdata :&[u8]) -> Result<ECDnsRrsig,Box<dyn Error>> {
+		if rdata.len() < 19 {
+			ecsimple_new_error!{ECDnssecError,"RRSIG rdata of {} bytes",rdata.len()}
+		}
+		let mut signer :Vec<u8> = Vec::new();
+		let off :usize = dns_wire_name_lower(rdata,18,&mut signer)?;
+		let mut labels :Vec<String> = Vec::new();
+		let mut idx :usize = 0;
+		while signer[idx] != 0 {
+			let l :usize = signer[idx] as usize;
+			let mut s :String = "".to_string();
+			for c in signer[(idx+1)..(idx+1+l)].iter() {
+				if c.is_ascii_graphic() && *c != b'.' && *c != b'\\' {
+					s.push(*c as char);
+				} else {
+					s.push_str(&format!("\\{:03}",c));
+				}
+			}
+			labels.push(s);
+			idx += 1 + l;
+		}
+		let u32at = |i :usize| u32::from_be_bytes([rdata[i],rdata[i+1],rdata[i+2],rdata[i+3]]);
+		Ok(ECDnsRrsig {
+			type_covered : u16::from_be_bytes([rdata[0],rdata[1]]),
+			alg : rdata[2],
+			labels : rdata[3],
+			original_ttl : u32at(4),
+			expiration : u32at(8),
+			inception : u32at(12),
+			key_tag : u16::from_be_bytes([rdata[16],rdata[17]]),
+			signer : format!("{}.",labels.join(".")),
+			signature : rdata[off..].to_vec(),
+		})
+	}
+
+	/*type alg labels ttl expiration inception tag signer base64*/
+	pub fn to_text(&self) -> String {
+		let mut signer :String = self.signer.clone();
+		if !signer.ends_with('.') {
+			signer.push('.');
+		}
+		format!("{} {} {} {} {} {} {} {} {}",dns_type_name(self.type_covered),self.alg,self.labels,self.original_ttl,
+			dns_time_to_text(self.expiration),dns_time_to_text(self.inception),self.key_tag,signer,base64_encode(&self.signature))
+	}
+
+	pub fn from_text(s :&str) -> Result<ECDnsRrsig,Box<dyn Error>> {
+		let toks :Vec<&str> = s.split_whitespace().filter(|t| *t != "(" && *t != ")").collect();
+		if toks.len() < 9 {
+			ecsimple_new_error!{ECDnssecError,"RRSIG [{}] not full",s}
+		}
+		Ok(ECDnsRrsig {
+			type_covered : dns_type_from_name(toks[0])?,
+			alg : toks[1].parse::<u8>()?,
+			labels : toks[2].parse::<u8>()?,
+			original_ttl : toks[3].parse::<u32>()?,
+			expiration : dns_time_from_text(toks[4])?,
+			inception : dns_time_from_text(toks[5])?,
+			key_tag : toks[6].parse::<u16>()?,
+			signer : toks[7].to_string(),
+			signature : base64_decode(&toks[8..].concat())?,
+		})
+	}
+
+	/*RFC 4034 3.1.8.1 , the RRs sorted by canonical rdata and duplicates dropped*/
+	fn signed_data(&self,rrset :&[ECDnsRecord]) -> Result<Vec<u8>,Box<dyn Error>> {
+		if rrset.is_empty() {
+			ecsimple_new_error!{ECDnssecError,"empty rrset"}
+		}
+		let owner :Vec<Vec<u8>> = dns_name_split(&rrset[0].name)?;
+		let ownerwire :Vec<u8> = dns_name_to_wire(&rrset[0].name)?;
+		let mut rdatas :Vec<Vec<u8>> = Vec::new();
+		for r in rrset.iter() {
+			if dns_name_to_wire(&r.name)? != ownerwire || r.rtype != rrset[0].rtype || r.class != rrset[0].class {
+				ecsimple_new_error!{ECDnssecError,"[{} {}] not in the rrset of [{} {}]",r.name,dns_type_name(r.rtype),
+					rrset[0].name,dns_type_name(rrset[0].rtype)}
+			}
+			rdatas.push(dns_canonical_rdata(r.rtype,&r.rdata)?);
+		}
+		rdatas.sort();
+		rdatas.dedup();
+
+		/*RFC 4035 5.3.2 wildcard expansion , * and the rightmost labels when fewer than the owner has*/
+		let mut name :Vec<u8> = Vec::new();
+		let mut nlabels :usize = owner.len();
+		if !owner.is_empty() && owner[0] == b"*".to_vec() {
+			nlabels -= 1;
+		}
+		if (self.labels as usize) > nlabels {
+			ecsimple_new_error!{ECDnssecError,"RRSIG labels {} > {} of owner",self.labels,nlabels}
+		}
+		if (self.labels as usize) < owner.len() {
+			name.extend_from_slice(&[1,b'*']);
+		}
+		for l in owner[(owner.len() - self.labels as usize)..].iter() {
+			name.push(l.len() as u8);
+			name.extend(l.iter().map(|c| c.to_ascii_lowercase()));
+		}
+		name.push(0);
+
+		let mut retv :Vec<u8> = self.rdata_head()?;
+		for rd in rdatas.iter() {
+			retv.extend_from_slice(&name);
+			retv.extend_from_slice(&rrset[0].rtype.to_be_bytes());
+			retv.extend_from_slice(&rrset[0].class.to_be_bytes());
+			retv.extend_from_slice(&self.original_ttl.to_be_bytes());
+			retv.extend_from_slice(&(rd.len() as u16).to_be_bytes());
+			retv.extend_from_slice(rd);
+		}
+		Ok(retv)
+	}
+
+	/*signer is the zone apex holding key , times in seconds since the epoch*/
+	pub fn sign(rrset :&[ECDnsRecord],key :&ECDnsKey,privkey :&ECPrivateKey,signer :&str,inception :u32,expiration :u32) -> Result<ECDnsRrsig,Box<dyn Error>> {
+		if rrset.is_empty() {
+			ecsimple_new_error!{ECDnssecError,"empty rrset"}
+		}
+		if privkey.export_pubkey().to_bin(EC_UNCOMPRESSED)? != key.pubkey.to_bin(EC_UNCOMPRESSED)? {
+			ecsimple_new_error!{ECDnssecError,"private key not for the DNSKEY"}
+		}
+		let mut retv :ECDnsRrsig = ECDnsRrsig {
+			type_covered : rrset[0].rtype,
+			alg : key.alg.number(),
+			labels : dns_name_labels(&rrset[0].name)?,
+			original_ttl : rrset[0].ttl,
+			expiration,
+			inception,
+			key_tag : key.key_tag()?,
+			signer : signer.to_string(),
+			signature : Vec::new(),
+		};
+		let data :Vec<u8> = retv.signed_data(rrset)?;
+		let sig :ECSignature = privkey.sign_base(&key.alg.hash_type().digest(&data))?;
+		retv.signature = sig.to_fixed_bytes(key.alg.sig_part_size())?;
+		Ok(retv)
+	}
+
+	/*signature check only , see valid_at for the validity period*/
+	pub fn verify(&self,rrset :&[ECDnsRecord],key :&ECDnsKey) -> Result<bool,Box<dyn Error>> {
+		if self.alg != key.alg.number() || self.key_tag != key.key_tag()? {
+			ecsimple_log_trace!("RRSIG alg {} tag {} not for the key",self.alg,self.key_tag);
+			return Ok(false);
+		}
+		if !rrset.is_empty() && rrset[0].rtype != self.type_covered {
+			ecsimple_new_error!{ECDnssecError,"RRSIG covers {} not {}",dns_type_name(self.type_covered),dns_type_name(rrset[0].rtype)}
+		}
+		if self.signature.len() != 2 * key.alg.sig_part_size() {
+			ecsimple_log_trace!("signature of {} bytes",self.signature.len());
+			return Ok(false);
+		}
+		let data :Vec<u8> = self.signed_data(rrset)?;
+		let sig :ECSignature = ECSignature::from_fixed_bytes(&self.signature)?;
+		key.pubkey.verify_base(&sig,&key.alg.hash_type().digest(&data))
+	}
+
+	/*RFC 4034 3.1.5 serial number arithmetic*/
+	pub fn valid_at(&self,now :u32) -> bool {
+		(now.wrapping_sub(self.inception) as i32) >= 0 && (self.expiration.wrapping_sub(now) as i32) >= 0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use num_bigint::{BigInt,Sign};
+
+	/*RFC 6605 6.1 and 6.2 , (algorithm , PrivateKey , DNSKEY , key tag , DS , RRSIG)*/
+	const RFC6605_EXAMPLES :[(ECDnsAlg,&str,&str,u16,&str,&str);2] = [
+		(ECDnsAlg::EcdsaP256Sha256,"GU6SnQ/Ou+xC5RumuIUIuJZteXT2z0O/ok1s38Et6mQ=",
+			"257 3 13 ( GojIhhXUN/u4v54ZQqGSnyhWJwaubCvTmeexv7bR6edb
+			krSqQpF64cYbcB7wNcP+e+MAnLr+Wi9xMWyQLc8NAA== )",
+			55648,"55648 13 2 B4C8C1FE2E7477127B27115656AD6256F424625BF5C1E2770CE6D6E37DF61D17",
+			"A 13 3 3600 ( 20100909100439 20100812100439 55648 example.net.
+			qx6wLYqmh+l9oCKTN6qIc+bw6ya+KJ8oMz0YP107epXA
+			yGmt+3SNruPFKG7tZoLBLlUzGGus7ZwmwWep666VCw== )"),
+		(ECDnsAlg::EcdsaP384Sha384,"WURgWHCcYIYUPWgeLmiPY2DJJk02vgrmTfitxgqcL4vwW7BOrbawVmVe0d9V94SR",
+			"257 3 14 ( xKYaNhWdGOfJ+nPrL8/arkwf2EY3MDJ+SErKivBVSum1
+			w/egsXvSADtNJhyem5RCOpgQ6K8X1DRSEkrbYQ+OB+v8
+			/uX45NBwY8rp65F6Glur8I/mlVNgF6W/qTI37m40 )",
+			10771,"10771 14 4 72D7B62976CE06438E9C0BF319013CF801F09ECC84B8D7E9495F27E305C6A9B0563A9B5F4D288405C3008A946DF983D6",
+			"A 14 3 3600 ( 20100909102025 20100812102025 10771 example.net.
+			/L5hDKIvGDyI1fcARX3z65qrmPsVz73QD1Mr5CEqOiLP
+			95hxQouuroGCeZOvzFaxsT8Glr74hbavRKayJNuydCuz
+			WTSSPdz7wnqXL5bdcJzusdnI0RSMROxxwGipWcJm )"),
+	];
+
+	fn www_a() -> Vec<ECDnsRecord> {
+		vec![ECDnsRecord::new("www.example.net.",DNS_TYPE_A,3600,&[192,0,2,1])]
+	}
+
+	#[test]
+	fn test_rfc6605_examples() {
+		for (alg,privb64,keytext,tag,dstext,sigtext) in RFC6605_EXAMPLES.iter() {
+			let key :ECDnsKey = ECDnsKey::from_text(keytext).unwrap();
+			assert_eq!(key.alg,*alg);
+			assert_eq!(key.flags,DNS_KEY_FLAG_ZONE | DNS_KEY_FLAG_SEP);
+			assert_eq!(key.key_tag().unwrap(),*tag);
+			assert_eq!(ECDnsKey::from_rdata(&key.to_rdata().unwrap()).unwrap().to_text().unwrap(),key.to_text().unwrap());
+			let ds :ECDnsDs = key.ds("example.net.",dns_ds_digest_from_id(dstext.split(' ').nth(2).unwrap().parse::<u8>().unwrap()).unwrap()).unwrap();
+			assert_eq!(ds.to_text(),*dstext);
+			assert!(ds.matches("Example.NET",&key).unwrap());
+
+			let grp :ECGroup = ecc_get_curve_group(alg.curve_name()).unwrap();
+			let privkey :ECPrivateKey = ECPrivateKey::new(&grp,&BigInt::from_bytes_be(Sign::Plus,&base64_decode(privb64).unwrap()));
+			assert_eq!(privkey.export_pubkey().to_bin(EC_UNCOMPRESSED).unwrap(),key.pubkey.to_bin(EC_UNCOMPRESSED).unwrap());
+
+			let rrsig :ECDnsRrsig = ECDnsRrsig::from_text(sigtext).unwrap();
+			assert_eq!(rrsig.key_tag,*tag);
+			assert_eq!(rrsig.to_text(),sigtext.split_whitespace().filter(|t| *t != "(" && *t != ")").collect::<Vec<&str>>()[..8].join(" ") + " " + &base64_encode(&rrsig.signature));
+			assert!(rrsig.verify(&www_a(),&key).unwrap());
+			assert!(rrsig.valid_at(dns_time_from_text("20100901000000").unwrap()));
+			assert!(!rrsig.valid_at(dns_time_from_text("20100910000000").unwrap()));
+			let bad :Vec<ECDnsRecord> = vec![ECDnsRecord::new("www.example.net.",DNS_TYPE_A,3600,&[192,0,2,2])];
+			assert!(!rrsig.verify(&bad,&key).unwrap());
+
+			let mine :ECDnsRrsig = ECDnsRrsig::sign(&www_a(),&key,&privkey,"example.net.",rrsig.inception,rrsig.expiration).unwrap();
+			assert_eq!(mine.rdata_head().unwrap(),rrsig.rdata_head().unwrap());
+			assert!(mine.verify(&www_a(),&key).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_wildcard_rrsig() {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let privkey :ECPrivateKey = ECPrivateKey::generate(&grp);
+		let key :ECDnsKey = ECDnsKey::new(&privkey.export_pubkey(),DNS_KEY_FLAG_ZONE).unwrap();
+		let wild :Vec<ECDnsRecord> = vec![ECDnsRecord::new("*.example.net.",DNS_TYPE_A,3600,&[192,0,2,1])];
+		let rrsig :ECDnsRrsig = ECDnsRrsig::sign(&wild,&key,&privkey,"example.net.",0,100).unwrap();
+		assert_eq!(rrsig.labels,2);
+		assert!(rrsig.verify(&wild,&key).unwrap());
+		for n in ["a.example.net.","b.a.example.net."].iter() {
+			let expanded :Vec<ECDnsRecord> = vec![ECDnsRecord::new(n,DNS_TYPE_A,3600,&[192,0,2,1])];
+			assert!(rrsig.verify(&expanded,&key).unwrap());
+		}
+		let apex :Vec<ECDnsRecord> = vec![ECDnsRecord::new("example.net.",DNS_TYPE_A,3600,&[192,0,2,1])];
+		assert!(!rrsig.verify(&apex,&key).unwrap());
+		let above :Vec<ECDnsRecord> = vec![ECDnsRecord::new("net.",DNS_TYPE_A,3600,&[192,0,2,1])];
+		assert!(rrsig.verify(&above,&key).is_err());
+		let other :Vec<ECDnsRecord> = vec![ECDnsRecord::new("a.example.org.",DNS_TYPE_A,3600,&[192,0,2,1])];
+		assert!(!rrsig.verify(&other,&key).unwrap());
+	}
+
+	#[test]
+	fn test_naptr_canonical_rdata() {
+		let mut rdata :Vec<u8> = vec![0,100,0,10];
+		for s in [&b"S"[..],&b"SIP+D2U"[..],&b"!^.*$!SIP:Info@Example.COM!"[..]].iter() {
+			rdata.push(s.len() as u8);
+			rdata.extend_from_slice(s);
+		}
+		let mut want :Vec<u8> = rdata.clone();
+		rdata.extend_from_slice(&[4,b'_',b'S',b'I',b'P',4,b'_',b'U',b'D',b'P',7,b'E',b'x',b'a',b'm',b'p',b'l',b'e',3,b'C',b'O',b'M',0]);
+		want.extend_from_slice(&dns_name_to_wire("_sip._udp.example.com.").unwrap());
+		assert_eq!(dns_canonical_rdata(DNS_TYPE_NAPTR,&rdata).unwrap(),want);
+		assert_eq!(dns_type_from_name("naptr").unwrap(),DNS_TYPE_NAPTR);
+		assert!(dns_canonical_rdata(DNS_TYPE_NAPTR,&rdata[..6]).is_err());
+	}
+}
@@ -12,29 +12,31 @@ use asn1obj::{asn1obj_error_class,asn1obj_new_error};
 use std::io::Write;
 use std::error::Error;
 
+asn1obj_error_class!{ECAsn1Error}
+
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_PENTANOMIALELem {
 	pub k1 :Asn1Integer,
 	pub k2 :Asn1Integer,
 	pub k3 :Asn1Integer,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_PENTANOMIAL {
 	pub elem :Asn1Seq<X9_62_PENTANOMIALELem>,
 }
 
-#[derive(Clone)]
 #[asn1_obj_selector(other=default,onBasis="1.2.840.10045.1.2.3.1",tpBasis="1.2.840.10045.1.2.3.2",ppBasis="1.2.840.10045.1.2.3.3")]
+#[derive(Clone)]
 pub struct X962Selector  {
 	pub val :Asn1Object,
 }
 
-#[derive(Clone)]
 #[asn1_choice(selector=otype)]
+#[derive(Clone)]
 pub struct X9_62_CHARACTERISTIC_TWO_ELEM_CHOICE {
 	pub otype : X962Selector,
 	pub onBasis : Asn1Null,
@@ -43,42 +45,42 @@ pub struct X9_62_CHARACTERISTIC_TWO_ELEM_CHOICE {
 	pub other :Asn1Any,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_CHARACTERISTIC_TWO_ELEM {
 	pub m :Asn1Integer,
 	pub elemchoice : X9_62_CHARACTERISTIC_TWO_ELEM_CHOICE,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_CHARACTERISTIC_TWO {
 	pub elem :Asn1Seq<X9_62_CHARACTERISTIC_TWO_ELEM>,
 }
 
 
-#[derive(Clone)]
 #[asn1_obj_selector(prime="1.2.840.10045.1.1",char_two="1.2.840.10045.1.2")]
+#[derive(Clone)]
 pub struct X964FieldSelector {
 	pub val :Asn1Object,
 }
 
-#[derive(Clone)]
 #[asn1_choice(selector=fieldType)]
+#[derive(Clone)]
 pub struct X9_62_FIELDIDElem {
 	pub fieldType :X964FieldSelector,
 	pub prime : Asn1BigNum,
 	pub char_two :X9_62_CHARACTERISTIC_TWO,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_FIELDID {
 	pub elem :Asn1Seq<X9_62_FIELDIDElem>,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_CURVEElem {
 	pub a :Asn1OctData,
 	pub b :Asn1OctData,
@@ -86,14 +88,14 @@ pub struct X9_62_CURVEElem {
 }
 
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct X9_62_CURVE {
 	pub elem :Asn1Seq<X9_62_CURVEElem>,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPARAMETERSElem {
 	pub version : Asn1Integer,
 	pub fieldID : X9_62_FIELDID,
@@ -104,13 +106,13 @@ pub struct ECPARAMETERSElem {
 
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPARAMETERS {
 	pub elem :Asn1Seq<ECPARAMETERSElem>,
 }
 
-#[asn1_int_choice(debug=0,selector=itype,named_curve=0,parameters=1,implicitCA=2)]
+#[asn1_int_choice(debug=disable,selector=itype,named_curve=0,parameters=1,implicitCA=2)]
 #[derive(Clone)]
 pub struct ECPKPARAMETERS {
 	pub itype :i32,
@@ -119,35 +121,35 @@ pub struct ECPKPARAMETERS {
 	pub implicitCA : Asn1Null,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPublicKeyPackElem {
 	pub typef :Asn1Object,
 	pub parameters :ECPKPARAMETERS,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPublicKeyPack {
 	pub elem :Asn1Seq<ECPublicKeyPackElem>,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPublicKeyAsn1Elem {
 	pub packed :ECPublicKeyPack,
 	pub pubdata :Asn1BitDataFlag,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPublicKeyAsn1 {
 	pub elem :Asn1Seq<ECPublicKeyAsn1Elem>,
 }
 
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub struct ECPrivateKeyAsn1Elem {
 	pub version :Asn1Integer,
 	pub privkey :Asn1OctData,
@@ -155,12 +157,40 @@ pub struct ECPrivateKeyAsn1Elem {
 	pub pubkey : Asn1ImpSet<Asn1BitDataFlag,1>,
 }
 
-#[derive(Clone)]
 #[asn1_sequence()]
+#[derive(Clone)]
 pub (crate) struct ECPrivateKeyAsn1 {
 	pub elem :Asn1Seq<ECPrivateKeyAsn1Elem>,
 }
 
+#[asn1_sequence()]
+#[derive(Clone)]
+pub (crate) struct ECPrivateAsn1Elem {
+	pub (crate) version :Asn1Integer,
+	pub (crate) privnum :Asn1BigNum,
+	pub (crate) ecoid :Asn1ImpSet<Asn1Object,0>,
+	pub (crate) pubdata :Asn1ImpSet<Asn1BitData,1>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub (crate) struct ECPrivateAsn1 {
+	pub (crate) elem :Asn1Seq<ECPrivateAsn1Elem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub (crate) struct Asn1ECSignatureElem {
+	pub (crate) r :Asn1BigNum,
+	pub (crate) s :Asn1BigNum,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub (crate) struct Asn1ECSignature {
+	pub (crate) elem :Asn1Seq<Asn1ECSignatureElem>,
+}
+
 #[asn1_sequence()]
 #[derive(Clone)]
 pub struct Asn1X509AttributeElem {
@@ -195,6 +225,8 @@ pub struct Asn1Pkcs8PrivKeyInfoElem {
 	pub pkeyalg : Asn1X509Algor,
 	pub pkey : Asn1OctData,
 	pub attributes : Asn1Opt<Asn1ImpSet<Asn1X509Attribute,0>>,
+	/*OneAsymmetricKey v2 of RFC 5958*/
+	pub pubkey : Asn1Opt<Asn1Imp<Asn1BitDataFlag,1>>,
 }
 
 #[asn1_sequence()]
@@ -202,3 +234,459 @@ pub struct Asn1Pkcs8PrivKeyInfoElem {
 pub struct Asn1Pkcs8PrivKeyInfo {
 	pub elem : Asn1Seq<Asn1Pkcs8PrivKeyInfoElem>,
 }
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pbkdf2ParamElem {
+	pub salt :Asn1OctData,
+	pub iterations :Asn1Integer,
+	pub keylength :Asn1Opt<Asn1Integer>,
+	pub prf :Asn1Opt<Asn1X509Algor>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pbkdf2Param {
+	pub elem :Asn1Seq<Asn1Pbkdf2ParamElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1ScryptParamElem {
+	pub salt :Asn1OctData,
+	pub costparam :Asn1Integer,
+	pub blocksize :Asn1Integer,
+	pub parallel :Asn1Integer,
+	pub keylength :Asn1Opt<Asn1Integer>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1ScryptParam {
+	pub elem :Asn1Seq<Asn1ScryptParamElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pbes2ParamElem {
+	pub keyfunc :Asn1X509Algor,
+	pub encscheme :Asn1X509Algor,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pbes2Param {
+	pub elem :Asn1Seq<Asn1Pbes2ParamElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1GcmParamElem {
+	pub nonce :Asn1OctData,
+	pub icvlen :Asn1Opt<Asn1Integer>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1GcmParam {
+	pub elem :Asn1Seq<Asn1GcmParamElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1EncryptedPrivKeyInfoElem {
+	pub encalgor :Asn1X509Algor,
+	pub encdata :Asn1OctData,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1EncryptedPrivKeyInfo {
+	pub elem :Asn1Seq<Asn1EncryptedPrivKeyInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509SigElem {
+	pub algor :Asn1X509Algor,
+	pub digest :Asn1OctData,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Sig {
+	pub elem :Asn1Seq<Asn1X509SigElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7ContentInfoElem {
+	pub contenttype :Asn1Object,
+	pub content :Asn1Ndef<Asn1Any,0>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7ContentInfo {
+	pub elem :Asn1Seq<Asn1Pkcs7ContentInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7EncryptedContentInfoElem {
+	pub contenttype :Asn1Object,
+	pub encalgor :Asn1X509Algor,
+	pub enccontent :Asn1Opt<Asn1Imp<Asn1OctData,0>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7EncryptedContentInfo {
+	pub elem :Asn1Seq<Asn1Pkcs7EncryptedContentInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7EncryptedDataElem {
+	pub version :Asn1Integer,
+	pub enccontentinfo :Asn1Pkcs7EncryptedContentInfo,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs7EncryptedData {
+	pub elem :Asn1Seq<Asn1Pkcs7EncryptedDataElem>,
+}
+
+/*AuthenticatedSafe ::= SEQUENCE OF ContentInfo*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12AuthSafe {
+	pub safes :Asn1Seq<Asn1Pkcs7ContentInfo>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12SafeBagElem {
+	pub bagid :Asn1Object,
+	pub bagvalue :Asn1Ndef<Asn1Any,0>,
+	pub attributes :Asn1Opt<Asn1Set<Asn1X509Attribute>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12SafeBag {
+	pub elem :Asn1Seq<Asn1Pkcs12SafeBagElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12SafeContents {
+	pub bags :Asn1Seq<Asn1Pkcs12SafeBag>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12CertBagElem {
+	pub certid :Asn1Object,
+	pub certvalue :Asn1Ndef<Asn1OctData,0>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12CertBag {
+	pub elem :Asn1Seq<Asn1Pkcs12CertBagElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12MacDataElem {
+	pub mac :Asn1X509Sig,
+	pub macsalt :Asn1OctData,
+	pub iterations :Asn1Opt<Asn1Integer>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12MacData {
+	pub elem :Asn1Seq<Asn1Pkcs12MacDataElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12Elem {
+	pub version :Asn1Integer,
+	pub authsafe :Asn1Pkcs7ContentInfo,
+	pub macdata :Asn1Opt<Asn1Pkcs12MacData>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1Pkcs12 {
+	pub elem :Asn1Seq<Asn1Pkcs12Elem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509NameEntryElem {
+	pub object :Asn1Object,
+	pub value :Asn1Any,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509NameEntry {
+	pub elem :Asn1Seq<Asn1X509NameEntryElem>,
+}
+
+/*RelativeDistinguishedName ::= SET OF AttributeTypeAndValue*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Rdn {
+	pub entries :Asn1Set<Asn1X509NameEntry>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Name {
+	pub rdns :Asn1Seq<Asn1X509Rdn>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ValidityElem {
+	pub notbefore :Asn1Time,
+	pub notafter :Asn1Time,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Validity {
+	pub elem :Asn1Seq<Asn1X509ValidityElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ExtensionElem {
+	pub extnid :Asn1Object,
+	pub critical :Asn1Opt<Asn1Boolean>,
+	pub extnvalue :Asn1OctData,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Extension {
+	pub elem :Asn1Seq<Asn1X509ExtensionElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Extensions {
+	pub exts :Asn1Seq<Asn1X509Extension>,
+}
+
+/*names and the key kept in Asn1Any to have the bytes as they are*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509TbsCertElem {
+	pub version :Asn1Opt<Asn1Ndef<Asn1Integer,0>>,
+	/*INTEGER kept as is , the serial is two's complement*/
+	pub serial :Asn1Any,
+	pub signature :Asn1X509Algor,
+	pub issuer :Asn1Any,
+	pub validity :Asn1X509Validity,
+	pub subject :Asn1Any,
+	pub spki :Asn1Any,
+	pub issueruid :Asn1Opt<Asn1Imp<Asn1BitDataFlag,1>>,
+	pub subjectuid :Asn1Opt<Asn1Imp<Asn1BitDataFlag,2>>,
+	pub extensions :Asn1Opt<Asn1Ndef<Asn1X509Extensions,3>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509TbsCert {
+	pub elem :Asn1Seq<Asn1X509TbsCertElem>,
+}
+
+/*tbs in Asn1Any , the signature is over its exact encoding*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509CertElem {
+	pub tbs :Asn1Any,
+	pub sigalg :Asn1X509Algor,
+	pub signature :Asn1BitDataFlag,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Cert {
+	pub elem :Asn1Seq<Asn1X509CertElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509BasicConstraintsElem {
+	pub ca :Asn1Opt<Asn1Boolean>,
+	pub pathlen :Asn1Opt<Asn1Integer>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509BasicConstraints {
+	pub elem :Asn1Seq<Asn1X509BasicConstraintsElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509AuthKeyIdElem {
+	pub keyid :Asn1Opt<Asn1Imp<Asn1OctData,0>>,
+	/*IMPLICIT [1] GeneralNames*/
+	pub issuer :Asn1Opt<Asn1ImpSet<Asn1Any,1>>,
+	pub serial :Asn1Opt<Asn1Imp<Asn1BigNum,2>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509AuthKeyId {
+	pub elem :Asn1Seq<Asn1X509AuthKeyIdElem>,
+}
+
+/*GeneralNames , each GeneralName kept in Asn1Any by its context tag*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509GeneralNames {
+	pub names :Asn1Seq<Asn1Any>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ReqInfoElem {
+	pub version :Asn1Integer,
+	pub subject :Asn1Any,
+	pub spki :Asn1Any,
+	pub attributes :Asn1ImpSet<Asn1X509Attribute,0>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ReqInfo {
+	pub elem :Asn1Seq<Asn1X509ReqInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ReqElem {
+	pub info :Asn1Any,
+	pub sigalg :Asn1X509Algor,
+	pub signature :Asn1BitDataFlag,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509Req {
+	pub elem :Asn1Seq<Asn1X509ReqElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1X509ExtKeyUsage {
+	pub oids :Asn1Seq<Asn1Object>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsEncapContentInfoElem {
+	pub econtenttype :Asn1Object,
+	pub econtent :Asn1Opt<Asn1Ndef<Asn1OctData,0>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsEncapContentInfo {
+	pub elem :Asn1Seq<Asn1CmsEncapContentInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsIssuerSerialElem {
+	pub issuer :Asn1Any,
+	/*INTEGER kept as is , the serial is two's complement*/
+	pub serial :Asn1Any,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsIssuerSerial {
+	pub elem :Asn1Seq<Asn1CmsIssuerSerialElem>,
+}
+
+/*sid is IssuerAndSerialNumber or [0] IMPLICIT SubjectKeyIdentifier*/
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsSignerInfoElem {
+	pub version :Asn1Integer,
+	pub sid :Asn1Any,
+	pub digestalg :Asn1X509Algor,
+	pub signedattrs :Asn1Opt<Asn1ImpSet<Asn1X509Attribute,0>>,
+	pub sigalg :Asn1X509Algor,
+	pub signature :Asn1OctData,
+	pub unsignedattrs :Asn1Opt<Asn1ImpSet<Asn1X509Attribute,1>>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsSignerInfo {
+	pub elem :Asn1Seq<Asn1CmsSignerInfoElem>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsSignedDataElem {
+	pub version :Asn1Integer,
+	pub digestalgs :Asn1Set<Asn1X509Algor>,
+	pub encapcontent :Asn1CmsEncapContentInfo,
+	pub certificates :Asn1Opt<Asn1ImpSet<Asn1Any,0>>,
+	pub crls :Asn1Opt<Asn1ImpSet<Asn1Any,1>>,
+	/*SignerInfo kept as is to hash the signedAttrs bytes received*/
+	pub signerinfos :Asn1Set<Asn1Any>,
+}
+
+#[asn1_sequence()]
+#[derive(Clone)]
+pub struct Asn1CmsSignedData {
+	pub elem :Asn1Seq<Asn1CmsSignedDataElem>,
+}
+
+pub (crate) fn der_to_any(data :&[u8]) -> Result<Asn1Any,Box<dyn Error>> {
+	let mut anydata :Asn1Any = Asn1Any::init_asn1();
+	let _ = anydata.decode_asn1(data)?;
+	Ok(anydata)
+}
+
+pub (crate) fn form_algor(oid :&str,params :Option<Vec<u8>>) -> Result<Asn1X509Algor,Box<dyn Error>> {
+	let mut algor :Asn1X509Algor = Asn1X509Algor::init_asn1();
+	let mut algelem :Asn1X509AlgorElem = Asn1X509AlgorElem::init_asn1();
+	algelem.algorithm.set_value(oid)?;
+	algelem.parameters.val = None;
+	if let Some(p) = params.as_ref() {
+		algelem.parameters.val = Some(der_to_any(p)?);
+	}
+	algor.elem.val.push(algelem);
+	Ok(algor)
+}
+
+/*algorithm oid and the DER of the parameters if any*/
+pub (crate) type ECAlgorElem = (String,Option<Vec<u8>>);
+
+pub (crate) fn get_algor_elem(algor :&Asn1X509Algor) -> Result<ECAlgorElem,Box<dyn Error>> {
+	if algor.elem.val.len() != 1 {
+		asn1obj_new_error!{ECAsn1Error,"algor elem {} != 1",algor.elem.val.len()}
+	}
+	let oid :String = algor.elem.val[0].algorithm.get_value();
+	let mut params :Option<Vec<u8>> = None;
+	if let Some(p) = algor.elem.val[0].parameters.val.as_ref() {
+		params = Some(p.encode_asn1()?);
+	}
+
+	Ok((oid,params))
+}
@@ -1,4 +1,5 @@
 
+#[allow(unused_imports)]
 use crate::{ecsimple_new_error,ecsimple_error_class};
 use std::error::Error;
 use std::io::{Read};
@@ -20,8 +21,8 @@ impl RandFile {
 		}
 		let f = ores.unwrap();
 		Ok(RandFile {
-			f : f,
-			fname : format!("{}",name),
+			f,
+			fname : name.to_string(),
 		})
 	}
 }
@@ -41,11 +42,7 @@ impl rand_core::RngCore for RandFile {
 		if cnt != 4 {
 			panic!("can not read [{}]", self.fname);
 		}
-		let mut retv :u32 = 0;
-		for i in 0..buf.len() {
-			retv |= (buf[i] as u32) << (i * 8);
-		}
-		retv
+		u32::from_le_bytes(buf)
 	}
 
 	fn next_u64(&mut self) -> u64 {
@@ -59,11 +56,8 @@ impl rand_core::RngCore for RandFile {
 		if cnt != 8 {
 			panic!("can not read [{}]", self.fname);
 		}
-		let mut retv :u64 = 0;
-		for i in 0..buf.len() {
-			retv |= (buf[i] as u64) << (i * 8);
-		}
-		retv
+		u64::from_le_bytes(buf)
+
 	}
 
 	fn fill_bytes(&mut self, dest: &mut [u8]) {
@@ -76,7 +70,6 @@ impl rand_core::RngCore for RandFile {
 		if cnt != dest.len() {
 			panic!("can not read [{}]", self.fname);	
 		}
-		return;
 	}
 
 	fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(),rand_core::Error> {
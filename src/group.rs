@@ -3,22 +3,31 @@
 use crate::bngf2m::*;
 #[allow(unused_imports)]
 use crate::logger::*;
+#[allow(unused_imports)]
 use crate::*;
 use num_bigint::{BigInt,Sign};
 use num_traits::{zero,one};
 //use crate::*;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::sync::RwLock;
 use crate::consts::*;
 use crate::mont::*;
 use crate::randop::*;
 use std::error::Error;
 use hex::FromHex;
 use std::cmp::PartialEq;
+use asn1obj::base::Asn1Object;
+use asn1obj::asn1impl::Asn1Op;
+use crate::ecasn1::*;
+use crate::keys::{form_ecpkparameters_prime,form_ecpkparameters_gf2m,get_group_from_ecpkparameters_der};
+use crate::pem::*;
+use crate::curve::{ecc_resolve_curve_name,CurveId};
 
 ecsimple_error_class!{ECGroupError}
 
 
+#[allow(dead_code)]
 pub (crate) trait ECGroupInterface  {
 	fn x(&self) -> BigInt ;
 	fn y(&self) -> BigInt ;
@@ -27,6 +36,7 @@ pub (crate) trait ECGroupInterface  {
 }
 
 #[derive(Clone)]
+#[derive(Default)]
 pub (crate) struct ECBnGf2mGenerator {
 	pub x :BnGf2m,
 	pub y :BnGf2m,
@@ -54,19 +64,10 @@ impl ECBnGf2mGenerator {
 		if !self.z.eq_op(&other.z) {
 			retv = false;
 		}
-		return retv;
+		retv
 	}
 }
 
-impl std::default::Default for ECBnGf2mGenerator {
-	fn default() -> Self {
-		ECBnGf2mGenerator {
-			x : BnGf2m::default(),
-			y :BnGf2m::default(),
-			z :BnGf2m::default(),
-		}
-	}
-}
 
 
 #[derive(Clone)]
@@ -106,6 +107,20 @@ impl std::default::Default for ECGroupBnGf2m {
 }
 
 impl ECGroupBnGf2m {
+	/*compare only the curve parameters , curvename and seed ignored*/
+	pub fn eq_params(&self,other :&ECGroupBnGf2m) -> bool {
+		if self.p != other.p || self.order != other.order || self.cofactor != other.cofactor {
+			return false;
+		}
+		if !self.a.eq_op(&other.a) || !self.b.eq_op(&other.b) {
+			return false;
+		}
+		if !self.generator.x.eq_op(&other.generator.x) || !self.generator.y.eq_op(&other.generator.y) {
+			return false;
+		}
+		true
+	}
+
 	pub fn eq_op(&self,other :&ECGroupBnGf2m) -> bool {
 		let mut retv :bool = true;
 		if !self.generator.eq_op(&other.generator) {
@@ -141,25 +156,25 @@ impl ECGroupBnGf2m {
 			retv = false;
 		}
 
-		return retv;
+		retv
 	}
 }
 
 impl ECGroupInterface for ECGroupBnGf2m {
 	fn x(&self) -> BigInt {
-		return self.generator.x.to_bigint();
+		self.generator.x.to_bigint()
 	}
 
 	fn y(&self) -> BigInt {
-		return self.generator.y.to_bigint();
+		self.generator.y.to_bigint()
 	}
 
 	fn z(&self) -> BigInt {
-		return self.generator.z.to_bigint();
+		self.generator.z.to_bigint()
 	}
 
 	fn degree(&self) -> i64 {
-		return get_max_bits(&self.p) - 1;
+		get_max_bits(&self.p) - 1
 	}
 }
 
@@ -193,17 +208,13 @@ impl ECPrimeGenerator {
 		if self.z != other.z {
 			retv = false;
 		}
-		return retv;
+		retv
 	}
 }
 
 impl PartialEq for ECPrimeGenerator {
 	fn eq(&self, other:&Self) -> bool {
-		return self.eq_op(other);
-	}
-
-	fn ne(&self, other:&Self) -> bool {
-		return ! self.eq_op(other);
+		self.eq_op(other)
 	}
 }
 
@@ -257,6 +268,32 @@ impl std::default::Default for ECGroupPrime {
 }
 
 impl ECGroupPrime {
+	/*compare only the curve parameters in plain (not montgomery) form , curvename and seed ignored*/
+	pub fn eq_params(&self,other :&ECGroupPrime) -> bool {
+		if self.p != other.p || self.order != other.order || self.cofactor != other.cofactor {
+			return false;
+		}
+		/*same p , so one montgomery context for both*/
+		let ores = MontNum::new(&self.p);
+		if ores.is_err() {
+			return false;
+		}
+		let mont :MontNum = ores.unwrap();
+		if mont.mont_from(&self.a) != mont.mont_from(&other.a) {
+			return false;
+		}
+		if mont.mont_from(&self.b) != mont.mont_from(&other.b) {
+			return false;
+		}
+		if mont.mont_from(&self.generator.x) != mont.mont_from(&other.generator.x) {
+			return false;
+		}
+		if mont.mont_from(&self.generator.y) != mont.mont_from(&other.generator.y) {
+			return false;
+		}
+		true
+	}
+
 	pub fn eq_op(&self, other :&ECGroupPrime) -> bool {
 		let mut retv : bool = true;
 		if !self.generator.eq_op(&other.generator) {
@@ -294,53 +331,42 @@ impl ECGroupPrime {
 			retv = false;
 		}
 
-		return retv;
+		retv
 	}
 }
 
 impl PartialEq for ECGroupPrime {
 	fn eq(&self, other:&Self) -> bool {
-		return self.eq_op(other);
-	}
-
-	fn ne(&self, other:&Self) -> bool {
-		return ! self.eq_op(other);
+		self.eq_op(other)
 	}
 }
 
 
 impl ECGroupInterface for ECGroupPrime {
 	fn x(&self) -> BigInt {
-		return self.generator.x.clone();
+		self.generator.x.clone()
 	}
 
 	fn y(&self) -> BigInt {
-		return self.generator.y.clone();
+		self.generator.y.clone()
 	}
 
 	fn z(&self) -> BigInt {
-		return self.generator.z.clone();
+		self.generator.z.clone()
 	}
 
 	fn degree(&self) -> i64 {
-		return get_max_bits(&self.p);
+		get_max_bits(&self.p)
 	}
 }
 
 #[derive(Clone)]
+#[derive(Default)]
 pub struct ECGroup {
 	pub (crate) bngrp :Option<ECGroupBnGf2m>,
 	pub (crate) primegrp :Option<ECGroupPrime>,
 }
 
-impl Default for ECGroup {
-	fn default() -> Self {
-		Self {
-			bngrp : None,
-			primegrp : None,
-		}
-	}
-}
 
 impl ECGroup {
 	pub (crate) fn new_bn_group( grp:&ECGroupBnGf2m) -> Self {
@@ -357,31 +383,35 @@ impl ECGroup {
 		}
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn new_bn_group_base(p :&BigInt,a :&BigInt,b :&BigInt,x :&BigInt,y :&BigInt, z :&BigInt, order :&BigInt,cofactor :&BigInt,name :&str) -> Result<Self,Box<dyn Error>> {
-		let mut bngrp :ECGroupBnGf2m = ECGroupBnGf2m::default();
-		bngrp.p = p.clone();
-		bngrp.a = BnGf2m::new_from_bigint(a);
-		bngrp.b = BnGf2m::new_from_bigint(b);
+		let mut bngrp :ECGroupBnGf2m = ECGroupBnGf2m {
+			p : p.clone(),
+			a : BnGf2m::new_from_bigint(a),
+			b : BnGf2m::new_from_bigint(b),
+			..Default::default()
+		};
 		bngrp.generator.x = BnGf2m::new_from_bigint(x);
 		bngrp.generator.y = BnGf2m::new_from_bigint(y);
 		bngrp.generator.z = BnGf2m::new_from_bigint(z);
 		bngrp.order = order.clone();
 		bngrp.cofactor = cofactor.clone();
-		bngrp.curvename = format!("{}",name);
+		bngrp.curvename = name.to_string();
 		Ok(Self {
 			bngrp : Some(bngrp.clone()),
 			primegrp : None,
 		})
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	pub fn new_prime_group_base(p :&BigInt,a :&BigInt, b:&BigInt, x :&BigInt,y :&BigInt, z :&BigInt, order :&BigInt, cofactor :&BigInt,name :&str) -> Result<Self,Box<dyn Error>> {
 		let mut primegrp :ECGroupPrime = ECGroupPrime::default();
-		let montv :MontNum;
+		
 		let ov :BigInt = one();
-		let tmpp :BigInt;
-		let tmpa :BigInt;
+		
+		
 		primegrp.p = p.clone();
-		montv = MontNum::new(&primegrp.p).unwrap();
+		let montv :MontNum = MontNum::new(&primegrp.p).unwrap();
 		primegrp.a = montv.mont_to(a);
 		primegrp.b = montv.mont_to(b);
 		primegrp.generator.x = montv.mont_to(x);
@@ -389,33 +419,39 @@ impl ECGroup {
 		primegrp.generator.z = montv.mont_to(z);
 		primegrp.order = order.clone();
 		primegrp.cofactor = cofactor.clone();
-		primegrp.curvename = format!("{}",name);
-		tmpp = p.clone();
-		tmpa = a.clone();
+		primegrp.curvename = name.to_string();
+		let tmpp :BigInt = p.clone();
+		let tmpa :BigInt = a.clone();
 
-		if tmpp == (tmpa + ov.clone() + ov.clone() + ov.clone()) {
-			primegrp.is_minus3 = true;
-		} else {
-			primegrp.is_minus3 = false;
-		}
+		primegrp.is_minus3 = tmpp == (tmpa + ov.clone() + ov.clone() + ov.clone());
 		Ok(Self {
 			bngrp : None,
 			primegrp : Some(primegrp.clone()),
 		})
 	}
 
-	pub (crate) fn is_bn_group(&self) -> bool {
+	pub (crate) fn set_curve_name(&mut self,name :&str) {
+		if let Some(g) = self.bngrp.as_mut() {
+			g.curvename = name.to_string();
+		}
+		if let Some(g) = self.primegrp.as_mut() {
+			g.curvename = name.to_string();
+		}
+
+	}
+
+	pub fn is_bn_group(&self) -> bool {
 		if self.bngrp.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
-	pub (crate) fn is_prime_group(&self) -> bool {
+	pub fn is_prime_group(&self) -> bool {
 		if self.primegrp.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	pub (crate) fn get_bn_group(&self) -> ECGroupBnGf2m {
@@ -423,7 +459,7 @@ impl ECGroup {
 		if self.is_bn_group() {
 			retv = self.bngrp.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
 	}
 
 	pub (crate) fn get_prime_group(&self) -> ECGroupPrime {
@@ -431,7 +467,151 @@ impl ECGroup {
 		if self.is_prime_group() {
 			retv = self.primegrp.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
+	}
+}
+
+#[derive(Clone,Debug)]
+pub struct ECGroupInfo {
+	pub curvename :String,
+	/*None for explicit parameters not registered*/
+	pub oid :Option<String>,
+	pub curve_id :Option<CurveId>,
+	pub is_prime_field :bool,
+	pub field_bits :i64,
+	pub order :BigInt,
+	pub order_bits :i64,
+	pub cofactor :BigInt,
+	/*half of the order bits , the pollard rho cost*/
+	pub security_bits :i64,
+}
+
+impl ECGroupInfo {
+	/*bytes of a field element or coordinate*/
+	pub fn field_bytes(&self) -> usize {
+		((self.field_bits + 7) / 8) as usize
+	}
+
+	/*bytes of a scalar or signature part*/
+	pub fn order_bytes(&self) -> usize {
+		((self.order_bits + 7) / 8) as usize
+	}
+}
+
+impl std::fmt::Display for ECGroupInfo {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let oid :String = match self.oid.as_ref() {
+			Some(v) => v.to_string(),
+			None => "".to_string(),
+		};
+		let ftype :&str = if self.is_prime_field { "prime" } else { "binary" };
+		write!(f,"curve {} oid {} {} field {} bits order {} bits cofactor 0x{:x} security {} bits",
+			self.curvename,oid,ftype,self.field_bits,self.order_bits,self.cofactor,self.security_bits)
+	}
+}
+
+impl ECGroup {
+	pub fn info(&self) -> ECGroupInfo {
+		let curvename :String;
+		let is_prime_field :bool;
+		let field_bits :i64;
+		let order :BigInt;
+		let cofactor :BigInt;
+		if self.is_prime_group() {
+			let grp :ECGroupPrime = self.get_prime_group();
+			curvename = grp.curvename.clone();
+			is_prime_field = true;
+			field_bits = get_max_bits(&grp.p);
+			order = grp.order.clone();
+			cofactor = grp.cofactor.clone();
+		} else {
+			let grp :ECGroupBnGf2m = self.get_bn_group();
+			curvename = grp.curvename.clone();
+			is_prime_field = false;
+			field_bits = grp.degree();
+			order = grp.order.clone();
+			cofactor = grp.cofactor.clone();
+		}
+		let order_bits :i64 = get_max_bits(&order);
+		let mut oid :Option<String> = None;
+		if !curvename.is_empty() {
+			match ecc_get_oid_from_name(&curvename) {
+				Ok(v) => {
+					oid = Some(v);
+				},
+				Err(_e) => {}
+			}
+		}
+		ECGroupInfo {
+			curvename,
+			oid,
+			curve_id : self.curve_id(),
+			is_prime_field,
+			field_bits,
+			order,
+			order_bits,
+			cofactor,
+			security_bits : order_bits / 2,
+		}
+	}
+}
+
+impl ECGroup {
+	/*compare only the curve parameters , name and seed are ignored*/
+	pub fn eq_params(&self,other :&ECGroup) -> bool {
+		if self.is_prime_group() && other.is_prime_group() {
+			return self.get_prime_group().eq_params(&other.get_prime_group());
+		} else if self.is_bn_group() && other.is_bn_group() {
+			return self.get_bn_group().eq_params(&other.get_bn_group());
+		}
+		false
+	}
+
+	/*return the named group with same parameters , or the clone of self if none*/
+	pub fn canonicalize(&self) -> ECGroup {
+		match ecc_find_named_group(self) {
+			Some(grp) => {
+				grp
+			},
+			None => {
+				self.clone()
+			}
+		}
+	}
+
+	/*paramenc "" for named curve , EC_PARAMS_EXLICIT for the explicit parameters , EC_PARAMS_EXLICIT_SEED to keep the brainpool seed*/
+	pub fn to_der(&self,cmprtype :&str,paramenc :&str) -> Result<Vec<u8>,Box<dyn Error>> {
+		let params :ECPKPARAMETERS;
+		if self.is_prime_group() {
+			params = form_ecpkparameters_prime(&self.get_prime_group(),cmprtype,paramenc)?;
+		} else if self.is_bn_group() {
+			params = form_ecpkparameters_gf2m(&self.get_bn_group(),cmprtype,paramenc)?;
+		} else {
+			ecsimple_new_error!{ECGroupError,"empty ECGroup"}
+		}
+		params.encode_asn1()
+	}
+
+	/*explicit parameters are mapped back to the named group*/
+	pub fn from_der(dercode :&[u8]) -> Result<ECGroup,Box<dyn Error>> {
+		Self::from_der_canonical(dercode,true)
+	}
+
+	/*canonical false to keep explicit parameters as the anonymous group*/
+	pub fn from_der_canonical(dercode :&[u8],canonical :bool) -> Result<ECGroup,Box<dyn Error>> {
+		let mut params :ECPKPARAMETERS = ECPKPARAMETERS::init_asn1();
+		let _ = params.decode_asn1(dercode)?;
+		get_group_from_ecpkparameters_der(&params,canonical)
+	}
+
+	pub fn to_pem(&self,cmprtype :&str,paramenc :&str) -> Result<String,Box<dyn Error>> {
+		let data :Vec<u8> = self.to_der(cmprtype,paramenc)?;
+		Ok(der_to_pem(&data,PEM_EC_PARAMETERS))
+	}
+
+	pub fn from_pem(ins :&str) -> Result<ECGroup,Box<dyn Error>> {
+		let b :PemBlock = pem_find_block(ins,&[PEM_EC_PARAMETERS])?;
+		ECGroup::from_der(&b.data)
 	}
 }
 
@@ -442,7 +622,7 @@ impl std::fmt::Display for ECGroup {
 		} else if self.is_bn_group() {
 			return write!(f,"{}",self.get_bn_group());
 		}
-		return write!(f,"empty ECGroup");
+		write!(f,"empty ECGroup")
 	}
 }
 
@@ -662,6 +842,10 @@ fn create_group_bn_curves() -> HashMap<String,ECGroupBnGf2m> {
 	bngrp.order = p.clone();
 	bngrp.cofactor = &ov + &ov;
 	bngrp.curvename = SECT163r2_NAME.to_string();
+	v8 = Vec::from_hex("85E25BFE5C86226CDB12016F7553F9D0E693A268").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	retv.insert(SECT163r2_NAME.to_string(),bngrp.clone());
 
@@ -2578,8 +2762,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP160r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("3243F6A8885A308D313198A2E03707344A409382").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2620,8 +2806,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP160t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("3243F6A8885A308D313198A2E03707344A409382").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2661,8 +2849,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP192r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("2299F31D0082EFA98EC4E6C89452821E638D0137").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2702,8 +2892,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP192t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("2299F31D0082EFA98EC4E6C89452821E638D0137").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2743,8 +2935,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP224r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("7BE5466CF34E90C6CC0AC29B7C97C50DD3F84D5B").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2784,8 +2978,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP224t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("7BE5466CF34E90C6CC0AC29B7C97C50DD3F84D5B").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2825,8 +3021,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP256r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("5B54709179216D5D98979FB1BD1310BA698DFB5A").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2866,8 +3064,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP256t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("5B54709179216D5D98979FB1BD1310BA698DFB5A").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2907,8 +3107,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP320r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("C2FFD72DBD01ADFB7B8E1AFED6A267E96BA7C904").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2948,8 +3150,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP320t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("C2FFD72DBD01ADFB7B8E1AFED6A267E96BA7C904").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -2989,8 +3193,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP384r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("5F12C7F9924A19947B3916CF70801F2E2858EFC1").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -3030,8 +3236,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP384t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("5F12C7F9924A19947B3916CF70801F2E2858EFC1").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -3071,8 +3279,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP512r1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("6636920D871574E69A458FEA3F4933D7E0D95748").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -3112,8 +3322,10 @@ fn create_group_prime_curves() -> HashMap<String,ECGroupPrime> {
 	bngrp.cofactor = ov.clone();
 	bngrp.curvename = BRAINPOOLP512t1_NAME.to_string();
 
-	bngrp.seed = zero();
-	bngrp.seed_len = 0;
+	v8 = Vec::from_hex("6636920D871574E69A458FEA3F4933D7E0D95748").unwrap();
+	p = BigInt::from_bytes_be(Sign::Plus,&v8);
+	bngrp.seed = p.clone();
+	bngrp.seed_len = 20;
 
 	//ecsimple_log_trace!("tmpp 0x{:X} tmpa 0x{:X}",tmpp,tmpa);
 	if tmpp == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone()) {
@@ -3183,7 +3395,8 @@ fn craete_curve_group() -> HashMap<String,ECGroup> {
 	retv
 }
 
-fn create_name_to_oid() -> HashMap<String,String> {
+pub (crate) fn create_name_to_oid() -> HashMap<String,String> {
+
 	let mut retv :HashMap<String,String> = HashMap::new();
 
 
@@ -3344,54 +3557,77 @@ fn create_oid_to_name() -> HashMap<String,String> {
 	let mut retv :HashMap<String,String> = HashMap::new();
 	let nc :HashMap<String,String> = create_name_to_oid();
 	for (k,v) in nc.iter() {
-		retv.insert(format!("{}",v),format!("{}",k));
+		retv.insert(v.to_string(),k.to_string());
 	}
 	retv
 }
 
 
 lazy_static ! {
-	static ref ECC_CURVES : HashMap<String,ECGroup> = {
-		craete_curve_group()
+	static ref ECC_CURVES : RwLock<HashMap<String,ECGroup>> = {
+		RwLock::new(craete_curve_group())
 	};
-	static ref ECC_NAME_TO_OID :HashMap<String,String> = {
-		create_name_to_oid()
+	static ref ECC_NAME_TO_OID :RwLock<HashMap<String,String>> = {
+		RwLock::new(create_name_to_oid())
 	};
 
-	static ref ECC_OID_TO_NAME :HashMap<String,String> = {
-		create_oid_to_name()
+	static ref ECC_OID_TO_NAME :RwLock<HashMap<String,String>> = {
+		RwLock::new(create_oid_to_name())
 	};
-}
-
 
-pub fn ecc_get_oid_from_name(name :&str) -> Result<String,Box<dyn Error>> {
-	match ECC_NAME_TO_OID.get(name) {
-		Some(pv) => {
-			return Ok(format!("{}",pv));
-		},
-		_ => {
+	static ref ECC_BUILTIN_NAME_TO_OID :HashMap<String,String> = {
+		create_name_to_oid()
+	};
+}
 
+/*find the registered named group with the same p/a/b/G/n/h , seed is not compared*/
+pub fn ecc_find_named_group(grp :&ECGroup) -> Option<ECGroup> {
+	let curves = ECC_CURVES.read().unwrap();
+	let mut names :Vec<String> = Vec::new();
+	for (k,v) in curves.iter() {
+		if v.eq_params(grp) {
+			names.push(k.to_string());
 		}
 	}
+	if names.is_empty() {
+		return None;
+	}
+	/*some curves share parameters , like secp224r1 and wap-wsg-idm-ecid-wtls12 , so make it stable*/
+	names.sort();
+	ecsimple_log_trace!("explicit group match [{:?}]",names);
+	Some(curves.get(&names[0]).unwrap().clone())
+}
+
+
+pub fn ecc_get_oid_from_name(name :&str) -> Result<String,Box<dyn Error>> {
+	if let Some(pv) = ECC_NAME_TO_OID.read().unwrap().get(name) {
+ 			return Ok(pv.to_string());
+ 		}
 	ecsimple_new_error!{ECGroupError,"can not find [{}] oid",name}
 }
 
 pub fn ecc_get_name_from_oid(oid :&str) -> Result<String,Box<dyn Error>> {
-	match ECC_OID_TO_NAME.get(oid) {
-		Some(pv) => {
-			return Ok(format!("{}",pv));
-		},
-		_ => {
-
-		}
-	}
+	if let Some(pv) = ECC_OID_TO_NAME.read().unwrap().get(oid) {
+ 			return Ok(pv.to_string());
+ 		}
 	ecsimple_new_error!{ECGroupError,"can not find [{}] name",oid}
 }
 pub fn ecc_get_curve_group(name :&str) -> Result<ECGroup,Box<dyn Error>> {
-	match ECC_CURVES.get(name) {
+	if let Some(pv) = ECC_CURVES.read().unwrap().get(name) {
+ 			ecsimple_log_trace!("load [{}]",name);
+ 			return Ok(pv.clone());
+ 		}
+	/*try the aliases like P-256 or nistp256*/
+	let cname :String = match ecc_resolve_curve_name(name) {
+		Ok(v) => v,
+		Err(_e) => {
+			ecsimple_new_error!{ECGroupError,"can not find [{}]",name}
+		}
+	};
+	match ECC_CURVES.read().unwrap().get(&cname) {
 		Some(pv) => {
-			ecsimple_log_trace!("load [{}]",name);
-			return Ok(pv.clone());
+			ecsimple_log_trace!("load [{}] for [{}]",cname,name);
+			Ok(pv.clone())
 		},
 		_ => {
 			ecsimple_new_error!{ECGroupError,"can not find [{}]",name}
@@ -3399,11 +3635,168 @@ pub fn ecc_get_curve_group(name :&str) -> Result<ECGroup,Box<dyn Error>> {
 	}
 }
 
+pub fn ecc_get_curve_group_by_oid(oid :&str) -> Result<ECGroup,Box<dyn Error>> {
+	let name :String = ecc_get_name_from_oid(oid)?;
+	ecc_get_curve_group(&name)
+}
+
+/*sorted names*/
 pub fn ecc_get_curve_names() -> Vec<String> {
 	let mut retv :Vec<String> = Vec::new();
-	for (k,_) in ECC_NAME_TO_OID.iter() {
-		retv.push(format!("{}",k));
+	for (k,_) in ECC_NAME_TO_OID.read().unwrap().iter() {
+		retv.push(k.to_string());
+	}
+	retv.sort();
+	retv
+}
+
+/*
+	sorted names which filter returns true , like prime curves with 128 bits security
+	ecc_get_curve_names_filter(|info| info.is_prime_field && info.security_bits >= 128)
+*/
+pub fn ecc_get_curve_names_filter<F>(filter :F) -> Vec<String> where F : Fn(&ECGroupInfo) -> bool {
+	let mut retv :Vec<String> = Vec::new();
+	for k in ecc_get_curve_names().iter() {
+		match ecc_get_curve_group(k) {
+			Ok(grp) => {
+				if filter(&grp.info()) {
+					retv.push(k.to_string());
+				}
+			},
+			Err(_e) => {}
+		}
+	}
+	retv
+}
+
+/*
+	register a private or newly standardized curve ,
+	after this it can be used by name and oid like the built-in ones
+*/
+pub fn ecc_register_curve(name :&str,oid :&str,grp :&ECGroup) -> Result<(),Box<dyn Error>> {
+	if name.is_empty() {
+		ecsimple_new_error!{ECGroupError,"empty curve name"}
+	}
+	if !grp.is_bn_group() && !grp.is_prime_group() {
+		ecsimple_new_error!{ECGroupError,"empty group for [{}]",name}
+	}
+	/*to check the oid format*/
+	let mut obj :Asn1Object = Asn1Object::init_asn1();
+	let _ = obj.set_value(oid)?;
+
+	let mut curves = ECC_CURVES.write().unwrap();
+	let mut nametooid = ECC_NAME_TO_OID.write().unwrap();
+	let mut oidtoname = ECC_OID_TO_NAME.write().unwrap();
+	if curves.contains_key(name) || nametooid.contains_key(name) {
+		ecsimple_new_error!{ECGroupError,"[{}] already registered",name}
+	}
+	if oidtoname.contains_key(oid) {
+		ecsimple_new_error!{ECGroupError,"oid [{}] already registered for [{}]",oid,oidtoname.get(oid).unwrap()}
+	}
+
+	let mut regrp :ECGroup = grp.clone();
+	regrp.set_curve_name(name);
+	curves.insert(name.to_string(),regrp);
+	nametooid.insert(name.to_string(),oid.to_string());
+	oidtoname.insert(oid.to_string(),name.to_string());
+	ecsimple_log_trace!("register [{}] oid [{}]",name,oid);
+	Ok(())
+}
+
+/*only the curves registered by ecc_register_curve can be removed*/
+pub fn ecc_unregister_curve(name :&str) -> Result<(),Box<dyn Error>> {
+	if ECC_BUILTIN_NAME_TO_OID.contains_key(name) {
+		ecsimple_new_error!{ECGroupError,"[{}] is built-in curve",name}
+	}
+	let mut curves = ECC_CURVES.write().unwrap();
+	let mut nametooid = ECC_NAME_TO_OID.write().unwrap();
+	let mut oidtoname = ECC_OID_TO_NAME.write().unwrap();
+	if curves.remove(name).is_none() {
+		ecsimple_new_error!{ECGroupError,"[{}] not registered",name}
+	}
+	if let Some(oid) = nametooid.remove(name) {
+ 			oidtoname.remove(&oid);
+ 		}
+	ecsimple_log_trace!("unregister [{}]",name);
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_from_der_canonical() {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let der :Vec<u8> = grp.to_der(EC_UNCOMPRESSED,EC_PARAMS_EXLICIT).unwrap();
+		let named :ECGroup = ECGroup::from_der(&der).unwrap();
+		assert_eq!(named.info().curvename,PRIME256v1_NAME);
+		let anon :ECGroup = ECGroup::from_der_canonical(&der,false).unwrap();
+		assert_eq!(anon.info().curvename,"");
+		assert!(anon.eq_params(&grp));
+		let reder :Vec<u8> = named.to_der(EC_UNCOMPRESSED,"").unwrap();
+		assert_eq!(ECGroup::from_der_canonical(&reder,false).unwrap().info().curvename,PRIME256v1_NAME);
+	}
+
+	/*prime256v1 with 2G as the generator , so no built-in curve has the same parameters*/
+	fn test_curve_p256_2g() -> ECGroup {
+		let p :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF").unwrap());
+		let a :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC").unwrap());
+		let b :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B").unwrap());
+		let x :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978").unwrap());
+		let y :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1").unwrap());
+		let n :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551").unwrap());
+		ECGroup::new_prime_group_base(&p,&a,&b,&x,&y,&one(),&n,&one(),"").unwrap()
+	}
+
+	#[test]
+	fn test_register_curve() {
+		let name :&str = "ecsimple-test-p256-2g";
+		let oid :&str = "1.3.6.1.4.1.99999.2.1";
+		let grp :ECGroup = test_curve_p256_2g();
+
+		assert!(ecc_find_named_group(&grp).is_none());
+		ecc_register_curve(name,oid,&grp).unwrap();
+		assert!(ecc_get_curve_names().contains(&name.to_string()));
+		let byname :ECGroup = ecc_get_curve_group(name).unwrap();
+		assert!(byname.eq_params(&grp));
+		assert_eq!(byname.info().curvename,name);
+		assert_eq!(ecc_get_oid_from_name(name).unwrap(),oid);
+		assert_eq!(ecc_get_name_from_oid(oid).unwrap(),name);
+		assert!(ecc_get_curve_group_by_oid(oid).unwrap().eq_params(&grp));
+
+		/*the named form carries the registered oid and the explicit form maps back to it*/
+		let der :Vec<u8> = byname.to_der(EC_UNCOMPRESSED,"").unwrap();
+		let named :ECGroup = ECGroup::from_der(&der).unwrap();
+		assert_eq!(named.info().curvename,name);
+		assert!(named.eq_params(&grp));
+		assert_eq!(named.to_der(EC_UNCOMPRESSED,"").unwrap(),der);
+		let der :Vec<u8> = byname.to_der(EC_UNCOMPRESSED,EC_PARAMS_EXLICIT).unwrap();
+		assert_eq!(ECGroup::from_der(&der).unwrap().info().curvename,name);
+		assert_eq!(ECGroup::from_der_canonical(&der,false).unwrap().info().curvename,"");
+
+		/*name and oid already taken , by this curve or a built-in one*/
+		assert!(ecc_register_curve(name,"1.3.6.1.4.1.99999.2.2",&grp).is_err());
+		assert!(ecc_register_curve("ecsimple-test-other",oid,&grp).is_err());
+		assert!(ecc_register_curve(PRIME256v1_NAME,"1.3.6.1.4.1.99999.2.2",&grp).is_err());
+		assert!(ecc_register_curve("ecsimple-test-other",PRIME256v1_OID,&grp).is_err());
+		assert!(ecc_register_curve("ecsimple-test-other","not an oid",&grp).is_err());
+		assert!(ecc_register_curve("",oid,&grp).is_err());
+		assert!(ecc_get_curve_group("ecsimple-test-other").is_err());
+
+		/*built-in curves stay*/
+		assert!(ecc_unregister_curve(PRIME256v1_NAME).is_err());
+		assert!(ecc_get_curve_group(PRIME256v1_NAME).is_ok());
+		assert_eq!(ecc_get_name_from_oid(PRIME256v1_OID).unwrap(),PRIME256v1_NAME);
+
+		ecc_unregister_curve(name).unwrap();
+		assert!(!ecc_get_curve_names().contains(&name.to_string()));
+		assert!(ecc_get_curve_group(name).is_err());
+		assert!(ecc_get_curve_group_by_oid(oid).is_err());
+		assert!(ecc_get_oid_from_name(name).is_err());
+		assert!(ecc_find_named_group(&grp).is_none());
+		assert!(ECGroup::from_der(&der).unwrap().info().curvename.is_empty());
+		assert!(ecc_unregister_curve(name).is_err());
 	}
-	return retv;
 }
 
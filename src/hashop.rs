@@ -0,0 +1,234 @@
+#[allow(unused_imports)]
+use crate::logger::*;
+use crate::consts::*;
+use sha1::{Sha1,Digest};
+use sha2::{Sha224,Sha256,Sha384,Sha512};
+use sm3::Sm3;
+use std::error::Error;
+
+ecsimple_error_class!{ECHashError}
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum ECHashType {
+	Sha1,
+	Sha224,
+	Sha256,
+	Sha384,
+	Sha512,
+	Sm3,
+}
+
+impl ECHashType {
+	pub fn digest(&self,data :&[u8]) -> Vec<u8> {
+		match self {
+			ECHashType::Sha1 => {
+				let mut hasher = Sha1::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+			ECHashType::Sha224 => {
+				let mut hasher = Sha224::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+			ECHashType::Sha256 => {
+				let mut hasher = Sha256::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+			ECHashType::Sha384 => {
+				let mut hasher = Sha384::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+			ECHashType::Sha512 => {
+				let mut hasher = Sha512::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+			ECHashType::Sm3 => {
+				let mut hasher = Sm3::new();
+				hasher.update(data);
+				hasher.finalize().to_vec()
+			},
+		}
+	}
+
+	pub fn output_size(&self) -> usize {
+		match self {
+			ECHashType::Sha1 => 20,
+			ECHashType::Sha224 => 28,
+			ECHashType::Sha256 => 32,
+			ECHashType::Sha384 => 48,
+			ECHashType::Sha512 => 64,
+			ECHashType::Sm3 => 32,
+		}
+	}
+
+	pub fn block_size(&self) -> usize {
+		match self {
+			ECHashType::Sha384 | ECHashType::Sha512 => 128,
+			_ => 64,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			ECHashType::Sha1 => "sha1",
+			ECHashType::Sha224 => "sha224",
+			ECHashType::Sha256 => "sha256",
+			ECHashType::Sha384 => "sha384",
+			ECHashType::Sha512 => "sha512",
+			ECHashType::Sm3 => "sm3",
+		}
+	}
+
+	/*the digest algorithm oid , not the hmac one*/
+	pub fn oid(&self) -> &'static str {
+		match self {
+			ECHashType::Sha1 => SHA1_OID,
+			ECHashType::Sha224 => SHA224_OID,
+			ECHashType::Sha256 => SHA256_OID,
+			ECHashType::Sha384 => SHA384_OID,
+			ECHashType::Sha512 => SHA512_OID,
+			ECHashType::Sm3 => SM3_OID,
+		}
+	}
+
+	pub fn from_oid(oid :&str) -> Result<ECHashType,Box<dyn Error>> {
+		for h in [ECHashType::Sha1,ECHashType::Sha224,ECHashType::Sha256,ECHashType::Sha384,ECHashType::Sha512,ECHashType::Sm3].iter() {
+			if h.oid() == oid {
+				return Ok(*h);
+			}
+		}
+		ecsimple_new_error!{ECHashError,"unsupported hash oid [{}]",oid}
+	}
+
+	/*the sha of the curve size , RFC 5656 6.2.1 and RFC 5480 4*/
+	pub fn from_order_bits(bits :i64) -> ECHashType {
+		if bits <= 256 {
+			return ECHashType::Sha256;
+		} else if bits <= 384 {
+			return ECHashType::Sha384;
+		}
+		ECHashType::Sha512
+	}
+
+	/*sha256 SHA-256 and SHA256 all accepted*/
+	pub fn from_name(name :&str) -> Result<ECHashType,Box<dyn Error>> {
+		let lname :String = name.to_lowercase().replace("-","");
+		for h in [ECHashType::Sha1,ECHashType::Sha224,ECHashType::Sha256,ECHashType::Sha384,ECHashType::Sha512,ECHashType::Sm3].iter() {
+			if h.name() == lname {
+				return Ok(*h);
+			}
+		}
+		ecsimple_new_error!{ECHashError,"unsupported hash [{}]",name}
+	}
+}
+
+impl std::fmt::Display for ECHashType {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f,"{}",self.name())
+	}
+}
+
+/*RFC 2104*/
+pub fn hmac_data(htype :ECHashType,key :&[u8],data :&[u8]) -> Vec<u8> {
+	let bsize :usize = htype.block_size();
+	let mut k :Vec<u8> = key.to_vec();
+	if k.len() > bsize {
+		k = htype.digest(&k);
+	}
+	while k.len() < bsize {
+		k.push(0x0);
+	}
+	let mut inner :Vec<u8> = Vec::new();
+	let mut outer :Vec<u8> = Vec::new();
+	for b in k.iter() {
+		inner.push(b ^ 0x36);
+		outer.push(b ^ 0x5c);
+	}
+	inner.extend_from_slice(data);
+	let ihash :Vec<u8> = htype.digest(&inner);
+	outer.extend_from_slice(&ihash);
+	htype.digest(&outer)
+}
+
+/*RFC 1321 section 3.4 , T[i] = floor(2^32 * abs(sin(i)))*/
+const MD5_K :[u32;64] = [
+	0xd76aa478,0xe8c7b756,0x242070db,0xc1bdceee,0xf57c0faf,0x4787c62a,0xa8304613,0xfd469501,
+	0x698098d8,0x8b44f7af,0xffff5bb1,0x895cd7be,0x6b901122,0xfd987193,0xa679438e,0x49b40821,
+	0xf61e2562,0xc040b340,0x265e5a51,0xe9b6c7aa,0xd62f105d,0x02441453,0xd8a1e681,0xe7d3fbc8,
+	0x21e1cde6,0xc33707d6,0xf4d50d87,0x455a14ed,0xa9e3e905,0xfcefa3f8,0x676f02d9,0x8d2a4c8a,
+	0xfffa3942,0x8771f681,0x6d9d6122,0xfde5380c,0xa4beea44,0x4bdecfa9,0xf6bb4b60,0xbebfbc70,
+	0x289b7ec6,0xeaa127fa,0xd4ef3085,0x04881d05,0xd9d4d039,0xe6db99e5,0x1fa27cf8,0xc4ac5665,
+	0xf4292244,0x432aff97,0xab9423a7,0xfc93a039,0x655b59c3,0x8f0ccc92,0xffeff47d,0x85845dd1,
+	0x6fa87e4f,0xfe2ce6e0,0xa3014314,0x4e0811a1,0xf7537e82,0xbd3af235,0x2ad7d2bb,0xeb86d391,
+];
+
+const MD5_SHIFTS :[u32;16] = [7,12,17,22,5,9,14,20,4,11,16,23,6,10,15,21];
+
+/*RFC 1321 , only for the legacy openssl EVP_BytesToKey , not in ECHashType on purpose*/
+pub fn md5_digest(data :&[u8]) -> Vec<u8> {
+	let mut h :[u32;4] = [0x67452301,0xefcdab89,0x98badcfe,0x10325476];
+	let mut msg :Vec<u8> = data.to_vec();
+	let bitlen :u64 = (data.len() as u64).wrapping_mul(8);
+	msg.push(0x80);
+	while msg.len() % 64 != 56 {
+		msg.push(0x0);
+	}
+	msg.extend_from_slice(&bitlen.to_le_bytes());
+	for chunk in msg.chunks(64) {
+		let mut m :[u32;16] = [0;16];
+		for i in 0..16 {
+			m[i] = u32::from_le_bytes([chunk[i * 4],chunk[i * 4 + 1],chunk[i * 4 + 2],chunk[i * 4 + 3]]);
+		}
+		let (mut a,mut b,mut c,mut d) = (h[0],h[1],h[2],h[3]);
+		for i in 0..64 {
+			let (f,g) = match i / 16 {
+				0 => ((b & c) | (!b & d),i),
+				1 => ((d & b) | (!d & c),(5 * i + 1) % 16),
+				2 => (b ^ c ^ d,(3 * i + 5) % 16),
+				_ => (c ^ (b | !d),(7 * i) % 16),
+			};
+			let s :u32 = MD5_SHIFTS[(i / 16) * 4 + (i % 4)];
+			let t :u32 = a.wrapping_add(f).wrapping_add(MD5_K[i]).wrapping_add(m[g]);
+			a = d;
+			d = c;
+			c = b;
+			b = b.wrapping_add(t.rotate_left(s));
+		}
+		h[0] = h[0].wrapping_add(a);
+		h[1] = h[1].wrapping_add(b);
+		h[2] = h[2].wrapping_add(c);
+		h[3] = h[3].wrapping_add(d);
+	}
+	let mut retv :Vec<u8> = Vec::new();
+	for v in h.iter() {
+		retv.extend_from_slice(&v.to_le_bytes());
+	}
+	retv
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	#[test]
+	fn test_md5_rfc1321() {
+		/*RFC 1321 A.5 test suite*/
+		let cases :Vec<(&str,&str)> = vec![
+			("","d41d8cd98f00b204e9800998ecf8427e"),
+			("a","0cc175b9c0f1b6a831c399e269772661"),
+			("abc","900150983cd24fb0d6963f7d28e17f72"),
+			("message digest","f96b697d7cb7938d525a2f31aaf161d0"),
+			("abcdefghijklmnopqrstuvwxyz","c3fcd3d76192e4007dfb496cca67e13b"),
+			("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789","d174ab98d277d9f5a5611c2c9f419d9f"),
+			("12345678901234567890123456789012345678901234567890123456789012345678901234567890","57edf4a22be3c955ac49da2e2107b67a"),
+		];
+		for (m,h) in cases.iter() {
+			assert_eq!(md5_digest(m.as_bytes()),Vec::from_hex(h).unwrap());
+		}
+	}
+}
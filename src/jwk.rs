@@ -0,0 +1,328 @@
+use crate::consts::*;
+use crate::curve::*;
+use crate::group::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::utils::bigint_to_fixed_bytes;
+#[allow(unused_imports)]
+use crate::logger::*;
+use num_bigint::{BigInt,Sign};
+use num_traits::{zero};
+use serde_json::{Value,Map};
+use std::error::Error;
+
+ecsimple_error_class!{ECJwkError}
+
+pub const JWK_KTY_EC :&str = "EC";
+/*no registered crv for SM2 , this is the private one used by the gmssl family*/
+pub const JWK_CRV_SM2 :&str = "SM2";
+
+#[derive(Clone)]
+pub struct ECJwk {
+	pub pubkey :ECPublicKey,
+	pub privkey :Option<ECPrivateKey>,
+	pub kid :Option<String>,
+	pub keyuse :Option<String>,
+	pub alg :Option<String>,
+}
+
+fn jwk_crv_from_group(grp :&ECGroup) -> Result<String,Box<dyn Error>> {
+	let sm2grp :ECGroup = ecc_get_curve_group(SM2_NAME)?;
+	if grp.eq_params(&sm2grp) {
+		return Ok(JWK_CRV_SM2.to_string());
+	}
+	if let Some(crv) = grp.curve_id().and_then(|c| c.jose_crv()) {
+		return Ok(crv);
+	}
+	ecsimple_new_error!{ECJwkError,"no jwk crv for [{}]",grp.info().curvename}
+}
+
+fn jwk_group_from_crv(crv :&str) -> Result<ECGroup,Box<dyn Error>> {
+	if crv == JWK_CRV_SM2 {
+		return ecc_get_curve_group(SM2_NAME);
+	}
+	let cid :CurveId = CurveId::from_jose_crv(crv)?;
+	cid.group()
+}
+
+fn jwk_get_str(m :&Map<String,Value>,key :&str) -> Result<Option<String>,Box<dyn Error>> {
+	match m.get(key) {
+		None => Ok(None),
+		Some(v) => {
+			if !v.is_string() {
+				ecsimple_new_error!{ECJwkError,"[{}] not string",key}
+			}
+			Ok(Some(v.as_str().unwrap().to_string()))
+		}
+	}
+}
+
+/*coordinate of exactly size bytes as RFC 7518 6.2.1.2 requires*/
+fn jwk_get_fixed(m :&Map<String,Value>,key :&str,size :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	let sv :Option<String> = jwk_get_str(m,key)?;
+	if sv.is_none() {
+		ecsimple_new_error!{ECJwkError,"no [{}] in jwk",key}
+	}
+	let retv :Vec<u8> = base64url_decode(sv.as_ref().unwrap())?;
+	if retv.len() != size {
+		ecsimple_new_error!{ECJwkError,"[{}] {} bytes != {}",key,retv.len(),size}
+	}
+	Ok(retv)
+}
+
+impl ECJwk {
+	pub fn new_public(pubkey :&ECPublicKey) -> ECJwk {
+		ECJwk {
+			pubkey : pubkey.clone(),
+			privkey : None,
+			kid : None,
+			keyuse : None,
+			alg : None,
+		}
+	}
+
+	pub fn new_private(privkey :&ECPrivateKey) -> ECJwk {
+		ECJwk {
+			pubkey : privkey.export_pubkey(),
+			privkey : Some(privkey.clone()),
+			kid : None,
+			keyuse : None,
+			alg : None,
+		}
+	}
+
+	pub fn from_value(val :&Value) -> Result<ECJwk,Box<dyn Error>> {
+		if !val.is_object() {
+			ecsimple_new_error!{ECJwkError,"jwk not object"}
+		}
+		let m :&Map<String,Value> = val.as_object().unwrap();
+		let kty :Option<String> = jwk_get_str(m,"kty")?;
+		if kty.is_none() || kty.as_ref().unwrap() != JWK_KTY_EC {
+			ecsimple_new_error!{ECJwkError,"kty {:?} not [{}]",kty,JWK_KTY_EC}
+		}
+		let crv :Option<String> = jwk_get_str(m,"crv")?;
+		if crv.is_none() {
+			ecsimple_new_error!{ECJwkError,"no crv in jwk"}
+		}
+		let grp :ECGroup = jwk_group_from_crv(crv.as_ref().unwrap())?;
+		let fsize :usize = grp.info().field_bytes();
+		let mut pubbin :Vec<u8> = vec![0x04];
+		pubbin.extend_from_slice(&jwk_get_fixed(m,"x",fsize)?);
+		pubbin.extend_from_slice(&jwk_get_fixed(m,"y",fsize)?);
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&pubbin)?;
+		let mut privkey :Option<ECPrivateKey> = None;
+		if m.contains_key("d") {
+			let d :Vec<u8> = jwk_get_fixed(m,"d",grp.info().order_bytes())?;
+			let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&d);
+			if privnum == zero() || privnum >= grp.info().order {
+				ecsimple_new_error!{ECJwkError,"d out of range"}
+			}
+			let pk :ECPrivateKey = ECPrivateKey::new(&grp,&privnum);
+			if pk.export_pubkey().to_bin(EC_UNCOMPRESSED)? != pubbin {
+				ecsimple_new_error!{ECJwkError,"x y not match d"}
+			}
+			privkey = Some(pk);
+		}
+		Ok(ECJwk {
+			pubkey,
+			privkey,
+			kid : jwk_get_str(m,"kid")?,
+			keyuse : jwk_get_str(m,"use")?,
+			alg : jwk_get_str(m,"alg")?,
+		})
+	}
+
+	pub fn from_json(s :&str) -> Result<ECJwk,Box<dyn Error>> {
+		let val :Value = serde_json::from_str(s)?;
+		ECJwk::from_value(&val)
+	}
+
+	/*withpriv false to give only the public members*/
+	pub fn to_value(&self,withpriv :bool) -> Result<Value,Box<dyn Error>> {
+		let grp :ECGroup = self.pubkey.get_group();
+		let crv :String = jwk_crv_from_group(&grp)?;
+		let fsize :usize = grp.info().field_bytes();
+		let pubbin :Vec<u8> = self.pubkey.to_bin(EC_UNCOMPRESSED)?;
+		if pubbin.len() != 1 + 2 * fsize {
+			ecsimple_new_error!{ECJwkError,"public key {} bytes != {}",pubbin.len(),1 + 2 * fsize}
+		}
+		let mut m :Map<String,Value> = Map::new();
+		m.insert("kty".to_string(),Value::String(JWK_KTY_EC.to_string()));
+		m.insert("crv".to_string(),Value::String(crv));
+		m.insert("x".to_string(),Value::String(base64url_encode(&pubbin[1..(1 + fsize)])));
+		m.insert("y".to_string(),Value::String(base64url_encode(&pubbin[(1 + fsize)..])));
+		if withpriv {
+			if self.privkey.is_none() {
+				ecsimple_new_error!{ECJwkError,"no private key"}
+			}
+			let d :Vec<u8> = bigint_to_fixed_bytes(&self.privkey.as_ref().unwrap().get_privnum(),grp.info().order_bytes())?;
+			m.insert("d".to_string(),Value::String(base64url_encode(&d)));
+		}
+		if let Some(kid) = self.kid.as_ref() {
+			m.insert("kid".to_string(),Value::String(kid.clone()));
+		}
+		if let Some(keyuse) = self.keyuse.as_ref() {
+			m.insert("use".to_string(),Value::String(keyuse.clone()));
+		}
+		if let Some(alg) = self.alg.as_ref() {
+			m.insert("alg".to_string(),Value::String(alg.clone()));
+		}
+
+		Ok(Value::Object(m))
+	}
+
+	pub fn to_json(&self,withpriv :bool) -> Result<String,Box<dyn Error>> {
+		let val :Value = self.to_value(withpriv)?;
+		Ok(serde_json::to_string(&val)?)
+	}
+
+	/*RFC 7638 , the required members in lexicographic order without whitespace*/
+	pub fn thumbprint(&self,htype :ECHashType) -> Result<Vec<u8>,Box<dyn Error>> {
+		let grp :ECGroup = self.pubkey.get_group();
+		let crv :String = jwk_crv_from_group(&grp)?;
+		let fsize :usize = grp.info().field_bytes();
+		let pubbin :Vec<u8> = self.pubkey.to_bin(EC_UNCOMPRESSED)?;
+		let s :String = format!("{{\"crv\":\"{}\",\"kty\":\"{}\",\"x\":\"{}\",\"y\":\"{}\"}}",crv,JWK_KTY_EC,
+			base64url_encode(&pubbin[1..(1 + fsize)]),base64url_encode(&pubbin[(1 + fsize)..]));
+		Ok(htype.digest(s.as_bytes()))
+	}
+}
+
+/*JWK Set , keys of other kty , unsupported crv or bad members skipped as RFC 7517 section 5 says*/
+pub fn jwk_parse_set(s :&str) -> Result<Vec<ECJwk>,Box<dyn Error>> {
+	let val :Value = serde_json::from_str(s)?;
+	let keys :Option<&Value> = val.get("keys");
+	if keys.is_none() || !keys.unwrap().is_array() {
+		ecsimple_new_error!{ECJwkError,"no keys array in jwk set"}
+	}
+	let mut retv :Vec<ECJwk> = Vec::new();
+	for k in keys.unwrap().as_array().unwrap().iter() {
+		let kty :Option<&Value> = k.get("kty");
+		if kty.is_none() || kty.unwrap().as_str() != Some(JWK_KTY_EC) {
+			ecsimple_log_trace!("skip kty {:?}",kty);
+			continue;
+		}
+		match ECJwk::from_value(k) {
+			Ok(v) => {
+				retv.push(v);
+			},
+			Err(_e) => {
+				ecsimple_log_trace!("skip key {:?}",_e);
+			}
+		}
+	}
+	Ok(retv)
+}
+
+pub fn jwk_format_set(keys :&[ECJwk],withpriv :bool) -> Result<String,Box<dyn Error>> {
+	let mut arr :Vec<Value> = Vec::new();
+	for k in keys.iter() {
+		arr.push(k.to_value(withpriv)?);
+	}
+	let mut m :Map<String,Value> = Map::new();
+	m.insert("keys".to_string(),Value::Array(arr));
+	Ok(serde_json::to_string(&Value::Object(m))?)
+}
+
+impl ECPublicKey {
+	pub fn to_jwk(&self) -> Result<String,Box<dyn Error>> {
+		ECJwk::new_public(self).to_json(false)
+	}
+
+	/*d is still checked to be in range and to match x y when given*/
+	pub fn from_jwk(s :&str) -> Result<ECPublicKey,Box<dyn Error>> {
+		let jwk :ECJwk = ECJwk::from_json(s)?;
+		Ok(jwk.pubkey)
+	}
+
+	/*base64url of the RFC 7638 SHA-256 thumbprint , the usual kid*/
+	pub fn jwk_thumbprint(&self) -> Result<String,Box<dyn Error>> {
+		let tb :Vec<u8> = ECJwk::new_public(self).thumbprint(ECHashType::Sha256)?;
+		Ok(base64url_encode(&tb))
+	}
+}
+
+impl ECPrivateKey {
+	pub fn to_jwk(&self) -> Result<String,Box<dyn Error>> {
+		ECJwk::new_private(self).to_json(true)
+	}
+
+	pub fn from_jwk(s :&str) -> Result<ECPrivateKey,Box<dyn Error>> {
+		let jwk :ECJwk = ECJwk::from_json(s)?;
+		if jwk.privkey.is_none() {
+			ecsimple_new_error!{ECJwkError,"no d in jwk"}
+		}
+		Ok(jwk.privkey.unwrap())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/*RFC 7517 A.2*/
+	const RFC7517_P256 :&str = r#"{"kty":"EC","crv":"P-256","x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4","y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM","d":"870MB6gfuTJ4HtUnUvYMyJpr5eUZNP4Bk43bVdj3eAE","use":"enc","kid":"1"}"#;
+	/*x has 519 bits , so one leading zero byte to make the 66 bytes*/
+	const P521_LEADING_ZERO :&str = r#"{"kty":"EC","crv":"P-521","x":"AH16RZ4pvGNIFlvHH4yYXsfVdhSMQIbZUUOwhd9ta0XriKGEkCna87-81xtMfSbxHnzg8zwEhWuOIbT9nyYSWxdU","y":"Aa3bas5IZl1_Px1QWrIkim2TUsHnwnt7dEWOugyHkM-KE0d4NyH3d3Ou5DWHsiJly8IzZjM1EbYJMWFSZznM9dLe","d":"AAG37no8HBt3stydVuTJ7Q5quPXITxoOvxpbThxA-dGnw5uKDx4tPEtaaXiHlqW0w9Lh8KGyw9Tl9gcYKTpLXG1-"}"#;
+
+	#[test]
+	fn test_rfc7517_p256() {
+		let jwk :ECJwk = ECJwk::from_json(RFC7517_P256).unwrap();
+		assert_eq!(jwk.kid,Some("1".to_string()));
+		assert_eq!(jwk.keyuse,Some("enc".to_string()));
+		let back :ECJwk = ECJwk::from_json(&jwk.to_json(true).unwrap()).unwrap();
+		assert_eq!(back.privkey.unwrap().get_privnum(),jwk.privkey.unwrap().get_privnum());
+	}
+
+	#[test]
+	fn test_p521_fixed_width() {
+		let privkey :ECPrivateKey = ECPrivateKey::from_jwk(P521_LEADING_ZERO).unwrap();
+		let val :Value = serde_json::from_str(&privkey.to_jwk().unwrap()).unwrap();
+		let orig :Value = serde_json::from_str(P521_LEADING_ZERO).unwrap();
+		for k in ["x","y","d"].iter() {
+			assert_eq!(val[k],orig[k]);
+		}
+		/*the zero byte stripped is not accepted*/
+		let short :String = P521_LEADING_ZERO.replace("AH16RZ4p","fXpFnim8");
+		assert!(ECPublicKey::from_jwk(&short).is_err());
+	}
+
+	#[test]
+	fn test_public_from_jwk_checks_d() {
+		assert!(ECPublicKey::from_jwk(RFC7517_P256).is_ok());
+		let badd :String = RFC7517_P256.replace("870MB6gf","870MB6gg");
+		assert!(ECPublicKey::from_jwk(&badd).is_err());
+	}
+
+	#[test]
+	fn test_non_canonical_base64url() {
+		/*the last char of x carries 2 unused bits , 4 -> 5 sets one of them*/
+		let badx :String = RFC7517_P256.replace("qv7D4\"","qv7D5\"");
+		assert!(ECJwk::from_json(&badx).is_err());
+		assert!(base64url_decode("AB").is_err());
+		assert_eq!(base64url_decode("AA").unwrap(),vec![0x0]);
+	}
+
+	#[test]
+	fn test_jwk_set_skips_unusable() {
+		let badx :String = RFC7517_P256.replace("qv7D4\"","qv7D5\"");
+		let set :String = format!(r#"{{"keys":[{},{{"kty":"RSA","n":"AQAB","e":"AQAB"}},{},{},{},{},7,{}]}}"#,
+			RFC7517_P256,
+			RFC7517_P256.replace("P-256","P-999"),
+			badx,
+			r#"{"kty":"EC","crv":"P-256","x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4"}"#,
+			RFC7517_P256.replace("870MB6gf","870MB6gg"),
+			P521_LEADING_ZERO);
+		let keys :Vec<ECJwk> = jwk_parse_set(&set).unwrap();
+		assert_eq!(keys.len(),2);
+		assert_eq!(keys[0].kid,Some("1".to_string()));
+		assert_eq!(keys[1].pubkey.get_group().info().curvename,SECP521r1_NAME);
+		let back :Vec<ECJwk> = jwk_parse_set(&jwk_format_set(&keys,false).unwrap()).unwrap();
+		assert_eq!(back.len(),2);
+		assert!(back[0].privkey.is_none());
+		assert!(jwk_parse_set(r#"{"keys":[{"kty":"EC","crv":"P-999"}]}"#).unwrap().is_empty());
+		assert!(jwk_parse_set(r#"{"keys":{}}"#).is_err());
+		assert!(jwk_parse_set("[]").is_err());
+	}
+}
@@ -0,0 +1,322 @@
+#[allow(unused_imports)]
+use crate::logger::*;
+use crate::hashop::*;
+use crate::symcipher::*;
+use std::error::Error;
+
+ecsimple_error_class!{ECKdfError}
+
+/*RFC 8018 section 5.2*/
+pub fn pbkdf2_hmac(prf :ECHashType,password :&[u8],salt :&[u8],iterations :u64,keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iterations == 0 {
+		ecsimple_new_error!{ECKdfError,"iterations 0"}
+	}
+	let hlen :usize = prf.output_size();
+	let blocks :usize = (keylen + hlen - 1) / hlen;
+	if (blocks as u64) > 0xffffffff {
+		ecsimple_new_error!{ECKdfError,"derived key too long {}",keylen}
+	}
+	let mut retv :Vec<u8> = Vec::new();
+	for i in 1..(blocks + 1) {
+		let mut sbuf :Vec<u8> = salt.to_vec();
+		sbuf.extend_from_slice(&(i as u32).to_be_bytes());
+		let mut u :Vec<u8> = hmac_data(prf,password,&sbuf);
+		let mut t :Vec<u8> = u.clone();
+		for _ in 1..iterations {
+			u = hmac_data(prf,password,&u);
+			for j in 0..t.len() {
+				t[j] ^= u[j];
+			}
+		}
+		retv.extend_from_slice(&t);
+	}
+	retv.truncate(keylen);
+	Ok(retv)
+}
+
+fn salsa20_8(b :&mut [u32;16]) {
+	let mut x :[u32;16] = *b;
+	for _ in 0..4 {
+		x[4] ^= x[0].wrapping_add(x[12]).rotate_left(7);
+		x[8] ^= x[4].wrapping_add(x[0]).rotate_left(9);
+		x[12] ^= x[8].wrapping_add(x[4]).rotate_left(13);
+		x[0] ^= x[12].wrapping_add(x[8]).rotate_left(18);
+		x[9] ^= x[5].wrapping_add(x[1]).rotate_left(7);
+		x[13] ^= x[9].wrapping_add(x[5]).rotate_left(9);
+		x[1] ^= x[13].wrapping_add(x[9]).rotate_left(13);
+		x[5] ^= x[1].wrapping_add(x[13]).rotate_left(18);
+		x[14] ^= x[10].wrapping_add(x[6]).rotate_left(7);
+		x[2] ^= x[14].wrapping_add(x[10]).rotate_left(9);
+		x[6] ^= x[2].wrapping_add(x[14]).rotate_left(13);
+		x[10] ^= x[6].wrapping_add(x[2]).rotate_left(18);
+		x[3] ^= x[15].wrapping_add(x[11]).rotate_left(7);
+		x[7] ^= x[3].wrapping_add(x[15]).rotate_left(9);
+		x[11] ^= x[7].wrapping_add(x[3]).rotate_left(13);
+		x[15] ^= x[11].wrapping_add(x[7]).rotate_left(18);
+
+		x[1] ^= x[0].wrapping_add(x[3]).rotate_left(7);
+		x[2] ^= x[1].wrapping_add(x[0]).rotate_left(9);
+		x[3] ^= x[2].wrapping_add(x[1]).rotate_left(13);
+		x[0] ^= x[3].wrapping_add(x[2]).rotate_left(18);
+		x[6] ^= x[5].wrapping_add(x[4]).rotate_left(7);
+		x[7] ^= x[6].wrapping_add(x[5]).rotate_left(9);
+		x[4] ^= x[7].wrapping_add(x[6]).rotate_left(13);
+		x[5] ^= x[4].wrapping_add(x[7]).rotate_left(18);
+		x[11] ^= x[10].wrapping_add(x[9]).rotate_left(7);
+		x[8] ^= x[11].wrapping_add(x[10]).rotate_left(9);
+		x[9] ^= x[8].wrapping_add(x[11]).rotate_left(13);
+		x[10] ^= x[9].wrapping_add(x[8]).rotate_left(18);
+		x[12] ^= x[15].wrapping_add(x[14]).rotate_left(7);
+		x[13] ^= x[12].wrapping_add(x[15]).rotate_left(9);
+		x[14] ^= x[13].wrapping_add(x[12]).rotate_left(13);
+		x[15] ^= x[14].wrapping_add(x[13]).rotate_left(18);
+	}
+	for i in 0..16 {
+		b[i] = b[i].wrapping_add(x[i]);
+	}
+}
+
+/*RFC 7914 scryptBlockMix , b is 2 * r blocks of 16 words*/
+fn scrypt_block_mix(b :&[u32],r :usize,out :&mut [u32]) {
+	let mut x :[u32;16] = [0;16];
+	x.copy_from_slice(&b[(2 * r - 1) * 16..(2 * r) * 16]);
+	for i in 0..(2 * r) {
+		for j in 0..16 {
+			x[j] ^= b[i * 16 + j];
+		}
+		salsa20_8(&mut x);
+		/*even blocks first then the odd ones*/
+		let off :usize = (i / 2) * 16 + (i % 2) * r * 16;
+		out[off..off + 16].copy_from_slice(&x);
+	}
+}
+
+fn scrypt_ro_mix(b :&mut [u32],r :usize,n :usize) {
+	let blen :usize = 32 * r;
+	let mut v :Vec<u32> = vec![0;blen * n];
+	let mut x :Vec<u32> = b.to_vec();
+	let mut t :Vec<u32> = vec![0;blen];
+	for i in 0..n {
+		v[i * blen..(i + 1) * blen].copy_from_slice(&x);
+		scrypt_block_mix(&x,r,&mut t);
+		std::mem::swap(&mut x,&mut t);
+	}
+	for _ in 0..n {
+		/*integerify : first word of the last block*/
+		let j :usize = (x[(2 * r - 1) * 16] as usize) & (n - 1);
+		for k in 0..blen {
+			x[k] ^= v[j * blen + k];
+		}
+		scrypt_block_mix(&x,r,&mut t);
+		std::mem::swap(&mut x,&mut t);
+	}
+	b.copy_from_slice(&x);
+}
+
+/*openssl SCRYPT_MAX_MEM , the memory for B and V together*/
+pub const SCRYPT_DEFAULT_MAXMEM :u64 = 32 * 1024 * 1024;
+
+/*RFC 7914 , n must be power of 2 , at most SCRYPT_DEFAULT_MAXMEM bytes used*/
+pub fn scrypt_kdf(password :&[u8],salt :&[u8],n :u64,r :u64,p :u64,keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	scrypt_kdf_maxmem(password,salt,n,r,p,SCRYPT_DEFAULT_MAXMEM,keylen)
+}
+
+/*the limits of openssl EVP_PBE_scrypt_ex , B of 128 * r * p bytes and V of 128 * r * (N + 2) bytes within maxmem*/
+pub fn scrypt_kdf_maxmem(password :&[u8],salt :&[u8],n :u64,r :u64,p :u64,maxmem :u64,keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if n < 2 || (n & (n - 1)) != 0 {
+		ecsimple_new_error!{ECKdfError,"scrypt N {} not power of 2",n}
+	}
+	if r == 0 || p == 0 {
+		ecsimple_new_error!{ECKdfError,"scrypt r {} p {} invalid",r,p}
+	}
+	/*RFC 7914 p <= (2^32 - 1) * 32 / (128 * r) , openssl keeps p * r below 2^30*/
+	if p > ((1 << 30) - 1) / r {
+		ecsimple_new_error!{ECKdfError,"scrypt r {} p {} too large",r,p}
+	}
+	/*RFC 7914 N < 2^(128 * r / 8)*/
+	if 16 * r < 64 && n >= (1 << (16 * r)) {
+		ecsimple_new_error!{ECKdfError,"scrypt N {} too large for r {}",n,r}
+	}
+	let blenall :Option<u64> = (128 * r).checked_mul(p);
+	let vlen :Option<u64> = (128 * r).checked_mul(n + 2);
+	let memsize :Option<u64> = blenall.zip(vlen).and_then(|(b,v)| b.checked_add(v));
+	if memsize.map_or(true,|m| m > maxmem) {
+		ecsimple_new_error!{ECKdfError,"scrypt N {} r {} p {} needs more than maxmem {}",n,r,p,maxmem}
+	}
+
+	let ru :usize = r as usize;
+	let blen :usize = 128 * ru;
+	let bdata :Vec<u8> = pbkdf2_hmac(ECHashType::Sha256,password,salt,1,blen * (p as usize))?;
+	let mut outb :Vec<u8> = Vec::new();
+	for i in 0..(p as usize) {
+		let mut words :Vec<u32> = Vec::new();
+		for c in bdata[i * blen..(i + 1) * blen].chunks(4) {
+			words.push(u32::from_le_bytes([c[0],c[1],c[2],c[3]]));
+		}
+		scrypt_ro_mix(&mut words,ru,n as usize);
+		for w in words.iter() {
+			outb.extend_from_slice(&w.to_le_bytes());
+		}
+	}
+	pbkdf2_hmac(ECHashType::Sha256,password,&outb,1,keylen)
+}
+
+/*RFC 7292 appendix B.2 , password in BMPString with the two zero bytes end*/
+pub fn pkcs12_kdf(htype :ECHashType,password :&[u8],salt :&[u8],id :u8,iterations :u64,keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iterations == 0 {
+		ecsimple_new_error!{ECKdfError,"iterations 0"}
+	}
+	let u :usize = htype.output_size();
+	let v :usize = htype.block_size();
+	let d :Vec<u8> = vec![id;v];
+	let mut ibuf :Vec<u8> = Vec::new();
+	if !salt.is_empty() {
+		let slen :usize = v * ((salt.len() + v - 1) / v);
+		for i in 0..slen {
+			ibuf.push(salt[i % salt.len()]);
+		}
+	}
+	if !password.is_empty() {
+		let plen :usize = v * ((password.len() + v - 1) / v);
+		for i in 0..plen {
+			ibuf.push(password[i % password.len()]);
+		}
+	}
+	let mut retv :Vec<u8> = Vec::new();
+	while retv.len() < keylen {
+		let mut a :Vec<u8> = d.clone();
+		a.extend_from_slice(&ibuf);
+		for _ in 0..iterations {
+			a = htype.digest(&a);
+		}
+		retv.extend_from_slice(&a);
+		if retv.len() >= keylen {
+			break;
+		}
+		let mut b :Vec<u8> = Vec::new();
+		for i in 0..v {
+			b.push(a[i % u]);
+		}
+		/*Ij = (Ij + B + 1) mod 2^(v*8)*/
+		for j in 0..(ibuf.len() / v) {
+			let mut carry :u16 = 1;
+			for k in (0..v).rev() {
+				let s :u16 = (ibuf[j * v + k] as u16) + (b[k] as u16) + carry;
+				ibuf[j * v + k] = (s & 0xff) as u8;
+				carry = s >> 8;
+			}
+		}
+	}
+	retv.truncate(keylen);
+	Ok(retv)
+}
+
+const BCRYPT_HASH_SIZE :usize = 32;
+const BCRYPT_MAGIC :&[u8;32] = b"OxychromaticBlowfishSwatDynamite";
+
+fn bcrypt_hash(sha2pass :&[u8],sha2salt :&[u8]) -> Vec<u8> {
+	let mut st :BlowfishState = BlowfishState::new();
+	st.expand_state(sha2salt,sha2pass);
+	for _ in 0..64 {
+		st.expand_state(&[],sha2salt);
+		st.expand_state(&[],sha2pass);
+	}
+	let mut cdata :[u32;8] = [0;8];
+	for i in 0..8 {
+		cdata[i] = u32::from_be_bytes([BCRYPT_MAGIC[4*i],BCRYPT_MAGIC[4*i+1],BCRYPT_MAGIC[4*i+2],BCRYPT_MAGIC[4*i+3]]);
+	}
+	for _ in 0..64 {
+		for j in 0..4 {
+			let (l,r) = cdata.split_at_mut(2*j+1);
+			st.encrypt(&mut l[2*j],&mut r[0]);
+		}
+	}
+	let mut retv :Vec<u8> = Vec::new();
+	for c in cdata.iter() {
+		retv.extend_from_slice(&c.to_le_bytes());
+	}
+
+	retv
+}
+
+/*the OpenBSD bcrypt_pbkdf used by openssh-key-v1 , output bytes spread over the blocks*/
+pub fn bcrypt_pbkdf(password :&[u8],salt :&[u8],rounds :u32,keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if rounds == 0 {
+		ecsimple_new_error!{ECKdfError,"rounds 0"}
+	}
+	if keylen == 0 || keylen > BCRYPT_HASH_SIZE * BCRYPT_HASH_SIZE || salt.is_empty() {
+		ecsimple_new_error!{ECKdfError,"keylen {} or salt len {} not valid",keylen,salt.len()}
+	}
+	let stride :usize = (keylen + BCRYPT_HASH_SIZE - 1) / BCRYPT_HASH_SIZE;
+	let amt :usize = (keylen + stride - 1) / stride;
+	let sha2pass :Vec<u8> = ECHashType::Sha512.digest(password);
+	let mut retv :Vec<u8> = vec![0;keylen];
+	let mut count :u32 = 1;
+	let mut left :usize = keylen;
+	while left > 0 {
+		let mut countsalt :Vec<u8> = salt.to_vec();
+		countsalt.extend_from_slice(&count.to_be_bytes());
+		let mut tmpout :Vec<u8> = bcrypt_hash(&sha2pass,&ECHashType::Sha512.digest(&countsalt));
+		let mut out :Vec<u8> = tmpout.clone();
+		for _ in 1..rounds {
+			tmpout = bcrypt_hash(&sha2pass,&ECHashType::Sha512.digest(&tmpout));
+			for j in 0..out.len() {
+				out[j] ^= tmpout[j];
+			}
+		}
+		let mut used :usize = 0;
+		for (i,v) in out.iter().enumerate().take(std::cmp::min(amt,left)) {
+			let dest :usize = i * stride + (count as usize - 1);
+			if dest >= keylen {
+				break;
+			}
+			retv[dest] = *v;
+			used += 1;
+		}
+
+		left -= used;
+		count += 1;
+	}
+	Ok(retv)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	#[test]
+	fn test_pbkdf2_rfc6070() {
+		assert_eq!(pbkdf2_hmac(ECHashType::Sha1,b"password",b"salt",1,20).unwrap(),Vec::from_hex("0c60c80f961f0e71f3a9b524af6012062fe037a6").unwrap());
+		assert_eq!(pbkdf2_hmac(ECHashType::Sha1,b"password",b"salt",2,20).unwrap(),Vec::from_hex("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957").unwrap());
+		assert_eq!(pbkdf2_hmac(ECHashType::Sha1,b"password",b"salt",4096,20).unwrap(),Vec::from_hex("4b007901b765489abead49d926f721d065a429c1").unwrap());
+	}
+
+	#[test]
+	fn test_pbkdf2_sha256() {
+		assert_eq!(pbkdf2_hmac(ECHashType::Sha256,b"password",b"salt",1,32).unwrap(),Vec::from_hex("120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b").unwrap());
+		assert_eq!(pbkdf2_hmac(ECHashType::Sha256,b"password",b"salt",4096,32).unwrap(),Vec::from_hex("c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a").unwrap());
+	}
+
+	#[test]
+	fn test_scrypt_rfc7914() {
+		assert_eq!(scrypt_kdf(b"",b"",16,1,1,64).unwrap(),Vec::from_hex("77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906").unwrap());
+		assert_eq!(scrypt_kdf(b"password",b"NaCl",1024,8,16,64).unwrap(),Vec::from_hex("fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640").unwrap());
+	}
+
+	#[test]
+	fn test_scrypt_maxmem() {
+		/*V of 128 * 8 * (2^15 + 2) bytes is just over 32 MiB*/
+		assert!(scrypt_kdf(b"password",b"NaCl",1 << 15,8,1,64).is_err());
+		/*B alone over 32 MiB*/
+		assert!(scrypt_kdf(b"password",b"NaCl",16,1,1 << 18,64).is_err());
+		assert!(scrypt_kdf(b"password",b"NaCl",16,1 << 20,1 << 20,64).is_err());
+		assert!(scrypt_kdf(b"password",b"NaCl",1 << 62,1 << 40,1,64).is_err());
+		/*N < 2^(16 * r)*/
+		assert!(scrypt_kdf(b"password",b"NaCl",1 << 16,1,1,64).is_err());
+		/*the RFC 7914 second vector uses 128 * 8 * 1026 + 128 * 8 * 16 bytes*/
+		assert!(scrypt_kdf_maxmem(b"password",b"NaCl",1024,8,16,1024 * 1024,64).is_err());
+		assert_eq!(scrypt_kdf_maxmem(b"password",b"NaCl",1024,8,16,128 * 8 * (1026 + 16),64).unwrap(),scrypt_kdf(b"password",b"NaCl",1024,8,16,64).unwrap());
+	}
+
+}
@@ -7,7 +7,9 @@ use crate::bngf2m::*;
 use crate::signature::*;
 use crate::utils::*;
 use crate::randop::*;
+use crate::hashop::*;
 use crate::logger::*;
+#[allow(unused_imports)]
 use crate::*;
 use crate::mont::*;
 use crate::ecasn1::*;
@@ -16,51 +18,31 @@ use num_traits::{zero,one};
 
 use std::error::Error;
 
-use asn1obj_codegen::{asn1_sequence};
-use asn1obj::base::{Asn1BigNum,Asn1Object,Asn1Integer,Asn1BitData,Asn1BitDataFlag,Asn1Any};
-use asn1obj::complex::{Asn1Seq,Asn1ImpSet};
+use asn1obj::base::{Asn1BigNum,Asn1BitDataFlag,Asn1Any};
+use asn1obj::complex::{Asn1ImpSet,Asn1Imp};
+#[allow(unused_imports)]
 use asn1obj::{asn1obj_error_class,asn1obj_new_error};
 use asn1obj::asn1impl::Asn1Op;
-use asn1obj::strop::asn1_format_line;
-use std::io::Write;
 use sm3::{Sm3,Digest};
 
 
 ecsimple_error_class!{EcKeyError}
 
+const PKCS8_VERSION_V1 :i64 = 0;
+const PKCS8_VERSION_V2 :i64 = 1;
 
-#[derive(Clone)]
-#[asn1_sequence()]
-struct ECPrivateAsn1Elem {
-	pub (crate) version :Asn1Integer,
-	pub (crate) privnum :Asn1BigNum,
-	pub (crate) ecoid :Asn1ImpSet<Asn1Object,0>,
-	pub (crate) pubdata :Asn1ImpSet<Asn1BitData,1>,
-}
 
-#[derive(Clone)]
-#[asn1_sequence()]
-struct ECPrivateAsn1 {
-	pub (crate) elem :Asn1Seq<ECPrivateAsn1Elem>,
-}
 
 
 #[derive(Clone)]
+#[derive(Default)]
 pub (crate) struct ECGf2mPubKey {
 	base :ECGf2mPoint,
 	pubk :ECGf2mPoint,
 }
 
-impl Default for ECGf2mPubKey {
-	fn default() -> Self {
-		Self {
-			base : ECGf2mPoint::default(),
-			pubk : ECGf2mPoint::default(),
-		}
-	}
-}
 
-fn form_ecpkparameters_gf2m(grp :&ECGroupBnGf2m,cmprtype:&str, paramenc:&str) -> Result<ECPKPARAMETERS,Box<dyn Error>> {
+pub (crate) fn form_ecpkparameters_gf2m(grp :&ECGroupBnGf2m,cmprtype:&str, paramenc:&str) -> Result<ECPKPARAMETERS,Box<dyn Error>> {
 	let mut tmpp :BigInt;
 	let mut idx :usize;
 	let mut kval :i64;
@@ -73,7 +55,7 @@ fn form_ecpkparameters_gf2m(grp :&ECGroupBnGf2m,cmprtype:&str, paramenc:&str) ->
 	let degr :i64 = grp.degree();
 	let fieldsize :usize = ((degr + 7) >> 3) as usize;
 	let zv :BigInt = zero();
-	if paramenc == EC_PARAMS_EXLICIT {
+	if paramenc == EC_PARAMS_EXLICIT || paramenc == EC_PARAMS_EXLICIT_SEED {
 		/*sect163r1*/
 		/*
 		v8 = Vec::from_hex("0800000000000000000000000000000000000000C9").unwrap();
@@ -106,7 +88,7 @@ fn form_ecpkparameters_gf2m(grp :&ECGroupBnGf2m,cmprtype:&str, paramenc:&str) ->
 		tmpp = grp.p.clone();
 		kval = get_max_bits(&tmpp);
 		chartwo.m.val = kval-1;
-		tmpp -= ov.clone() << kval-1;
+		tmpp -= ov.clone() << (kval-1);
 		idx = 3;
 		while tmpp != ov {
 			kval = get_max_bits(&tmpp);
@@ -202,7 +184,7 @@ fn form_ecpkparameters_gf2m(grp :&ECGroupBnGf2m,cmprtype:&str, paramenc:&str) ->
 		params.itype = 1;
 		params.parameters.elem.val.push(paramselem);
 		return Ok(params);
-	} else if paramenc == "" {
+	} else if paramenc.is_empty() {
 		params.itype = 0;
 		let oid :String = ecc_get_oid_from_name(&grp.curvename)?;
 		let _ = params.named_curve.set_value(&oid)?;
@@ -235,12 +217,12 @@ impl ECGf2mPubKey {
 		pubkasn1elem.pubdata.data = pubdata.clone();
 		pubkasn1elem.pubdata.flag = 0;
 		pubkasn1.elem.val.push(pubkasn1elem);
-		return pubkasn1.encode_asn1();
+		pubkasn1.encode_asn1()
 	}
 
 	fn uncompress_x_point(grp :&ECGroupBnGf2m, x_ :&BigInt, ybit :u8) -> Result<BigInt,Box<dyn Error>> {
 		let b = ECGf2mPoint::new(grp);
-		let xb :BnGf2m = BnGf2m::new_from_bigint(&x_);
+		let xb :BnGf2m = BnGf2m::new_from_bigint(x_);
 		let field :BnGf2m = BnGf2m::new_from_bigint(&b.group.p);
 		let x :BnGf2m = &xb % &field;
 		let y :BigInt;
@@ -280,7 +262,7 @@ impl ECGf2mPubKey {
 	pub fn from_bin(grp :&ECGroupBnGf2m, dercode :&[u8]) -> Result<Self,Box<dyn Error>> {
 		let b = ECGf2mPoint::new(grp);
 		let mut pubk :ECGf2mPoint = b.clone();
-		if dercode.len() < 1 {
+		if dercode.is_empty() {
 			ecsimple_new_error!{EcKeyError,"code [{}] < 1", dercode.len()}
 		}
 		let code :u8 = dercode[0] & EC_CODE_MASK;
@@ -333,7 +315,7 @@ impl ECGf2mPubKey {
 		pubk.set_y(&bval);
 		bval = BnGf2m::one();
 		pubk.set_z(&bval);
-		let _ = pubk.check_on_curve()?;
+		pubk.check_on_curve()?;
 		ecsimple_log_trace!("x 0x{:X} y 0x{:X}", x,y);
 
 		Ok(Self {
@@ -422,7 +404,7 @@ impl ECGf2mPubKey {
 	pub (crate) fn verify_base(&self,sig :&ECSignature, hashnum :&[u8]) -> Result<bool,Box<dyn Error>> {
 		let mut u2 :BigInt;
 		let order :BigInt = self.base.group.order.clone();
-		let vfypnt :ECGf2mPoint;
+		
 		ecsimple_log_trace!("sig.r 0x{:X} sig.s 0x{:X}", sig.r,sig.s);
 		if sig.r == zero() || sig.s == zero() {
 			ecsimple_new_error!{EcKeyError,"sig.r 0x{:X} or sig.s 0x{:X} zero",sig.r,sig.s}
@@ -440,7 +422,7 @@ impl ECGf2mPubKey {
 		u2 = &(&u2 * &sig.r) % &order;
 		ecsimple_log_trace!("u2 0x{:X} sig->r 0x{:X} order 0x{:X}", u2,sig.r,order);
 
-		vfypnt = self.pubk.mulex_op(&u1,&u2)?;
+		let vfypnt :ECGf2mPoint = self.pubk.mulex_op(&u1,&u2)?;
 		let xn :BigInt = vfypnt.x().to_bigint();
 		u1 = &xn % &order;
 		ecsimple_log_trace!("u1 0x{:X} = X 0x{:X} % order 0x{:X} sig->r 0x{:X}",u1,xn,order,sig.r);
@@ -505,13 +487,13 @@ impl ECGf2mPrivateKey {
 		}
 		ECGf2mPrivateKey {
 			base :b ,
-			privnum : privnum,
+			privnum,
 		}
 	}
 
 	pub fn export_pubkey(&self) -> ECGf2mPubKey {
-		let ck : ECGf2mPoint;
-		ck = self.base.mul_op(&self.privnum,false);
+		
+		let ck : ECGf2mPoint = self.base.mul_op(&self.privnum,false);
 		let retv :ECGf2mPubKey = ECGf2mPubKey {
 			base : self.base.clone(),
 			pubk : ck.clone(),
@@ -519,25 +501,43 @@ impl ECGf2mPrivateKey {
 		retv
 	}
 
+	/*x coordinate of privnum * peer*/
+	pub (crate) fn ecdh(&self,peer :&ECGf2mPubKey) -> Result<Vec<u8>,Box<dyn Error>> {
+		/*SEC 1 3.2.2.1 , with a cofactor the peer must be in the order subgroup*/
+		if peer.pubk.is_infinity() || (self.base.group.cofactor != one() && !peer.pubk.mul_op(&self.base.group.order,true).is_infinity()) {
+			ecsimple_new_error!{EcKeyError,"peer point not in the order subgroup"}
+		}
+		let ck :ECGf2mPoint = peer.pubk.mul_op(&self.privnum,true);
+		if ck.is_infinity() {
+			ecsimple_new_error!{EcKeyError,"shared point at infinity"}
+		}
+		let retk :ECGf2mPubKey = ECGf2mPubKey {
+			base : self.base.clone(),
+			pubk : ck,
+		};
+		let bin :Vec<u8> = retk.to_bin(EC_UNCOMPRESSED)?;
+		let fieldsize :usize = (bin.len() - 1) / 2;
+		Ok(bin[1..(fieldsize+1)].to_vec())
+	}
+
 	pub (crate)  fn to_der(&self,cmprtype :&str,paramenc :&str) -> Result<Vec<u8>,Box<dyn Error>> {
 		let pubk :ECGf2mPubKey = self.export_pubkey();
 		//let pubdata :Vec<u8> = pubk.to_bin(cmprtype)?;
 		let privlen :usize = ((get_max_bits(&self.base.group.order) + 7 ) >> 3) as usize;
-		let pubdata :Vec<u8>;
-		if paramenc == EC_PARAMS_EXLICIT {
+		let pubdata :Vec<u8> = if paramenc == EC_PARAMS_EXLICIT || paramenc == EC_PARAMS_EXLICIT_SEED {
 			//pubdata  = pubk.to_bin(EC_UNCOMPRESSED)?;
-			pubdata = pubk.to_bin(cmprtype)?;
+			pubk.to_bin(cmprtype)?
 		} else {
-			pubdata = pubk.to_bin(cmprtype)?;
-		}
+			pubk.to_bin(cmprtype)?
+		};
 		
 		let mut ecprivasn1elem :ECPrivateKeyAsn1Elem = ECPrivateKeyAsn1Elem::init_asn1();
 		let mut ecprivasn1 :ECPrivateKeyAsn1 = ECPrivateKeyAsn1::init_asn1();
 		let mut bevecs :Vec<u8>;
-		let params :ECPKPARAMETERS;
+		
 		let mut impparams :Asn1ImpSet<ECPKPARAMETERS,0> = Asn1ImpSet::init_asn1();
 		let mut asn1pubdata : Asn1BitDataFlag = Asn1BitDataFlag::init_asn1();
-		params = form_ecpkparameters_gf2m(&self.base.group,cmprtype,paramenc)?;
+		let params :ECPKPARAMETERS = form_ecpkparameters_gf2m(&self.base.group,cmprtype,paramenc)?;
 		ecprivasn1elem.version.val = 1;
 		(_,bevecs) = self.privnum.to_bytes_be();
 		while bevecs.len() < privlen {
@@ -551,7 +551,7 @@ impl ECGf2mPrivateKey {
 		//imppubk.val.push(asn1pubdata);
 		ecprivasn1elem.pubkey.val.push(asn1pubdata);
 		ecprivasn1.elem.val.push(ecprivasn1elem);
-		return ecprivasn1.encode_asn1();
+		ecprivasn1.encode_asn1()
 	}
 
 
@@ -638,22 +638,15 @@ impl ECGf2mPrivateKey {
 
 
 #[derive(Clone)]
+#[derive(Default)]
 pub (crate) struct ECPrimePubKey {
 	base :ECPrimePoint,
 	pubk :ECPrimePoint,
 }
 
-impl Default for ECPrimePubKey {
-	fn default() -> Self {
-		Self {
-			base : ECPrimePoint::default(),
-			pubk : ECPrimePoint::default(),
-		}
-	}
-}
 
-fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -> Result<ECPKPARAMETERS,Box<dyn Error>> {
-	let montv :MontNum;
+pub (crate) fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -> Result<ECPKPARAMETERS,Box<dyn Error>> {
+	
 	let mut tmpu :BigUint;
 	let mut tmpp :BigInt;
 	let mut bevecs :Vec<u8>;
@@ -663,7 +656,7 @@ fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -
 	let zv :BigInt = zero();
 	let degr :i64 = grp.degree();
 	let fieldsize :usize = ((degr + 7) >> 3) as usize;
-	montv = MontNum::new(&grp.p).unwrap();
+	let montv :MontNum = MontNum::new(&grp.p).unwrap();
 
 	/*secp224r1*/
 	/*
@@ -701,7 +694,7 @@ fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -
 	}
 	retv.insert(SECP224r1_NAME.to_string(),bngrp.clone());
 	*/
-	if paramenc == EC_PARAMS_EXLICIT {
+	if paramenc == EC_PARAMS_EXLICIT || paramenc == EC_PARAMS_EXLICIT_SEED {
 		let mut fieldidelem :X9_62_FIELDIDElem = X9_62_FIELDIDElem::init_asn1();
 		let _ = fieldidelem.fieldType.val.set_value(EC_PRIME_GROUP_TYPE_OID)?;
 		(_,bevecs) = grp.p.to_bytes_be();
@@ -723,7 +716,8 @@ fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -
 		}
 		curveelem.b.data = bevecs.clone();
 
-		if grp.seed == zv {
+		/*the brainpool seed is the one of p , not an X9.62 seed of b*/
+		if grp.seed == zv || (grp.curvename.starts_with("brainpool") && paramenc != EC_PARAMS_EXLICIT_SEED) {
 			curveelem.seed.val = None;	
 		} else {
 			(_,bevecs) = grp.seed.to_bytes_be();
@@ -759,7 +753,7 @@ fn form_ecpkparameters_prime(grp :&ECGroupPrime,cmprtype :&str,paramenc :&str) -
 		params.itype = 1;
 		params.parameters.elem.val.push(paramselem);
 		return Ok(params);
-	} else if paramenc == "" {
+	} else if paramenc.is_empty() {
 		params.itype = 0;
 		let oid :String = ecc_get_oid_from_name(&grp.curvename)?;
 		let _ = params.named_curve.set_value(&oid)?;
@@ -777,7 +771,7 @@ impl ECPrimePubKey {
 		let zn :BigInt = one();
 		ECPrimePubKey {
 			base : b,
-			pubk : ECPrimePoint::new_point(&x,&y,&zn,grp),
+			pubk : ECPrimePoint::new_point(x,y,&zn,grp),
 		}
 	}
 
@@ -795,7 +789,7 @@ impl ECPrimePubKey {
 			pubkasn1elem.pubdata.data = pubdata.clone();
 			pubkasn1elem.pubdata.flag = 0;
 			pubkasn1.elem.val.push(pubkasn1elem);
-			return pubkasn1.encode_asn1();			
+			pubkasn1.encode_asn1()			
 		} else {
 			let mut pubkasn1 :ECPublicKeyAsn1 = ECPublicKeyAsn1::init_asn1();
 			let mut pubkasn1elem :ECPublicKeyAsn1Elem = ECPublicKeyAsn1Elem::init_asn1();
@@ -807,14 +801,14 @@ impl ECPrimePubKey {
 			pubkasn1elem.pubdata.data = pubdata.clone();
 			pubkasn1elem.pubdata.flag = 0;
 			pubkasn1.elem.val.push(pubkasn1elem);
-			return pubkasn1.encode_asn1();			
+			pubkasn1.encode_asn1()			
 		}
 	}
 
 	fn uncompress_x_point(grp :&ECGroupPrime, x_ :&BigInt, ybit :u8) -> Result<BigInt,Box<dyn Error>> {
 		let b = ECPrimePoint::new(grp);
 		let field :BigInt = b.group.p.clone();
-		let x :BigInt = nmod(&x_,&grp.p);
+		let x :BigInt = nmod(x_,&grp.p);
 		ecsimple_log_trace!("nnmod(x 0x{:X},x_ 0x{:X},group.field 0x{:X})", x,x_,grp.p);
 		let mut y :BigInt;
 		let mut tmp2 :BigInt;
@@ -866,7 +860,7 @@ impl ECPrimePubKey {
 	pub fn from_bin(grp :&ECGroupPrime, dercode :&[u8]) -> Result<Self,Box<dyn Error>> {
 		let b = ECPrimePoint::new(grp);
 		let pubk :ECPrimePoint = b.clone();
-		if dercode.len() < 1 {
+		if dercode.is_empty() {
 			ecsimple_new_error!{EcKeyError,"code [{}] < 1", dercode.len()}
 		}
 		let code :u8 = dercode[0] & EC_CODE_MASK;
@@ -901,14 +895,13 @@ impl ECPrimePubKey {
 			ecsimple_log_trace!("y 0x{:X}",y);
 			if x == zero() && ybit != 0{
 				ecsimple_new_error!{EcKeyError,"x == 0 and ybit set"}
-			} else {
-			}
+			} 
 		} else {
 			ecsimple_new_error!{EcKeyError,"unsupport code [0x{:X}] for public point", dercode[0]}
 		}
 		let z :BigInt = one();
 		let pubk = pubk.set_affine_coordinates(&x,&y,&z)?;
-		let _ = pubk.check_on_curve()?;
+		pubk.check_on_curve()?;
 		ecsimple_log_trace!("pubkey.x 0x{:X} pubkey.y 0x{:X} pubkey.z 0x{:X}",pubk.x(),pubk.y(),pubk.z());
 
 		Ok(Self {
@@ -1007,9 +1000,9 @@ impl ECPrimePubKey {
 
 		u2 = &(&u2 * &sig.r) % &order;
 		ecsimple_log_trace!("u2 0x{:X} sig->r 0x{:X} order 0x{:X}", u2,sig.r,order);
-		let vfypnt :ECPrimePoint;
+		
 
-		vfypnt = self.pubk.mulex_op(&u1,&u2)?;
+		let vfypnt :ECPrimePoint = self.pubk.mulex_op(&u1,&u2)?;
 		(x,_) = vfypnt.get_affine_points()?;
 		if x != sig.r {
 			u1 = x.clone() % self.base.group.order.clone();
@@ -1069,15 +1062,19 @@ impl ECPrimePubKey {
 
 	#[allow(non_snake_case)]
 	pub (crate) fn get_sm3_hashcode(&self,idv :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
-		let retv :Vec<u8>;
+		
 		let mut inputv :Vec<u8> = Vec::new();
-		let idlen :usize = idv.len();
+		/*ENTL is the id length in bits , GB/T 32918.2 5.5*/
+		if idv.len() > 0x1fff {
+			ecsimple_new_error!{EcKeyError,"id len {} > 0x1fff",idv.len()}
+		}
+		let idlen :usize = idv.len() * 8;
 		let order :BigInt = self.base.group.p.clone();
 		let orderlen :usize = ((get_max_bits(&order) + 7) >> 3) as usize;
 		let mut bufv :Vec<u8>;
-		let idvec :Vec<u8>;
-		let montv :MontNum;
-		montv = MontNum::new(&order)?;
+		
+		
+		let montv :MontNum = MontNum::new(&order)?;
 		let a:BigInt = montv.mont_from(&self.base.group.a);
 		let b:BigInt = montv.mont_from(&self.base.group.b);
 		/*first for the length of id, no id*/
@@ -1085,7 +1082,7 @@ impl ECPrimePubKey {
 		ecsimple_debug_buffer_trace!(inputv.as_ptr(),inputv.len(),"idlen >> 8 number");
 		inputv.push((idlen & 0xff) as u8);
 		ecsimple_debug_buffer_trace!(inputv[1..].as_ptr(),inputv.len()-1,"idlen & 0xff number");
-		idvec = idv.clone().to_vec();
+		let idvec :Vec<u8> = idv.to_vec();
 		ecsimple_debug_buffer_trace!(idvec.as_ptr(),idvec.len(),"id len");
 		inputv.extend(idvec);
 		(_,bufv) = a.to_bytes_be();
@@ -1133,7 +1130,7 @@ impl ECPrimePubKey {
 		inputv.extend(bufv.clone());
 		let mut hasher :Sm3 = Sm3::new();
 		hasher.update(&inputv);
-		retv = hasher.finalize().to_vec();
+		let retv :Vec<u8> = hasher.finalize().to_vec();
 		ecsimple_debug_buffer_trace!(retv.as_ptr(),retv.len(),"sm3 hash");
 		Ok(retv)
 	}
@@ -1191,13 +1188,13 @@ impl ECPrimePrivateKey {
 		}
 		ECPrimePrivateKey {
 			base :b,
-			privnum : privnum,
+			privnum,
 		}
 	}
 
 	pub fn export_pubkey(&self) -> ECPrimePubKey {
-		let ck2 : ECPrimePoint;
-		ck2 = self.base.mul_op(&self.privnum,false);
+		
+		let ck2 : ECPrimePoint = self.base.mul_op(&self.privnum,false);
 		let x :BigInt = ck2.x();
 		let y :BigInt = ck2.y();
 		let z :BigInt = one();
@@ -1209,10 +1206,33 @@ impl ECPrimePrivateKey {
 		retv
 	}
 
+	/*x coordinate of privnum * peer*/
+	pub (crate) fn ecdh(&self,peer :&ECPrimePubKey) -> Result<Vec<u8>,Box<dyn Error>> {
+		/*SEC 1 3.2.2.1 , with a cofactor the peer must be in the order subgroup*/
+		if peer.pubk.is_infinity() || (self.base.group.cofactor != one() && !peer.pubk.mul_op(&self.base.group.order,true).is_infinity()) {
+			ecsimple_new_error!{EcKeyError,"peer point not in the order subgroup"}
+		}
+		let ck2 :ECPrimePoint = peer.pubk.mul_op(&self.privnum,true);
+		if ck2.is_infinity() {
+			ecsimple_new_error!{EcKeyError,"shared point at infinity"}
+		}
+		let x :BigInt = ck2.x();
+		let y :BigInt = ck2.y();
+		let z :BigInt = one();
+		let ck = ck2.set_affine_coordinates(&x,&y,&z)?;
+		let retk :ECPrimePubKey = ECPrimePubKey {
+			base : self.base.clone(),
+			pubk : ck,
+		};
+		let bin :Vec<u8> = retk.to_bin(EC_UNCOMPRESSED)?;
+		let fieldsize :usize = (bin.len() - 1) / 2;
+		Ok(bin[1..(fieldsize+1)].to_vec())
+	}
+
 	pub (crate)  fn to_der(&self,cmprtype :&str,paramenc :&str) -> Result<Vec<u8>,Box<dyn Error>> {
 		let pubk :ECPrimePubKey = self.export_pubkey();
 		let pubdata :Vec<u8> = pubk.to_bin(cmprtype)?;
-		let params :ECPKPARAMETERS;
+		
 		let privlen :usize = ((get_max_bits(&self.base.group.order) + 7 ) >> 3) as usize;
 		let mut bevecs :Vec<u8>;
 		let mut impparams :Asn1ImpSet<ECPKPARAMETERS,0> = Asn1ImpSet::init_asn1();
@@ -1220,7 +1240,7 @@ impl ECPrimePrivateKey {
 		let mut ecprivasn1elem:ECPrivateKeyAsn1Elem = ECPrivateKeyAsn1Elem::init_asn1();
 		let mut ecprivasn1 :ECPrivateKeyAsn1 = ECPrivateKeyAsn1::init_asn1();
 
-		params = form_ecpkparameters_prime(&self.base.group,cmprtype,paramenc)?;
+		let params :ECPKPARAMETERS = form_ecpkparameters_prime(&self.base.group,cmprtype,paramenc)?;
 		(_,bevecs) = self.privnum.to_bytes_be();
 		while bevecs.len() < privlen {
 			bevecs.insert(0,0);
@@ -1233,7 +1253,7 @@ impl ECPrimePrivateKey {
 		asn1pubdata.flag = 0;
 		ecprivasn1elem.pubkey.val.push(asn1pubdata);
 		ecprivasn1.elem.val.push(ecprivasn1elem);
-		return ecprivasn1.encode_asn1();
+		ecprivasn1.encode_asn1()
 	}
 
 
@@ -1245,7 +1265,7 @@ impl ECPrimePrivateKey {
 		let zv :BigInt = zero();
 		let ov :BigInt = one();
 		let mut X :BigInt;
-		let e :BigInt;
+		
 		tmppnt.set_x(&zv);
 		tmppnt.set_y(&zv);
 		tmppnt.set_z(&zv);
@@ -1270,7 +1290,7 @@ impl ECPrimePrivateKey {
 			}
 		}
 
-		e = self.base.group.order.clone() - ov.clone() - ov.clone();
+		let e :BigInt = self.base.group.order.clone() - ov.clone() - ov.clone();
 		ecsimple_log_trace!("k 0x{:X}",k);
 		k = k.modpow(&e,&self.base.group.order);
 		ecsimple_log_trace!("k 0x{:X} r 0x{:X}",k,r);
@@ -1303,6 +1323,53 @@ impl ECPrimePrivateKey {
 		Ok(retv)
 	}
 
+	/*RFC 6979 3.2 , k and r from HMAC_DRBG over the private key and the hash*/
+	fn setup_sign_rfc6979(&self,hashnum :&[u8],htype :ECHashType) -> Result<(BigInt,BigInt),Box<dyn Error>> {
+		let order :BigInt = self.base.group.order.clone();
+		let rlen :usize = ((get_max_bits(&order) + 7) >> 3) as usize;
+		let h1 :BigInt = format_bigint_as_order(hashnum,&order) % &order;
+		let mut seed :Vec<u8> = bigint_to_fixed_bytes(&self.privnum,rlen)?;
+		seed.extend(bigint_to_fixed_bytes(&h1,rlen)?);
+		let mut v :Vec<u8> = vec![0x01;htype.output_size()];
+		let mut k :Vec<u8> = vec![0x00;htype.output_size()];
+		for b in [0x00,0x01].iter() {
+			let mut data :Vec<u8> = v.clone();
+			data.push(*b);
+			data.extend_from_slice(&seed);
+			k = hmac_data(htype,&k,&data);
+			v = hmac_data(htype,&k,&v);
+		}
+		loop {
+			let mut t :Vec<u8> = Vec::new();
+			while t.len() < rlen {
+				v = hmac_data(htype,&k,&v);
+				t.extend_from_slice(&v);
+			}
+			let kv :BigInt = format_bigint_as_order(&t,&order);
+			ecsimple_log_trace!("rfc6979 k 0x{:X}",kv);
+			if kv > zero() && kv < order {
+				let r :BigInt = nmod(&self.base.mul_op(&kv,false).x(),&order);
+				if r != zero() {
+					let e :BigInt = order.clone() - 2;
+					return Ok((kv.modpow(&e,&order),r));
+				}
+			}
+			let mut data :Vec<u8> = v.clone();
+			data.push(0x00);
+			k = hmac_data(htype,&k,&data);
+			v = hmac_data(htype,&k,&v);
+		}
+	}
+
+	/*sign_base with the deterministic k of RFC 6979 , htype is the hash of hashnum*/
+	pub fn sign_base_rfc6979(&self,hashnum :&[u8],htype :ECHashType) -> Result<ECSignature,Box<dyn Error>> {
+		let realhash :BigInt = format_bigint_as_order(hashnum,&self.base.group.order);
+		let (kinv,r) = self.setup_sign_rfc6979(hashnum,htype)?;
+		let mut s :BigInt = (&realhash + &self.privnum * &r) % &self.base.group.order;
+		s = (&s * &kinv) % &self.base.group.order;
+		Ok(ECSignature::new(&r,&s))
+	}
+
 	#[allow(non_snake_case)]
 	#[allow(unused_variables)]
 	pub (crate) fn sign_sm2_base(&self,hashnum :&[u8]) -> Result<ECSignature,Box<dyn Error>> {
@@ -1318,7 +1385,7 @@ impl ECPrimePrivateKey {
 		let mut s :BigInt;
 		let dA :BigInt = self.privnum.clone();
 		let e2 :BigInt = order.clone() - ov.clone() - ov.clone();
-		let rs :ECSignature;
+		
 		let mut tmp :BigInt;
 		let mut tmppnt :ECPrimePoint;
 
@@ -1369,13 +1436,13 @@ impl ECPrimePrivateKey {
 		}
 
 		ecsimple_log_trace!("final r 0x{:X} s 0x{:X}",r,s);
-		rs = ECSignature::new(&r,&s);
+		let rs :ECSignature = ECSignature::new(&r,&s);
 		Ok(rs)
 	}
 
 	pub (crate) fn get_sm3_hashcode(&self, idv :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
 		let pubk :ECPrimePubKey = self.export_pubkey();
-		return pubk.get_sm3_hashcode(idv);
+		pubk.get_sm3_hashcode(idv)
 	}
 }
 
@@ -1383,7 +1450,7 @@ impl ECPrimePrivateKey {
 pub (crate) fn extract_compressed_y_prime(grp :&ECGroupPrime, x_ :&BigInt, ybit :u8) -> Result<BigInt,Box<dyn Error>> {
 	let b = ECPrimePoint::new(grp);
 	let field :BigInt = b.group.p.clone();
-	let x :BigInt = nmod(&x_,&grp.p);
+	let x :BigInt = nmod(x_,&grp.p);
 	ecsimple_log_trace!("nnmod(x 0x{:X},x_ 0x{:X},group.field 0x{:X})", x,x_,grp.p);
 	let mut y :BigInt;
 	let mut tmp2 :BigInt;
@@ -1434,7 +1501,7 @@ pub (crate) fn extract_compressed_y_prime(grp :&ECGroupPrime, x_ :&BigInt, ybit
 
 pub (crate) fn extract_compressed_y_gf2m(grp :&ECGroupBnGf2m,x_ :&BigInt,ybit :u8) -> Result<BigInt,Box<dyn Error>> {
 	let b = ECGf2mPoint::new(grp);
-	let xb :BnGf2m = BnGf2m::new_from_bigint(&x_);
+	let xb :BnGf2m = BnGf2m::new_from_bigint(x_);
 	let field :BnGf2m = BnGf2m::new_from_bigint(&b.group.p);
 	let x :BnGf2m = &xb % &field;
 	let y :BigInt;
@@ -1471,7 +1538,7 @@ pub (crate) fn extract_compressed_y_gf2m(grp :&ECGroupBnGf2m,x_ :&BigInt,ybit :u
 	Ok(y)
 }
 
-pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) -> Result<ECGroup,Box<dyn Error>> {
+pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS,canonical :bool) -> Result<ECGroup,Box<dyn Error>> {
 	let curveparams :X9_62_CURVEElem;
 	let ov :BigInt = one();
 	let retgrp :ECGroup;
@@ -1489,12 +1556,12 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 		ecsimple_log_trace!("{}",retgrp);
 		return Ok(retgrp);
 	} else if ecpkparams.itype == 1 {
-		let paramselem :ECPARAMETERSElem;
+		
 		if ecpkparams.parameters.elem.val.len() != 1 {
 			ecsimple_new_error!{EcKeyError,"params elem {} != 1",ecpkparams.parameters.elem.val.len()}
 		}
-		paramselem = ecpkparams.parameters.elem.val[0].clone();
-		let fieldid :X9_62_FIELDIDElem ;
+		let paramselem :ECPARAMETERSElem = ecpkparams.parameters.elem.val[0].clone();
+		
 
 		if paramselem.curve.elem.val.len() != 1 {
 			ecsimple_new_error!{EcKeyError,"curve {} != 1", paramselem.curve.elem.val.len()}
@@ -1504,11 +1571,11 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 		if paramselem.fieldID.elem.val.len() != 1 {
 			ecsimple_new_error!{EcKeyError,"fieldID elem {} != 1 ", paramselem.fieldID.elem.val.len()}
 		}
-		fieldid = paramselem.fieldID.elem.val[0].clone();
+		let fieldid :X9_62_FIELDIDElem = paramselem.fieldID.elem.val[0].clone();
 		if fieldid.fieldType.val.get_value() == EC_PRIME_GROUP_TYPE_OID {
 			let mut primegrp :ECGroupPrime = ECGroupPrime::default();
-			let montv :MontNum;
-			let tmpa :BigInt;
+			
+			
 			/*secp112r1*/
 			/*
 			v8 = Vec::from_hex("DB7C2ABF62E35E668076BEAD208B").unwrap();
@@ -1549,10 +1616,10 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 			*/
 			bevecs = fieldid.prime.val.to_bytes_be();
 			primegrp.p = BigInt::from_bytes_be(Sign::Plus,&bevecs);
-			montv = MontNum::new(&primegrp.p).unwrap();
+			let montv :MontNum = MontNum::new(&primegrp.p).unwrap();
 			bevecs = curveparams.a.data.clone();
 			tmpp = BigInt::from_bytes_be(Sign::Plus,&bevecs);
-			tmpa = tmpp.clone();
+			let tmpa :BigInt = tmpp.clone();
 			primegrp.a = montv.mont_to(&tmpp);
 			bevecs = curveparams.b.data.clone();
 			tmpp = BigInt::from_bytes_be(Sign::Plus,&bevecs);
@@ -1560,8 +1627,7 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 
 			primegrp.seed = zero();
 			primegrp.seed_len = 0;
-			if curveparams.seed.val.is_some() {
-				let bn :Asn1BitDataFlag = curveparams.seed.val.as_ref().unwrap().clone();
+			if let Some(bn) = curveparams.seed.val.as_ref() {
 				bevecs = bn.data.clone();
 				primegrp.seed = BigInt::from_bytes_be(Sign::Plus,&bevecs);
 				primegrp.seed_len = bevecs.len();
@@ -1572,18 +1638,17 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 			primegrp.order = tmpp.clone();
 
 
-			if paramselem.cofactor.val.is_none() {
-				primegrp.cofactor = ov.clone();
-			} else {
-				bevecs = paramselem.cofactor.val.as_ref().unwrap().clone().val.to_bytes_be();
-				primegrp.cofactor = BigInt::from_bytes_be(Sign::Plus,&bevecs);
+			match paramselem.cofactor.val.as_ref() {
+				Some(c) => {
+					bevecs = c.val.to_bytes_be();
+					primegrp.cofactor = BigInt::from_bytes_be(Sign::Plus,&bevecs);
+				},
+				None => {
+					primegrp.cofactor = ov.clone();
+				}
 			}
 
-			if primegrp.p == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone() ) {
-				primegrp.is_minus3 = true;
-			} else {
-				primegrp.is_minus3 = false;
-			}
+			primegrp.is_minus3 = primegrp.p == (tmpa.clone() + ov.clone() + ov.clone() + ov.clone() );
 
 
 			bevecs = paramselem.base.data.clone();
@@ -1622,10 +1687,13 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 
 			ecsimple_log_trace!("primegrp\n{}",primegrp);
 			retgrp = ECGroup::new_prime_group(&primegrp);
+			if canonical {
+				return Ok(retgrp.canonicalize());
+			}
 			return Ok(retgrp);
 		} else if fieldid.fieldType.val.get_value() == EC_GF2M_GROUP_TYPE_OID {
 			let mut bngrp :ECGroupBnGf2m = ECGroupBnGf2m::default();
-			let x962elem : X9_62_CHARACTERISTIC_TWO_ELEM;
+			
 			let ppbasis :X9_62_PENTANOMIALELem;
 			let mut kval :i32;
 			/*sect113r1*/
@@ -1658,7 +1726,7 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 			if fieldid.char_two.elem.val.len() != 1 {
 				ecsimple_new_error!{EcKeyError,"char_two elem {} != 1",fieldid.char_two.elem.val.len()}
 			}
-			x962elem =  fieldid.char_two.elem.val[0].clone();
+			let x962elem : X9_62_CHARACTERISTIC_TWO_ELEM = fieldid.char_two.elem.val[0].clone();
 			let oidstr :String = x962elem.elemchoice.otype.val.get_value();
 			if oidstr != EC_PP_BASIS_OID && oidstr != EC_TP_BASIS_OID && oidstr != EC_ON_BASIS_OID {
 				ecsimple_new_error!{EcKeyError,"otype [{}] not supported", oidstr}
@@ -1709,8 +1777,7 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 
 			bngrp.seed = zero();
 			bngrp.seed_len = 0;
-			if curveparams.seed.val.is_some() {
-				let bn :Asn1BitDataFlag = curveparams.seed.val.as_ref().unwrap().clone();
+			if let Some(bn) = curveparams.seed.val.as_ref() {
 				bevecs = bn.data.clone();
 				bngrp.seed = BigInt::from_bytes_be(Sign::Plus,&bevecs);
 				bngrp.seed_len = bevecs.len();
@@ -1719,11 +1786,14 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 			bevecs = paramselem.order.val.to_bytes_be();
 			tmpp = BigInt::from_bytes_be(Sign::Plus,&bevecs);
 			bngrp.order = tmpp.clone();
-			if paramselem.cofactor.val.is_none() {
-				bngrp.cofactor = ov.clone();
-			} else {
-				bevecs = paramselem.cofactor.val.as_ref().unwrap().clone().val.to_bytes_be();
-				bngrp.cofactor = BigInt::from_bytes_be(Sign::Plus,&bevecs);
+			match paramselem.cofactor.val.as_ref() {
+				Some(c) => {
+					bevecs = c.val.to_bytes_be();
+					bngrp.cofactor = BigInt::from_bytes_be(Sign::Plus,&bevecs);
+				},
+				None => {
+					bngrp.cofactor = ov.clone();
+				}
 			}
 
 
@@ -1762,6 +1832,9 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 			bngrp.generator.z = BnGf2m::one();
 			ecsimple_log_trace!("bngrp\n{}",bngrp);
 			retgrp = ECGroup::new_bn_group(&bngrp);
+			if canonical {
+				return Ok(retgrp.canonicalize());
+			}
 			return Ok(retgrp);
 		} 
 		ecsimple_new_error!{EcKeyError,"not supported oid fieldType [{}]",fieldid.fieldType.val.get_value()}
@@ -1770,7 +1843,7 @@ pub (crate) fn get_group_from_ecpkparameters_der(ecpkparams :&ECPKPARAMETERS) ->
 	ecsimple_new_error!{EcKeyError,"not supported type [{}]",ecpkparams.itype}	
 }
 
-pub (crate) fn get_group_from_public_der(pubkey :&ECPublicKeyAsn1) -> Result<ECGroup,Box<dyn Error>> {
+pub (crate) fn get_group_from_public_der(pubkey :&ECPublicKeyAsn1,canonical :bool) -> Result<ECGroup,Box<dyn Error>> {
 	if pubkey.elem.val.len() != 1 {
 		ecsimple_new_error!{EcKeyError,"ECPublicKeyAsn1.elem.val.len() {} != 1",pubkey.elem.val.len()}
 	}
@@ -1787,67 +1860,62 @@ pub (crate) fn get_group_from_public_der(pubkey :&ECPublicKeyAsn1) -> Result<ECG
 		/*now we should give the */	
 		return ecc_get_curve_group(SM2_NAME);
 	}
-	return get_group_from_ecpkparameters_der(&packedelem.parameters);
+	get_group_from_ecpkparameters_der(&packedelem.parameters,canonical)
 }
 
 
 #[derive(Clone)]
+#[derive(Default)]
 pub struct ECPublicKey {
 	pub (crate) bnkey :Option<ECGf2mPubKey>,
 	pub (crate) primekey :Option<ECPrimePubKey>,
 }
 
-impl Default for ECPublicKey {
-	fn default() -> Self {
-		Self {
-			bnkey : None,
-			primekey : None,
-		}
-	}
-}
 
 impl ECPublicKey {
 	pub fn new(grp :&ECGroup,x :&BigInt,y :&BigInt) -> ECPublicKey {
-		let retv :ECPublicKey;
-		if grp.is_bn_group() {
-			retv =  ECPublicKey{
+		let retv :ECPublicKey = if grp.is_bn_group() {
+			ECPublicKey{
 				bnkey : Some(ECGf2mPubKey::new(&grp.get_bn_group(),x,y)),
 				primekey : None,
-			};
+			}
 		} else {
-			retv = ECPublicKey {
+			ECPublicKey {
 				bnkey : None,
 				primekey : Some(ECPrimePubKey::new(&grp.get_prime_group(),x,y)),
-			} ;
-		}
-		return retv;
+			} 
+		};
+		retv
 	}
 
 	pub fn from_der(dercode :&[u8]) -> Result<ECPublicKey, Box<dyn Error>> {
+		Self::from_der_canonical(dercode,true)
+	}
+
+	/*canonical false to keep explicit parameters as the anonymous group*/
+	pub fn from_der_canonical(dercode :&[u8],canonical :bool) -> Result<ECPublicKey, Box<dyn Error>> {
 		let mut pubkeyasn1 :ECPublicKeyAsn1 = ECPublicKeyAsn1::init_asn1();
 		let _ = pubkeyasn1.decode_asn1(dercode)?;
-		let grp :ECGroup = get_group_from_public_der(&pubkeyasn1)?;
+		let grp :ECGroup = get_group_from_public_der(&pubkeyasn1,canonical)?;
 		let pubkeyasn1elem :ECPublicKeyAsn1Elem = pubkeyasn1.elem.val[0].clone();
-		return Self::from_bin(&grp,&pubkeyasn1elem.pubdata.data);
+		Self::from_bin(&grp,&pubkeyasn1elem.pubdata.data)
 	}
 
 	pub fn to_der(&self,cmprtype :&str,paramenc :&str) -> Result<Vec<u8>,Box<dyn Error>> {
-		let retv :Vec<u8>;
-		if self.is_bn_key() {
-			retv= self.get_bn_key().to_der(cmprtype,paramenc)?;
+		let retv :Vec<u8> = if self.is_bn_key() {
+			self.get_bn_key().to_der(cmprtype,paramenc)?
 		} else {
-			retv = self.get_prime_key().to_der(cmprtype,paramenc)?;
-		} 
+			self.get_prime_key().to_der(cmprtype,paramenc)?
+		};
 		Ok(retv)
 	}
 
 	pub fn to_bin(&self,cmprtype :&str) -> Result<Vec<u8>,Box<dyn Error>> {
-		let retv :Vec<u8>;
-		if self.is_bn_key() {
-			retv= self.get_bn_key().to_bin(cmprtype)?;
+		let retv :Vec<u8> = if self.is_bn_key() {
+			self.get_bn_key().to_bin(cmprtype)?
 		} else {
-			retv = self.get_prime_key().to_bin(cmprtype)?;
-		} 
+			self.get_prime_key().to_bin(cmprtype)?
+		};
 		Ok(retv)
 
 	}
@@ -1858,35 +1926,34 @@ impl ECPublicKey {
 		}
 		let curpriv :ECPrimePubKey = self.primekey.as_ref().unwrap().clone();
 		let curgrp :ECGroupPrime = curpriv.base.group.clone();
-		let sm2grp :ECGroup ;
+		
 		let ores = ecc_get_curve_group(SM2_NAME);
 		if ores.is_err() {
 			return false;
 		}
-		sm2grp = ores.unwrap();
+		let sm2grp :ECGroup = ores.unwrap();
 		let sm2primegrp = sm2grp.get_prime_group();
-		if curgrp != sm2primegrp {
+		if !curgrp.eq_params(&sm2primegrp) {
 			return false;
 		}
-		return true;
+		true
 	}
 
 
 	pub fn from_bin(grp :&ECGroup, dercode :&[u8]) -> Result<Self,Box<dyn Error>> {
-		let retv :ECPublicKey;
-		if grp.is_bn_group() {
+		let retv :ECPublicKey = if grp.is_bn_group() {
 			let bnkey : ECGf2mPubKey = ECGf2mPubKey::from_bin(&grp.get_bn_group(),dercode)?;
-			retv = ECPublicKey {
+			ECPublicKey {
 				bnkey : Some(bnkey),
 				primekey : None,
-			};
+			}
 		} else {
 			let primekey : ECPrimePubKey = ECPrimePubKey::from_bin(&grp.get_prime_group(),dercode)?;
-			retv = ECPublicKey {
+			ECPublicKey {
 				bnkey : None,
 				primekey : Some(primekey),
-			};
-		} 
+			}
+		};
 		Ok(retv)
 	}
 
@@ -1894,14 +1961,14 @@ impl ECPublicKey {
 		if self.bnkey.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	fn is_prime_key(&self) -> bool {
 		if self.primekey.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	fn get_bn_key(&self) -> ECGf2mPubKey {
@@ -1909,7 +1976,7 @@ impl ECPublicKey {
 		if self.is_bn_key() {
 			retv = self.bnkey.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
 	}
 
 	fn get_prime_key(&self) -> ECPrimePubKey {
@@ -1917,7 +1984,14 @@ impl ECPublicKey {
 		if self.is_prime_key() {
 			retv = self.primekey.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
+	}
+
+	pub fn get_group(&self) -> ECGroup {
+		if self.is_bn_key() {
+			return ECGroup::new_bn_group(&self.get_bn_key().base.group);
+		}
+		ECGroup::new_prime_group(&self.get_prime_key().base.group)
 	}
 
 	pub fn verify_base(&self,sig :&ECSignature, hashnum :&[u8]) -> Result<bool,Box<dyn Error>> {
@@ -1949,28 +2023,51 @@ impl std::fmt::Display for ECPublicKey {
 		if self.is_prime_key() {
 			return self.get_prime_key().fmt(f);
 		}
-		return self.get_bn_key().fmt(f);		
+		self.get_bn_key().fmt(f)		
 	}
 }
 
-pub (crate) fn get_group_from_private_pk8_der(_privkey :&ECPrivateKeyAsn1,pk8 :&Asn1Pkcs8PrivKeyInfo) -> Result<ECGroup,Box<dyn Error>> {
+pub (crate) fn get_group_from_private_pk8_der(privkey :&ECPrivateKeyAsn1,pk8 :&Asn1Pkcs8PrivKeyInfo,canonical :bool) -> Result<ECGroup,Box<dyn Error>> {
 	if pk8.elem.val.len() != 1 {
 		ecsimple_new_error!{EcKeyError,"Asn1Pkcs8PrivKeyInfo elem {} != 1",pk8.elem.val.len()}
 	}
 	let pk8elem :Asn1Pkcs8PrivKeyInfoElem = pk8.elem.val[0].clone();
-	if pk8elem.version.val != 0 {
-		ecsimple_new_error!{EcKeyError,"Asn1Pkcs8PrivKeyInfo version {} != 0",pk8elem.version.val}
+	if pk8elem.version.val != PKCS8_VERSION_V1 && pk8elem.version.val != PKCS8_VERSION_V2 {
+		ecsimple_new_error!{EcKeyError,"Asn1Pkcs8PrivKeyInfo version {} not supported",pk8elem.version.val}
+	}
+	if pk8elem.version.val == PKCS8_VERSION_V1 && pk8elem.pubkey.val.is_some() {
+		ecsimple_new_error!{EcKeyError,"publicKey only in version {}",PKCS8_VERSION_V2}
 	}
 	if pk8elem.pkeyalg.elem.val.len() != 1 {
 		ecsimple_new_error!{EcKeyError,"Asn1X509Algor elem {} != 1" ,pk8elem.pkeyalg.elem.val.len()}
 	}
 	let algoelem :Asn1X509AlgorElem = pk8elem.pkeyalg.elem.val[0].clone();
 	let oid :String = algoelem.algorithm.get_value();
-	let ecname :String = ecc_get_name_from_oid(&oid)?;
-	return ecc_get_curve_group(&ecname);
+	if oid == EC_PUBLIC_KEY_OID || oid == SM2_OID {
+		if let Some(params) = algoelem.parameters.val.as_ref() {
+			/*namedCurve or specifiedCurve ; to_der_sm2 puts the SM2 oid here too*/
+			let pdata :Vec<u8> = params.encode_asn1()?;
+			let mut ecpkparams :ECPKPARAMETERS = ECPKPARAMETERS::init_asn1();
+			let _ = ecpkparams.decode_asn1(&pdata)?;
+			return get_group_from_ecpkparameters_der(&ecpkparams,canonical);
+		}
+		if oid == SM2_OID {
+			return ecc_get_curve_group(SM2_NAME);
+		}
+		/*parameters left in the inner ECPrivateKey*/
+		return get_group_from_private_der(privkey,canonical);
+	}
+	/*some writers give the curve oid as the algorithm*/
+	let ecname :String = match ecc_get_name_from_oid(&oid) {
+		Ok(v) => v,
+		Err(_e) => {
+			ecsimple_new_error!{EcKeyError,"unsupported private key algorithm [{}]",oid}
+		}
+	};
+	ecc_get_curve_group(&ecname)
 }
 
-pub (crate) fn get_group_from_private_der(privkey :&ECPrivateKeyAsn1) -> Result<ECGroup,Box<dyn Error>> {
+pub (crate) fn get_group_from_private_der(privkey :&ECPrivateKeyAsn1,canonical :bool) -> Result<ECGroup,Box<dyn Error>> {
 	if privkey.elem.val.len() != 1 {
 		ecsimple_new_error!{EcKeyError,"ECPrivateKeyAsn1.elem.val.len() {} != 1",privkey.elem.val.len()}
 	}
@@ -1983,34 +2080,32 @@ pub (crate) fn get_group_from_private_der(privkey :&ECPrivateKeyAsn1) -> Result<
 		ecsimple_new_error!{EcKeyError,"paramters impset len {} != 1", ecpkparamsset.val.len()}
 	}
 	let ecpkparams :ECPKPARAMETERS = ecpkparamsset.val[0].clone();
-	return get_group_from_ecpkparameters_der(&ecpkparams);
+	get_group_from_ecpkparameters_der(&ecpkparams,canonical)
 }
 
 
 #[derive(Clone)]
+#[derive(Default)]
 pub struct ECPrivateKey {
 	pub (crate) bnkey :Option<ECGf2mPrivateKey>,
 	pub (crate) primekey :Option<ECPrimePrivateKey>,
 }
 
-impl Default for ECPrivateKey {
-	fn default() -> Self {
-		Self {
-			bnkey : None,
-			primekey : None,
-		}
-	}
-}
 
 
 impl ECPrivateKey {
 	pub fn from_der(dercode :&[u8]) -> Result<ECPrivateKey,Box<dyn Error>> {
+		Self::from_der_canonical(dercode,true)
+	}
+
+	/*canonical false to keep explicit parameters as the anonymous group*/
+	pub fn from_der_canonical(dercode :&[u8],canonical :bool) -> Result<ECPrivateKey,Box<dyn Error>> {
 		let mut privkey :ECPrivateKeyAsn1 = ECPrivateKeyAsn1::init_asn1();
-		let ores = privkey.decode_asn1(&dercode);
+		let ores = privkey.decode_asn1(dercode);
 		let mut setpkinfo8 :bool = false;
 		let mut pk8info :Asn1Pkcs8PrivKeyInfo = Asn1Pkcs8PrivKeyInfo::init_asn1();
 		if ores.is_err() {
-			let _ = pk8info.decode_asn1(&dercode)?;
+			let _ = pk8info.decode_asn1(dercode)?;
 			if pk8info.elem.val.len() != 1 {
 				ecsimple_new_error!{EcKeyError,"pk8info elem val len {} != 1",pk8info.elem.val.len()}
 			}
@@ -2022,12 +2117,11 @@ impl ECPrivateKey {
 		}
 		let privkeyelem :ECPrivateKeyAsn1Elem = privkey.elem.val[0].clone();
 
-		let grp :ECGroup ;
-		if setpkinfo8 {
-			grp = get_group_from_private_pk8_der(&privkey,&pk8info)?;
+		let grp :ECGroup = if setpkinfo8 {
+			get_group_from_private_pk8_der(&privkey,&pk8info,canonical)?
 		} else {
-			grp = get_group_from_private_der(&privkey)?;
-		}
+			get_group_from_private_der(&privkey,canonical)?
+		};
 		
 		if privkeyelem.version.val != 1 {
 			ecsimple_new_error!{EcKeyError,"privkey version {} != 1",privkeyelem.version.val}
@@ -2035,39 +2129,100 @@ impl ECPrivateKey {
 		let privdata :Vec<u8> = privkeyelem.privkey.data.clone();
 		let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&privdata);
 		let retv :ECPrivateKey = ECPrivateKey::new(&grp,&privnum);
-		return Ok(retv);
+		if setpkinfo8 && pk8info.elem.val[0].pubkey.val.is_some() {
+			let pubdata :Vec<u8> = pk8info.elem.val[0].pubkey.val.as_ref().unwrap().val.data.clone();
+			let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&pubdata)?;
+			let expkey :ECPublicKey = retv.export_pubkey();
+			if pubkey.to_bin(EC_UNCOMPRESSED)? != expkey.to_bin(EC_UNCOMPRESSED)? {
+				ecsimple_new_error!{EcKeyError,"publicKey not match the private key"}
+			}
+		}
+		Ok(retv)
+	}
+
+	/*only PKCS#8 PrivateKeyInfo or OneAsymmetricKey accepted*/
+	pub fn from_pkcs8_der(dercode :&[u8]) -> Result<ECPrivateKey,Box<dyn Error>> {
+		let mut pk8info :Asn1Pkcs8PrivKeyInfo = Asn1Pkcs8PrivKeyInfo::init_asn1();
+		let _ = pk8info.decode_asn1(dercode)?;
+		ECPrivateKey::from_der(dercode)
+	}
+
+	/*
+		PKCS#8 with id-ecPublicKey and the curve in the AlgorithmIdentifier ,
+		withpub to give OneAsymmetricKey v2 of RFC 5958 with the publicKey
+	*/
+	pub fn to_pkcs8_der(&self,cmprtype :&str,paramenc :&str,withpub :bool) -> Result<Vec<u8>,Box<dyn Error>> {
+		let sec1data :Vec<u8> = self.to_der(cmprtype,paramenc)?;
+		let mut privkey :ECPrivateKeyAsn1 = ECPrivateKeyAsn1::init_asn1();
+		let _ = privkey.decode_asn1(&sec1data)?;
+		if privkey.elem.val.len() != 1 {
+			ecsimple_new_error!{EcKeyError,"privkey elem val len {} != 1",privkey.elem.val.len()}
+		}
+		let mut privkeyelem :ECPrivateKeyAsn1Elem = privkey.elem.val[0].clone();
+		if privkeyelem.parameters.val.is_none() || privkeyelem.parameters.val.as_ref().unwrap().val.len() != 1 {
+			ecsimple_new_error!{EcKeyError,"no parameters in private key"}
+		}
+		let params :ECPKPARAMETERS = privkeyelem.parameters.val.as_ref().unwrap().val[0].clone();
+		let pdata :Vec<u8> = params.encode_asn1()?;
+		let mut anydata :Asn1Any = Asn1Any::init_asn1();
+		let _ = anydata.decode_asn1(&pdata)?;
+		let mut algelem :Asn1X509AlgorElem = Asn1X509AlgorElem::init_asn1();
+		algelem.algorithm.set_value(EC_PUBLIC_KEY_OID)?;
+		algelem.parameters.val = Some(anydata);
+
+		/*RFC 5915 : parameters are in the AlgorithmIdentifier already*/
+		privkeyelem.parameters.val = None;
+		let mut innerkey :ECPrivateKeyAsn1 = ECPrivateKeyAsn1::init_asn1();
+		innerkey.elem.val.push(privkeyelem.clone());
+
+		let mut pk8info :Asn1Pkcs8PrivKeyInfo = Asn1Pkcs8PrivKeyInfo::init_asn1();
+		let mut elem :Asn1Pkcs8PrivKeyInfoElem = Asn1Pkcs8PrivKeyInfoElem::init_asn1();
+		elem.version.val = PKCS8_VERSION_V1;
+		elem.pkeyalg.elem.val.push(algelem);
+		elem.pkey.data = innerkey.encode_asn1()?;
+		elem.attributes.val = None;
+		elem.pubkey.val = None;
+		if withpub {
+			if privkeyelem.pubkey.val.len() != 1 {
+				ecsimple_new_error!{EcKeyError,"no public key in private key"}
+			}
+			let mut imppub :Asn1Imp<Asn1BitDataFlag,1> = Asn1Imp::init_asn1();
+			imppub.val = privkeyelem.pubkey.val[0].clone();
+			elem.version.val = PKCS8_VERSION_V2;
+			elem.pubkey.val = Some(imppub);
+		}
+		pk8info.elem.val.push(elem);
+		pk8info.encode_asn1()
 	}
 
 	pub fn new(grp :&ECGroup , privnum :&BigInt) -> ECPrivateKey {
-		let retv :ECPrivateKey;
-		if grp.is_bn_group() {
-			retv = ECPrivateKey {
+		let retv :ECPrivateKey = if grp.is_bn_group() {
+			ECPrivateKey {
 				bnkey : Some(ECGf2mPrivateKey::new(&grp.get_bn_group(),privnum)),
 				primekey : None,
-			};
+			}
 		} else {
-			retv = ECPrivateKey {
+			ECPrivateKey {
 				bnkey : None,
 				primekey : Some(ECPrimePrivateKey::new(&grp.get_prime_group(),privnum)),
-			};
-		}
-		return retv;
+			}
+		};
+		retv
 	}
 
 	pub fn generate(grp :&ECGroup) -> ECPrivateKey {
-		let retv :ECPrivateKey;
-		if grp.is_bn_group() {
-			retv = ECPrivateKey {
+		let retv :ECPrivateKey = if grp.is_bn_group() {
+			ECPrivateKey {
 				bnkey : Some(ECGf2mPrivateKey::generate(&grp.get_bn_group())),
 				primekey : None,
-			};
+			}
 		} else {
-			retv = ECPrivateKey {
+			ECPrivateKey {
 				bnkey : None,
 				primekey : Some(ECPrimePrivateKey::generate(&grp.get_prime_group())),
-			};
-		}
-		return retv;
+			}
+		};
+		retv
 	}
 
 	pub fn export_pubkey(&self) -> ECPublicKey {
@@ -2079,21 +2234,21 @@ impl ECPrivateKey {
 			let pubk = self.get_bn_key().export_pubkey();
 			retv.bnkey = Some(pubk);
 		}
-		return retv;
+		retv
 	}
 
 	fn is_bn_key(&self) -> bool {
 		if self.bnkey.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	fn is_prime_key(&self) -> bool {
 		if self.primekey.is_some() {
 			return true;
 		}
-		return false;
+		false
 	}
 
 	pub fn is_sm2(&self) -> bool {
@@ -2102,17 +2257,17 @@ impl ECPrivateKey {
 		}
 		let curpriv :ECPrimePrivateKey = self.primekey.as_ref().unwrap().clone();
 		let curgrp :ECGroupPrime = curpriv.base.group.clone();
-		let sm2grp :ECGroup ;
+		
 		let ores = ecc_get_curve_group(SM2_NAME);
 		if ores.is_err() {
 			return false;
 		}
-		sm2grp = ores.unwrap();
+		let sm2grp :ECGroup = ores.unwrap();
 		let sm2primegrp = sm2grp.get_prime_group();
-		if curgrp != sm2primegrp {
+		if !curgrp.eq_params(&sm2primegrp) {
 			return false;
 		}
-		return true;
+		true
 	}
 
 	fn get_bn_key(&self) -> ECGf2mPrivateKey {
@@ -2120,7 +2275,7 @@ impl ECPrivateKey {
 		if self.is_bn_key() {
 			retv = self.bnkey.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
 	}
 
 	fn get_prime_key(&self) -> ECPrimePrivateKey {
@@ -2128,10 +2283,24 @@ impl ECPrivateKey {
 		if self.is_prime_key() {
 			retv = self.primekey.as_ref().unwrap().clone();
 		}
-		return retv;
+		retv
 	}
 
 
+	pub fn get_group(&self) -> ECGroup {
+		if self.is_bn_key() {
+			return ECGroup::new_bn_group(&self.get_bn_key().base.group);
+		}
+		ECGroup::new_prime_group(&self.get_prime_key().base.group)
+	}
+
+	pub fn get_privnum(&self) -> BigInt {
+		if self.is_bn_key() {
+			return self.get_bn_key().privnum.clone();
+		}
+		self.get_prime_key().privnum.clone()
+	}
+
 	pub fn sign_base(&self,hashnum :&[u8]) -> Result<ECSignature,Box<dyn Error>> {
 		if self.is_bn_key() {
 			return self.get_bn_key().sign_base(hashnum);
@@ -2141,6 +2310,33 @@ impl ECPrivateKey {
 		ecsimple_new_error!{EcKeyError,"not supported private key"}
 	}
 
+	/*RFC 6979 deterministic ECDSA , prime curves only*/
+	pub fn sign_base_rfc6979(&self,hashnum :&[u8],htype :ECHashType) -> Result<ECSignature,Box<dyn Error>> {
+		if self.is_prime_key() {
+			return self.get_prime_key().sign_base_rfc6979(hashnum,htype);
+		}
+		ecsimple_new_error!{EcKeyError,"rfc6979 not supported for the key"}
+	}
+
+	/*SEC 1 3.3.1 shared secret , the x coordinate of the field size*/
+	pub fn ecdh(&self,peer :&ECPublicKey) -> Result<Vec<u8>,Box<dyn Error>> {
+		if !self.get_group().eq_params(&peer.get_group()) {
+			ecsimple_new_error!{EcKeyError,"peer key not on the same curve"}
+		}
+		match (peer.bnkey.as_ref(),peer.primekey.as_ref()) {
+			(Some(pk),_) if self.is_bn_key() => {
+				return self.get_bn_key().ecdh(pk);
+			},
+			(_,Some(pk)) if self.is_prime_key() => {
+				return self.get_prime_key().ecdh(pk);
+			},
+			_ => {}
+		}
+
+
+		ecsimple_new_error!{EcKeyError,"not supported private key"}
+	}
+
 	pub fn sign_sm2_base(&self,hashnum :&[u8]) -> Result<ECSignature,Box<dyn Error>> {
 		if self.is_sm2() {
 			return self.get_prime_key().sign_sm2_base(hashnum);
@@ -2161,7 +2357,7 @@ impl ECPrivateKey {
 		if self.is_bn_key() {
 			ecsimple_new_error!{EcKeyError,"not support sm3"}
 		}
-		return self.get_prime_key().get_sm3_hashcode(idv);
+		self.get_prime_key().get_sm3_hashcode(idv)
 	}
 }
 
@@ -2170,7 +2366,7 @@ impl std::fmt::Display for ECPrivateKey {
 		if self.is_prime_key() {
 			return self.get_prime_key().fmt(f);
 		}
-		return self.get_bn_key().fmt(f);		
+		self.get_bn_key().fmt(f)		
 	}
 }
 
@@ -2190,5 +2386,65 @@ pub fn to_der_sm2(incode :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
 	elem.pkey.data = incode.to_vec().clone();
 	elem.attributes.val = None;
 	pk8info.elem.val.push(elem);
-	return pk8info.encode_asn1();
-}
\ No newline at end of file
+	pk8info.encode_asn1()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	/*RFC 6979 A.2.5 and A.2.6 , message "sample" , (curve , hash , x , k , r , s)*/
+	const RFC6979_SAMPLES :[(&str,ECHashType,&str,&str,&str,&str);2] = [
+		(PRIME256v1_NAME,ECHashType::Sha256,"C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721",
+			"A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60",
+			"EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716",
+			"F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8"),
+		(SECP384r1_NAME,ECHashType::Sha384,"6B9D3DAD2E1B8C1C05B19875B6659F4DE23C3B667BF297BA9AA47740787137D896D5724E4C70A825F872C9EA60D2EDF5",
+			"94ED910D1A099DAD3254E9242AE85ABDE4BA15168EAF0CA87A555FD56D10FBCA2907E3E83BA95368623B8C4686915CF9",
+			"94EDBB92A5ECB8AAD4736E56C691916B3F88140666CE9FA73D64C4EA95AD133C81A648152E44ACF96E36DD1E80FABE46",
+			"99EF4AEB15F178CEA1FE40DB2603138F130E740A19624526203B6351D0A3A94FA329C145786E679E7B82C71A38628AC8"),
+	];
+
+	fn hex_num(s :&str) -> BigInt {
+		BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(s).unwrap())
+	}
+
+	#[test]
+	fn test_rfc6979_sample() {
+		for (cname,htype,x,k,r,s) in RFC6979_SAMPLES.iter() {
+			let grp :ECGroup = ecc_get_curve_group(cname).unwrap();
+			let privkey :ECPrivateKey = ECPrivateKey::new(&grp,&hex_num(x));
+			let hashv :Vec<u8> = htype.digest(b"sample");
+			let (kinv,kr) = privkey.get_prime_key().setup_sign_rfc6979(&hashv,*htype).unwrap();
+			assert_eq!(kinv,hex_num(k).modpow(&(grp.info().order - 2),&grp.info().order));
+			assert_eq!(kr,hex_num(r));
+			let sig :ECSignature = privkey.sign_base_rfc6979(&hashv,*htype).unwrap();
+			assert_eq!(sig.r,hex_num(r));
+			assert_eq!(sig.s,hex_num(s));
+			assert!(privkey.export_pubkey().verify_base(&sig,&hashv).unwrap());
+			assert_eq!(privkey.sign_base_rfc6979(&htype.digest(b"test"),*htype).unwrap().r,
+				privkey.sign_base_rfc6979(&htype.digest(b"test"),*htype).unwrap().r);
+		}
+	}
+
+	#[test]
+	fn test_ecdh_subgroup() {
+		let grp :ECGroup = ecc_get_curve_group(SECT163k1_NAME).unwrap();
+		let a :ECPrivateKey = ECPrivateKey::generate(&grp);
+		let b :ECPrivateKey = ECPrivateKey::generate(&grp);
+		assert_eq!(a.ecdh(&b.export_pubkey()).unwrap(),b.ecdh(&a.export_pubkey()).unwrap());
+		/*(0,1) is on sect163k1 with order 2 , outside the order subgroup*/
+		let mut lowbin :Vec<u8> = vec![EC_CODE_UNCOMPRESSED];
+		lowbin.extend_from_slice(&[0u8;21]);
+		lowbin.extend_from_slice(&[0u8;20]);
+		lowbin.push(1);
+		let low :ECPublicKey = ECPublicKey::from_bin(&grp,&lowbin).unwrap();
+		assert!(a.ecdh(&low).is_err());
+		let p256 :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let c :ECPrivateKey = ECPrivateKey::generate(&p256);
+		let d :ECPrivateKey = ECPrivateKey::generate(&p256);
+		assert_eq!(c.ecdh(&d.export_pubkey()).unwrap(),d.ecdh(&c.export_pubkey()).unwrap());
+		assert!(a.ecdh(&c.export_pubkey()).is_err());
+	}
+}
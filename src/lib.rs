@@ -10,20 +10,20 @@
 //!    use sha1::{Sha1,Digest};
 //!    
 //!    fn get_hash_value(data :&[u8]) -> Vec<u8> {
-//!    	let mut hasher = Sha1::new();
-//!    	hasher.update(data);
-//!    	return hasher.finalize().to_vec();
+//!        let mut hasher = Sha1::new();
+//!        hasher.update(data);
+//!        return hasher.finalize().to_vec();
 //!    }
 //!    
 //!    /*
-//!    	this is command like
-//!    	openssl ecparam -genkey -name prime256v1 -noout -out ecpriv.pem
-//!    	openssl ec -in ecpriv.pem -pubout -out ecpub.pem
-//!    	openssl dgst -sha1 -sign ecpriv.pem -out sig.bin realdata.bin
-//!    	openssl dgst -sha1 -verify ecpub.pem realdata.bin
+//!        this is command like
+//!        openssl ecparam -genkey -name prime256v1 -noout -out ecpriv.pem
+//!        openssl ec -in ecpriv.pem -pubout -out ecpub.pem
+//!        openssl dgst -sha1 -sign ecpriv.pem -out sig.bin realdata.bin
+//!        openssl dgst -sha1 -verify ecpub.pem realdata.bin
 //!    */
 //!    fn main() -> Result<(),Box<dyn Error>> {
-//!    	let grp :ECGroup = ecc_get_curve_group("prime256v1")?;
+//!        let grp :ECGroup = ecc_get_curve_group("prime256v1")?;
 //!        let privkey :ECPrivateKey = ECPrivateKey::generate(&grp);
 //!        let pubkey :ECPublicKey = privkey.export_pubkey();
 //!        let realdata :Vec<u8> = vec![0x11,0x22,0x33];
@@ -31,9 +31,9 @@
 //!        let sig :ECSignature = privkey.sign_base(&signhash)?;
 //!        let retval :bool = pubkey.verify_base(&sig,&signhash)?;
 //!        /*
-//!        	privdata = privkey.to_der("compressed","")?;
-//!        	pubdata = pubkey.to_der("compressed","")?;
-//!        	sigdata = sig.encode_asn1()?;
+//!            privdata = privkey.to_der("compressed","")?;
+//!            pubdata = pubkey.to_der("compressed","")?;
+//!            sigdata = sig.encode_asn1()?;
 //!    
 //!        */
 //!        assert!(retval == true);
@@ -70,6 +70,8 @@ pub mod logger;
 pub (crate) mod fileop;
 pub mod randop;
 //pub (crate) mod utils;
+/*the asn1obj_codegen output ignores the write counts*/
+#[allow(clippy::unused_io_amount)]
 pub (crate) mod ecasn1;
 pub mod utils;
 pub mod mont;
@@ -79,3 +81,25 @@ pub mod point;
 pub mod curve;
 pub mod signature;
 pub mod keys;
+pub mod security;
+pub mod pem;
+pub mod seed;
+pub mod hashop;
+pub mod kdf;
+pub mod symcipher;
+pub mod pbes2;
+pub mod pkcs12;
+pub mod jwk;
+pub mod jws;
+pub mod cbor;
+pub mod cose;
+pub mod ssh;
+pub mod sshsig;
+pub mod x509;
+pub mod csr;
+pub mod x509path;
+pub mod cms;
+pub mod pgp;
+pub mod dnssec;
+pub mod paseto;
+pub mod tls13;
@@ -12,7 +12,7 @@ use std::sync::RwLock;
 fn _ecsimple_get_environ_var(envname :&str) -> String {
 	match env::var(envname) {
 		Ok(v) => {
-			format!("{}",v)
+			v.to_string()
 		},
 		Err(_e) => {
 			String::from("")
@@ -41,7 +41,7 @@ fn ecsimple_proc_log_init(prefix :&str) -> LogVar {
 
 	key = format!("{}_LEVEL", prefix);
 	getv = _ecsimple_get_environ_var(&key);
-	if getv.len() > 0 {
+	if !getv.is_empty() {
 		match getv.parse::<i32>() {
 			Ok(v) => {
 				retv = v;
@@ -55,7 +55,7 @@ fn ecsimple_proc_log_init(prefix :&str) -> LogVar {
 
 	key = format!("{}_NOSTDERR",prefix);
 	getv = _ecsimple_get_environ_var(&key);
-	if getv.len() > 0 {
+	if !getv.is_empty() {
 		nostderr = true;
 	}
 
@@ -63,24 +63,26 @@ fn ecsimple_proc_log_init(prefix :&str) -> LogVar {
 
 	key = format!("{}_LOGFILE",prefix);
 	getv = _ecsimple_get_environ_var(&key);
-	if getv.len() > 0 {
-		fname = format!("{}",getv);
-		let fo = fs::File::create(&getv);
-		if fo.is_err() {
-			eprintln!("can not open [{}]", getv);
-		} else {
-			coptfile = Some(fo.unwrap());
+	if !getv.is_empty() {
+		fname = getv.to_string();
+		match fs::File::create(&getv) {
+			Ok(fo) => {
+				coptfile = Some(fo);
+			},
+			Err(_) => {
+				eprintln!("can not open [{}]", getv);
+			}
 		}
 	}
 
-	return LogVar {
+	LogVar {
 		level : retv,
-		nostderr : nostderr,
+		nostderr,
 		wfile : coptfile,
 		wfilename : fname,
 		baklevel : 0,
 		baknostderr : true,
-	};
+	}
 }
 
 
@@ -97,20 +99,17 @@ pub fn set_ecsimple_logger_disable() {
 	ecsimpleref.wfile = None;
 	ecsimpleref.level = 0;
 	ecsimpleref.nostderr = true;
-	return;
 }
 
 pub fn set_ecsimple_logger_enable() {
 	let mut ecsimpleref = ECSIMPLE_LOG_LEVEL.write().unwrap();
 	ecsimpleref.level = ecsimpleref.baklevel;
 	ecsimpleref.nostderr = ecsimpleref.baknostderr;	
-	if ecsimpleref.wfilename.len() > 0 {
-		let fo = fs::File::create(&ecsimpleref.wfilename);
-		if fo.is_ok() {
-			ecsimpleref.wfile = Some(fo.unwrap());
+	if !ecsimpleref.wfilename.is_empty() {
+		if let Ok(fo) = fs::File::create(&ecsimpleref.wfilename) {
+			ecsimpleref.wfile = Some(fo);
 		}
 	}
-	return ;
 }
 
 
@@ -123,17 +122,16 @@ pub (crate)  fn ecsimple_debug_out(level :i32, outs :&str) {
 			let _ = std::io::stderr().write_all(c.as_bytes());
 		}
 
-		if refecsimple.wfile.is_some() {
-			let mut wf = refecsimple.wfile.as_ref().unwrap();
-			let _ = wf.write(c.as_bytes());
+		if let Some(mut wf) = refecsimple.wfile.as_ref() {
+			let _ = wf.write_all(c.as_bytes());
 		}
+
 	}
-	return;
 }
 
 pub (crate) fn ecsimple_log_get_timestamp() -> String {
 	let now = Local::now();
-	return format!("{}/{}/{} {}:{}:{}",now.year(),now.month(),now.day(),now.hour(),now.minute(),now.second());
+	format!("{}/{}/{} {}:{}:{}",now.year(),now.month(),now.day(),now.hour(),now.minute(),now.second())
 }
 
 #[macro_export]
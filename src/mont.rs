@@ -1,5 +1,6 @@
 use num_bigint::{BigInt};
 use num_traits::{one,zero};
+#[allow(unused_imports)]
 use crate::*;
 use crate::consts::*;
 use crate::randop::*;
@@ -40,7 +41,7 @@ impl MontNum {
 		bl = ((bl + MONT_BIT_SIZE - 1) / MONT_BIT_SIZE) * MONT_BIT_SIZE;
 		let r :BigInt =  &ov << bl ;
 		let rr :BigInt = &ov << (bl * 2);
-		let invr :BigInt = r.modpow(&(modv.clone() - &tv), &modv);
+		let invr :BigInt = r.modpow(&(modv.clone() - &tv), modv);
 		let factor :BigInt = (&r * &invr - &ov) / modv;
 		let mask :BigInt = &r - &ov;
 		let convertdone :BigInt = (&mask + &ov) % modv.clone();
@@ -58,13 +59,13 @@ impl MontNum {
 
 	pub fn mont_to(&self,bn :&BigInt) -> BigInt {
 		let retv :BigInt = (bn * &self.RR * &self.INVR) % &self.N;
-		return retv;
+		retv
 	}
 
 	pub fn mont_from(&self,bn :&BigInt) -> BigInt {
 		let retv :BigInt = (bn * &self.INVR) % &self.N;
 		ecsimple_log_trace!("BN_from_montgomery(r 0x{:X},a 0x{:X},group.field 0x{:X})",retv,bn,self.N);
-		return retv;
+		retv
 	}
 
 	pub fn mont_mul(&self,a :&BigInt,b :&BigInt) -> BigInt {
@@ -78,10 +79,10 @@ impl MontNum {
 		if reduced >= self.N {
 			reduced = &reduced - &self.N;
 		}
-		return reduced;
+		reduced
 	}
 
 	pub fn mont_pow(&self,a :&BigInt,e :&BigInt) -> BigInt {
-		return a.modpow(e,&self.N);
+		a.modpow(e,&self.N)
 	}
 }
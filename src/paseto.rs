@@ -0,0 +1,251 @@
+use crate::consts::*;
+use crate::group::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::signature::*;
+use crate::utils::bigint_to_fixed_bytes;
+#[allow(unused_imports)]
+use crate::logger::*;
+use num_bigint::{BigInt,Sign};
+use num_traits::{zero};
+use std::error::Error;
+
+ecsimple_error_class!{ECPasetoError}
+
+pub const PASETO_V3_PUBLIC_HEADER :&str = "v3.public.";
+pub const PASERK_K3_PUBLIC_HEADER :&str = "k3.public.";
+pub const PASERK_K3_SECRET_HEADER :&str = "k3.secret.";
+
+/*r || s of P-384*/
+const PASETO_V3_SIG_SIZE :usize = 96;
+const PASETO_V3_SCALAR_SIZE :usize = 48;
+const PASETO_V3_PUBKEY_SIZE :usize = 49;
+
+fn paseto_v3_check_group(grp :&ECGroup) -> Result<(),Box<dyn Error>> {
+	let p384 :ECGroup = ecc_get_curve_group(SECP384r1_NAME)?;
+	if !grp.eq_params(&p384) {
+		ecsimple_new_error!{ECPasetoError,"v3 needs P-384 not [{}]",grp.info().curvename}
+	}
+	Ok(())
+}
+
+/*little endian with the top bit cleared*/
+fn paseto_le64(n :u64) -> [u8;8] {
+	(n & 0x7fff_ffff_ffff_ffff).to_le_bytes()
+}
+
+/*pre-authentication encoding of the PASETO specification*/
+pub fn paseto_pae(pieces :&[&[u8]]) -> Vec<u8> {
+	let mut retv :Vec<u8> = paseto_le64(pieces.len() as u64).to_vec();
+	for p in pieces.iter() {
+		retv.extend_from_slice(&paseto_le64(p.len() as u64));
+		retv.extend_from_slice(p);
+	}
+	retv
+}
+
+/*the compressed point folded into PAE*/
+fn paseto_v3_pubkey_bytes(pubkey :&ECPublicKey) -> Result<Vec<u8>,Box<dyn Error>> {
+	paseto_v3_check_group(&pubkey.get_group())?;
+	let pk :Vec<u8> = pubkey.to_bin(EC_COMPRESSED)?;
+	if pk.len() != PASETO_V3_PUBKEY_SIZE {
+		ecsimple_new_error!{ECPasetoError,"compressed key of {} bytes",pk.len()}
+	}
+	Ok(pk)
+}
+
+/*the verified message and the footer as it was in the token*/
+#[derive(Clone,Debug)]
+pub struct ECPasetoToken {
+	pub message :Vec<u8>,
+	pub footer :Vec<u8>,
+}
+
+/*footer and implicit assertion may be empty , no footer part is emitted then , k of RFC 6979 as the specification recommends*/
+pub fn paseto_v3_sign(privkey :&ECPrivateKey,message :&[u8],footer :&[u8],implicit :&[u8]) -> Result<String,Box<dyn Error>> {
+	let pk :Vec<u8> = paseto_v3_pubkey_bytes(&privkey.export_pubkey())?;
+	let m2 :Vec<u8> = paseto_pae(&[&pk,PASETO_V3_PUBLIC_HEADER.as_bytes(),message,footer,implicit]);
+	let sig :ECSignature = privkey.sign_base_rfc6979(&ECHashType::Sha384.digest(&m2),ECHashType::Sha384)?;
+	let mut body :Vec<u8> = message.to_vec();
+	body.extend_from_slice(&sig.to_fixed_bytes(PASETO_V3_SIG_SIZE / 2)?);
+	let mut rets :String = format!("{}{}",PASETO_V3_PUBLIC_HEADER,base64url_encode(&body));
+	if !footer.is_empty() {
+		rets.push_str(&format!(".{}",base64url_encode(footer)));
+	}
+	Ok(rets)
+}
+
+/*(body , footer) of a token with the header checked*/
+fn paseto_v3_split(token :&str) -> Result<(Vec<u8>,Vec<u8>),Box<dyn Error>> {
+	if !token.starts_with(PASETO_V3_PUBLIC_HEADER) {
+		ecsimple_new_error!{ECPasetoError,"token not {}",PASETO_V3_PUBLIC_HEADER}
+	}
+	let parts :Vec<&str> = token[PASETO_V3_PUBLIC_HEADER.len()..].split('.').collect();
+	if parts.len() > 2 {
+		ecsimple_new_error!{ECPasetoError,"token of {} parts",parts.len() + 2}
+	}
+	let body :Vec<u8> = base64url_decode(parts[0])?;
+	let mut footer :Vec<u8> = Vec::new();
+	if parts.len() == 2 {
+		footer = base64url_decode(parts[1])?;
+	}
+	Ok((body,footer))
+}
+
+/*not verified , for picking the key by a kid in the footer*/
+pub fn paseto_v3_footer(token :&str) -> Result<Vec<u8>,Box<dyn Error>> {
+	let (_,footer) = paseto_v3_split(token)?;
+	Ok(footer)
+}
+
+/*the implicit assertion must be the one given at signing*/
+pub fn paseto_v3_verify(pubkey :&ECPublicKey,token :&str,implicit :&[u8]) -> Result<ECPasetoToken,Box<dyn Error>> {
+	let pk :Vec<u8> = paseto_v3_pubkey_bytes(pubkey)?;
+	let (body,footer) = paseto_v3_split(token)?;
+	if body.len() < PASETO_V3_SIG_SIZE {
+		ecsimple_new_error!{ECPasetoError,"token body of {} bytes",body.len()}
+	}
+	let (message,sigbytes) = body.split_at(body.len() - PASETO_V3_SIG_SIZE);
+	let m2 :Vec<u8> = paseto_pae(&[&pk,PASETO_V3_PUBLIC_HEADER.as_bytes(),message,&footer,implicit]);
+	let sig :ECSignature = ECSignature::from_fixed_bytes(sigbytes)?;
+	if !pubkey.verify_base(&sig,&ECHashType::Sha384.digest(&m2))? {
+		ecsimple_new_error!{ECPasetoError,"token signature not valid"}
+	}
+	Ok(ECPasetoToken {
+		message : message.to_vec(),
+		footer,
+	})
+}
+
+/*PASERK k3.public , the compressed point*/
+pub fn paserk_k3_public_encode(pubkey :&ECPublicKey) -> Result<String,Box<dyn Error>> {
+	Ok(format!("{}{}",PASERK_K3_PUBLIC_HEADER,base64url_encode(&paseto_v3_pubkey_bytes(pubkey)?)))
+}
+
+pub fn paserk_k3_public_decode(s :&str) -> Result<ECPublicKey,Box<dyn Error>> {
+	if !s.starts_with(PASERK_K3_PUBLIC_HEADER) {
+		ecsimple_new_error!{ECPasetoError,"not {}",PASERK_K3_PUBLIC_HEADER}
+	}
+	let pk :Vec<u8> = base64url_decode(&s[PASERK_K3_PUBLIC_HEADER.len()..])?;
+	if pk.len() != PASETO_V3_PUBKEY_SIZE || (pk[0] != 0x02 && pk[0] != 0x03) {
+		ecsimple_new_error!{ECPasetoError,"k3.public not a compressed P-384 point"}
+	}
+	let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME)?;
+	ECPublicKey::from_bin(&grp,&pk)
+}
+
+/*PASERK k3.secret , the 48 byte scalar*/
+pub fn paserk_k3_secret_encode(privkey :&ECPrivateKey) -> Result<String,Box<dyn Error>> {
+	paseto_v3_check_group(&privkey.get_group())?;
+	let d :Vec<u8> = bigint_to_fixed_bytes(&privkey.get_privnum(),PASETO_V3_SCALAR_SIZE)?;
+	Ok(format!("{}{}",PASERK_K3_SECRET_HEADER,base64url_encode(&d)))
+}
+
+pub fn paserk_k3_secret_decode(s :&str) -> Result<ECPrivateKey,Box<dyn Error>> {
+	if !s.starts_with(PASERK_K3_SECRET_HEADER) {
+		ecsimple_new_error!{ECPasetoError,"not {}",PASERK_K3_SECRET_HEADER}
+	}
+	let d :Vec<u8> = base64url_decode(&s[PASERK_K3_SECRET_HEADER.len()..])?;
+	if d.len() != PASETO_V3_SCALAR_SIZE {
+		ecsimple_new_error!{ECPasetoError,"k3.secret of {} bytes",d.len()}
+	}
+	let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME)?;
+	let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&d);
+	if privnum == zero() || privnum >= grp.info().order {
+		ecsimple_new_error!{ECPasetoError,"k3.secret out of range"}
+	}
+	Ok(ECPrivateKey::new(&grp,&privnum))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	const V3_SECRET_HEX :&str = "20347609607477aca8fbfbc5e6218455f3199669792ef8b466faa87bdc67798144c848dd03661eed5ac62461340cea96";
+	const V3_PUBLIC_HEX :&str = "02fbcb7c69ee1c60579be7a334134878d9c5c5bf35d552dab63c0140397ed14cef637d7720925c44699ea30e72874c72fb";
+	const V3_PAYLOAD :&str = "{\"data\":\"this is a signed message\",\"exp\":\"2022-01-01T00:00:00+00:00\"}";
+	const V3_FOOTER :&str = "{\"kid\":\"dYkISylxQeecEcHELfzF88UZrwbLolNiCdpzUHGw9Uqn\"}";
+
+	/*3-S-1 3-S-2 3-S-3 of the PASETO v3 test vectors , (footer , implicit , token) , the RFC 6979 signatures also made by python cryptography*/
+	const V3_PUBLIC_VECTORS :[(&str,&str,&str);3] = [
+		("","","v3.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9qqEwwrKHKi5lJ7b9MBKc0G4MGZy0ptUiMv3lAUAaz-JY_zjoqBSIxMxhfAoeNYiSyvfUErj76KOPWm1OeNnBPkTSespeSXDGaDfxeIrl3bRrPEIy7tLwLAIsRzsXkfph"),
+		(V3_FOOTER,"","v3.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9ZWrbGZ6L0MDK72skosUaS0Dz7wJ_2bMcM6tOxFuCasO9GhwHrvvchqgXQNLQQyWzGC2wkr-VKII71AvkLpC8tJOrzJV1cap9NRwoFzbcXjzMZyxQ0wkshxZxx8ImmNWP.eyJraWQiOiJkWWtJU3lseFFlZWNFY0hFTGZ6Rjg4VVpyd2JMb2xOaUNkcHpVSEd3OVVxbiJ9"),
+		(V3_FOOTER,"{\"test-vector\":\"3-S-3\"}","v3.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ94SjWIbjmS7715GjLSnHnpJrC9Z-cnwK45dmvnVvCRQDCCKAXaKEopTajX0DKYx1Xqr6gcTdfqscLCAbiB4eOW9jlt-oNqdG8TjsYEi6aloBfTzF1DXff_45tFlnBukEX.eyJraWQiOiJkWWtJU3lseFFlZWNFY0hFTGZ6Rjg4VVpyd2JMb2xOaUNkcHpVSEd3OVVxbiJ9"),
+	];
+
+	fn v3_keys() -> (ECPrivateKey,ECPublicKey) {
+		let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME).unwrap();
+		let privkey :ECPrivateKey = ECPrivateKey::new(&grp,&BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(V3_SECRET_HEX).unwrap()));
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&Vec::from_hex(V3_PUBLIC_HEX).unwrap()).unwrap();
+		(privkey,pubkey)
+	}
+
+	#[test]
+	fn test_v3_public_vectors() {
+		let (privkey,pubkey) = v3_keys();
+		assert_eq!(privkey.export_pubkey().to_bin(EC_COMPRESSED).unwrap(),pubkey.to_bin(EC_COMPRESSED).unwrap());
+		for (footer,implicit,token) in V3_PUBLIC_VECTORS.iter() {
+			let t :ECPasetoToken = paseto_v3_verify(&pubkey,token,implicit.as_bytes()).unwrap();
+			assert_eq!(t.message,V3_PAYLOAD.as_bytes());
+			assert_eq!(t.footer,footer.as_bytes());
+			assert_eq!(paseto_v3_footer(token).unwrap(),footer.as_bytes());
+			assert_eq!(paseto_v3_sign(&privkey,V3_PAYLOAD.as_bytes(),footer.as_bytes(),implicit.as_bytes()).unwrap(),*token);
+		}
+	}
+
+	#[test]
+	fn test_v3_public_failures() {
+		let (_,pubkey) = v3_keys();
+		let (_,_,token) = V3_PUBLIC_VECTORS[2];
+		assert!(paseto_v3_verify(&pubkey,token,b"").is_err());
+		assert!(paseto_v3_verify(&pubkey,token,b"{\"test-vector\":\"3-S-2\"}").is_err());
+		let (_,_,token2) = V3_PUBLIC_VECTORS[1];
+		let parts :Vec<&str> = token2.split('.').collect();
+		let nofooter :String = parts[..3].join(".");
+		assert!(paseto_v3_verify(&pubkey,&nofooter,b"").is_err());
+		let otherfooter :String = format!("{}.{}",nofooter,base64url_encode(b"{\"kid\":\"other\"}"));
+		assert!(paseto_v3_verify(&pubkey,&otherfooter,b"").is_err());
+		for h in ["v3.local.","v4.public.","v2.public."].iter() {
+			assert!(paseto_v3_verify(&pubkey,&token2.replacen(PASETO_V3_PUBLIC_HEADER,h,1),b"").is_err());
+		}
+		let mut body :Vec<u8> = base64url_decode(parts[2]).unwrap();
+		let l :usize = body.len();
+		body[l - 1] ^= 1;
+		let flipped :String = format!("v3.public.{}.{}",base64url_encode(&body),parts[3]);
+		assert!(paseto_v3_verify(&pubkey,&flipped,b"").is_err());
+		let short :String = format!("v3.public.{}",base64url_encode(&body[..PASETO_V3_SIG_SIZE - 1]));
+		assert!(paseto_v3_verify(&pubkey,&short,b"").is_err());
+		let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME).unwrap();
+		let other :ECPrivateKey = ECPrivateKey::generate(&grp);
+		assert!(paseto_v3_verify(&other.export_pubkey(),token2,b"").is_err());
+		let p256 :ECPrivateKey = ECPrivateKey::generate(&ecc_get_curve_group(PRIME256v1_NAME).unwrap());
+		assert!(paseto_v3_verify(&p256.export_pubkey(),token2,b"").is_err());
+	}
+
+	#[test]
+	fn test_paserk_k3() {
+		let (privkey,pubkey) = v3_keys();
+		let pk :&str = "k3.public.AvvLfGnuHGBXm-ejNBNIeNnFxb811VLatjwBQDl-0UzvY313IJJcRGmeow5yh0xy-w";
+		let sk :&str = "k3.secret.IDR2CWB0d6yo-_vF5iGEVfMZlml5Lvi0Zvqoe9xneYFEyEjdA2Ye7VrGJGE0DOqW";
+		assert_eq!(paserk_k3_public_encode(&pubkey).unwrap(),pk);
+		assert_eq!(paserk_k3_secret_encode(&privkey).unwrap(),sk);
+		assert_eq!(paserk_k3_public_decode(pk).unwrap().to_bin(EC_COMPRESSED).unwrap(),Vec::from_hex(V3_PUBLIC_HEX).unwrap());
+		assert_eq!(paserk_k3_secret_decode(sk).unwrap().get_privnum(),privkey.get_privnum());
+
+		let grp :ECGroup = ecc_get_curve_group(SECP384r1_NAME).unwrap();
+		let small :ECPrivateKey = ECPrivateKey::new(&grp,&BigInt::from(1));
+		let ssk :String = paserk_k3_secret_encode(&small).unwrap();
+		assert_eq!(base64url_decode(&ssk[PASERK_K3_SECRET_HEADER.len()..]).unwrap().len(),PASETO_V3_SCALAR_SIZE);
+		assert_eq!(paserk_k3_secret_decode(&ssk).unwrap().get_privnum(),BigInt::from(1));
+
+		assert!(paserk_k3_public_decode(&pk.replace("k3.","k4.")).is_err());
+		assert!(paserk_k3_secret_decode(&sk.replace("k3.secret.","k3.public.")).is_err());
+		let unc :String = format!("{}{}",PASERK_K3_PUBLIC_HEADER,base64url_encode(&pubkey.to_bin(EC_UNCOMPRESSED).unwrap()));
+		assert!(paserk_k3_public_decode(&unc).is_err());
+		let order :Vec<u8> = bigint_to_fixed_bytes(&grp.info().order,PASETO_V3_SCALAR_SIZE).unwrap();
+		assert!(paserk_k3_secret_decode(&format!("{}{}",PASERK_K3_SECRET_HEADER,base64url_encode(&order))).is_err());
+		assert!(paserk_k3_secret_decode(&format!("{}{}",PASERK_K3_SECRET_HEADER,base64url_encode(&[0u8;48]))).is_err());
+	}
+}
@@ -0,0 +1,511 @@
+use crate::consts::*;
+use crate::ecasn1::*;
+use crate::hashop::*;
+use crate::kdf::*;
+use crate::keys::*;
+use crate::randop::*;
+use crate::symcipher::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Integer,Asn1OctData};
+use asn1obj::asn1impl::Asn1Op;
+use std::error::Error;
+
+ecsimple_error_class!{ECPbes2Error}
+
+/*OWASP suggests more for sha256 , but this keeps the debug build usable*/
+pub const PBES2_DEFAULT_ITERATIONS :u64 = 100000;
+pub const PBES2_DEFAULT_SALT_LEN :usize = 16;
+/*refuse the files that would keep us busy for hours*/
+pub const PBES2_MAX_ITERATIONS :u64 = 10000000;
+/*openssl defaults for scrypt*/
+pub const PBES2_DEFAULT_SCRYPT_N :u64 = 16384;
+pub const PBES2_DEFAULT_SCRYPT_R :u64 = 8;
+pub const PBES2_DEFAULT_SCRYPT_P :u64 = 1;
+/*scrypt_kdf takes no more than 32 MiB , these only keep the parsed values sane*/
+pub const PBES2_MAX_SCRYPT_N :u64 = 1 << 20;
+pub const PBES2_MAX_SCRYPT_R :u64 = 32;
+pub const PBES2_MAX_SCRYPT_P :u64 = 16;
+
+const GCM_NONCE_LEN :usize = 12;
+const ASN1_NULL_DER :[u8;2] = [0x05,0x00];
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum ECPbeKdf {
+	Pbkdf2 {
+		prf :ECHashType,
+		iterations :u64,
+	},
+	Scrypt {
+		n :u64,
+		r :u64,
+		p :u64,
+	},
+}
+
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECPbeCipher {
+	Aes128Cbc,
+	Aes192Cbc,
+	Aes256Cbc,
+	Aes128Gcm,
+	Aes192Gcm,
+	Aes256Gcm,
+	Sm4Cbc,
+	DesEde3Cbc,
+}
+
+impl ECPbeCipher {
+	pub fn key_len(&self) -> usize {
+		match self {
+			ECPbeCipher::Aes128Cbc | ECPbeCipher::Aes128Gcm | ECPbeCipher::Sm4Cbc => 16,
+			ECPbeCipher::Aes192Cbc | ECPbeCipher::Aes192Gcm | ECPbeCipher::DesEde3Cbc => 24,
+			ECPbeCipher::Aes256Cbc | ECPbeCipher::Aes256Gcm => 32,
+		}
+	}
+
+	pub fn is_gcm(&self) -> bool {
+		matches!(self,ECPbeCipher::Aes128Gcm | ECPbeCipher::Aes192Gcm | ECPbeCipher::Aes256Gcm)
+	}
+
+	pub fn oid(&self) -> &'static str {
+		match self {
+			ECPbeCipher::Aes128Cbc => AES128_CBC_OID,
+			ECPbeCipher::Aes192Cbc => AES192_CBC_OID,
+			ECPbeCipher::Aes256Cbc => AES256_CBC_OID,
+			ECPbeCipher::Aes128Gcm => AES128_GCM_OID,
+			ECPbeCipher::Aes192Gcm => AES192_GCM_OID,
+			ECPbeCipher::Aes256Gcm => AES256_GCM_OID,
+			ECPbeCipher::Sm4Cbc => SM4_CBC_OID,
+			ECPbeCipher::DesEde3Cbc => DES_EDE3_CBC_OID,
+		}
+	}
+
+	pub fn from_oid(oid :&str) -> Result<ECPbeCipher,Box<dyn Error>> {
+		for c in [ECPbeCipher::Aes128Cbc,ECPbeCipher::Aes192Cbc,ECPbeCipher::Aes256Cbc,
+				ECPbeCipher::Aes128Gcm,ECPbeCipher::Aes192Gcm,ECPbeCipher::Aes256Gcm,ECPbeCipher::Sm4Cbc,ECPbeCipher::DesEde3Cbc].iter() {
+			if c.oid() == oid {
+				return Ok(*c);
+			}
+		}
+		ecsimple_new_error!{ECPbes2Error,"unsupported cipher [{}]",oid}
+	}
+
+	/*iv for cbc , nonce for gcm*/
+	pub fn iv_len(&self) -> usize {
+		if self.is_gcm() {
+			return GCM_NONCE_LEN;
+		} else if *self == ECPbeCipher::DesEde3Cbc {
+			return DES_BLOCK_SIZE;
+		}
+		SYM_BLOCK_SIZE
+	}
+
+	pub (crate) fn encrypt(&self,key :&[u8],iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+		match self {
+			ECPbeCipher::Sm4Cbc => {
+				let cipher :Sm4Cipher = Sm4Cipher::new(key)?;
+				cbc_encrypt(&cipher,iv,data)
+			},
+			ECPbeCipher::DesEde3Cbc => {
+				let cipher :Des3Cipher = Des3Cipher::new(key)?;
+				des3_cbc_encrypt(&cipher,iv,data)
+			},
+			_ => {
+				let cipher :AesCipher = AesCipher::new(key)?;
+				if self.is_gcm() {
+					return gcm_encrypt(&cipher,iv,&[],data,GCM_TAG_SIZE);
+				}
+				cbc_encrypt(&cipher,iv,data)
+			}
+		}
+	}
+
+	pub (crate) fn decrypt(&self,key :&[u8],iv :&[u8],data :&[u8],taglen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+		match self {
+			ECPbeCipher::Sm4Cbc => {
+				let cipher :Sm4Cipher = Sm4Cipher::new(key)?;
+				cbc_decrypt(&cipher,iv,data)
+			},
+			ECPbeCipher::DesEde3Cbc => {
+				let cipher :Des3Cipher = Des3Cipher::new(key)?;
+				des3_cbc_decrypt(&cipher,iv,data)
+			},
+			_ => {
+				let cipher :AesCipher = AesCipher::new(key)?;
+				if self.is_gcm() {
+					return gcm_decrypt(&cipher,iv,&[],data,taglen);
+				}
+				cbc_decrypt(&cipher,iv,data)
+			}
+		}
+	}
+}
+
+fn prf_oid(prf :ECHashType) -> Result<&'static str,Box<dyn Error>> {
+	match prf {
+		ECHashType::Sha1 => Ok(HMAC_SHA1_OID),
+		ECHashType::Sha224 => Ok(HMAC_SHA224_OID),
+		ECHashType::Sha256 => Ok(HMAC_SHA256_OID),
+		ECHashType::Sha384 => Ok(HMAC_SHA384_OID),
+		ECHashType::Sha512 => Ok(HMAC_SHA512_OID),
+		ECHashType::Sm3 => Ok(HMAC_SM3_OID),
+	}
+}
+
+fn prf_from_oid(oid :&str) -> Result<ECHashType,Box<dyn Error>> {
+	for h in [ECHashType::Sha1,ECHashType::Sha224,ECHashType::Sha256,ECHashType::Sha384,ECHashType::Sha512,ECHashType::Sm3].iter() {
+		if prf_oid(*h)? == oid {
+			return Ok(*h);
+		}
+	}
+	ecsimple_new_error!{ECPbes2Error,"unsupported prf [{}]",oid}
+}
+
+#[derive(Clone,Debug)]
+pub struct ECPbes2Params {
+	pub kdf :ECPbeKdf,
+	pub cipher :ECPbeCipher,
+	pub salt_len :usize,
+}
+
+/*PBKDF2 with hmacWithSHA256 and aes-256-cbc like openssl pkcs8 -topk8 -v2 aes-256-cbc*/
+impl Default for ECPbes2Params {
+	fn default() -> Self {
+		ECPbes2Params {
+			kdf : ECPbeKdf::Pbkdf2 {
+				prf : ECHashType::Sha256,
+				iterations : PBES2_DEFAULT_ITERATIONS,
+			},
+			cipher : ECPbeCipher::Aes256Cbc,
+			salt_len : PBES2_DEFAULT_SALT_LEN,
+		}
+	}
+}
+
+impl ECPbes2Params {
+	pub fn new_pbkdf2(prf :ECHashType,iterations :u64,cipher :ECPbeCipher) -> ECPbes2Params {
+		ECPbes2Params {
+			kdf : ECPbeKdf::Pbkdf2 {
+				prf,
+				iterations,
+			},
+			cipher,
+			salt_len : PBES2_DEFAULT_SALT_LEN,
+		}
+	}
+
+	pub fn new_scrypt(n :u64,r :u64,p :u64,cipher :ECPbeCipher) -> ECPbes2Params {
+		ECPbes2Params {
+			kdf : ECPbeKdf::Scrypt {
+				n,
+				r,
+				p,
+			},
+			cipher,
+			salt_len : PBES2_DEFAULT_SALT_LEN,
+		}
+	}
+}
+
+/*keyLength left out as openssl does for fixed key ciphers*/
+fn form_kdf_algor(kdf :&ECPbeKdf,salt :&[u8]) -> Result<Asn1X509Algor,Box<dyn Error>> {
+	match kdf {
+		ECPbeKdf::Pbkdf2{prf,iterations} => {
+			let mut param :Asn1Pbkdf2Param = Asn1Pbkdf2Param::init_asn1();
+			let mut elem :Asn1Pbkdf2ParamElem = Asn1Pbkdf2ParamElem::init_asn1();
+			elem.salt.data = salt.to_vec();
+			elem.iterations.val = *iterations as i64;
+			elem.keylength.val = None;
+			elem.prf.val = None;
+			if *prf != ECHashType::Sha1 {
+				elem.prf.val = Some(form_algor(prf_oid(*prf)?,Some(ASN1_NULL_DER.to_vec()))?);
+			}
+			param.elem.val.push(elem);
+			return form_algor(PBKDF2_OID,Some(param.encode_asn1()?));
+		},
+		ECPbeKdf::Scrypt{n,r,p} => {
+			let mut param :Asn1ScryptParam = Asn1ScryptParam::init_asn1();
+			let mut elem :Asn1ScryptParamElem = Asn1ScryptParamElem::init_asn1();
+			elem.salt.data = salt.to_vec();
+			elem.costparam.val = *n as i64;
+			elem.blocksize.val = *r as i64;
+			elem.parallel.val = *p as i64;
+			elem.keylength.val = None;
+			param.elem.val.push(elem);
+			return form_algor(SCRYPT_OID,Some(param.encode_asn1()?));
+		}
+	}
+}
+
+fn derive_key(kdf :&ECPbeKdf,password :&[u8],salt :&[u8],keylen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	match kdf {
+		ECPbeKdf::Pbkdf2{prf,iterations} => {
+			pbkdf2_hmac(*prf,password,salt,*iterations,keylen)
+		},
+		ECPbeKdf::Scrypt{n,r,p} => {
+			scrypt_kdf(password,salt,*n,*r,*p,keylen)
+		}
+	}
+}
+
+/*return the kdf and the salt*/
+fn parse_kdf_algor(algor :&Asn1X509Algor,keylen :usize) -> Result<(ECPbeKdf,Vec<u8>),Box<dyn Error>> {
+	let (oid,params) = get_algor_elem(algor)?;
+	if params.is_none() {
+		ecsimple_new_error!{ECPbes2Error,"no parameters for kdf [{}]",oid}
+	}
+	let pdata :Vec<u8> = params.unwrap();
+	if oid == PBKDF2_OID {
+		let mut param :Asn1Pbkdf2Param = Asn1Pbkdf2Param::init_asn1();
+		let _ = param.decode_asn1(&pdata)?;
+		if param.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPbes2Error,"pbkdf2 param elem {} != 1",param.elem.val.len()}
+		}
+		let elem :Asn1Pbkdf2ParamElem = param.elem.val[0].clone();
+		if elem.iterations.val <= 0 {
+			ecsimple_new_error!{ECPbes2Error,"pbkdf2 iterations {} invalid",elem.iterations.val}
+		}
+		if elem.iterations.val as u64 > PBES2_MAX_ITERATIONS {
+			ecsimple_new_error!{ECPbes2Error,"pbkdf2 iterations {} above {}",elem.iterations.val,PBES2_MAX_ITERATIONS}
+		}
+		if let Some(kl) = elem.keylength.val.as_ref() {
+			if kl.val != keylen as i64 {
+				ecsimple_new_error!{ECPbes2Error,"pbkdf2 keylength {} != cipher key {}",kl.val,keylen}
+			}
+		}
+		let mut prf :ECHashType = ECHashType::Sha1;
+		if let Some(prfalgor) = elem.prf.val.as_ref() {
+			let (prfoid,_) = get_algor_elem(prfalgor)?;
+			prf = prf_from_oid(&prfoid)?;
+		}
+		return Ok((ECPbeKdf::Pbkdf2{prf, iterations : elem.iterations.val as u64},elem.salt.data.clone()));
+	} else if oid == SCRYPT_OID {
+		let mut param :Asn1ScryptParam = Asn1ScryptParam::init_asn1();
+		let _ = param.decode_asn1(&pdata)?;
+		if param.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPbes2Error,"scrypt param elem {} != 1",param.elem.val.len()}
+		}
+		let elem :Asn1ScryptParamElem = param.elem.val[0].clone();
+		if elem.costparam.val <= 0 || elem.blocksize.val <= 0 || elem.parallel.val <= 0 {
+			ecsimple_new_error!{ECPbes2Error,"scrypt N {} r {} p {} invalid",elem.costparam.val,elem.blocksize.val,elem.parallel.val}
+		}
+		if elem.costparam.val as u64 > PBES2_MAX_SCRYPT_N || elem.blocksize.val as u64 > PBES2_MAX_SCRYPT_R || elem.parallel.val as u64 > PBES2_MAX_SCRYPT_P {
+			ecsimple_new_error!{ECPbes2Error,"scrypt N {} r {} p {} above {} {} {}",elem.costparam.val,elem.blocksize.val,elem.parallel.val,PBES2_MAX_SCRYPT_N,PBES2_MAX_SCRYPT_R,PBES2_MAX_SCRYPT_P}
+		}
+
+		if let Some(kl) = elem.keylength.val.as_ref() {
+			if kl.val != keylen as i64 {
+				ecsimple_new_error!{ECPbes2Error,"scrypt keylength {} != cipher key {}",kl.val,keylen}
+			}
+		}
+		return Ok((ECPbeKdf::Scrypt{n : elem.costparam.val as u64, r : elem.blocksize.val as u64, p : elem.parallel.val as u64},elem.salt.data.clone()));
+	}
+	ecsimple_new_error!{ECPbes2Error,"unsupported kdf [{}]",oid}
+}
+
+/*return cipher , iv and the tag length for gcm*/
+fn parse_cipher_algor(algor :&Asn1X509Algor) -> Result<(ECPbeCipher,Vec<u8>,usize),Box<dyn Error>> {
+	let (oid,params) = get_algor_elem(algor)?;
+	let cipher :ECPbeCipher = ECPbeCipher::from_oid(&oid)?;
+	if params.is_none() {
+		ecsimple_new_error!{ECPbes2Error,"no parameters for cipher [{}]",oid}
+	}
+	let pdata :Vec<u8> = params.unwrap();
+	if cipher.is_gcm() {
+		let mut param :Asn1GcmParam = Asn1GcmParam::init_asn1();
+		let _ = param.decode_asn1(&pdata)?;
+		if param.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPbes2Error,"gcm param elem {} != 1",param.elem.val.len()}
+		}
+		let elem :Asn1GcmParamElem = param.elem.val[0].clone();
+		/*RFC 5084 default icv length*/
+		let mut taglen :usize = 12;
+		if let Some(icv) = elem.icvlen.val.as_ref() {
+			taglen = icv.val as usize;
+		}
+
+		return Ok((cipher,elem.nonce.data.clone(),taglen));
+	}
+	let mut iv :Asn1OctData = Asn1OctData::init_asn1();
+	let _ = iv.decode_asn1(&pdata)?;
+	if iv.data.len() != cipher.iv_len() {
+		ecsimple_new_error!{ECPbes2Error,"iv len {} != {}",iv.data.len(),cipher.iv_len()}
+	}
+	Ok((cipher,iv.data.clone(),0))
+}
+
+fn form_cipher_algor(cipher :ECPbeCipher,iv :&[u8]) -> Result<Asn1X509Algor,Box<dyn Error>> {
+	if cipher.is_gcm() {
+		let mut param :Asn1GcmParam = Asn1GcmParam::init_asn1();
+		let mut elem :Asn1GcmParamElem = Asn1GcmParamElem::init_asn1();
+		let mut icv :Asn1Integer = Asn1Integer::init_asn1();
+		elem.nonce.data = iv.to_vec();
+		icv.val = GCM_TAG_SIZE as i64;
+		elem.icvlen.val = Some(icv);
+		param.elem.val.push(elem);
+		return form_algor(cipher.oid(),Some(param.encode_asn1()?));
+	}
+	let mut ivdata :Asn1OctData = Asn1OctData::init_asn1();
+	ivdata.data = iv.to_vec();
+	return form_algor(cipher.oid(),Some(ivdata.encode_asn1()?));
+}
+
+/*PBES2 AlgorithmIdentifier and the encrypted data , also for PKCS#12 and CMS*/
+pub (crate) fn pbes2_encrypt_algor(data :&[u8],password :&[u8],params :&ECPbes2Params) -> Result<(Asn1X509Algor,Vec<u8>),Box<dyn Error>> {
+	if params.salt_len < 8 {
+		ecsimple_new_error!{ECPbes2Error,"salt len {} < 8",params.salt_len}
+	}
+	let keylen :usize = params.cipher.key_len();
+	let salt :Vec<u8> = ecsimple_rand_bytes(params.salt_len);
+	let iv :Vec<u8> = ecsimple_rand_bytes(params.cipher.iv_len());
+	let key :Vec<u8> = derive_key(&params.kdf,password,&salt,keylen)?;
+	let encdata :Vec<u8> = params.cipher.encrypt(&key,&iv,data)?;
+
+	let mut pbes2 :Asn1Pbes2Param = Asn1Pbes2Param::init_asn1();
+	let mut pbes2elem :Asn1Pbes2ParamElem = Asn1Pbes2ParamElem::init_asn1();
+	pbes2elem.keyfunc = form_kdf_algor(&params.kdf,&salt)?;
+	pbes2elem.encscheme = form_cipher_algor(params.cipher,&iv)?;
+	pbes2.elem.val.push(pbes2elem);
+	let algor :Asn1X509Algor = form_algor(PBES2_OID,Some(pbes2.encode_asn1()?))?;
+	Ok((algor,encdata))
+}
+
+/*return the plain data and the parameters used*/
+pub (crate) fn pbes2_decrypt_algor(algor :&Asn1X509Algor,encdata :&[u8],password :&[u8]) -> Result<(Vec<u8>,ECPbes2Params),Box<dyn Error>> {
+	let (oid,params) = get_algor_elem(algor)?;
+	if oid != PBES2_OID {
+		ecsimple_new_error!{ECPbes2Error,"[{}] not PBES2 [{}]",oid,PBES2_OID}
+	}
+	if params.is_none() {
+		ecsimple_new_error!{ECPbes2Error,"no PBES2 parameters"}
+	}
+	let mut pbes2 :Asn1Pbes2Param = Asn1Pbes2Param::init_asn1();
+	let _ = pbes2.decode_asn1(params.as_ref().unwrap())?;
+	if pbes2.elem.val.len() != 1 {
+		ecsimple_new_error!{ECPbes2Error,"PBES2-params elem {} != 1",pbes2.elem.val.len()}
+	}
+	let pbes2elem :Asn1Pbes2ParamElem = pbes2.elem.val[0].clone();
+	let (cipher,iv,taglen) = parse_cipher_algor(&pbes2elem.encscheme)?;
+	let keylen :usize = cipher.key_len();
+	let (kdf,salt) = parse_kdf_algor(&pbes2elem.keyfunc,keylen)?;
+	let key :Vec<u8> = derive_key(&kdf,password,&salt,keylen)?;
+	let data :Vec<u8> = match cipher.decrypt(&key,&iv,encdata,taglen) {
+		Ok(v) => v,
+		Err(e) => {
+			ecsimple_new_error!{ECPbes2Error,"decrypt failed , maybe wrong password : {}",e}
+		}
+	};
+	Ok((data,ECPbes2Params {
+		kdf,
+		cipher,
+		salt_len : salt.len(),
+	}))
+}
+
+/*PKCS#5 v2.1 PBES2 , return the EncryptedPrivateKeyInfo der*/
+pub fn pbes2_encrypt(data :&[u8],password :&[u8],params :&ECPbes2Params) -> Result<Vec<u8>,Box<dyn Error>> {
+	let (algor,encdata) = pbes2_encrypt_algor(data,password,params)?;
+	let mut encinfo :Asn1EncryptedPrivKeyInfo = Asn1EncryptedPrivKeyInfo::init_asn1();
+	let mut encelem :Asn1EncryptedPrivKeyInfoElem = Asn1EncryptedPrivKeyInfoElem::init_asn1();
+	encelem.encalgor = algor;
+	encelem.encdata.data = encdata;
+	encinfo.elem.val.push(encelem);
+	encinfo.encode_asn1()
+}
+
+pub fn pbes2_decrypt(dercode :&[u8],password :&[u8]) -> Result<(Vec<u8>,ECPbes2Params),Box<dyn Error>> {
+	let mut encinfo :Asn1EncryptedPrivKeyInfo = Asn1EncryptedPrivKeyInfo::init_asn1();
+	let _ = encinfo.decode_asn1(dercode)?;
+	if encinfo.elem.val.len() != 1 {
+		ecsimple_new_error!{ECPbes2Error,"EncryptedPrivateKeyInfo elem {} != 1",encinfo.elem.val.len()}
+	}
+	let encelem :Asn1EncryptedPrivKeyInfoElem = encinfo.elem.val[0].clone();
+	pbes2_decrypt_algor(&encelem.encalgor,&encelem.encdata.data,password)
+}
+
+impl ECPrivateKey {
+	/*EncryptedPrivateKeyInfo over PKCS#8 with named curve , like openssl pkcs8 -topk8 -v2*/
+	pub fn to_encrypted_pkcs8(&self,password :&[u8],params :&ECPbes2Params) -> Result<Vec<u8>,Box<dyn Error>> {
+		let data :Vec<u8> = self.to_pkcs8_der(EC_UNCOMPRESSED,"",false)?;
+		pbes2_encrypt(&data,password,params)
+	}
+
+	pub fn from_encrypted_pkcs8(dercode :&[u8],password :&[u8]) -> Result<ECPrivateKey,Box<dyn Error>> {
+		let (data,_) = pbes2_decrypt(dercode,password)?;
+		ECPrivateKey::from_pkcs8_der(&data)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pem::*;
+	use num_bigint::{BigInt,Sign};
+	use hex::FromHex;
+
+	/*openssl pkcs8 -topk8 of the same prime256v1 key with password ecsimple*/
+	const OPENSSL_PRIV_HEX :&str = "79a015553b785743c8161d773974f09d902c92ed089e15ddc041aa9614296213";
+	const OPENSSL_AES256_PBKDF2 :&str = "MIH0MF8GCSqGSIb3DQEFDTBSMDEGCSqGSIb3DQEFDDAkBBArnW658tINblAqRSkdPmYnAgIIADAMBggqhkiG9w0CCQUAMB0GCWCGSAFlAwQBKgQQsEebHVsU1Ul3SrCucUIy/QSBkC+bjLz79MD9kDX1PGqrNx0WB1zs16XGMftiCpcguV+3DVK8Pdh6GokfUQmLzjqtOpiUFL43Q1KFK9lrBkqD8K3YEE+g1UVYQMcak9Qx8hNIbNnUfVChjMClnZ0Mx95WsyqKC1UybcggkPBCB7D2uMvqJ2vi9YgsaJMFscaUdJlE+RMkJZ/8scL7j8kzHNh1Xg==";
+	const OPENSSL_AES128_SCRYPT :&str = "MIHsMFcGCSqGSIb3DQEFDTBKMCkGCSsGAQQB2kcECzAcBBDO2M39Sye0gm1RH1i9syvhAgIEAAIBCAIBATAdBglghkgBZQMEAQIEED3s/sppTwMNFRn41fENuZkEgZCjX0ECVQMFhqoO/3qLLPF9FXDMDns3windsmdnmAH89ZPYIPpj3Kol4z1CBKIaSi3ucpP1+nWQt0C9XOiebxx7YeFCkh0Mi+t8p3j/5ns8V2NjCsNXrpLcubpyuxy4Bcnn+nQlc7fp5EIoqSYpAHyal3Eczwg33qdfj7k8pRxbgnSSMOwZEfY/NTQpAKNks2I=";
+	const OPENSSL_SM4_PBKDF2 :&str = "MIHzMF4GCSqGSIb3DQEFDTBRMDEGCSqGSIb3DQEFDDAkBBAFeywTVYDG77EfeWStI7YdAgIIADAMBggqhkiG9w0CCQUAMBwGCCqBHM9VAWgCBBDYbDldOadkB6lnrLm3T0OsBIGQ4Q57avGx4O0kIRDKxjwQ8HKre0fvB1QBZpBnrlJrWHC1MhgDLzyi1a5VjFbIYzWshX3bq1p/7aqlXqaz2HQA0nI3V6x5bPn/OsKkRSzqT4nNbHC64zAwCq+2R8XLk7oe5VtDL0TYxcVWTmi2wyq4hp+DD52J7L56yZ+TjvtjIN5MfaA0Cmu7Kqh0JKQq6yxV";
+	const OPENSSL_DES3_PBKDF2 :&str = "MIHrMFYGCSqGSIb3DQEFDTBJMDEGCSqGSIb3DQEFDDAkBBBlAYMgWrFz8cT6FNXUwS9hAgIIADAMBggqhkiG9w0CCQUAMBQGCCqGSIb3DQMHBAjYhMQbpzhqpwSBkCR+s09xFfoPCn6s/fI6Xv25yU+Qg2hXQNaNvHge8sFLJC/8fhiHGPTxZHylFpAIJueUnaiFtQbf0wGfK+PGk/RVUUKzbmSPJxs1Qsy9YF142n9VrbkNX1+jI4WU2Z6aidsmvROWUj1x9AY8iytITigyuQFEi+v/ElomfIz9Y02u5e1ifsZH+reoiW3o5MfNdA==";
+
+	fn check_openssl_vector(b64 :&str,params :ECPbes2Params) {
+		let der :Vec<u8> = base64_decode(b64).unwrap();
+		let privkey :ECPrivateKey = ECPrivateKey::from_encrypted_pkcs8(&der,b"ecsimple").unwrap();
+		let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(OPENSSL_PRIV_HEX).unwrap());
+		assert_eq!(privkey.get_privnum(),privnum);
+		let (_,gotparams) = pbes2_decrypt(&der,b"ecsimple").unwrap();
+		assert_eq!(gotparams.kdf,params.kdf);
+		assert_eq!(gotparams.cipher,params.cipher);
+		assert!(ECPrivateKey::from_encrypted_pkcs8(&der,b"wrong").is_err());
+	}
+
+	#[test]
+	fn test_openssl_aes256_pbkdf2() {
+		check_openssl_vector(OPENSSL_AES256_PBKDF2,ECPbes2Params::new_pbkdf2(ECHashType::Sha256,2048,ECPbeCipher::Aes256Cbc));
+	}
+
+	#[test]
+	fn test_openssl_aes128_scrypt() {
+		check_openssl_vector(OPENSSL_AES128_SCRYPT,ECPbes2Params::new_scrypt(1024,8,1,ECPbeCipher::Aes128Cbc));
+	}
+
+	#[test]
+	fn test_openssl_sm4_pbkdf2() {
+		check_openssl_vector(OPENSSL_SM4_PBKDF2,ECPbes2Params::new_pbkdf2(ECHashType::Sha256,2048,ECPbeCipher::Sm4Cbc));
+	}
+
+	#[test]
+	fn test_openssl_des3_pbkdf2() {
+		check_openssl_vector(OPENSSL_DES3_PBKDF2,ECPbes2Params::new_pbkdf2(ECHashType::Sha256,2048,ECPbeCipher::DesEde3Cbc));
+	}
+
+	#[test]
+	fn test_pbkdf2_iterations_cap() {
+		let kdf :ECPbeKdf = ECPbeKdf::Pbkdf2{prf : ECHashType::Sha256, iterations : PBES2_MAX_ITERATIONS + 1};
+		let algor :Asn1X509Algor = form_kdf_algor(&kdf,&[0x11;16]).unwrap();
+		assert!(parse_kdf_algor(&algor,32).is_err());
+		let kdf :ECPbeKdf = ECPbeKdf::Pbkdf2{prf : ECHashType::Sha256, iterations : PBES2_MAX_ITERATIONS};
+		let algor :Asn1X509Algor = form_kdf_algor(&kdf,&[0x11;16]).unwrap();
+		assert!(parse_kdf_algor(&algor,32).is_ok());
+	}
+
+	#[test]
+	fn test_scrypt_params_cap() {
+		let caps :[(u64,u64,u64);3] = [(PBES2_MAX_SCRYPT_N * 2,8,1),(16384,PBES2_MAX_SCRYPT_R + 1,1),(16384,8,PBES2_MAX_SCRYPT_P + 1)];
+		for (n,r,p) in caps.iter() {
+			let kdf :ECPbeKdf = ECPbeKdf::Scrypt{n : *n, r : *r, p : *p};
+			let algor :Asn1X509Algor = form_kdf_algor(&kdf,&[0x11;16]).unwrap();
+			assert!(parse_kdf_algor(&algor,16).is_err());
+		}
+		let kdf :ECPbeKdf = ECPbeKdf::Scrypt{n : PBES2_MAX_SCRYPT_N, r : PBES2_MAX_SCRYPT_R, p : PBES2_MAX_SCRYPT_P};
+		let algor :Asn1X509Algor = form_kdf_algor(&kdf,&[0x11;16]).unwrap();
+		assert!(parse_kdf_algor(&algor,16).is_ok());
+		/*within the caps but over the scrypt maxmem*/
+		let params :ECPbes2Params = ECPbes2Params::new_scrypt(PBES2_MAX_SCRYPT_N,PBES2_MAX_SCRYPT_R,1,ECPbeCipher::Aes128Cbc);
+		assert!(derive_key(&params.kdf,b"password",&[0x11;16],16).is_err());
+	}
+
+}
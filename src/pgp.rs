@@ -0,0 +1,876 @@
+use crate::consts::*;
+use crate::group::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::signature::*;
+use crate::randop::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Object};
+use asn1obj::asn1impl::Asn1Op;
+use num_bigint::{BigInt,Sign};
+use std::error::Error;
+
+ecsimple_error_class!{ECPgpError}
+
+pub const PGP_TAG_SIGNATURE :u8 = 2;
+pub const PGP_TAG_PUBLIC_KEY :u8 = 6;
+pub const PGP_TAG_USER_ID :u8 = 13;
+pub const PGP_TAG_PUBLIC_SUBKEY :u8 = 14;
+
+pub const PGP_ALGO_ECDH :u8 = 18;
+pub const PGP_ALGO_ECDSA :u8 = 19;
+
+pub const PGP_SIG_BINARY :u8 = 0x00;
+pub const PGP_SIG_TEXT :u8 = 0x01;
+pub const PGP_SIG_POSITIVE_CERT :u8 = 0x13;
+
+pub const PGP_SUBPACKET_CREATION_TIME :u8 = 2;
+pub const PGP_SUBPACKET_ISSUER_KEY_ID :u8 = 16;
+pub const PGP_SUBPACKET_KEY_FLAGS :u8 = 27;
+pub const PGP_SUBPACKET_ISSUER_FINGERPRINT :u8 = 33;
+
+pub const PGP_KEY_FLAG_CERTIFY :u8 = 0x01;
+pub const PGP_KEY_FLAG_SIGN :u8 = 0x02;
+
+pub const PGP_ARMOR_SIGNATURE :&str = "PGP SIGNATURE";
+pub const PGP_ARMOR_PUBLIC_KEY :&str = "PGP PUBLIC KEY BLOCK";
+
+const PGP_ARMOR_LINE_LENGTH :usize = 64;
+const PGP_CRC24_INIT :u32 = 0xb704ce;
+const PGP_CRC24_POLY :u32 = 0x1864cfb;
+const PGP_SUBPACKET_CRITICAL :u8 = 0x80;
+/*the subpackets understood here , others critical in the hashed area fail the signature*/
+const PGP_SUBPACKET_KNOWN :[u8;4] = [PGP_SUBPACKET_CREATION_TIME,PGP_SUBPACKET_ISSUER_KEY_ID,PGP_SUBPACKET_KEY_FLAGS,PGP_SUBPACKET_ISSUER_FINGERPRINT];
+
+/*RFC 9580 9.2 ECC curves for ECDSA and ECDH*/
+fn pgp_curve_table() -> [(&'static str,&'static str);6] {
+	[
+		(PRIME256v1_OID,PRIME256v1_NAME),
+		(SECP384r1_OID,SECP384r1_NAME),
+		(SECP521r1_OID,SECP521r1_NAME),
+		(BRAINPOOLP256r1_OID,BRAINPOOLP256r1_NAME),
+		(BRAINPOOLP384r1_OID,BRAINPOOLP384r1_NAME),
+		(BRAINPOOLP512r1_OID,BRAINPOOLP512r1_NAME),
+	]
+}
+
+/*the OID without its DER tag and length , as in the key packet*/
+fn pgp_curve_oid(grp :&ECGroup) -> Result<Vec<u8>,Box<dyn Error>> {
+	for (oid,cname) in pgp_curve_table().iter() {
+		let cgrp :ECGroup = ecc_get_curve_group(cname)?;
+		if grp.eq_params(&cgrp) {
+			let mut obj :Asn1Object = Asn1Object::init_asn1();
+			let _ = obj.set_value(oid)?;
+			let code :Vec<u8> = obj.encode_asn1()?;
+			return Ok(code[2..].to_vec());
+		}
+	}
+	ecsimple_new_error!{ECPgpError,"no openpgp curve for [{}]",grp.info().curvename}
+}
+
+fn pgp_group_from_oid(oidbytes :&[u8]) -> Result<ECGroup,Box<dyn Error>> {
+	if oidbytes.is_empty() || oidbytes.len() > 0x7f {
+		ecsimple_new_error!{ECPgpError,"curve oid length {}",oidbytes.len()}
+	}
+	let mut code :Vec<u8> = vec![0x06,oidbytes.len() as u8];
+	code.extend_from_slice(oidbytes);
+	let mut obj :Asn1Object = Asn1Object::init_asn1();
+	let _ = obj.decode_asn1(&code)?;
+	let oid :String = obj.get_value();
+	for (o,cname) in pgp_curve_table().iter() {
+		if *o == oid {
+			return ecc_get_curve_group(cname);
+		}
+	}
+	ecsimple_new_error!{ECPgpError,"curve oid [{}] not supported",oid}
+}
+
+pub fn pgp_hash_id(htype :ECHashType) -> Result<u8,Box<dyn Error>> {
+	match htype {
+		ECHashType::Sha1 => Ok(2),
+		ECHashType::Sha256 => Ok(8),
+		ECHashType::Sha384 => Ok(9),
+		ECHashType::Sha512 => Ok(10),
+		ECHashType::Sha224 => Ok(11),
+		ECHashType::Sm3 => {
+			ecsimple_new_error!{ECPgpError,"no openpgp id for sm3"}
+		}
+	}
+}
+
+pub fn pgp_hash_from_id(id :u8) -> Result<ECHashType,Box<dyn Error>> {
+	for h in [ECHashType::Sha1,ECHashType::Sha224,ECHashType::Sha256,ECHashType::Sha384,ECHashType::Sha512].iter() {
+		if pgp_hash_id(*h)? == id {
+			return Ok(*h);
+		}
+	}
+	ecsimple_new_error!{ECPgpError,"hash algorithm {} not supported",id}
+}
+
+/*RFC 9580 5.2.4 salt size of v6 signatures*/
+fn pgp_salt_len(htype :ECHashType) -> Result<usize,Box<dyn Error>> {
+	match htype {
+		ECHashType::Sha224 | ECHashType::Sha256 => Ok(16),
+		ECHashType::Sha384 => Ok(24),
+		ECHashType::Sha512 => Ok(32),
+		_ => {
+			ecsimple_new_error!{ECPgpError,"{} not for v6 signatures",htype.name()}
+		}
+	}
+}
+
+fn pgp_put_mpi(v :&mut Vec<u8>,data :&[u8]) {
+	let mut idx :usize = 0;
+	while idx < data.len() && data[idx] == 0 {
+		idx += 1;
+	}
+	let d :&[u8] = &data[idx..];
+	let mut bits :usize = 0;
+	if !d.is_empty() {
+		bits = (d.len() - 1) * 8 + (8 - d[0].leading_zeros() as usize);
+	}
+	v.extend_from_slice(&(bits as u16).to_be_bytes());
+	v.extend_from_slice(d);
+}
+
+/*RFC 9580 4.2.1 new format length*/
+fn pgp_put_length(v :&mut Vec<u8>,len :usize) {
+	if len < 192 {
+		v.push(len as u8);
+	} else if len < 8384 {
+		let l :usize = len - 192;
+		v.push(((l >> 8) + 192) as u8);
+		v.push((l & 0xff) as u8);
+	} else {
+		v.push(0xff);
+		v.extend_from_slice(&(len as u32).to_be_bytes());
+	}
+}
+
+fn pgp_put_subpacket(v :&mut Vec<u8>,stype :u8,data :&[u8]) {
+	pgp_put_length(v,data.len() + 1);
+	v.push(stype);
+	v.extend_from_slice(data);
+}
+
+/*(tag , body) of a packet*/
+pub type ECPgpPacket = (u8,Vec<u8>);
+/*(type , critical , data) of a subpacket*/
+type PgpSubpacket = (u8,bool,Vec<u8>);
+
+struct PgpReader<'a> {
+	data :&'a [u8],
+	off :usize,
+}
+
+impl<'a> PgpReader<'a> {
+	fn new(data :&'a [u8]) -> PgpReader<'a> {
+		PgpReader {
+			data,
+			off : 0,
+		}
+	}
+
+	fn read_bytes(&mut self,n :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+		if self.data.len() - self.off < n {
+			ecsimple_new_error!{ECPgpError,"no {} bytes at offset {}",n,self.off}
+		}
+		let retv :Vec<u8> = self.data[self.off..(self.off+n)].to_vec();
+		self.off += n;
+		Ok(retv)
+	}
+
+	fn read_u8(&mut self) -> Result<u8,Box<dyn Error>> {
+		let b :Vec<u8> = self.read_bytes(1)?;
+		Ok(b[0])
+	}
+
+	fn read_u16(&mut self) -> Result<u16,Box<dyn Error>> {
+		let b :Vec<u8> = self.read_bytes(2)?;
+		Ok(u16::from_be_bytes([b[0],b[1]]))
+	}
+
+	fn read_u32(&mut self) -> Result<u32,Box<dyn Error>> {
+		let b :Vec<u8> = self.read_bytes(4)?;
+		Ok(u32::from_be_bytes([b[0],b[1],b[2],b[3]]))
+	}
+
+	fn read_mpi(&mut self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let bits :usize = self.read_u16()? as usize;
+		self.read_bytes((bits + 7) / 8)
+	}
+
+	fn read_length(&mut self) -> Result<usize,Box<dyn Error>> {
+		let o1 :usize = self.read_u8()? as usize;
+		if o1 < 192 {
+			return Ok(o1);
+		} else if o1 < 224 {
+			let o2 :usize = self.read_u8()? as usize;
+			return Ok(((o1 - 192) << 8) + o2 + 192);
+		} else if o1 == 255 {
+			return Ok(self.read_u32()? as usize);
+		}
+		ecsimple_new_error!{ECPgpError,"partial body length not supported"}
+	}
+
+	fn remain(&self) -> usize {
+		self.data.len() - self.off
+	}
+}
+
+/*new format packet*/
+pub fn pgp_packet_encode(tag :u8,body :&[u8]) -> Vec<u8> {
+	let mut retv :Vec<u8> = vec![0xc0 | tag];
+	pgp_put_length(&mut retv,body.len());
+	retv.extend_from_slice(body);
+	retv
+}
+
+/*(tag , body) of old and new format packets*/
+pub fn pgp_packets_decode(data :&[u8]) -> Result<Vec<ECPgpPacket>,Box<dyn Error>> {
+	let mut retv :Vec<ECPgpPacket> = Vec::new();
+	let mut r :PgpReader = PgpReader::new(data);
+	while r.remain() > 0 {
+		let b :u8 = r.read_u8()?;
+		if (b & 0x80) == 0 {
+			ecsimple_new_error!{ECPgpError,"packet header 0x{:02x} at {}",b,r.off - 1}
+		}
+		let tag :u8;
+		let len :usize;
+		if (b & 0x40) != 0 {
+			tag = b & 0x3f;
+			len = r.read_length()?;
+		} else {
+			tag = (b >> 2) & 0xf;
+			len = match b & 0x3 {
+				0 => r.read_u8()? as usize,
+				1 => r.read_u16()? as usize,
+				2 => r.read_u32()? as usize,
+				_ => r.remain(),
+			};
+		}
+		retv.push((tag,r.read_bytes(len)?));
+	}
+	Ok(retv)
+}
+
+fn pgp_crc24(data :&[u8]) -> u32 {
+	let mut crc :u32 = PGP_CRC24_INIT;
+	for b in data.iter() {
+		crc ^= (*b as u32) << 16;
+		for _ in 0..8 {
+			crc <<= 1;
+			if (crc & 0x1000000) != 0 {
+				crc ^= PGP_CRC24_POLY;
+			}
+		}
+	}
+	crc & 0xffffff
+}
+
+/*RFC 9580 6.2 with the CRC24 line gpg 2.2 still expects*/
+pub fn pgp_armor(data :&[u8],label :&str) -> String {
+	let outs :String = base64_encode(data);
+	let mut rets :String = format!("-----BEGIN {}-----\n\n",label);
+	let mut idx :usize = 0;
+	while idx < outs.len() {
+		let end :usize = std::cmp::min(idx + PGP_ARMOR_LINE_LENGTH,outs.len());
+		rets.push_str(&format!("{}\n",&outs[idx..end]));
+		idx = end;
+	}
+	let crc :u32 = pgp_crc24(data);
+	rets.push_str(&format!("={}\n",base64_encode(&crc.to_be_bytes()[1..])));
+	rets.push_str(&format!("-----END {}-----\n",label));
+	rets
+}
+
+/*(label , data) of the first armored block , headers skipped and the checksum checked*/
+pub fn pgp_dearmor(ins :&str) -> Result<(String,Vec<u8>),Box<dyn Error>> {
+	let mut label :Option<String> = None;
+	let mut inhdr :bool = false;
+	let mut b64 :String = "".to_string();
+	let mut crc :Option<Vec<u8>> = None;
+	for l in ins.lines() {
+		let l :&str = l.trim_end();
+		let curlabel :&str = match label.as_deref() {
+			Some(lb) => lb,
+			None => {
+				if l.starts_with("-----BEGIN ") && l.ends_with("-----") {
+					label = Some(l[11..(l.len()-5)].to_string());
+					inhdr = true;
+				}
+				continue;
+			},
+		};
+		if let Some(endlabel) = l.strip_prefix("-----END ") {
+			if endlabel.trim_end_matches('-') != curlabel {
+				ecsimple_new_error!{ECPgpError,"[{}] not end of [{}]",l,curlabel}
+			}
+			let data :Vec<u8> = base64_decode(&b64)?;
+			if let Some(cv) = crc {
+				if cv.len() != 3 || pgp_crc24(&data).to_be_bytes()[1..] != cv[..] {
+					ecsimple_new_error!{ECPgpError,"armor checksum mismatch"}
+				}
+			}
+			return Ok((curlabel.to_string(),data));
+		}
+		if inhdr {
+			if l.is_empty() {
+				inhdr = false;
+			} else if !l.contains(':') {
+				/*no header and no blank line*/
+				inhdr = false;
+				b64.push_str(l);
+			}
+			continue;
+		}
+		if l.starts_with('=') && l.len() == 5 {
+			crc = Some(base64_decode(&l[1..])?);
+		} else {
+			b64.push_str(l);
+		}
+	}
+	ecsimple_new_error!{ECPgpError,"no armored block"}
+}
+
+/*armored or binary input*/
+fn pgp_input_packets(data :&[u8]) -> Result<Vec<ECPgpPacket>,Box<dyn Error>> {
+	let s :String = String::from_utf8_lossy(data).to_string();
+	if s.trim_start().starts_with("-----BEGIN PGP") {
+		let (_,bin) = pgp_dearmor(&s)?;
+		return pgp_packets_decode(&bin);
+	}
+	pgp_packets_decode(data)
+}
+
+/*RFC 9580 5.2.4 , lone LF to CRLF for text signatures*/
+fn pgp_canonical_text(data :&[u8]) -> Vec<u8> {
+	let mut retv :Vec<u8> = Vec::new();
+	for (i,b) in data.iter().enumerate() {
+		if *b == b'\n' && (i == 0 || data[i-1] != b'\r') {
+			retv.push(b'\r');
+		}
+		retv.push(*b);
+	}
+	retv
+}
+
+/*v4 or v6 ECDSA or ECDH public key packet*/
+#[derive(Clone)]
+pub struct ECPgpPublicKey {
+	pub version :u8,
+	pub created :u32,
+	pub algo :u8,
+	pub pubkey :ECPublicKey,
+	/*ECDH KDF hash and symmetric cipher ids*/
+	pub kdf :Option<(u8,u8)>,
+}
+
+impl ECPgpPublicKey {
+	pub fn new(pubkey :&ECPublicKey,version :u8,created :u32) -> Result<ECPgpPublicKey,Box<dyn Error>> {
+		if version != 4 && version != 6 {
+			ecsimple_new_error!{ECPgpError,"key version {} not 4 or 6",version}
+		}
+		let _ = pgp_curve_oid(&pubkey.get_group())?;
+		Ok(ECPgpPublicKey {
+			version,
+			created,
+			algo : PGP_ALGO_ECDSA,
+			pubkey : pubkey.clone(),
+			kdf : None,
+		})
+	}
+
+	pub fn new_ecdh(pubkey :&ECPublicKey,version :u8,created :u32,kdfhash :ECHashType,symalgo :u8) -> Result<ECPgpPublicKey,Box<dyn Error>> {
+		let mut retv :ECPgpPublicKey = ECPgpPublicKey::new(pubkey,version,created)?;
+		retv.algo = PGP_ALGO_ECDH;
+		retv.kdf = Some((pgp_hash_id(kdfhash)?,symalgo));
+		Ok(retv)
+	}
+
+	pub fn from_body(body :&[u8]) -> Result<ECPgpPublicKey,Box<dyn Error>> {
+		let mut r :PgpReader = PgpReader::new(body);
+		let version :u8 = r.read_u8()?;
+		if version != 4 && version != 6 {
+			ecsimple_new_error!{ECPgpError,"key version {} not 4 or 6",version}
+		}
+		let created :u32 = r.read_u32()?;
+		let algo :u8 = r.read_u8()?;
+		if algo != PGP_ALGO_ECDSA && algo != PGP_ALGO_ECDH {
+			ecsimple_new_error!{ECPgpError,"public key algorithm {} not ECDSA or ECDH",algo}
+		}
+		if version == 6 {
+			let mlen :usize = r.read_u32()? as usize;
+			if mlen != r.remain() {
+				ecsimple_new_error!{ECPgpError,"key material {} != {}",mlen,r.remain()}
+			}
+		}
+		let oidlen :usize = r.read_u8()? as usize;
+		let grp :ECGroup = pgp_group_from_oid(&r.read_bytes(oidlen)?)?;
+		let q :Vec<u8> = r.read_mpi()?;
+		if q.is_empty() || q[0] != 0x04 {
+			ecsimple_new_error!{ECPgpError,"point not uncompressed"}
+		}
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&q)?;
+		let mut kdf :Option<(u8,u8)> = None;
+		if algo == PGP_ALGO_ECDH {
+			let klen :usize = r.read_u8()? as usize;
+			let kdfparams :Vec<u8> = r.read_bytes(klen)?;
+			if kdfparams.len() != 3 || kdfparams[0] != 0x01 {
+				ecsimple_new_error!{ECPgpError,"ECDH KDF parameters not valid"}
+			}
+			kdf = Some((kdfparams[1],kdfparams[2]));
+		}
+		if r.remain() != 0 {
+			ecsimple_new_error!{ECPgpError,"{} bytes left after key",r.remain()}
+		}
+		Ok(ECPgpPublicKey {
+			version,
+			created,
+			algo,
+			pubkey,
+			kdf,
+		})
+	}
+
+	pub fn to_body(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut material :Vec<u8> = Vec::new();
+		let oid :Vec<u8> = pgp_curve_oid(&self.pubkey.get_group())?;
+		material.push(oid.len() as u8);
+		material.extend_from_slice(&oid);
+		pgp_put_mpi(&mut material,&self.pubkey.to_bin(EC_UNCOMPRESSED)?);
+		if self.algo == PGP_ALGO_ECDH {
+			let (h,s) = match self.kdf {
+				Some(v) => v,
+				None => {
+					ecsimple_new_error!{ECPgpError,"ECDH key without KDF parameters"}
+				}
+			};
+			material.extend_from_slice(&[0x03,0x01,h,s]);
+		}
+		let mut retv :Vec<u8> = vec![self.version];
+		retv.extend_from_slice(&self.created.to_be_bytes());
+		retv.push(self.algo);
+		if self.version == 6 {
+			retv.extend_from_slice(&(material.len() as u32).to_be_bytes());
+		}
+		retv.extend_from_slice(&material);
+		Ok(retv)
+	}
+
+	/*the first public key packet of binary or armored data*/
+	pub fn from_bytes(data :&[u8]) -> Result<ECPgpPublicKey,Box<dyn Error>> {
+		for (tag,body) in pgp_input_packets(data)?.iter() {
+			if *tag == PGP_TAG_PUBLIC_KEY {
+				return ECPgpPublicKey::from_body(body);
+			}
+		}
+		ecsimple_new_error!{ECPgpError,"no public key packet"}
+	}
+
+	pub fn to_packet(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		Ok(pgp_packet_encode(PGP_TAG_PUBLIC_KEY,&self.to_body()?))
+	}
+
+	/*0x99 and two octet length for v4 , 0x9b and four octet length for v6*/
+	fn hash_prefix(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let body :Vec<u8> = self.to_body()?;
+		let mut retv :Vec<u8>;
+		if self.version == 6 {
+			retv = vec![0x9b];
+			retv.extend_from_slice(&(body.len() as u32).to_be_bytes());
+		} else {
+			retv = vec![0x99];
+			retv.extend_from_slice(&(body.len() as u16).to_be_bytes());
+		}
+		retv.extend_from_slice(&body);
+		Ok(retv)
+	}
+
+	/*SHA-1 for v4 and SHA-256 for v6*/
+	pub fn fingerprint(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		if self.version == 6 {
+			return Ok(ECHashType::Sha256.digest(&self.hash_prefix()?));
+		}
+		Ok(ECHashType::Sha1.digest(&self.hash_prefix()?))
+	}
+
+	pub fn key_id(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let fp :Vec<u8> = self.fingerprint()?;
+		if self.version == 6 {
+			return Ok(fp[..8].to_vec());
+		}
+		Ok(fp[(fp.len()-8)..].to_vec())
+	}
+
+	/*key , user id and positive certification for gpg --import*/
+	pub fn to_transferable(&self,privkey :&ECPrivateKey,uid :&str,created :u32) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut prefix :Vec<u8> = self.hash_prefix()?;
+		prefix.push(0xb4);
+		prefix.extend_from_slice(&(uid.len() as u32).to_be_bytes());
+		prefix.extend_from_slice(uid.as_bytes());
+		let sig :ECPgpSignature = ECPgpSignature::sign_prefix(self,privkey,&prefix,PGP_SIG_POSITIVE_CERT,created,
+			Some(PGP_KEY_FLAG_CERTIFY | PGP_KEY_FLAG_SIGN))?;
+		let mut retv :Vec<u8> = self.to_packet()?;
+		retv.extend_from_slice(&pgp_packet_encode(PGP_TAG_USER_ID,uid.as_bytes()));
+		retv.extend_from_slice(&sig.to_packet()?);
+		Ok(retv)
+	}
+}
+
+/*v4 or v6 ECDSA signature packet*/
+#[derive(Clone)]
+pub struct ECPgpSignature {
+	pub version :u8,
+	pub sigtype :u8,
+	pub hash :ECHashType,
+	/*the raw subpacket areas*/
+	pub hashed :Vec<u8>,
+	pub unhashed :Vec<u8>,
+	pub left16 :[u8;2],
+	/*v6 only*/
+	pub salt :Vec<u8>,
+	pub r :BigInt,
+	pub s :BigInt,
+}
+
+impl ECPgpSignature {
+	/*the version , type , algorithms and hashed area with the trailer*/
+	fn hash_trailer(&self) -> Vec<u8> {
+		let mut retv :Vec<u8> = vec![self.version,self.sigtype,PGP_ALGO_ECDSA,pgp_hash_id(self.hash).unwrap_or(0)];
+		if self.version == 6 {
+			retv.extend_from_slice(&(self.hashed.len() as u32).to_be_bytes());
+		} else {
+			retv.extend_from_slice(&(self.hashed.len() as u16).to_be_bytes());
+		}
+		retv.extend_from_slice(&self.hashed);
+		let hlen :u32 = retv.len() as u32;
+		retv.push(self.version);
+		retv.push(0xff);
+		retv.extend_from_slice(&hlen.to_be_bytes());
+		retv
+	}
+
+	fn digest(&self,prefix :&[u8]) -> Vec<u8> {
+		let mut data :Vec<u8> = Vec::new();
+		if self.version == 6 {
+			data.extend_from_slice(&self.salt);
+		}
+		data.extend_from_slice(prefix);
+		data.extend_from_slice(&self.hash_trailer());
+		self.hash.digest(&data)
+	}
+
+	fn sign_prefix(key :&ECPgpPublicKey,privkey :&ECPrivateKey,prefix :&[u8],sigtype :u8,created :u32,keyflags :Option<u8>) -> Result<ECPgpSignature,Box<dyn Error>> {
+		if key.algo != PGP_ALGO_ECDSA {
+			ecsimple_new_error!{ECPgpError,"key algorithm {} can not sign",key.algo}
+		}
+		if privkey.export_pubkey().to_bin(EC_UNCOMPRESSED)? != key.pubkey.to_bin(EC_UNCOMPRESSED)? {
+			ecsimple_new_error!{ECPgpError,"private key not for the public key packet"}
+		}
+		let htype :ECHashType = ECHashType::from_order_bits(key.pubkey.get_group().info().order_bits);
+		let fp :Vec<u8> = key.fingerprint()?;
+		let mut hashed :Vec<u8> = Vec::new();
+		pgp_put_subpacket(&mut hashed,PGP_SUBPACKET_CREATION_TIME,&created.to_be_bytes());
+		let mut fpdata :Vec<u8> = vec![key.version];
+		fpdata.extend_from_slice(&fp);
+		pgp_put_subpacket(&mut hashed,PGP_SUBPACKET_ISSUER_FINGERPRINT,&fpdata);
+		if let Some(kf) = keyflags {
+			pgp_put_subpacket(&mut hashed,PGP_SUBPACKET_KEY_FLAGS,&[kf]);
+		}
+
+		let mut unhashed :Vec<u8> = Vec::new();
+		let mut salt :Vec<u8> = Vec::new();
+		if key.version == 6 {
+			salt = ecsimple_rand_bytes(pgp_salt_len(htype)?);
+		} else {
+			pgp_put_subpacket(&mut unhashed,PGP_SUBPACKET_ISSUER_KEY_ID,&key.key_id()?);
+		}
+		let mut retv :ECPgpSignature = ECPgpSignature {
+			version : key.version,
+			sigtype,
+			hash : htype,
+			hashed,
+			unhashed,
+			left16 : [0,0],
+			salt,
+			r : BigInt::from(0),
+			s : BigInt::from(0),
+		};
+		let hashv :Vec<u8> = retv.digest(prefix);
+		retv.left16 = [hashv[0],hashv[1]];
+		let sig :ECSignature = privkey.sign_base(&hashv)?;
+		retv.r = sig.r.clone();
+		retv.s = sig.s.clone();
+		Ok(retv)
+	}
+
+	/*detached signature of binary data , text canonicalizes line ends to CRLF*/
+	pub fn sign(key :&ECPgpPublicKey,privkey :&ECPrivateKey,data :&[u8],text :bool,created :u32) -> Result<ECPgpSignature,Box<dyn Error>> {
+		if text {
+			return ECPgpSignature::sign_prefix(key,privkey,&pgp_canonical_text(data),PGP_SIG_TEXT,created,None);
+		}
+		ECPgpSignature::sign_prefix(key,privkey,data,PGP_SIG_BINARY,created,None)
+	}
+
+	pub fn from_body(body :&[u8]) -> Result<ECPgpSignature,Box<dyn Error>> {
+		let mut r :PgpReader = PgpReader::new(body);
+		let version :u8 = r.read_u8()?;
+		if version != 4 && version != 6 {
+			ecsimple_new_error!{ECPgpError,"signature version {} not 4 or 6",version}
+		}
+		let sigtype :u8 = r.read_u8()?;
+		let algo :u8 = r.read_u8()?;
+		if algo != PGP_ALGO_ECDSA {
+			ecsimple_new_error!{ECPgpError,"signature algorithm {} not ECDSA",algo}
+		}
+		let hash :ECHashType = pgp_hash_from_id(r.read_u8()?)?;
+		let hashed :Vec<u8>;
+		let unhashed :Vec<u8>;
+		if version == 6 {
+			let n :usize = r.read_u32()? as usize;
+			hashed = r.read_bytes(n)?;
+			let n :usize = r.read_u32()? as usize;
+			unhashed = r.read_bytes(n)?;
+		} else {
+			let n :usize = r.read_u16()? as usize;
+			hashed = r.read_bytes(n)?;
+			let n :usize = r.read_u16()? as usize;
+			unhashed = r.read_bytes(n)?;
+		}
+		let lb :Vec<u8> = r.read_bytes(2)?;
+		let mut salt :Vec<u8> = Vec::new();
+		if version == 6 {
+			let n :usize = r.read_u8()? as usize;
+			if n != pgp_salt_len(hash)? {
+				ecsimple_new_error!{ECPgpError,"salt length {} for {}",n,hash.name()}
+			}
+			salt = r.read_bytes(n)?;
+		}
+		let rb :Vec<u8> = r.read_mpi()?;
+		let sb :Vec<u8> = r.read_mpi()?;
+		if r.remain() != 0 {
+			ecsimple_new_error!{ECPgpError,"{} bytes left after signature",r.remain()}
+		}
+		Ok(ECPgpSignature {
+			version,
+			sigtype,
+			hash,
+			hashed,
+			unhashed,
+			left16 : [lb[0],lb[1]],
+			salt,
+			r : BigInt::from_bytes_be(Sign::Plus,&rb),
+			s : BigInt::from_bytes_be(Sign::Plus,&sb),
+		})
+	}
+
+	pub fn to_body(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = vec![self.version,self.sigtype,PGP_ALGO_ECDSA,pgp_hash_id(self.hash)?];
+		if self.version == 6 {
+			retv.extend_from_slice(&(self.hashed.len() as u32).to_be_bytes());
+			retv.extend_from_slice(&self.hashed);
+			retv.extend_from_slice(&(self.unhashed.len() as u32).to_be_bytes());
+			retv.extend_from_slice(&self.unhashed);
+		} else {
+			retv.extend_from_slice(&(self.hashed.len() as u16).to_be_bytes());
+			retv.extend_from_slice(&self.hashed);
+			retv.extend_from_slice(&(self.unhashed.len() as u16).to_be_bytes());
+			retv.extend_from_slice(&self.unhashed);
+		}
+		retv.extend_from_slice(&self.left16);
+		if self.version == 6 {
+			retv.push(self.salt.len() as u8);
+			retv.extend_from_slice(&self.salt);
+		}
+		let (_,rb) = self.r.to_bytes_be();
+		let (_,sb) = self.s.to_bytes_be();
+		pgp_put_mpi(&mut retv,&rb);
+		pgp_put_mpi(&mut retv,&sb);
+		Ok(retv)
+	}
+
+	/*the first signature packet of binary or armored data*/
+	pub fn from_bytes(data :&[u8]) -> Result<ECPgpSignature,Box<dyn Error>> {
+		for (tag,body) in pgp_input_packets(data)?.iter() {
+			if *tag == PGP_TAG_SIGNATURE {
+				return ECPgpSignature::from_body(body);
+			}
+		}
+		ecsimple_new_error!{ECPgpError,"no signature packet"}
+	}
+
+	pub fn to_packet(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		Ok(pgp_packet_encode(PGP_TAG_SIGNATURE,&self.to_body()?))
+	}
+
+	pub fn to_armored(&self) -> Result<String,Box<dyn Error>> {
+		Ok(pgp_armor(&self.to_packet()?,PGP_ARMOR_SIGNATURE))
+	}
+
+	/*(type , critical , data) of the subpackets in the area*/
+	fn subpackets(area :&[u8]) -> Result<Vec<PgpSubpacket>,Box<dyn Error>> {
+		let mut retv :Vec<PgpSubpacket> = Vec::new();
+		let mut r :PgpReader = PgpReader::new(area);
+		while r.remain() > 0 {
+			let len :usize = r.read_length()?;
+			if len == 0 {
+				ecsimple_new_error!{ECPgpError,"empty subpacket"}
+			}
+			let stype :u8 = r.read_u8()?;
+			retv.push((stype & !PGP_SUBPACKET_CRITICAL,(stype & PGP_SUBPACKET_CRITICAL) != 0,r.read_bytes(len - 1)?));
+		}
+		Ok(retv)
+	}
+
+	fn find_subpacket(&self,stype :u8,hashedonly :bool) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		for (t,_,d) in ECPgpSignature::subpackets(&self.hashed)?.iter() {
+			if *t == stype {
+				return Ok(Some(d.clone()));
+			}
+		}
+		if !hashedonly {
+			for (t,_,d) in ECPgpSignature::subpackets(&self.unhashed)?.iter() {
+				if *t == stype {
+					return Ok(Some(d.clone()));
+				}
+			}
+		}
+		Ok(None)
+	}
+
+	pub fn creation_time(&self) -> Result<Option<u32>,Box<dyn Error>> {
+		let d = self.find_subpacket(PGP_SUBPACKET_CREATION_TIME,true)?;
+		if d.is_none() {
+			return Ok(None);
+		}
+		let d :Vec<u8> = d.unwrap();
+		if d.len() != 4 {
+			ecsimple_new_error!{ECPgpError,"creation time length {}",d.len()}
+		}
+		Ok(Some(u32::from_be_bytes([d[0],d[1],d[2],d[3]])))
+	}
+
+	/*without the key version octet*/
+	pub fn issuer_fingerprint(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		let d = self.find_subpacket(PGP_SUBPACKET_ISSUER_FINGERPRINT,false)?;
+		if d.is_none() || d.as_ref().unwrap().len() < 2 {
+			return Ok(None);
+		}
+		Ok(Some(d.unwrap()[1..].to_vec()))
+	}
+
+	pub fn issuer_key_id(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		self.find_subpacket(PGP_SUBPACKET_ISSUER_KEY_ID,false)
+	}
+
+	/*binary or text signature over data , false on a bad signature*/
+	pub fn verify(&self,key :&ECPgpPublicKey,data :&[u8]) -> Result<bool,Box<dyn Error>> {
+		if key.algo != PGP_ALGO_ECDSA || key.version != self.version {
+			ecsimple_new_error!{ECPgpError,"v{} signature for v{} key algorithm {}",self.version,key.version,key.algo}
+		}
+		let hashv :Vec<u8>;
+		if self.sigtype == PGP_SIG_BINARY {
+			hashv = self.digest(data);
+		} else if self.sigtype == PGP_SIG_TEXT {
+			hashv = self.digest(&pgp_canonical_text(data));
+		} else {
+			ecsimple_new_error!{ECPgpError,"signature type 0x{:02x} not for documents",self.sigtype}
+		}
+		/*RFC 9580 5.2.3.7 , as gpg an unknown critical subpacket makes the signature bad*/
+		for (t,c,_) in ECPgpSignature::subpackets(&self.hashed)?.iter() {
+			if *c && !PGP_SUBPACKET_KNOWN.contains(t) {
+				ecsimple_log_trace!("unknown critical subpacket {}",t);
+				return Ok(false);
+			}
+		}
+		if hashv[0..2] != self.left16[..] {
+			ecsimple_log_trace!("left 16 bits {:02x}{:02x} mismatch",self.left16[0],self.left16[1]);
+			return Ok(false);
+		}
+		let sig :ECSignature = ECSignature::new(&self.r,&self.s);
+		key.pubkey.verify_base(&sig,&hashv)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	const PGP_CONTENT :&[u8] = b"hello ecsimple\n";
+
+	/*gpg --quick-gen-key nistp256 sign*/
+	const GPG_P256_KEY :&str = "
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mFIEatXoqRMIKoZIzj0DAQcCAwRisfaCpI0plD9+6LkieqFfK9ee+Jeq4C7F5XuZ
+HWr9iU/SKrgpij+BbaOHp1ucUQrOJxrOPOwNtLdMxCf88BVctB9lY3NpbXBsZSA8
+ZWNzaW1wbGVAZXhhbXBsZS5jb20+iJAEExMIADgWIQSqDOJL8CbUoAwfTGy7RZyL
+OMjgswUCatXoqQIbAwULCQgHAgYVCgkICwIEFgIDAQIeAQIXgAAKCRC7RZyLOMjg
+s0vjAQD67gMYJYARZH5RQY9jCPfk0dezaDUEW98e3nly0UOCrgEAyj1HvK3pcKGn
+0Yjb1a0L244DmCkKCZLe/pHE4nOeXmw=
+=tKVk
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+	/*gpg --digest-algo SHA256 --detach-sign --armor*/
+	const GPG_P256_SIG :&str = "
+-----BEGIN PGP SIGNATURE-----
+
+iHUEABMIAB0WIQSqDOJL8CbUoAwfTGy7RZyLOMjgswUCatXoqQAKCRC7RZyLOMjg
+sznlAP9cO+g6rE+OpAD2/AP2GbfsTto38a11X4DCK/wHLTYkEAD+NPj3QS/een7i
+7exxuzIww5q5oZt76gQDO8vFYJ8StfA=
+=RlEs
+-----END PGP SIGNATURE-----
+";
+
+	/*gpg --sig-notation '!crit@ecsimple.example=1' , a critical notation subpacket*/
+	const GPG_P256_CRITICAL_SIG :&str = "
+-----BEGIN PGP SIGNATURE-----
+
+iJUEABMIAD0WIQSqDOJL8CbUoAwfTGy7RZyLOMjgswUCatXorR+UgAAAAAAVAAFj
+cml0QGVjc2ltcGxlLmV4YW1wbGUxAAoJELtFnIs4yOCzEhYBAOdWS/tujDzgVvER
+Lxq+4INNe2HXXMAJ8CTeiNXPtqzPAP4okxC/NkcrAkewpjhAib1TrtMiAggAz9w/
+ED+djDJEGQ==
+=m/VG
+-----END PGP SIGNATURE-----
+";
+
+	/*gpg --sig-notation 'note@ecsimple.example=1' , not critical*/
+	const GPG_P256_NOTATION_SIG :&str = "
+-----BEGIN PGP SIGNATURE-----
+
+iJUEABMIAD0WIQSqDOJL8CbUoAwfTGy7RZyLOMjgswUCatXorR8UgAAAAAAVAAFu
+b3RlQGVjc2ltcGxlLmV4YW1wbGUxAAoJELtFnIs4yOCzqwcBAJVYuWAukte0rwvs
+OXjbdkz6fHJIzjoSirNK9Vop9EJ5AQCi6fKs9tcq+hAguyUPnrybUiJKxcwY/pR9
+1czF33WZjA==
+=p5sQ
+-----END PGP SIGNATURE-----
+";
+
+	#[test]
+	fn test_gpg_p256_signature() {
+		let key :ECPgpPublicKey = ECPgpPublicKey::from_bytes(GPG_P256_KEY.as_bytes()).unwrap();
+		assert_eq!(key.fingerprint().unwrap(),Vec::from_hex("AA0CE24BF026D4A00C1F4C6CBB459C8B38C8E0B3").unwrap());
+		assert_eq!(key.key_id().unwrap(),Vec::from_hex("BB459C8B38C8E0B3").unwrap());
+		let sig :ECPgpSignature = ECPgpSignature::from_bytes(GPG_P256_SIG.as_bytes()).unwrap();
+		assert_eq!(sig.hash,ECHashType::Sha256);
+		assert_eq!(sig.issuer_fingerprint().unwrap(),Some(key.fingerprint().unwrap()));
+		assert!(sig.verify(&key,PGP_CONTENT).unwrap());
+		assert!(!sig.verify(&key,b"hello ecsimple").unwrap());
+	}
+
+	#[test]
+	fn test_gpg_critical_subpacket() {
+		let key :ECPgpPublicKey = ECPgpPublicKey::from_bytes(GPG_P256_KEY.as_bytes()).unwrap();
+		let sig :ECPgpSignature = ECPgpSignature::from_bytes(GPG_P256_NOTATION_SIG.as_bytes()).unwrap();
+		assert!(sig.verify(&key,PGP_CONTENT).unwrap());
+		let sig :ECPgpSignature = ECPgpSignature::from_bytes(GPG_P256_CRITICAL_SIG.as_bytes()).unwrap();
+		let subs = ECPgpSignature::subpackets(&sig.hashed).unwrap();
+		assert!(subs.iter().any(|(t,c,_)| *t == 20 && *c));
+		assert!(!sig.verify(&key,PGP_CONTENT).unwrap());
+	}
+}
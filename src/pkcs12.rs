@@ -0,0 +1,466 @@
+use crate::consts::*;
+use crate::ecasn1::*;
+use crate::hashop::*;
+use crate::kdf::*;
+use crate::keys::*;
+use crate::pbes2::*;
+use crate::randop::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Any,Asn1Integer,Asn1OctData,Asn1BMPString};
+use asn1obj::complex::{Asn1Set,Asn1Imp};
+use asn1obj::asn1impl::Asn1Op;
+use std::error::Error;
+
+ecsimple_error_class!{ECPkcs12Error}
+
+const PKCS12_VERSION :i64 = 3;
+/*RFC 7292 B.3 id for the mac key*/
+const PKCS12_MAC_ID :u8 = 3;
+/*the openssl 3 defaults of pkcs12 -export*/
+pub const PKCS12_DEFAULT_ITERATIONS :u64 = 2048;
+pub const PKCS12_DEFAULT_MAC_SALT_LEN :usize = 8;
+/*refuse the mac iterations that would keep us busy for hours , as PBES2_MAX_ITERATIONS*/
+pub const PKCS12_MAX_MAC_ITERATIONS :u64 = 10000000;
+
+#[derive(Clone,Debug)]
+pub struct ECPkcs12Params {
+	pub key_enc :ECPbes2Params,
+	/*None to put the certificates in plain data*/
+	pub cert_enc :Option<ECPbes2Params>,
+	pub mac_hash :ECHashType,
+	pub mac_iterations :u64,
+	pub mac_salt_len :usize,
+}
+
+/*PBES2 PBKDF2 hmacWithSHA256 aes-256-cbc and HMAC-SHA256 mac , as openssl pkcs12 -export*/
+impl Default for ECPkcs12Params {
+	fn default() -> Self {
+		let pbe :ECPbes2Params = ECPbes2Params::new_pbkdf2(ECHashType::Sha256,PKCS12_DEFAULT_ITERATIONS,ECPbeCipher::Aes256Cbc);
+		ECPkcs12Params {
+			key_enc : pbe.clone(),
+			cert_enc : Some(pbe),
+			mac_hash : ECHashType::Sha256,
+			mac_iterations : PKCS12_DEFAULT_ITERATIONS,
+			mac_salt_len : PKCS12_DEFAULT_MAC_SALT_LEN,
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct ECPkcs12 {
+	pub privkey :Option<ECPrivateKey>,
+	/*certificates in DER , the first one is the key's*/
+	pub certs :Vec<Vec<u8>>,
+	pub friendly_name :Option<String>,
+	pub local_key_id :Option<Vec<u8>>,
+}
+
+/*BMPString with the two zero bytes end , empty password gives only the end*/
+fn pkcs12_password_bmp(password :&str) -> Vec<u8> {
+	let mut retv :Vec<u8> = Vec::new();
+	for c in password.encode_utf16() {
+		retv.extend_from_slice(&c.to_be_bytes());
+	}
+	retv.extend_from_slice(&[0x0,0x0]);
+	retv
+}
+
+fn pkcs12_mac(htype :ECHashType,password :&str,salt :&[u8],iterations :u64,data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let key :Vec<u8> = pkcs12_kdf(htype,&pkcs12_password_bmp(password),salt,PKCS12_MAC_ID,iterations,htype.output_size())?;
+	Ok(hmac_data(htype,&key,data))
+}
+
+fn any_to_der(anyv :&Option<Asn1Any>) -> Result<Vec<u8>,Box<dyn Error>> {
+	if anyv.is_none() {
+		ecsimple_new_error!{ECPkcs12Error,"no content"}
+	}
+	anyv.as_ref().unwrap().encode_asn1()
+}
+
+fn form_content_info(oid :&str,content :&[u8]) -> Result<Asn1Pkcs7ContentInfo,Box<dyn Error>> {
+	let mut info :Asn1Pkcs7ContentInfo = Asn1Pkcs7ContentInfo::init_asn1();
+	let mut elem :Asn1Pkcs7ContentInfoElem = Asn1Pkcs7ContentInfoElem::init_asn1();
+	elem.contenttype.set_value(oid)?;
+	elem.content.val = Some(der_to_any(content)?);
+	info.elem.val.push(elem);
+	Ok(info)
+}
+
+fn form_data_info(data :&[u8]) -> Result<Asn1Pkcs7ContentInfo,Box<dyn Error>> {
+	let mut octdata :Asn1OctData = Asn1OctData::init_asn1();
+	octdata.data = data.to_vec();
+	return form_content_info(PKCS7_DATA_OID,&octdata.encode_asn1()?);
+}
+
+fn form_encrypted_data_info(data :&[u8],password :&str,params :&ECPbes2Params) -> Result<Asn1Pkcs7ContentInfo,Box<dyn Error>> {
+	let (algor,encdata) = pbes2_encrypt_algor(data,password.as_bytes(),params)?;
+	let mut encinfo :Asn1Pkcs7EncryptedContentInfo = Asn1Pkcs7EncryptedContentInfo::init_asn1();
+	let mut encinfoelem :Asn1Pkcs7EncryptedContentInfoElem = Asn1Pkcs7EncryptedContentInfoElem::init_asn1();
+	let mut enccontent :Asn1Imp<Asn1OctData,0> = Asn1Imp::init_asn1();
+	encinfoelem.contenttype.set_value(PKCS7_DATA_OID)?;
+	encinfoelem.encalgor = algor;
+	enccontent.val.data = encdata;
+	encinfoelem.enccontent.val = Some(enccontent);
+	encinfo.elem.val.push(encinfoelem);
+
+	let mut encdatav :Asn1Pkcs7EncryptedData = Asn1Pkcs7EncryptedData::init_asn1();
+	let mut encdataelem :Asn1Pkcs7EncryptedDataElem = Asn1Pkcs7EncryptedDataElem::init_asn1();
+	encdataelem.version.val = 0;
+	encdataelem.enccontentinfo = encinfo;
+	encdatav.elem.val.push(encdataelem);
+	return form_content_info(PKCS7_ENCRYPTED_DATA_OID,&encdatav.encode_asn1()?);
+}
+
+/*the SafeContents der from data or encryptedData*/
+fn decode_content_info(info :&Asn1Pkcs7ContentInfo,password :&str) -> Result<Vec<u8>,Box<dyn Error>> {
+	if info.elem.val.len() != 1 {
+		ecsimple_new_error!{ECPkcs12Error,"ContentInfo elem {} != 1",info.elem.val.len()}
+	}
+	let elem :&Asn1Pkcs7ContentInfoElem = &info.elem.val[0];
+	let oid :String = elem.contenttype.get_value();
+	let content :Vec<u8> = any_to_der(&elem.content.val)?;
+	if oid == PKCS7_DATA_OID {
+		let mut octdata :Asn1OctData = Asn1OctData::init_asn1();
+		let _ = octdata.decode_asn1(&content)?;
+		return Ok(octdata.data.clone());
+	} else if oid == PKCS7_ENCRYPTED_DATA_OID {
+		let mut encdatav :Asn1Pkcs7EncryptedData = Asn1Pkcs7EncryptedData::init_asn1();
+		let _ = encdatav.decode_asn1(&content)?;
+		if encdatav.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPkcs12Error,"EncryptedData elem {} != 1",encdatav.elem.val.len()}
+		}
+		let encinfo :&Asn1Pkcs7EncryptedContentInfo = &encdatav.elem.val[0].enccontentinfo;
+		if encinfo.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPkcs12Error,"EncryptedContentInfo elem {} != 1",encinfo.elem.val.len()}
+		}
+		let encinfoelem :&Asn1Pkcs7EncryptedContentInfoElem = &encinfo.elem.val[0];
+		if encinfoelem.enccontent.val.is_none() {
+			ecsimple_new_error!{ECPkcs12Error,"no encryptedContent"}
+		}
+		let encdata :Vec<u8> = encinfoelem.enccontent.val.as_ref().unwrap().val.data.clone();
+		let (data,_) = pbes2_decrypt_algor(&encinfoelem.encalgor,&encdata,password.as_bytes())?;
+		return Ok(data);
+	}
+	ecsimple_new_error!{ECPkcs12Error,"unsupported content type [{}]",oid}
+}
+
+fn form_attribute(oid :&str,setder :&[u8]) -> Result<Asn1X509Attribute,Box<dyn Error>> {
+	let mut attr :Asn1X509Attribute = Asn1X509Attribute::init_asn1();
+	let mut attrelem :Asn1X509AttributeElem = Asn1X509AttributeElem::init_asn1();
+	attrelem.object.set_value(oid)?;
+	attrelem.set = der_to_any(setder)?;
+	attr.elem.val.push(attrelem);
+	Ok(attr)
+}
+
+fn form_bag_attributes(name :&Option<String>,keyid :&Option<Vec<u8>>) -> Result<Option<Asn1Set<Asn1X509Attribute>>,Box<dyn Error>> {
+	let mut attrs :Asn1Set<Asn1X509Attribute> = Asn1Set::init_asn1();
+	if name.is_some() {
+		let mut setv :Asn1Set<Asn1BMPString> = Asn1Set::init_asn1();
+		let mut bmp :Asn1BMPString = Asn1BMPString::init_asn1();
+		bmp.val = name.as_ref().unwrap().clone();
+		setv.val.push(bmp);
+		attrs.val.push(form_attribute(PKCS9_FRIENDLY_NAME_OID,&setv.encode_asn1()?)?);
+	}
+	if keyid.is_some() {
+		let mut setv :Asn1Set<Asn1OctData> = Asn1Set::init_asn1();
+		let mut octdata :Asn1OctData = Asn1OctData::init_asn1();
+		octdata.data = keyid.as_ref().unwrap().clone();
+		setv.val.push(octdata);
+		attrs.val.push(form_attribute(PKCS9_LOCAL_KEY_ID_OID,&setv.encode_asn1()?)?);
+	}
+	if attrs.val.is_empty() {
+		return Ok(None);
+	}
+	Ok(Some(attrs))
+}
+
+/*(friendlyName , localKeyId)*/
+type Pkcs12BagAttrs = (Option<String>,Option<Vec<u8>>);
+
+/*friendlyName and localKeyId of the bag , others ignored*/
+fn parse_bag_attributes(attrs :&Option<Asn1Set<Asn1X509Attribute>>) -> Result<Pkcs12BagAttrs,Box<dyn Error>> {
+	let mut name :Option<String> = None;
+	let mut keyid :Option<Vec<u8>> = None;
+	let attrs :&Asn1Set<Asn1X509Attribute> = match attrs.as_ref() {
+		Some(a) => a,
+		None => return Ok((name,keyid)),
+	};
+	for attr in attrs.val.iter() {
+		for attrelem in attr.elem.val.iter() {
+			let oid :String = attrelem.object.get_value();
+			let setder :Vec<u8> = attrelem.set.encode_asn1()?;
+			if oid == PKCS9_FRIENDLY_NAME_OID {
+				let mut setv :Asn1Set<Asn1BMPString> = Asn1Set::init_asn1();
+				let _ = setv.decode_asn1(&setder)?;
+				if !setv.val.is_empty() {
+					name = Some(setv.val[0].val.clone());
+				}
+			} else if oid == PKCS9_LOCAL_KEY_ID_OID {
+				let mut setv :Asn1Set<Asn1OctData> = Asn1Set::init_asn1();
+				let _ = setv.decode_asn1(&setder)?;
+				if !setv.val.is_empty() {
+					keyid = Some(setv.val[0].data.clone());
+				}
+			}
+		}
+	}
+	Ok((name,keyid))
+}
+
+fn form_safe_bag(oid :&str,value :&[u8],attrs :Option<Asn1Set<Asn1X509Attribute>>) -> Result<Asn1Pkcs12SafeBag,Box<dyn Error>> {
+	let mut bag :Asn1Pkcs12SafeBag = Asn1Pkcs12SafeBag::init_asn1();
+	let mut bagelem :Asn1Pkcs12SafeBagElem = Asn1Pkcs12SafeBagElem::init_asn1();
+	bagelem.bagid.set_value(oid)?;
+	bagelem.bagvalue.val = Some(der_to_any(value)?);
+	bagelem.attributes.val = attrs;
+	bag.elem.val.push(bagelem);
+	Ok(bag)
+}
+
+impl ECPkcs12 {
+	/*localKeyId is the SHA-1 of the first certificate as openssl does*/
+	pub fn new(privkey :&ECPrivateKey,certs :&[Vec<u8>]) -> ECPkcs12 {
+		let mut keyid :Option<Vec<u8>> = None;
+		if !certs.is_empty() {
+			keyid = Some(ECHashType::Sha1.digest(&certs[0]));
+		}
+		ECPkcs12 {
+			privkey : Some(privkey.clone()),
+			certs : certs.to_vec(),
+			friendly_name : None,
+			local_key_id : keyid,
+		}
+	}
+
+	/*MacData required , the password is not checked otherwise*/
+	pub fn from_der(dercode :&[u8],password :&str) -> Result<ECPkcs12,Box<dyn Error>> {
+		ECPkcs12::from_der_mac(dercode,password,false)
+	}
+
+	/*also accept the files without MacData , like openssl pkcs12 -nomac*/
+	pub fn from_der_without_mac(dercode :&[u8],password :&str) -> Result<ECPkcs12,Box<dyn Error>> {
+		ECPkcs12::from_der_mac(dercode,password,true)
+	}
+
+	fn from_der_mac(dercode :&[u8],password :&str,allownomac :bool) -> Result<ECPkcs12,Box<dyn Error>> {
+		let mut pfx :Asn1Pkcs12 = Asn1Pkcs12::init_asn1();
+		let _ = pfx.decode_asn1(dercode)?;
+		if pfx.elem.val.len() != 1 {
+			ecsimple_new_error!{ECPkcs12Error,"PFX elem {} != 1",pfx.elem.val.len()}
+		}
+		let pfxelem :Asn1Pkcs12Elem = pfx.elem.val[0].clone();
+		if pfxelem.version.val != PKCS12_VERSION {
+			ecsimple_new_error!{ECPkcs12Error,"PFX version {} != {}",pfxelem.version.val,PKCS12_VERSION}
+		}
+		if pfxelem.authsafe.elem.val.len() != 1 || pfxelem.authsafe.elem.val[0].contenttype.get_value() != PKCS7_DATA_OID {
+			ecsimple_new_error!{ECPkcs12Error,"authSafe not data , public key integrity mode not supported"}
+		}
+		let authdata :Vec<u8> = decode_content_info(&pfxelem.authsafe,password)?;
+
+		if let Some(macdata) = pfxelem.macdata.val.as_ref() {
+			if macdata.elem.val.len() != 1 {
+				ecsimple_new_error!{ECPkcs12Error,"MacData elem {} != 1",macdata.elem.val.len()}
+			}
+			let macelem :&Asn1Pkcs12MacDataElem = &macdata.elem.val[0];
+			if macelem.mac.elem.val.len() != 1 {
+				ecsimple_new_error!{ECPkcs12Error,"DigestInfo elem {} != 1",macelem.mac.elem.val.len()}
+			}
+			let (hashoid,_) = get_algor_elem(&macelem.mac.elem.val[0].algor)?;
+			let htype :ECHashType = ECHashType::from_oid(&hashoid)?;
+			let mut iterations :u64 = 1;
+			if let Some(ivobj) = macelem.iterations.val.as_ref() {
+				let iv :i64 = ivobj.val;
+				if iv <= 0 || (iv as u64) > PKCS12_MAX_MAC_ITERATIONS {
+					ecsimple_new_error!{ECPkcs12Error,"mac iterations {} invalid",iv}
+				}
+				iterations = iv as u64;
+			}
+			let calcmac :Vec<u8> = pkcs12_mac(htype,password,&macelem.macsalt.data,iterations,&authdata)?;
+			let gotmac :&[u8] = &macelem.mac.elem.val[0].digest.data;
+			if calcmac.len() != gotmac.len() {
+				ecsimple_new_error!{ECPkcs12Error,"mac len {} != {}",gotmac.len(),calcmac.len()}
+			}
+			let mut diff :u8 = 0;
+			for i in 0..calcmac.len() {
+				diff |= calcmac[i] ^ gotmac[i];
+			}
+			if diff != 0 {
+				ecsimple_new_error!{ECPkcs12Error,"mac verify failed , maybe wrong password"}
+			}
+		} else if !allownomac {
+			ecsimple_new_error!{ECPkcs12Error,"no MacData , use from_der_without_mac to accept it"}
+		}
+
+		let mut authsafe :Asn1Pkcs12AuthSafe = Asn1Pkcs12AuthSafe::init_asn1();
+		let _ = authsafe.decode_asn1(&authdata)?;
+		let mut retv :ECPkcs12 = ECPkcs12 {
+			privkey : None,
+			certs : Vec::new(),
+			friendly_name : None,
+			local_key_id : None,
+		};
+		let mut keyidx :Option<usize> = None;
+		let mut certids :Vec<Option<Vec<u8>>> = Vec::new();
+		for info in authsafe.safes.val.iter() {
+			let safedata :Vec<u8> = decode_content_info(info,password)?;
+			let mut safes :Asn1Pkcs12SafeContents = Asn1Pkcs12SafeContents::init_asn1();
+			let _ = safes.decode_asn1(&safedata)?;
+			for bag in safes.bags.val.iter() {
+				if bag.elem.val.len() != 1 {
+					ecsimple_new_error!{ECPkcs12Error,"SafeBag elem {} != 1",bag.elem.val.len()}
+				}
+				let bagelem :&Asn1Pkcs12SafeBagElem = &bag.elem.val[0];
+				let bagid :String = bagelem.bagid.get_value();
+				let value :Vec<u8> = any_to_der(&bagelem.bagvalue.val)?;
+				let (name,keyid) = parse_bag_attributes(&bagelem.attributes.val)?;
+				if bagid == PKCS12_SHROUDED_KEY_BAG_OID || bagid == PKCS12_KEY_BAG_OID {
+					if retv.privkey.is_some() {
+						ecsimple_new_error!{ECPkcs12Error,"more than one private key"}
+					}
+					if bagid == PKCS12_SHROUDED_KEY_BAG_OID {
+						retv.privkey = Some(ECPrivateKey::from_encrypted_pkcs8(&value,password.as_bytes())?);
+					} else {
+						retv.privkey = Some(ECPrivateKey::from_pkcs8_der(&value)?);
+					}
+					retv.friendly_name = name;
+					retv.local_key_id = keyid;
+				} else if bagid == PKCS12_CERT_BAG_OID {
+					let mut certbag :Asn1Pkcs12CertBag = Asn1Pkcs12CertBag::init_asn1();
+					let _ = certbag.decode_asn1(&value)?;
+					if certbag.elem.val.len() != 1 {
+						ecsimple_new_error!{ECPkcs12Error,"CertBag elem {} != 1",certbag.elem.val.len()}
+					}
+					let certelem :&Asn1Pkcs12CertBagElem = &certbag.elem.val[0];
+					if certelem.certid.get_value() != PKCS9_X509_CERTIFICATE_OID || certelem.certvalue.val.is_none() {
+						ecsimple_log_trace!("skip cert type [{}]",certelem.certid.get_value());
+						continue;
+					}
+					retv.certs.push(certelem.certvalue.val.as_ref().unwrap().data.clone());
+					certids.push(keyid);
+				} else {
+					ecsimple_log_trace!("skip bag [{}]",bagid);
+				}
+			}
+		}
+
+		/*put the certificate matching localKeyId first*/
+		if retv.local_key_id.is_some() {
+			keyidx = certids.iter().position(|c| *c == retv.local_key_id);
+		}
+		if let Some(idx) = keyidx {
+			if idx != 0 {
+				let c :Vec<u8> = retv.certs.remove(idx);
+				retv.certs.insert(0,c);
+			}
+		}
+		Ok(retv)
+	}
+
+	pub fn to_der(&self,password :&str,params :&ECPkcs12Params) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut authsafe :Asn1Pkcs12AuthSafe = Asn1Pkcs12AuthSafe::init_asn1();
+		if !self.certs.is_empty() {
+			let mut safes :Asn1Pkcs12SafeContents = Asn1Pkcs12SafeContents::init_asn1();
+			for i in 0..self.certs.len() {
+				let mut certbag :Asn1Pkcs12CertBag = Asn1Pkcs12CertBag::init_asn1();
+				let mut certelem :Asn1Pkcs12CertBagElem = Asn1Pkcs12CertBagElem::init_asn1();
+				let mut octdata :Asn1OctData = Asn1OctData::init_asn1();
+				certelem.certid.set_value(PKCS9_X509_CERTIFICATE_OID)?;
+				octdata.data = self.certs[i].clone();
+				certelem.certvalue.val = Some(octdata);
+				certbag.elem.val.push(certelem);
+				let mut attrs :Option<Asn1Set<Asn1X509Attribute>> = None;
+				if i == 0 {
+					attrs = form_bag_attributes(&self.friendly_name,&self.local_key_id)?;
+				}
+				safes.bags.val.push(form_safe_bag(PKCS12_CERT_BAG_OID,&certbag.encode_asn1()?,attrs)?);
+			}
+			let safedata :Vec<u8> = safes.encode_asn1()?;
+			if let Some(certenc) = params.cert_enc.as_ref() {
+				authsafe.safes.val.push(form_encrypted_data_info(&safedata,password,certenc)?);
+			} else {
+				authsafe.safes.val.push(form_data_info(&safedata)?);
+			}
+		}
+
+		if let Some(privkey) = self.privkey.as_ref() {
+			let mut safes :Asn1Pkcs12SafeContents = Asn1Pkcs12SafeContents::init_asn1();
+			let keyder :Vec<u8> = privkey.to_encrypted_pkcs8(password.as_bytes(),&params.key_enc)?;
+
+			let attrs :Option<Asn1Set<Asn1X509Attribute>> = form_bag_attributes(&self.friendly_name,&self.local_key_id)?;
+			safes.bags.val.push(form_safe_bag(PKCS12_SHROUDED_KEY_BAG_OID,&keyder,attrs)?);
+			authsafe.safes.val.push(form_data_info(&safes.encode_asn1()?)?);
+		}
+
+		let authdata :Vec<u8> = authsafe.encode_asn1()?;
+		let salt :Vec<u8> = ecsimple_rand_bytes(params.mac_salt_len);
+		let macv :Vec<u8> = pkcs12_mac(params.mac_hash,password,&salt,params.mac_iterations,&authdata)?;
+		let mut digestinfo :Asn1X509SigElem = Asn1X509SigElem::init_asn1();
+		digestinfo.algor = form_algor(params.mac_hash.oid(),Some(vec![0x05,0x00]))?;
+		digestinfo.digest.data = macv;
+		let mut macdata :Asn1Pkcs12MacData = Asn1Pkcs12MacData::init_asn1();
+		let mut macelem :Asn1Pkcs12MacDataElem = Asn1Pkcs12MacDataElem::init_asn1();
+		let mut iterations :Asn1Integer = Asn1Integer::init_asn1();
+		macelem.mac.elem.val.push(digestinfo);
+		macelem.macsalt.data = salt;
+		iterations.val = params.mac_iterations as i64;
+		macelem.iterations.val = Some(iterations);
+		macdata.elem.val.push(macelem);
+
+		let mut pfx :Asn1Pkcs12 = Asn1Pkcs12::init_asn1();
+		let mut pfxelem :Asn1Pkcs12Elem = Asn1Pkcs12Elem::init_asn1();
+		pfxelem.version.val = PKCS12_VERSION;
+		pfxelem.authsafe = form_data_info(&authdata)?;
+		pfxelem.macdata.val = Some(macdata);
+		pfx.elem.val.push(pfxelem);
+		pfx.encode_asn1()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pem::*;
+	use num_bigint::{BigInt,Sign};
+	use hex::FromHex;
+
+	/*openssl pkcs12 -export of a prime256v1 key and its self-signed certificate with password ecsimple*/
+	const OPENSSL_PRIV_HEX :&str = "79a015553b785743c8161d773974f09d902c92ed089e15ddc041aa9614296213";
+	const OPENSSL_P12 :&str = "MIIEHAIBAzCCA9IGCSqGSIb3DQEHAaCCA8MEggO/MIIDuzCCAmoGCSqGSIb3DQEHBqCCAlswggJXAgEAMIICUAYJKoZIhvcNAQcBMF8GCSqGSIb3DQEFDTBSMDEGCSqGSIb3DQEFDDAkBBBwbyAvCP124e5EkpIACJ4xAgIIADAMBggqhkiG9w0CCQUAMB0GCWCGSAFlAwQBKgQQOdgIrdFTyuNMP2H9Az6rI4CCAeBvM20ZGH73BqOUxcrYy+oYA3sSu015cDMKEKGEx8vr7jo6toovgBScWfCO9elGLzm41aKMuAiRGGEZEcJh0zA9YJRxBl1iL1FUiXJC4iDiADxAvd+T67bdnawTAbbzZhQhXphFpehCk0IhhHiMZOeBWMn4zQLtsljAlYpLQXNSEVRQrxVUxrOWkPoSoUgWoACvTP9yn5/U85PCUtK7CpH7Rf2386vXZuiPoZXZnFXrf9clC9rXOwJMUjwHJQyQJ2S22SCwrYMmT33OUTvg6T8pfLMRZXJW63qhBwpTMG+l8/DuVTAkOPgcsvGfU7g+kzDsm+96S9MEbyROw+SRMcsk/uCyuBqxvLyuJYr//fRhH2fN8DSxKAjIvnFWLklYtJO7GoCT1cUC/dKPjBLS+qOIEqAqiGyUuOWDoPqhcCBo1DBy90tmnwVrs0Pkso+ZOS0RPL234gIvFzQCfupEHDO/cfeA4lZZ43BBtguw2sCLNEaxxDprN/wD2K09vPrlZVFk5pYU2msI5pChBK5eJ6kcvxPTXBraBCUXo9uQ9wXKu28MXFg9V1ddMEh2GZRst0S7UIgWxKD1LeI+OztzV4yt6pOBZOv/uH716Jw9UUFQJiNZzvnx0f1EJHmdkq8QfegwggFJBgkqhkiG9w0BBwGgggE6BIIBNjCCATIwggEuBgsqhkiG9w0BDAoBAqCB9zCB9DBfBgkqhkiG9w0BBQ0wUjAxBgkqhkiG9w0BBQwwJAQQd4/iP/KVMFq29WWc5xX0LgICCAAwDAYIKoZIhvcNAgkFADAdBglghkgBZQMEASoEEBkXZorLeuvlm4tXpyv69E0EgZB6qa9Oo7yaxZrRiG6EZwb+7t+BJwxvPGw+yAVDjrrN1BmAYVGf/X8RQV8+yPIpVuJXpOxJwk/1334iLJiBko8Q5bpEkA8eatJ70XnXzYo8RNtbeJuQjbId9GOHv//lkrmQyyb2UuW8Ctr9ekAoj/UZjzLVRjS6LZCQElO742CJOTbDF1CqsSwhu015e0fYN/8xJTAjBgkqhkiG9w0BCRUxFgQUiK0WV5/tHbhimec8+L10nSs3zN8wQTAxMA0GCWCGSAFlAwQCAQUABCDHGTKnNmwizGoYL3l0iTDDX4tk0eIM1Pe5u3ngFhDWTgQI5ncmOxI2iY8CAggA";
+	/*the same with -nomac*/
+	const OPENSSL_P12_NOMAC :&str = "MIIDWAIBAzCCA1EGCSqGSIb3DQEHAaCCA0IEggM+MIIDOjCCAekGCSqGSIb3DQEHAaCCAdoEggHWMIIB0jCCAc4GCyqGSIb3DQEMCgEDoIIBljCCAZIGCiqGSIb3DQEJFgGgggGCBIIBfjCCAXowggEhoAMCAQICFEphAvH7YvzEZdup8uzrLWspBZM2MAoGCCqGSM49BAMCMBMxETAPBgNVBAMMCGVjc2ltcGxlMB4XDTI2MTAxOTA5MTE0MloXDTM2MTAxNjA5MTE0MlowEzERMA8GA1UEAwwIZWNzaW1wbGUwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAARsOGkV/vzBfvUxRISot/efoeuGYKd0TJs5sJfv4UZUi52pz5JzpKlc1IF5O5GIjbDWhGbPoUa7uwAVqUD8PTJIo1MwUTAdBgNVHQ4EFgQUCpsP/T1VyckuV/osMQ22jN37h5swHwYDVR0jBBgwFoAUCpsP/T1VyckuV/osMQ22jN37h5swDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNHADBEAiAUvK+idlSZPJyk3dhNRYaivPPN1O9DwDRxwymjrNeXdAIgNrDKcxh2tXVNg8DP+ZGDrsW0YCtkwwVBG3wK4QoG+BkxJTAjBgkqhkiG9w0BCRUxFgQUiK0WV5/tHbhimec8+L10nSs3zN8wggFJBgkqhkiG9w0BBwGgggE6BIIBNjCCATIwggEuBgsqhkiG9w0BDAoBAqCB9zCB9DBfBgkqhkiG9w0BBQ0wUjAxBgkqhkiG9w0BBQwwJAQQorJ/bsXm06LBzn5QeKCGJgICCAAwDAYIKoZIhvcNAgkFADAdBglghkgBZQMEASoEEO/TE1jW7iefM1DxMgWpB7cEgZAi3zkejP7685XOqtcSvNDPxRWc4SkWkE0W06fGJn3KSkYruALoPd9OFfIcw+K4sgGy1fk3515p0gSazI0EGKHCdNMl5qY2Ar4K65hxqFs+Fx/vSjgP5slZ3BVSUFEjGFnqYYlm62o8Nb8VaF48bGbn01zMx6AwNUJ5tSNfdv44N8VS6Wq3084AkKBBuaX3BssxJTAjBgkqhkiG9w0BCRUxFgQUiK0WV5/tHbhimec8+L10nSs3zN8=";
+
+	fn check_openssl_p12(p12 :&ECPkcs12) {
+		let privnum :BigInt = BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(OPENSSL_PRIV_HEX).unwrap());
+		assert_eq!(p12.privkey.as_ref().unwrap().get_privnum(),privnum);
+		assert_eq!(p12.certs.len(),1);
+		assert_eq!(p12.local_key_id,Some(ECHashType::Sha1.digest(&p12.certs[0])));
+	}
+
+	#[test]
+	fn test_openssl_pkcs12() {
+		let der :Vec<u8> = base64_decode(OPENSSL_P12).unwrap();
+		check_openssl_p12(&ECPkcs12::from_der(&der,"ecsimple").unwrap());
+		assert!(ECPkcs12::from_der(&der,"wrong").is_err());
+	}
+
+	#[test]
+	fn test_openssl_pkcs12_nomac() {
+		let der :Vec<u8> = base64_decode(OPENSSL_P12_NOMAC).unwrap();
+		assert!(ECPkcs12::from_der(&der,"ecsimple").is_err());
+		check_openssl_p12(&ECPkcs12::from_der_without_mac(&der,"ecsimple").unwrap());
+	}
+
+	#[test]
+	fn test_mac_iterations_cap() {
+		let der :Vec<u8> = base64_decode(OPENSSL_P12).unwrap();
+		let mut pfx :Asn1Pkcs12 = Asn1Pkcs12::init_asn1();
+		let _ = pfx.decode_asn1(&der).unwrap();
+		let mut iterations :Asn1Integer = Asn1Integer::init_asn1();
+		iterations.val = (PKCS12_MAX_MAC_ITERATIONS + 1) as i64;
+		pfx.elem.val[0].macdata.val.as_mut().unwrap().elem.val[0].iterations.val = Some(iterations);
+		let der :Vec<u8> = pfx.encode_asn1().unwrap();
+		assert!(ECPkcs12::from_der(&der,"ecsimple").is_err());
+	}
+}
@@ -47,7 +47,7 @@ impl std::default::Default for ECGf2mPoint {
 
 impl ECGf2mPoint {
 	pub fn is_infinity(&self) -> bool {
-		return self.infinity;
+		self.infinity
 	}
 
 	pub fn new(grp :&ECGroupBnGf2m) -> ECGf2mPoint {
@@ -86,39 +86,39 @@ impl ECGf2mPoint {
 	}
 
 	pub fn x(&self) -> BnGf2m {
-		return self.x.clone();
+		self.x.clone()
 	}
 
 	pub fn y(&self) -> BnGf2m {
-		return self.y.clone();
+		self.y.clone()
 	}
 
 	#[allow(dead_code)]
 	pub fn z(&self) -> BnGf2m {
-		return self.z.clone();
+		self.z.clone()
 	}
 
 	pub fn field_mul(&self,a :&BnGf2m, b :&BnGf2m) -> BnGf2m {
-		let retv :BnGf2m ;
-		retv = a * b;
+		
+		let retv :BnGf2m = a * b;
 		let ord :BnGf2m = BnGf2m::new_from_bigint(&self.group.p);
 		ecsimple_log_trace!("a 0x{:X} * b 0x{:X} % ord 0x{:X} = 0x{:X}",a,b,ord, retv.clone() % ord.clone());
-		return retv % ord;
+		retv % ord
 	}
 
 	pub fn field_sqr(&self,a :&BnGf2m) -> BnGf2m {
-		let retv :BnGf2m;
-		retv = a * a;
+		
+		let retv :BnGf2m = a * a;
 		let ord :BnGf2m = BnGf2m::new_from_bigint(&self.group.p);
 		ecsimple_log_trace!("a 0x{:X} * a 0x{:X} % ord 0x{:X} = 0x{:X}",a,a, ord,retv.clone() % ord.clone());
-		return retv % ord;		
+		retv % ord		
 	}
 
 	pub fn field_div(&self,a :&BnGf2m, b:&BnGf2m) -> Result<BnGf2m,Box<dyn Error>> {
 		let fp :BnGf2m = BnGf2m::new_from_bigint(&self.group.p);
 		let invb :BnGf2m = b.inv_op(&fp)?;
 		ecsimple_log_trace!("0x{:X} * 0x{:X} = 1 % 0x{:X}",invb,b,fp);
-		let retv :BnGf2m = invb.mul_op(&a).mod_op(&fp);
+		let retv :BnGf2m = invb.mul_op(a).mod_op(&fp);
 		ecsimple_log_trace!("r 0x{:X} = ( y 0x{:X} * xinv 0x{:X} % p 0x{:X} )",retv,a,invb,fp);
 		Ok(retv)
 	}
@@ -143,8 +143,6 @@ impl ECGf2mPoint {
 		r.x = self.field_mul(&(r.x),&(r.y));
 
 		ecsimple_log_trace!("r->X 0x{:X} r->Y 0x{:X} r->Z 0x{:X}", r.x,r.y,r.z);
-
-		return;
 	}
 
 	fn ladder_step(&self, r :&mut ECGf2mPoint, s :&mut ECGf2mPoint, p :&ECGf2mPoint) {
@@ -164,12 +162,9 @@ impl ECGf2mPoint {
 		s.y = self.field_sqr(&(s.y));
 		s.y = self.field_mul(&(s.y),&(self.group.b));
 		r.x = &r.y + &s.y;
-
-		return;
 	}
 
 	fn make_affine(&self, _r :&mut ECGf2mPoint) {
-		return;
 	}
 
 	fn point_invert(&self, r :&mut ECGf2mPoint) {
@@ -179,14 +174,13 @@ impl ECGf2mPoint {
 
 		self.make_affine(r);
 		r.y = &r.x + &r.y;
-		return;
 	}
 
 	fn field_inv(&self,a :&BnGf2m) -> BnGf2m {
 		let pbn :BnGf2m = BnGf2m::new_from_bigint(&self.group.p);
 		let bn = a.inv_op(&pbn).unwrap();
 		ecsimple_log_trace!("r 0x{:X} * a 0x{:X} = 1 % 0x{:X}", bn,a,pbn);
-		return bn;
+		bn
 	}
 
 	fn ladder_post(&self,r :&mut ECGf2mPoint,s :&ECGf2mPoint,p :&ECGf2mPoint) {
@@ -199,11 +193,11 @@ impl ECGf2mPoint {
 			self.point_invert(r);
 			return;
 		}
-		let t0 :BnGf2m;
+		
 		let mut t1 :BnGf2m;
 		let mut t2 :BnGf2m;
 
-		t0 = self.field_mul(&(r.z),&(s.z));
+		let t0 :BnGf2m = self.field_mul(&(r.z),&(s.z));
 		t1 = self.field_mul(&(p.x),&(r.z));
 		t1 = &r.x + &t1;
 		//ecsimple_log_trace!("t1 0x{:X}",t1);
@@ -229,15 +223,13 @@ impl ECGf2mPoint {
 		//ecsimple_log_trace!("r->Y 0x{:X}",r.y);
 		r.z = BnGf2m::one();
 		//ecsimple_log_trace!("r->Z 0x{:X}",r.z);
-
-		return;
 	}
 
 	pub fn mulex_op(&self,bn1 :&BigInt,bn2 :&BigInt) -> Result<ECGf2mPoint,Box<dyn Error>> {
-		let retv :ECGf2mPoint;
+		
 		let t :ECGf2mPoint = self.mul_op(bn1,false);
 		let r :ECGf2mPoint = self.mul_op(bn2,true);
-		retv = t.add_op_res(&r)?;
+		let retv :ECGf2mPoint = t.add_op_res(&r)?;
 
 		Ok(retv)
 	}
@@ -313,12 +305,11 @@ impl ECGf2mPoint {
 
 	pub fn mul_op(&self, bn :&BigInt,copyxy :bool) -> ECGf2mPoint {
 		let zv :BigInt = zero();
-		let  p :ECGf2mPoint ;
 		let mut s :ECGf2mPoint = ECGf2mPoint::new(&self.group);
 		let mut r :ECGf2mPoint = ECGf2mPoint::new(&self.group);
 		let mut tmp :ECGf2mPoint;
-		let cardinal :BigInt;
-		let lamda :BigInt;
+		
+		
 		let mut k :BigInt;
 		if bn <= &zv {
 			r = self.clone();
@@ -330,18 +321,18 @@ impl ECGf2mPoint {
 			return self.clone();
 		}
 
-		if copyxy {
-			p = self.clone();
+		let p :ECGf2mPoint = if copyxy {
+			self.clone()
 		} else {
-			p = ECGf2mPoint::new(&self.group);
-		}
+			ECGf2mPoint::new(&self.group)
+		};
 
 
 		if self.group.order == zv || self.group.cofactor == zv {
 			panic!("group order 0x{:x} or group cofactor 0x{:x}", self.group.order, self.group.cofactor);
 		}
 
-		cardinal = &self.group.order * &self.group.cofactor;
+		let cardinal :BigInt = &self.group.order * &self.group.cofactor;
 
 		//ecsimple_log_trace!("field 0x{:X} p 0x{:X}", self.group.p,self.group.p);
 		//ecsimple_log_trace!("group->a 0x{:X} a 0x{:X}", self.group.a,self.group.a);
@@ -351,7 +342,7 @@ impl ECGf2mPoint {
 		//ecsimple_log_trace!("k 0x{:X} lambda 0x{:X}", k, lamda);
 
 		k = bn.clone();
-		lamda = &k + &cardinal;
+		let lamda :BigInt = &k + &cardinal;
 		ecsimple_log_trace!("scalar 0x{:X} k 0x{:X}",k,k);
 		ecsimple_log_trace!("lambda 0x{:X}",lamda);
 
@@ -426,12 +417,12 @@ impl ECGf2mPoint {
 		ecsimple_log_trace!("s.X 0x{:X} s.Y 0x{:X} s.Z 0x{:X}",s.x,s.y,s.z);
 		ecsimple_log_trace!("r.X 0x{:X} r.Y 0x{:X} r.Z 0x{:X}",r.x,r.y,r.z);
 
-		self.ladder_post(&mut r,&mut s,&p);
+		self.ladder_post(&mut r,&s,&p);
 		//ecsimple_log_trace!("s.X 0x{:X} s.Y 0x{:X} s.Z 0x{:X}",s.x,s.y,s.z);
 		ecsimple_log_trace!("r.X 0x{:X} r.Y 0x{:X} r.Z 0x{:X}",r.x,r.y,r.z);
 		//ecsimple_log_trace!("p.X 0x{:X} p.Y 0x{:X} p.Z 0x{:X}",p.x,p.y,p.z);
 
-		return r;
+		r
 	}
 
 	pub fn get_affine_points(&self) -> Result<(BnGf2m,BnGf2m),Box<dyn Error>> {
@@ -448,7 +439,7 @@ impl ECGf2mPoint {
 
 	pub fn check_on_curve(&self) -> Result<(),Box<dyn Error>> {
 		let mut lh :BnGf2m;
-		let y2 :BnGf2m;
+		
 		if self.infinity {
 			return Ok(());
 		}
@@ -460,7 +451,7 @@ impl ECGf2mPoint {
 		lh = lh.add_op(&self.y);
 		lh =self.field_mul(&lh,&self.x);
 		lh = lh.add_op(&self.group.b);
-		y2 = self.field_sqr(&self.y);
+		let y2 :BnGf2m = self.field_sqr(&self.y);
 		lh = lh.add_op(&y2);
 		if !lh.is_zero() {
 			ecsimple_new_error!{BnGf2mPointError,"x 0x{:X} y 0x{:X} not on group {}",self.x,self.y,self.group.curvename}
@@ -492,11 +483,7 @@ impl std::fmt::Display for ECPrimePoint {
 
 impl PartialEq for ECPrimePoint {
 	fn eq(&self, other:&Self) -> bool {
-		return self.eq_op(other);
-	}
-
-	fn ne(&self, other:&Self) -> bool {
-		return ! self.eq_op(other);
+		self.eq_op(other)
 	}
 }
 
@@ -518,6 +505,10 @@ impl std::default::Default for ECPrimePoint {
 }
 
 impl ECPrimePoint {
+	pub fn is_infinity(&self) -> bool {
+		self.infinity
+	}
+
 	pub (crate) fn new(grp :&ECGroupPrime) -> ECPrimePoint {
 		let pnt :ECPrimePoint = ECPrimePoint {
 			x : grp.generator.x.clone(),
@@ -528,7 +519,7 @@ impl ECPrimePoint {
 			infinity : false,
 			z_is_one : true,
 		};
-		return pnt;
+		pnt
 	}
 
 	pub (crate) fn new_point(x :&BigInt, y :&BigInt,z :&BigInt, grp :&ECGroupPrime) -> Self {
@@ -541,7 +532,7 @@ impl ECPrimePoint {
 			infinity : false,
 			z_is_one : true,
 		};
-		return pnt;
+		pnt
 	}
 
 
@@ -559,29 +550,29 @@ impl ECPrimePoint {
 
 
 	pub fn x(&self) -> BigInt {
-		return self.x.clone();
+		self.x.clone()
 	}
 
 	pub fn y(&self) -> BigInt {
-		return self.y.clone();
+		self.y.clone()
 	}
 
 	#[allow(dead_code)]
 	pub fn z(&self) -> BigInt {
-		return self.z.clone();
+		self.z.clone()
 	}
 
 
 	pub fn field_sqr(&self,a :&BigInt) -> BigInt {
 		let retv :BigInt = self.montv.mont_mul(a,a);
 		ecsimple_log_trace!("r 0x{:X} = a 0x{:X} ^ 2 % group.field 0x{:X}",retv,a,self.group.p);
-		return retv;
+		retv
 	}
 
 	pub fn field_mul(&self,a :&BigInt,b :&BigInt) -> BigInt {
 		let retv :BigInt = self.montv.mont_mul(a,b);
 		ecsimple_log_trace!("r 0x{:X} = a 0x{:X} * b 0x{:X} % m 0x{:X}",retv,a,b,self.group.p);
-		return retv;
+		retv
 	}
 
 	pub fn sub_mod_quick(&self,a :&BigInt,b :&BigInt,m :&BigInt) -> BigInt {
@@ -591,32 +582,32 @@ impl ECPrimePoint {
 		if r < zv {
 			r += m;
 		}
-		return r;
+		r
 	}
 
 	pub (crate) fn lshift_mod_quick(&self,a :&BigInt,sn :i64, m :&BigInt) -> BigInt {
-		let r :BigInt;
-		r = a << sn;
-		return r % m;
+		
+		let r :BigInt = a << sn;
+		r % m
 	}
 
 	pub (crate) fn lshift1_mod_quick(&self,a :&BigInt,m :&BigInt) -> BigInt {
-		let r :BigInt;
-		r = a << 1;
-		return r % m;
+		
+		let r :BigInt = a << 1;
+		r % m
 	}
 
 	pub (crate) fn add_mod_quick(&self,a :&BigInt,b :&BigInt, m :&BigInt) -> BigInt {
-		let retv :BigInt;
-		retv = a + b;
-		return retv % m;
+		
+		let retv :BigInt = a + b;
+		retv % m
 	}
 
 	fn field_encode(&self,a :&BigInt) -> BigInt {
-		let retv :BigInt;
-		retv = self.montv.mont_to(a);
+		
+		let retv :BigInt = self.montv.mont_to(a);
 		ecsimple_log_trace!("r 0x{:X} = BN_to_montgomery(a 0x{:X},field 0x{:X});",retv,a,self.group.p);
-		return retv;
+		retv
 	}
 
 
@@ -674,16 +665,12 @@ impl ECPrimePoint {
 		r.x = self.field_mul(&r.x,&r.y);
 
 		s.x = self.field_mul(&p.x,&s.z);
-
-
-
-		return;
 	}
 
 	fn ladder_step(&self, r :&mut ECPrimePoint, s :&mut ECPrimePoint, p :&ECPrimePoint) {
 		let mut t0 :BigInt;
 		let mut t1 :BigInt;
-		let t2 :BigInt;
+		
 		let mut t3 :BigInt;
 		let mut t4 :BigInt;
 		let mut t5 :BigInt;
@@ -700,7 +687,7 @@ impl ECPrimePoint {
 		ecsimple_log_trace!("add_mod_quick(t6 0x{:X},t3 0x{:X},t4 0x{:X},group.field 0x{:X})",t6,t3,t4,self.group.p);
 		t5 = self.field_mul(&t6,&t5);
 		t0 = self.field_sqr(&t0);
-		t2 = self.lshift_mod_quick(&self.group.b,2,&self.group.p);
+		let t2 :BigInt = self.lshift_mod_quick(&self.group.b,2,&self.group.p);
 		ecsimple_log_trace!("mod_lshift_quick(t2 0x{:X},group.b 0x{:X},2,group.field 0x{:X})",t2,self.group.b,self.group.p);
 		t0 = self.field_mul(&t2,&t0);
 		t5 = self.lshift1_mod_quick(&t5 , &self.group.p);
@@ -740,12 +727,11 @@ impl ECPrimePoint {
 		ecsimple_log_trace!("lshift1_mod_quick(t1 0x{:X},t1,group.field 0x{:X})",t1,self.group.p);
 		r.z = self.add_mod_quick(&t4,&t1,&self.group.p);
 		ecsimple_log_trace!("add_mod_quick(r.z 0x{:X},t4 0x{:X},t1 0x{:X},group.field 0x{:X})",r.z,t4,t1,self.group.p);
-		return;
 	}
 
 	pub (crate) fn field_decode(&self,a :&BigInt) -> BigInt {
 		let retv :BigInt =  self.montv.mont_from(a);
-		return retv;
+		retv
 	}
 
 	fn field_inv(&self,a :&BigInt) -> BigInt {
@@ -754,35 +740,35 @@ impl ECPrimePoint {
 		let e :BigInt = &self.group.p - &ov - &ov;
 		let retv:BigInt =  self.montv.mont_pow(a,&e);
 		ecsimple_log_trace!("field_inv(r 0x{:X},a 0x{:X},e 0x{:X},group.field 0x{:X})",retv,a,e,self.group.p);
-		return retv;
+		retv
 	}
 
 	fn field_set_to_one(&self) -> BigInt {
 		let retv :BigInt = self.group.generator.z.clone();
 		ecsimple_log_trace!("set_to_one(r 0x{:X})",retv);
-		return retv;
+		retv
 	}
 
 	fn ladder_post(&self, r :&mut ECPrimePoint, s :&mut ECPrimePoint, p :&ECPrimePoint) {
 		let mut t0 :BigInt;
 		let mut t1 :BigInt;
-		let t2 :BigInt;
-		let t3 :BigInt;
-		let t4 :BigInt;
-		let t5 :BigInt;
+		
+		
+		
+		
 		let mut t6 :BigInt;
 
-		t4 = self.lshift1_mod_quick(&p.y,&self.group.p);
+		let t4 :BigInt = self.lshift1_mod_quick(&p.y,&self.group.p);
 		ecsimple_log_trace!("lshift1_mod_quick(t4 0x{:X},p.y 0x{:X},group.field 0x{:X})",t4,p.y,self.group.p);
 		t6 = self.field_mul(&r.x,&t4);
 		t6 = self.field_mul(&s.z,&t6);
-		t5 = self.field_mul(&r.z,&t6);
+		let t5 :BigInt = self.field_mul(&r.z,&t6);
 
 		t1 = self.lshift1_mod_quick(&self.group.b,&self.group.p);
 		ecsimple_log_trace!("lshift1_mod_quick(t1 0x{:X},group.b 0x{:X},group.field 0x{:X})",t1,self.group.b,self.group.p);
 		t1 = self.field_mul(&s.z,&t1);
-		t3 = self.field_sqr(&r.z);
-		t2 = self.field_mul(&t3,&t1);
+		let t3 :BigInt = self.field_sqr(&r.z);
+		let t2 :BigInt = self.field_mul(&t3,&t1);
 		t6 = self.field_mul(&r.z,&self.group.a);
 		t1 = self.field_mul(&p.x,&r.x);
 		t1 = self.add_mod_quick(&t1,&t6,&self.group.p);
@@ -808,8 +794,6 @@ impl ECPrimePoint {
 		r.x = self.field_mul(&t5,&t1);
 		r.y = self.field_mul(&t0,&t1);
 		r.z = self.field_set_to_one();
-
-		return;
 	}
 
 	pub fn get_affine_coordinates(&self,r :&ECPrimePoint) -> ECPrimePoint  {
@@ -819,7 +803,7 @@ impl ECPrimePoint {
 		retv.x = self.montv.mont_from(&r.x);
 		retv.y = self.montv.mont_from(&r.y);
 		ecsimple_log_trace!("x 0x{:X} y 0x{:X} z 0x{:X}",retv.x,retv.y,retv.z);
-		return retv;
+		retv
 	}
 
 	pub fn set_affine_coordinates(&self,x :&BigInt,y :&BigInt,z :&BigInt) -> Result<ECPrimePoint,Box<dyn Error>> {
@@ -852,11 +836,10 @@ impl ECPrimePoint {
 	#[allow(unused_variables)]
 	pub fn mul_op(&self, bn :&BigInt,copyxy :bool ) -> ECPrimePoint {
 		let zv :BigInt = zero();
-		let  p :ECPrimePoint ;
 		let mut s :ECPrimePoint = ECPrimePoint::new(&self.group);
 		let mut r :ECPrimePoint = ECPrimePoint::new(&self.group);
 		let mut tmp :ECPrimePoint;
-		let cardinal :BigInt;
+		
 		let mut lamda :BigInt = zero();
 		let mut k :BigInt = zero();
 		if bn <= &zv {
@@ -869,11 +852,11 @@ impl ECPrimePoint {
 			return self.clone();
 		}
 
-		if copyxy {
-			p = self.clone();
+		let p :ECPrimePoint = if copyxy {
+			self.clone()
 		} else {
-			p = ECPrimePoint::new(&self.group);
-		}
+			ECPrimePoint::new(&self.group)
+		};
 		ecsimple_log_trace!("p.x 0x{:X} p.y 0x{:X} p.z 0x{:X}",p.x,p.y,p.z);
 
 
@@ -881,7 +864,7 @@ impl ECPrimePoint {
 			panic!("group order 0x{:x} or group cofactor 0x{:x}", self.group.order, self.group.cofactor);
 		}
 
-		cardinal = &self.group.order * &self.group.cofactor;
+		let cardinal :BigInt = &self.group.order * &self.group.cofactor;
 
 		//ecsimple_log_trace!("field 0x{:X} p 0x{:X}", self.group.p,self.group.p);
 		//ecsimple_log_trace!("group->a 0x{:X} a 0x{:X}", self.group.a,self.group.a);
@@ -975,7 +958,7 @@ impl ECPrimePoint {
 
 		r = self.get_affine_coordinates(&r);
 
-		return r;
+		r
 	}
 
 	pub fn check_on_curve(&self) -> Result<(),Box<dyn Error>> {
@@ -1131,7 +1114,7 @@ impl ECPrimePoint {
 	 	let mut n3 :BigInt;
 	 	let mut n4 :BigInt;
 	 	let mut n5 :BigInt;
-	 	let n6 :BigInt;
+	 	
 	 	let field :BigInt = self.group.p.clone();
 	 	let zv :BigInt = zero();
 	 	let ov :BigInt = one();
@@ -1185,7 +1168,7 @@ impl ECPrimePoint {
 
 	 	n5 = self.sub_mod_quick(&n1,&n3,&field);
 	 	ecsimple_log_trace!("mod_sub_quick(n5 0x{:X},n1 0x{:X},n3 0x{:X},p 0x{:X})",n5,n1,n3,field);
-	 	n6 = self.sub_mod_quick(&n2,&n4,&field);
+	 	let n6 :BigInt = self.sub_mod_quick(&n2,&n4,&field);
 	 	ecsimple_log_trace!("mod_sub_quick(n6 0x{:X},n2 0x{:X},n4 0x{:X},p 0x{:X})",n6,n2,n4,field);
 	 	/* n5 = n1 - n3 */
 	 	/* n6 = n2 - n4 */
@@ -1286,7 +1269,7 @@ impl ECPrimePoint {
 	 		return false;
 	 	}
 
-	 	return true;
+	 	true
 	 }
 
 	 fn points_make_affine(&self,points :&mut [ECPrimePoint]) -> Result<(),Box<dyn Error>> {
@@ -1295,7 +1278,7 @@ impl ECPrimePoint {
 	 	let mut prod_z :Vec<BigInt> = Vec::new();
 	 	let zv :BigInt = zero();
 	 	let mut idx :usize;
-	 	if points.len() == 0 {
+	 	if points.is_empty() {
 	 		return Ok(());
 	 	}
 	 	while prod_z.len() < points.len() {
@@ -1400,7 +1383,6 @@ impl ECPrimePoint {
 	 	self.z_is_one = false;
 	 	self.z = zero();
 	 	self.infinity = true;
-	 	return;
 	 }
 
 	 pub fn mulex_op(&self,bn1 :&BigInt,bn2 :&BigInt) -> Result<ECPrimePoint,Box<dyn Error>> {
@@ -1426,8 +1408,7 @@ impl ECPrimePoint {
 	 	wnaf.push(curnaf.clone());
 	 	ecsimple_log_trace!("scalars[0] 0x{:X}",bn2);
 	 	ecsimple_debug_buffer_trace!(curnaf.as_ptr(),curnaf.len(),"wNAF wsize[0] 0x{:x}",wnaf_bits[0]);
-	 	cv = Vec::new();
-	 	cv.push(self.clone());
+	 	cv = vec![self.clone()];
 	 	val_sub.push(cv.clone());
 	 	ecsimple_log_trace!("val_sub[0][0].X 0x{:X} val_sub[0][0].Y 0x{:X} val_sub[0][0].Z 0x{:X}",val_sub[0][0].x(),val_sub[0][0].y(),val_sub[0][0].z());
 
@@ -1438,8 +1419,8 @@ impl ECPrimePoint {
 	 	ecsimple_log_trace!("scalar 0x{:X}",bn1);
 	 	ecsimple_debug_buffer_trace!(curnaf.as_ptr(),curnaf.len(),"wNAF wsize[1] 0x{:x}",wnaf_bits[1]);
 	 	wnaf.push(curnaf.clone());
-	 	cv = Vec::new();
-	 	cv.push(ECPrimePoint::new(&self.group));
+	 	cv = vec![ECPrimePoint::new(&self.group)];
+
 	 	val_sub.push(cv.clone());
 	 	ecsimple_log_trace!("val_sub[1][0].X 0x{:X} val_sub[1][0].Y 0x{:X} val_sub[1][0].Z 0x{:X}",val_sub[1][0].x(),val_sub[1][0].y(),val_sub[1][0].z());
 
@@ -1579,8 +1560,8 @@ impl ECPrimePoint {
 	 	let ov :BigInt = one();
 	 	let x :BigInt;
 	 	let y :BigInt;
-	 	let z :BigInt;
-	 	let z_ :BigInt;
+	 	
+	 	
 	 	let z_1 :BigInt;
 	 	let z_2 :BigInt;
 	 	let z_3 :BigInt;
@@ -1590,8 +1571,8 @@ impl ECPrimePoint {
 
 	 	ecsimple_log_trace!("point.X 0x{:X} point.Y 0x{:X} point.Z 0x{:X}",self.x,self.y,self.z);
 
-	 	z  = self.field_decode(&self.z);
-	 	z_ = z.clone();
+	 	let z :BigInt = self.field_decode(&self.z);
+	 	let z_ :BigInt = z.clone();
 	 	if z_ == ov {
 	 		x = self.x.clone();
 	 		y = self.y.clone();
@@ -2,6 +2,7 @@
 
 use rand;
 use crate::fileop::*;
+#[allow(unused_imports)]
 use crate::*;
 #[allow(unused_imports)]
 use crate::logger::*;
@@ -28,7 +29,7 @@ pub (crate) fn get_max_bits(bn :&BigInt) -> i64 {
 		idx += 1;
 		cv <<= 1;
 	}
-	return retv;
+	retv
 }
 
 
@@ -47,20 +48,20 @@ impl RandOps {
 			begen : true,
 			pos : 0,
 		};
-		if fname.is_none() {
-			retv.gencore = Some(rand::thread_rng());
-		} else {
-			retv.filerand = Some(RandFile::new(fname.as_ref().unwrap())?);
-			retv.begen = false;
+		match fname.as_ref() {
+			Some(f) => {
+				retv.filerand = Some(RandFile::new(f)?);
+				retv.begen = false;
+			},
+			None => {
+				retv.gencore = Some(rand::thread_rng());
+			}
 		}
 		Ok(retv)
 	}
 
 	pub fn get_bytes(&mut self, num :usize) -> Result<Vec<u8>,Box<dyn Error>> {
-		let mut buf :Vec<u8> = Vec::new();
-		for _ in 0..num {
-			buf.push(0x0);
-		}
+		let mut buf :Vec<u8> = vec![0x0;num];
 		if self.begen {
 			self.gencore.as_mut().unwrap().try_fill_bytes(&mut buf)?;
 		} else {
@@ -79,9 +80,9 @@ impl rand_core::CryptoRng  for RandOps {
 impl rand_core::RngCore for RandOps {
 	fn next_u32(&mut self) -> u32 {
 		if self.begen {
-			return self.gencore.as_mut().unwrap().next_u32();
+			self.gencore.as_mut().unwrap().next_u32()
 		} else {
-			return self.filerand.as_mut().unwrap().next_u32();
+			self.filerand.as_mut().unwrap().next_u32()
 		}
 	}
 
@@ -89,21 +90,21 @@ impl rand_core::RngCore for RandOps {
 		if self.begen {
 			return self.gencore.as_mut().unwrap().next_u64();
 		}
-		return self.filerand.as_mut().unwrap().next_u64();	
+		self.filerand.as_mut().unwrap().next_u64()	
 	}
 
 	fn fill_bytes(&mut self, dest: &mut [u8]) {
 		if self.begen {
 			return self.gencore.as_mut().unwrap().fill_bytes(dest);
 		}
-		return self.filerand.as_mut().unwrap().fill_bytes(dest);	
+		self.filerand.as_mut().unwrap().fill_bytes(dest)	
 	}
 
 	fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(),rand_core::Error> {
 		if self.begen {
 			return self.gencore.as_mut().unwrap().try_fill_bytes(dest);
 		}
-		return self.filerand.as_mut().unwrap().try_fill_bytes(dest);	
+		self.filerand.as_mut().unwrap().try_fill_bytes(dest)	
 	}
 }
 
@@ -112,21 +113,20 @@ unsafe impl Sync for RandOps {}
 
 fn create_randop() -> RwLock<RandOps> {
 	let envname :String = "ECSIMPLE_RANDOP".to_string();
-	let randop : RandOps;
-
-	match env::var(&envname) {
+	let randop : RandOps = match env::var(&envname) {
 		Ok(v) => {
-			let randfile = Some(format!("{}",v));
+			let randfile = Some(v.to_string());
 			//ecsimple_log_trace!("file {}",v);
-			randop = RandOps::new(randfile).unwrap();
+			RandOps::new(randfile).unwrap()
 		},
 		Err(_e) => {
 			//ecsimple_log_trace!("none file");
-			randop = RandOps::new(None).unwrap();
+			RandOps::new(None).unwrap()
 		}
-	}
+	};
+
 
-	return RwLock::new(randop);
+	RwLock::new(randop)
 }
 
 lazy_static ! {
@@ -162,12 +162,18 @@ pub fn ecsimple_rand_bits(bits :u64, top :i32 , bottom : i32) -> BigInt {
 	retv[0] &= (!mask) as u8;
 	ecsimple_log_trace!("buf[0] 0x{:x}", retv[0]);
 	if bottom != 0 {
-		retv[(rnbytes - 1)] |= 1;
-		ecsimple_log_trace!("buf[0x{:x}] = [0x{:x}]",rnbytes - 1,retv[(rnbytes-1)]);
+		retv[rnbytes - 1] |= 1;
+		ecsimple_log_trace!("buf[0x{:x}] = [0x{:x}]",rnbytes - 1,retv[rnbytes-1]);
 	}
 	bn = BigInt::from_bytes_be(Sign::Plus,&retv);
 	ecsimple_log_trace!("rnd 0x{:X}",bn);
-	return bn;
+	bn
+}
+
+pub fn ecsimple_rand_bytes(num :usize) -> Vec<u8> {
+	let retv :Vec<u8> = EC_SIMPLE_RANDOP.write().unwrap().get_bytes(num).unwrap();
+	ecsimple_debug_buffer_trace!(retv.as_ptr(),retv.len(),"rand bytes");
+	retv
 }
 
 pub fn ecsimple_rand_range(rangeval :&BigInt) -> BigInt {
@@ -177,7 +183,7 @@ pub fn ecsimple_rand_range(rangeval :&BigInt) -> BigInt {
 		ecsimple_debug_buffer_trace!(retv.as_ptr(),retv.len(),"get value");
 		let mut bv = BigInt::from_bytes_be(Sign::Plus,&retv);
 		ecsimple_log_trace!("random number 0x{:X}", bv);
-		bv = bv % rangeval;
+		bv %= rangeval;
 		if bv != zero() {
 			ecsimple_log_trace!("result 0x{:X} range 0x{:X}", bv,rangeval);
 			return bv;	
@@ -0,0 +1,635 @@
+use crate::group::*;
+use crate::bngf2m::*;
+use crate::mont::*;
+use crate::randop::*;
+use crate::utils::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use num_bigint::{BigInt,Sign};
+use num_traits::{zero,one};
+use std::error::Error;
+
+ecsimple_error_class!{ECSecurityError}
+
+/*largest embedding degree we search for, MOV/FR only matters for tiny ones*/
+const EC_SEC_EMBEDDING_MAX :u64 = 10000;
+/*largest |D| we search for the small CM discriminant*/
+const EC_SEC_CM_SMALL_MAX :u64 = 10000;
+const EC_SEC_TRIAL_DIV_MAX :u32 = 0x10000;
+const EC_SEC_RHO_STEPS :u64 = 0x10000;
+const EC_SEC_PRIME_ROUNDS :usize = 12;
+
+/*safecurves requirement in bits*/
+const EC_SEC_RHO_MIN_BITS :f64 = 100.0;
+const EC_SEC_CM_MIN_BITS :f64 = 100.0;
+const EC_SEC_TWIST_MIN_BITS :f64 = 100.0;
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum ECCurveRigidity {
+	/*coefficients are the smallest ones satisfying the requirements*/
+	FullyRigid,
+	/*coefficients derive from a published seed*/
+	Seeded,
+	/*coefficients have no explanation*/
+	Manipulable,
+}
+
+impl std::fmt::Display for ECCurveRigidity {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ECCurveRigidity::FullyRigid => write!(f,"fully rigid"),
+			ECCurveRigidity::Seeded => write!(f,"seeded"),
+			ECCurveRigidity::Manipulable => write!(f,"manipulable"),
+		}
+	}
+}
+
+#[derive(Clone,Debug)]
+pub struct ECSecurityReport {
+	pub curvename :String,
+	pub is_prime_field :bool,
+	pub field_bits :i64,
+	/*p prime , or the binary field polynomial irreducible*/
+	pub field_prime :bool,
+
+	pub order_bits :i64,
+	pub order_prime :bool,
+	pub cofactor :BigInt,
+	/*q + 1 - h * n*/
+	pub trace :BigInt,
+	pub hasse_ok :bool,
+	pub anomalous :bool,
+	/*None when larger than the search bound*/
+	pub embedding_degree :Option<u64>,
+	pub embedding_safe :bool,
+	/*t^2 - 4q*/
+	pub frobenius_discriminant :BigInt,
+	/*Some when the cm field discriminant is small or t^2 - 4q could be fully factored*/
+	pub cm_discriminant :Option<BigInt>,
+	pub cm_safe :bool,
+	pub rho_bits :f64,
+	pub rho_safe :bool,
+	pub twist_order :BigInt,
+	/*prime factors found in the twist order, some may be composite when twist_factored is false*/
+	pub twist_factors :Vec<BigInt>,
+	pub twist_factored :bool,
+	pub twist_rho_bits :f64,
+	pub twist_safe :bool,
+	pub rigidity :ECCurveRigidity,
+	/*birational to a complete edwards curve , safecurves completeness*/
+	pub complete_formulas :bool,
+	pub safe :bool,
+}
+
+impl std::fmt::Display for ECSecurityReport {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut s :String = "".to_string();
+		s.push_str(&format!("curve {}\n",self.curvename));
+		s.push_str(&format!("field {} bits {} prime {}\n",if self.is_prime_field {"prime"} else {"binary"},self.field_bits,self.field_prime));
+		s.push_str(&format!("order bits {} prime {} cofactor 0x{:x}\n",self.order_bits,self.order_prime,self.cofactor));
+		s.push_str(&format!("trace 0x{:x} hasse {} anomalous {}\n",self.trace,self.hasse_ok,self.anomalous));
+		match &self.embedding_degree {
+			Some(k) => s.push_str(&format!("embedding degree {} safe {}\n",k,self.embedding_safe)),
+			None => s.push_str(&format!("embedding degree > {} safe {}\n",EC_SEC_EMBEDDING_MAX,self.embedding_safe)),
+		}
+		match &self.cm_discriminant {
+			Some(d) => s.push_str(&format!("cm discriminant {} safe {}\n",d,self.cm_safe)),
+			None => s.push_str(&format!("cm discriminant |D| > {} safe {}\n",EC_SEC_CM_SMALL_MAX,self.cm_safe)),
+		}
+		s.push_str(&format!("rho cost 2^{:.1} safe {}\n",self.rho_bits,self.rho_safe));
+		s.push_str(&format!("twist order 0x{:x} factored {} rho cost 2^{:.1} safe {}\n",self.twist_order,self.twist_factored,self.twist_rho_bits,self.twist_safe));
+		s.push_str(&format!("rigidity {}\n",self.rigidity));
+		s.push_str(&format!("complete formulas {}\n",self.complete_formulas));
+		s.push_str(&format!("safe {}",self.safe));
+		write!(f,"{}",s)
+	}
+}
+
+/*rho cost in bits for a group of prime order l , sqrt(pi/4 * l)*/
+fn rho_cost_bits(l :&BigInt) -> f64 {
+	0.5 * bn_log2(l) + 0.5 * (std::f64::consts::PI / 4.0).log2()
+}
+
+fn bn_abs(n :&BigInt) -> BigInt {
+	if n.sign() == Sign::Minus {
+		return -n.clone();
+	}
+	n.clone()
+}
+
+fn small_primes(maxv :u32) -> Vec<u32> {
+	let mut sieve :Vec<bool> = vec![true; maxv as usize];
+	let mut retv :Vec<u32> = Vec::new();
+	let mut i :usize = 2;
+	while i < sieve.len() {
+		if sieve[i] {
+			retv.push(i as u32);
+			let mut j :usize = i * i;
+			while j < sieve.len() {
+				sieve[j] = false;
+				j += i;
+			}
+		}
+		i += 1;
+	}
+	retv
+}
+
+fn bn_gcd(a :&BigInt,b :&BigInt) -> BigInt {
+	let zv :BigInt = zero();
+	let mut x :BigInt = bn_abs(a);
+	let mut y :BigInt = bn_abs(b);
+	while y != zv {
+		let t :BigInt = &x % &y;
+		x = y;
+		y = t;
+	}
+	x
+}
+
+/*pollard-brent with bounded steps, None when nothing found ,
+  true when the walk collapsed to n so another c may still split it*/
+fn pollard_brent(n :&BigInt,c :u32) -> (Option<BigInt>,bool) {
+	let ov :BigInt = one();
+	let cv :BigInt = BigInt::from(c);
+	let mut y :BigInt = BigInt::from(2u32);
+	let mut x :BigInt;
+	let mut g :BigInt = ov.clone();
+	let mut r :u64 = 1;
+	let mut q :BigInt = ov.clone();
+	let mut steps :u64 = 0;
+	let mut ys :BigInt = y.clone();
+	x = y.clone();
+
+	while g == ov {
+		x = y.clone();
+		let mut i :u64 = 0;
+		while i < r {
+			y = (&y * &y + &cv) % n;
+			i += 1;
+		}
+		let mut k :u64 = 0;
+		while k < r && g == ov {
+			ys = y.clone();
+			let mut j :u64 = 0;
+			while j < 128 && j < (r - k) {
+				y = (&y * &y + &cv) % n;
+				q = (&q * bn_abs(&(&x - &y))) % n;
+				j += 1;
+			}
+			g = bn_gcd(&q,n);
+			k += 128;
+			steps += 128;
+		}
+		r <<= 1;
+		if steps > EC_SEC_RHO_STEPS {
+			break;
+		}
+	}
+
+	if &g == n {
+		steps = 0;
+		loop {
+			ys = (&ys * &ys + &cv) % n;
+			g = bn_gcd(&bn_abs(&(&x - &ys)),n);
+			steps += 1;
+			if g != ov || steps > EC_SEC_RHO_STEPS {
+				break;
+			}
+		}
+	}
+
+	if g == ov || &g == n {
+		return (None,&g == n);
+	}
+	(Some(g),false)
+}
+
+/*return the factors found and whether the last one is known to be prime*/
+fn factor_partial(n :&BigInt) -> (Vec<BigInt>,bool) {
+	let zv :BigInt = zero();
+	let ov :BigInt = one();
+	let mut retv :Vec<BigInt> = Vec::new();
+	let mut rest :BigInt = bn_abs(n);
+	let mut pending :Vec<BigInt> = Vec::new();
+	let mut factored :bool = true;
+
+	for p in small_primes(EC_SEC_TRIAL_DIV_MAX).iter() {
+		let bp :BigInt = BigInt::from(*p);
+		while (&rest % &bp) == zv {
+			retv.push(bp.clone());
+			rest = &rest / &bp;
+		}
+	}
+
+	if rest == ov {
+		return (retv,true);
+	}
+
+	pending.push(rest);
+	while let Some(cur) = pending.pop() {
+
+		if bn_is_probable_prime(&cur,EC_SEC_PRIME_ROUNDS) {
+			retv.push(cur);
+			continue;
+		}
+		let mut found :Option<BigInt> = None;
+		let mut c :u32 = 1;
+		let mut retry :bool = true;
+		/*the step bound is per number , only a collapsed walk is worth another c*/
+		while c < 4 && found.is_none() && retry {
+			(found,retry) = pollard_brent(&cur,c);
+			c += 1;
+		}
+
+		match found {
+			Some(d) => {
+				let e :BigInt = &cur / &d;
+				pending.push(d);
+				pending.push(e);
+			},
+			None => {
+				/*a composite we could not split*/
+				retv.push(cur);
+				factored = false;
+			}
+		}
+	}
+
+	retv.sort();
+	(retv,factored)
+}
+
+/*smallest squarefree d with t^2 - 4q = -d * v^2 , only searched up to EC_SEC_CM_SMALL_MAX*/
+fn small_cm_discriminant(fd :&BigInt) -> Option<BigInt> {
+	let zv :BigInt = zero();
+	let absd :BigInt = bn_abs(fd);
+	let mut d :u64 = 1;
+	while d <= EC_SEC_CM_SMALL_MAX {
+		let bd :BigInt = BigInt::from(d);
+		if (&absd % &bd) == zv {
+			let quot :BigInt = &absd / &bd;
+			let sq :BigInt = quot.sqrt();
+			if &sq * &sq == quot {
+				/*fundamental discriminant*/
+				let retv :BigInt = if (d % 4) == 3 {
+					-bd
+				} else {
+					-(bd * BigInt::from(4u32))
+				};
+				return Some(retv);
+			}
+		}
+		d += 1;
+	}
+	None
+}
+
+/*squarefree part of |n| , exact when the factoring finished ,
+  otherwise an unsplit composite counts as squarefree unless it is a square itself*/
+fn squarefree_part(n :&BigInt) -> (BigInt,bool) {
+	let ov :BigInt = one();
+	let (factors,factored) = factor_partial(n);
+	let mut retv :BigInt = ov.clone();
+	let mut idx :usize = 0;
+	while idx < factors.len() {
+		let mut cnt :usize = 1;
+		while (idx + cnt) < factors.len() && factors[idx + cnt] == factors[idx] {
+			cnt += 1;
+		}
+		let f :&BigInt = &factors[idx];
+		if (cnt % 2) == 1 {
+			let sq :BigInt = f.sqrt();
+			if &sq * &sq != *f {
+				retv = &retv * f;
+			}
+		}
+		idx += cnt;
+	}
+	(retv,factored)
+}
+
+/*fundamental discriminant of t^2 - 4q from its squarefree part*/
+fn cm_field_discriminant(fd :&BigInt) -> (BigInt,bool) {
+	let (sqf,exact) = squarefree_part(fd);
+	let retv :BigInt = if (&sqf % BigInt::from(4u32)) == BigInt::from(3u32) {
+		-sqf
+	} else {
+		-(sqf * BigInt::from(4u32))
+	};
+	(retv,exact)
+}
+
+fn embedding_degree(q :&BigInt,n :&BigInt) -> Option<u64> {
+	let ov :BigInt = one();
+	let qn :BigInt = q % n;
+	let mut cur :BigInt = qn.clone();
+	let mut k :u64 = 1;
+	while k <= EC_SEC_EMBEDDING_MAX {
+		if cur == ov {
+			return Some(k);
+		}
+		cur = (&cur * &qn) % n;
+		k += 1;
+	}
+	None
+}
+
+/*polynomials over GF(2) with bit i the coefficient of x^i*/
+fn gf2_poly_rem(a :&BigInt,b :&BigInt) -> BigInt {
+	let zv :BigInt = zero();
+	let mut retv :BigInt = a.clone();
+	while retv != zv && retv.bits() >= b.bits() {
+		let sh :u64 = retv.bits() - b.bits();
+		retv ^= b << sh;
+	}
+	retv
+}
+
+fn gf2_poly_gcd(a :&BigInt,b :&BigInt) -> BigInt {
+	let zv :BigInt = zero();
+	let mut x :BigInt = a.clone();
+	let mut y :BigInt = b.clone();
+	while y != zv {
+		let r :BigInt = gf2_poly_rem(&x,&y);
+		x = y;
+		y = r;
+	}
+	x
+}
+
+/*rabin test , f of degree m is irreducible when x^(2^m) = x mod f and gcd(x^(2^(m/r)) - x , f) = 1 for every prime r | m*/
+fn gf2_poly_irreducible(f :&BigInt) -> bool {
+	let mut fp :BnGf2m = BnGf2m::new_from_bigint(f);
+	fp.extend_poly();
+	let m :i32 = fp.max_bits() - 1;
+	if m < 1 {
+		return false;
+	}
+	if m == 1 {
+		return true;
+	}
+	/*x divides it*/
+	if !fp.is_odd() {
+		return false;
+	}
+
+	let ov :BigInt = one();
+	let xb :BigInt = BigInt::from(2u32);
+	let checks :Vec<i32> = small_primes((m + 1) as u32).iter().filter(|r| (m as u32) % **r == 0).map(|r| m / (*r as i32)).collect();
+	let mut cur :BnGf2m = BnGf2m::new_from_bigint(&xb);
+	let mut k :i32 = 1;
+	while k <= m {
+		cur = cur.mul_op(&cur).mod_op(&fp);
+		if checks.contains(&k) && gf2_poly_gcd(f,&(cur.to_bigint() ^ &xb)) != ov {
+			return false;
+		}
+		k += 1;
+	}
+	cur.to_bigint() == xb
+
+}
+
+/*complete edwards formulas need a point of order 4 and a unique point of order 2 ,
+  the cubic x^3 + ax + b has a single root when its discriminant -4a^3 - 27b^2 is a non-square ,
+  ordinary binary curves always have a single point of order 2*/
+fn has_complete_edwards_form(isprime :bool,q :&BigInt,a :&BigInt,b :&BigInt,h :&BigInt) -> bool {
+	if (h % BigInt::from(4u32)) != zero() {
+		return false;
+	}
+	if !isprime {
+		return true;
+	}
+	let disc :BigInt = -(a * a * a * BigInt::from(4u32)) - (b * b * BigInt::from(27u32));
+
+	bn_jacobi(&disc,q) == -1
+}
+
+fn coefficient_small(v :&BigInt,q :&BigInt) -> bool {
+	let lim :BigInt = BigInt::from(EC_SEC_TRIAL_DIV_MAX);
+	if v < &lim {
+		return true;
+	}
+	/*like -3 mod p*/
+	if (q - v) < lim {
+		return true;
+	}
+	false
+}
+
+impl ECGroup {
+	pub fn security_report(&self) -> Result<ECSecurityReport,Box<dyn Error>> {
+		let ov :BigInt = one();
+		let zv :BigInt = zero();
+		let q :BigInt;
+		let a :BigInt;
+		let b :BigInt;
+		let n :BigInt;
+		let h :BigInt;
+		let fieldbits :i64;
+		let isprime :bool;
+		let fieldprime :bool;
+		let hasseed :bool;
+		let curvename :String;
+
+		if self.is_prime_group() {
+			let grp = self.get_prime_group();
+			let montv :MontNum = MontNum::new(&grp.p)?;
+			q = grp.p.clone();
+			a = montv.mont_from(&grp.a);
+			b = montv.mont_from(&grp.b);
+			n = grp.order.clone();
+			h = grp.cofactor.clone();
+			fieldbits = get_max_bits(&grp.p);
+			isprime = true;
+			fieldprime = bn_is_probable_prime(&grp.p,EC_SEC_PRIME_ROUNDS);
+			hasseed = grp.seed != zv;
+			curvename = grp.curvename.clone();
+		} else if self.is_bn_group() {
+			let grp = self.get_bn_group();
+			fieldbits = get_max_bits(&grp.p) - 1;
+			q = ov.clone() << fieldbits;
+			a = grp.a.to_bigint();
+			b = grp.b.to_bigint();
+			n = grp.order.clone();
+			h = grp.cofactor.clone();
+			isprime = false;
+			fieldprime = gf2_poly_irreducible(&grp.p);
+			hasseed = grp.seed != zv;
+			curvename = grp.curvename.clone();
+		} else {
+			ecsimple_new_error!{ECSecurityError,"empty group"}
+		}
+
+		if n <= ov || h <= zv {
+			ecsimple_new_error!{ECSecurityError,"order 0x{:x} or cofactor 0x{:x} not valid",n,h}
+		}
+
+		let ordbits :i64 = get_max_bits(&n);
+		let orderprime :bool = bn_is_probable_prime(&n,EC_SEC_PRIME_ROUNDS);
+		let curveorder :BigInt = &h * &n;
+		let trace :BigInt = &q + &ov - &curveorder;
+		/*|t| <= 2 sqrt(q) => t^2 <= 4q*/
+		let frobdisc :BigInt = &trace * &trace - (&q << 2);
+		let hasseok :bool = frobdisc <= zv;
+		let anomalous :bool = curveorder == q || n == q;
+		ecsimple_log_trace!("{} trace 0x{:X} frobenius discriminant 0x{:X}",curvename,trace,frobdisc);
+
+		let embdeg :Option<u64> = embedding_degree(&q,&n);
+		let embsafe :bool = embdeg.is_none();
+
+		let mut cmdisc :Option<BigInt>;
+		if a == zv || b == zv {
+			/*j-invariant 0 or 1728 for prime fields gives D = -3 or -4*/
+			if isprime && a == zv {
+				cmdisc = Some(BigInt::from(-3i32));
+			} else if isprime && b == zv {
+				cmdisc = Some(BigInt::from(-4i32));
+			} else {
+				cmdisc = small_cm_discriminant(&frobdisc);
+			}
+		} else {
+			cmdisc = small_cm_discriminant(&frobdisc);
+		}
+		let cmsafe :bool;
+		if cmdisc.is_some() {
+			cmsafe = false;
+		} else {
+			let (d,exact) = cm_field_discriminant(&frobdisc);
+			ecsimple_log_trace!("{} cm discriminant -0x{:X} exact {}",curvename,bn_abs(&d),exact);
+			cmsafe = bn_log2(&d) > EC_SEC_CM_MIN_BITS;
+			if exact {
+				cmdisc = Some(d);
+			}
+		}
+
+		let rhobits :f64 = rho_cost_bits(&n);
+		let rhosafe :bool = rhobits >= EC_SEC_RHO_MIN_BITS;
+
+		let twistorder :BigInt = &q + &ov + &trace;
+		let (twistfactors,twistfactored) = factor_partial(&twistorder);
+		let twistrhobits :f64 = if !twistfactors.is_empty() {
+			let largest :BigInt = twistfactors[twistfactors.len() - 1].clone();
+			rho_cost_bits(&largest)
+		} else {
+			0.0
+		};
+		let twistsafe :bool = twistfactored && twistrhobits >= EC_SEC_TWIST_MIN_BITS;
+
+		let rigidity :ECCurveRigidity;
+		if coefficient_small(&a,&q) && coefficient_small(&b,&q) {
+			rigidity = ECCurveRigidity::FullyRigid;
+		} else if hasseed {
+			rigidity = ECCurveRigidity::Seeded;
+		} else {
+			rigidity = ECCurveRigidity::Manipulable;
+		}
+
+		/*informational only , the short weierstrass formulas of this crate do not use it*/
+		let complete :bool = has_complete_edwards_form(isprime,&q,&a,&b,&h);
+		let safe :bool = fieldprime && orderprime && hasseok && !anomalous && embsafe && cmsafe && rhosafe && twistsafe && rigidity != ECCurveRigidity::Manipulable;
+
+		Ok(ECSecurityReport {
+			curvename,
+			is_prime_field : isprime,
+			field_bits : fieldbits,
+			field_prime : fieldprime,
+			order_bits : ordbits,
+			order_prime : orderprime,
+			cofactor : h,
+			trace,
+			hasse_ok : hasseok,
+			anomalous,
+			embedding_degree : embdeg,
+			embedding_safe : embsafe,
+			frobenius_discriminant : frobdisc,
+			cm_discriminant : cmdisc,
+			cm_safe : cmsafe,
+			rho_bits : rhobits,
+			rho_safe : rhosafe,
+			twist_order : twistorder,
+			twist_factors : twistfactors,
+			twist_factored : twistfactored,
+			twist_rho_bits : twistrhobits,
+			twist_safe : twistsafe,
+			rigidity,
+			complete_formulas : complete,
+			safe,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::consts::*;
+
+	#[test]
+	fn test_squarefree_part() {
+		let n :BigInt = BigInt::from(8u32 * 9 * 5 * 7);
+		let (sqf,exact) = squarefree_part(&n);
+		assert!(exact);
+		assert_eq!(sqf,BigInt::from(70u32));
+		let (d,exact) = cm_field_discriminant(&BigInt::from(-27i32));
+		assert!(exact);
+		assert_eq!(d,BigInt::from(-3i32));
+	}
+
+	#[test]
+	fn test_report_secp256k1() {
+		let grp :ECGroup = ecc_get_curve_group(SECP256k1_NAME).unwrap();
+		let r :ECSecurityReport = grp.security_report().unwrap();
+		assert_eq!(r.cm_discriminant,Some(BigInt::from(-3i32)));
+		assert!(!r.cm_safe);
+		assert!(!r.safe);
+	}
+
+	#[test]
+	fn test_report_prime256v1() {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let r :ECSecurityReport = grp.security_report().unwrap();
+		assert!(r.order_prime);
+		assert!(r.hasse_ok);
+		assert!(r.embedding_safe);
+		assert!(r.cm_safe);
+		assert!(r.rho_safe);
+		assert_eq!(r.rigidity,ECCurveRigidity::Seeded);
+		assert!(!r.complete_formulas);
+	}
+
+	#[test]
+	fn test_report_all_curves() {
+		for name in ecc_get_curve_names().iter() {
+			let grp :ECGroup = match ecc_get_curve_group(name) {
+				Ok(v) => v,
+				Err(_e) => {
+					/*registered by group::tests and removed since*/
+					continue;
+				}
+			};
+
+			let r :ECSecurityReport = grp.security_report().unwrap();
+			assert!(r.field_prime,"{} field",name);
+			assert!(r.order_prime,"{} order",name);
+			assert!(r.hasse_ok,"{} hasse",name);
+			assert!(!r.anomalous,"{} anomalous",name);
+			assert!(r.cofactor > zero(),"{} cofactor",name);
+			assert!(!format!("{}",r).is_empty());
+		}
+	}
+
+	#[test]
+	fn test_gf2_irreducible() {
+		/*x^163 + x^7 + x^6 + x^3 + 1*/
+		let f :BigInt = (BigInt::from(1u32) << 163) | BigInt::from(0xc9u32);
+		assert!(gf2_poly_irreducible(&f));
+		/*x^4 + x + 1 and x^4 + x^3 + x^2 + x + 1 irreducible , x^4 + x^2 + 1 = (x^2 + x + 1)^2*/
+		assert!(gf2_poly_irreducible(&BigInt::from(0x13u32)));
+		assert!(gf2_poly_irreducible(&BigInt::from(0x1fu32)));
+		assert!(!gf2_poly_irreducible(&BigInt::from(0x15u32)));
+		/*x^6 + x^5 + x^4 + x^3 + x^2 + x + 1 = (x^3 + x + 1)(x^3 + x^2 + 1)*/
+		assert!(!gf2_poly_irreducible(&BigInt::from(0x7fu32)));
+		/*x^163 + x^7 + x^6 + x^3 + x has the root 0*/
+		assert!(!gf2_poly_irreducible(&((BigInt::from(1u32) << 163) | BigInt::from(0xcau32))));
+	}
+}
@@ -0,0 +1,385 @@
+use crate::group::*;
+use crate::mont::*;
+use crate::randop::*;
+use crate::utils::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use num_bigint::{BigInt,Sign};
+use num_traits::{zero,one};
+use sha1::{Sha1,Digest};
+use std::error::Error;
+
+ecsimple_error_class!{ECSeedError}
+
+const SHA1_OUT_BITS :i64 = 160;
+/*the brainpool p of 384 bits needs one update_seed , give some room*/
+const RFC5639_SEED_UPDATE_MAX :usize = 16;
+/*p is the smallest prime above the hash , so the gap is small*/
+const RFC5639_PRIME_GAP_MAX :u64 = 0x10000;
+/*brainpoolP384r1 takes 5655 updates from its seed*/
+const RFC5639_CURVE_UPDATE_MAX :usize = 0x4000;
+/*the curve seeds are the 160 bits pieces of e , as the prime seeds are the ones of pi*/
+const RFC5639_CURVE_SEEDS :[(i64,&str);7] = [
+	(160,"2B7E151628AED2A6ABF7158809CF4F3C762E7160"),
+	(192,"F38B4DA56A784D9045190CFEF324E7738926CFBE"),
+	(224,"5F4BF8D8D8C31D763DA06C80ABB1185EB4F7C7B5"),
+	(256,"757F5958490CFD47D7C19BB42158D9554F7B46BC"),
+	(320,"ED55C4D79FD5F24D6613C31C3839A2DDF8A9A276"),
+	(384,"BCFBFA1C877C56284DAB79CD4C2B3293D20E9E5E"),
+	(512,"AF02AC60ACC93ED874422A52ECB238FEEE5AB6AD"),
+];
+const SEED_PRIME_ROUNDS :usize = 12;
+
+fn sha1_seed(seed :&BigInt,seed_len :usize) -> BigInt {
+	let (_,mut bevecs) = seed.to_bytes_be();
+	while bevecs.len() < seed_len {
+		bevecs.insert(0,0x0);
+	}
+	let mut hasher = Sha1::new();
+	hasher.update(&bevecs);
+	let digest = hasher.finalize().to_vec();
+	BigInt::from_bytes_be(Sign::Plus,&digest)
+}
+
+/*
+	X9.62 A.3.3 and RFC 5639 find_integer:
+	W0 is the rightmost h bits of SHA1(seed) , Wi = SHA1(seed + i mod 2^g) ,
+	return W0 || W1 || ... || Ws as bits long integer
+*/
+fn seed_to_integer(seed :&BigInt,seed_len :usize,bits :i64,cleartop :bool) -> BigInt {
+	let ov :BigInt = one();
+	let s :i64 = (bits - 1) / SHA1_OUT_BITS;
+	let h :i64 = bits - SHA1_OUT_BITS * s;
+	let gmod :BigInt = &ov << (seed_len * 8);
+	let mut retv :BigInt = sha1_seed(seed,seed_len) & ((&ov << h) - &ov);
+	if cleartop {
+		let topbit :BigInt = &ov << (h - 1);
+		if (&retv & &topbit) != zero() {
+			retv = &retv - &topbit;
+		}
+	}
+	let mut i :i64 = 1;
+	while i <= s {
+		let curseed :BigInt = (seed + BigInt::from(i)) % &gmod;
+		retv = (retv << SHA1_OUT_BITS) | sha1_seed(&curseed,seed_len);
+		i += 1;
+	}
+	ecsimple_log_trace!("seed 0x{:X} bits {} integer 0x{:X}",seed,bits,retv);
+	retv
+}
+
+/*X9.62 A.3.3.1 , the r with r * b^2 = a^3 mod p for prime field of bits*/
+pub fn x962_seed_prime_coefficient(seed :&BigInt,seed_len :usize,bits :i64) -> BigInt {
+	seed_to_integer(seed,seed_len,bits,true)
+}
+
+/*X9.62 A.3.3.2 , the b coefficient for binary field of degree m*/
+pub fn x962_seed_binary_coefficient(seed :&BigInt,seed_len :usize,degree :i64) -> BigInt {
+	seed_to_integer(seed,seed_len,degree,false)
+}
+
+/*RFC 5639 find_integer with 160 bits seed*/
+pub fn rfc5639_find_integer(seed :&BigInt,bits :i64) -> BigInt {
+	seed_to_integer(seed,(SHA1_OUT_BITS / 8) as usize,bits,false)
+}
+
+/*RFC 5639 update_seed*/
+pub fn rfc5639_update_seed(seed :&BigInt) -> BigInt {
+	let ov :BigInt = one();
+	(seed + &ov) % (&ov << SHA1_OUT_BITS)
+}
+
+fn rfc5639_prime_from_integer(h :&BigInt,p :&BigInt) -> bool {
+	let four :BigInt = BigInt::from(4);
+	let three :BigInt = BigInt::from(3);
+	if p < h || (p % &four) != three {
+		return false;
+	}
+	let mut c :BigInt = h + nmod(&(&three - (h % &four)),&four);
+	let mut cnt :u64 = 0;
+	while &c < p {
+		if cnt >= RFC5639_PRIME_GAP_MAX {
+			return false;
+		}
+		if bn_is_probable_prime(&c,SEED_PRIME_ROUNDS) {
+			/*a smaller prime was found before p*/
+			return false;
+		}
+		c = &c + &four;
+		cnt += 1;
+	}
+	&c == p
+}
+
+/*
+	RFC 5639 section 5 : p is the smallest prime = 3 mod 4 not below find_integer(s) ,
+	seed updated when the candidate is rejected
+*/
+pub fn rfc5639_seed_check_prime(seed :&BigInt,p :&BigInt) -> bool {
+	let bits :i64 = get_max_bits(p);
+	let mut curseed :BigInt = seed.clone();
+	for _ in 0..RFC5639_SEED_UPDATE_MAX {
+		let h :BigInt = rfc5639_find_integer(&curseed,bits);
+		if rfc5639_prime_from_integer(&h,p) {
+			return true;
+		}
+		curseed = rfc5639_update_seed(&curseed);
+	}
+	false
+}
+
+/*x is a 4th power mod p , which is a square for p = 3 mod 4*/
+fn rfc5639_is_fourth_power(x :&BigInt,p :&BigInt) -> bool {
+	let ov :BigInt = one();
+	let four :BigInt = BigInt::from(4);
+	let e :BigInt = if (p % &four) == ov {
+		(p - &ov) / &four
+	} else {
+		(p - &ov) >> 1
+	};
+	x.modpow(&e,p) == ov
+}
+
+/*
+	RFC 5639 section 5 steps 3 , 7 and 8 : -3 = A*Z^4 solvable , B not a square ,
+	and the curve not singular
+*/
+pub fn rfc5639_check_coefficients(p :&BigInt,a :&BigInt,b :&BigInt) -> bool {
+	let ov :BigInt = one();
+	let zv :BigInt = zero();
+	let a :BigInt = nmod(a,p);
+	let b :BigInt = nmod(b,p);
+	if a == zv || b == zv {
+		return false;
+	}
+	let ainv :BigInt = a.modpow(&(p - BigInt::from(2)),p);
+	let t :BigInt = nmod(&(-BigInt::from(3) * &ainv),p);
+	if !rfc5639_is_fourth_power(&t,p) {
+		return false;
+	}
+	if b.modpow(&((p - &ov) >> 1),p) == ov {
+		return false;
+	}
+	let disc :BigInt = (BigInt::from(4) * a.modpow(&BigInt::from(3),p) + BigInt::from(27) * &b * &b) % p;
+	disc != zv
+}
+
+/*the seed A and B of the p bits brainpool curves generate from*/
+pub fn rfc5639_curve_seed(bits :i64) -> Option<BigInt> {
+	for (b,s) in RFC5639_CURVE_SEEDS.iter() {
+		if *b == bits {
+			return BigInt::parse_bytes(s.as_bytes(),16);
+		}
+	}
+	None
+}
+
+/*
+	RFC 5639 section 5 steps 1 to 8 from the curve seed , A and B taken of bits(p) - 1 bits ,
+	step 9 needs the point count , so the candidates before A and B are taken as refused by it
+*/
+pub fn rfc5639_seed_check_coefficients(seed :&BigInt,p :&BigInt,a :&BigInt,b :&BigInt) -> bool {
+	let ov :BigInt = one();
+	let zv :BigInt = zero();
+	let bits :i64 = get_max_bits(p) - 1;
+	let a :BigInt = nmod(a,p);
+	let b :BigInt = nmod(b,p);
+	let sqexp :BigInt = (p - &ov) >> 1;
+	let mut curseed :BigInt = seed.clone();
+	let mut cnt :usize = 0;
+	while cnt < RFC5639_CURVE_UPDATE_MAX {
+		let ca :BigInt = rfc5639_find_integer(&curseed,bits);
+		curseed = rfc5639_update_seed(&curseed);
+		cnt += 1;
+		if ca == zv {
+			continue;
+		}
+		/*step 3 : -3 = A*Z^4 solvable*/
+		let ainv :BigInt = ca.modpow(&(p - BigInt::from(2)),p);
+		if !rfc5639_is_fourth_power(&nmod(&(-BigInt::from(3) * &ainv),p),p) {
+			continue;
+		}
+		/*step 7 : B not a square*/
+		let mut cb :BigInt = rfc5639_find_integer(&curseed,bits);
+		while (cb == zv || cb.modpow(&sqexp,p) == ov) && cnt < RFC5639_CURVE_UPDATE_MAX {
+			curseed = rfc5639_update_seed(&curseed);
+			cnt += 1;
+			cb = rfc5639_find_integer(&curseed,bits);
+		}
+		/*step 8 : not singular*/
+		let disc :BigInt = (BigInt::from(4) * ca.modpow(&BigInt::from(3),p) + BigInt::from(27) * &cb * &cb) % p;
+		if disc != zv {
+			ecsimple_log_trace!("candidate {} A 0x{:X} B 0x{:X}",cnt,ca,cb);
+			if ca == a && cb == b {
+				return true;
+			}
+		}
+		curseed = rfc5639_update_seed(&curseed);
+		cnt += 1;
+	}
+	false
+}
+
+/*the twisted t1 curve is y^2 = x^3 - 3x + B*Z^6 with -3 = A*Z^4 of the r1 curve*/
+pub fn rfc5639_check_twist(p :&BigInt,ar :&BigInt,br :&BigInt,at :&BigInt,bt :&BigInt) -> bool {
+	let zv :BigInt = zero();
+	if nmod(&(at + BigInt::from(3)),p) != zv {
+		return false;
+	}
+	let lhs :BigInt = (bt * bt % p) * ar.modpow(&BigInt::from(3),p) % p;
+	let rhs :BigInt = nmod(&(-BigInt::from(27) * br * br),p);
+	lhs == rhs
+}
+
+impl ECGroup {
+	pub fn get_seed(&self) -> Option<Vec<u8>> {
+		let seed :BigInt;
+		let seed_len :usize;
+		if self.is_prime_group() {
+			let grp = self.get_prime_group();
+			seed = grp.seed.clone();
+			seed_len = grp.seed_len;
+		} else {
+			let grp = self.get_bn_group();
+			seed = grp.seed.clone();
+			seed_len = grp.seed_len;
+		}
+		if seed_len == 0 {
+			return None;
+		}
+		let (_,mut bevecs) = seed.to_bytes_be();
+		while bevecs.len() < seed_len {
+			bevecs.insert(0,0x0);
+		}
+		Some(bevecs)
+	}
+
+	/*
+		check the coefficients derive from the seed :
+		X9.62 prime and binary procedure , brainpool curves by RFC 5639 ,
+		where the seed is the one the field prime generated from ,
+		A and B are generated again from the curve seed of the field size
+	*/
+	pub fn verify_seed(&self) -> Result<bool,Box<dyn Error>> {
+		if self.is_prime_group() {
+			let grp :ECGroupPrime = self.get_prime_group();
+			if grp.seed_len == 0 {
+				ecsimple_new_error!{ECSeedError,"no seed in [{}]",grp.curvename}
+			}
+			let montv :MontNum = MontNum::new(&grp.p)?;
+			let a :BigInt = montv.mont_from(&grp.a);
+			let b :BigInt = montv.mont_from(&grp.b);
+			if grp.curvename.starts_with("brainpool") {
+				if !rfc5639_seed_check_prime(&grp.seed,&grp.p) {
+					return Ok(false);
+				}
+				let cseed :BigInt = match rfc5639_curve_seed(get_max_bits(&grp.p)) {
+					Some(s) => s,
+					None => {
+						ecsimple_new_error!{ECSeedError,"no RFC 5639 curve seed for [{}]",grp.curvename}
+					},
+				};
+				if !grp.curvename.ends_with("t1") {
+					return Ok(rfc5639_seed_check_coefficients(&cseed,&grp.p,&a,&b));
+				}
+				/*A and B of the twisted curve come from the r1 one*/
+				let rname :String = format!("{}r1",&grp.curvename[..grp.curvename.len() - 2]);
+				let rgrp :ECGroupPrime = ecc_get_curve_group(&rname)?.get_prime_group();
+				let ar :BigInt = montv.mont_from(&rgrp.a);
+				let br :BigInt = montv.mont_from(&rgrp.b);
+				return Ok(rgrp.p == grp.p && rfc5639_seed_check_coefficients(&cseed,&grp.p,&ar,&br) && rfc5639_check_twist(&grp.p,&ar,&br,&a,&b));
+
+			}
+			let r :BigInt = x962_seed_prime_coefficient(&grp.seed,grp.seed_len,get_max_bits(&grp.p));
+			let lhs :BigInt = (&r * &b * &b) % &grp.p;
+			let rhs :BigInt = a.modpow(&BigInt::from(3),&grp.p);
+			ecsimple_log_trace!("[{}] r 0x{:X} r*b^2 0x{:X} a^3 0x{:X}",grp.curvename,r,lhs,rhs);
+			if r == zero() {
+				return Ok(false);
+			}
+			return Ok(lhs == rhs);
+		}
+
+		let grp :ECGroupBnGf2m = self.get_bn_group();
+		if grp.seed_len == 0 {
+			ecsimple_new_error!{ECSeedError,"no seed in [{}]",grp.curvename}
+		}
+		let b :BigInt = x962_seed_binary_coefficient(&grp.seed,grp.seed_len,get_max_bits(&grp.p) - 1);
+		ecsimple_log_trace!("[{}] b 0x{:X} curve b 0x{:X}",grp.curvename,b,grp.b.to_bigint());
+		Ok(b == grp.b.to_bigint())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::consts::*;
+
+	#[test]
+	fn test_verify_seed_brainpool() {
+		let names :Vec<String> = ecc_get_curve_names().into_iter().filter(|n| n.starts_with("brainpool")).collect();
+		assert_eq!(names.len(),14);
+		for name in names.iter() {
+			let grp :ECGroup = ecc_get_curve_group(name).unwrap();
+			assert!(grp.verify_seed().unwrap(),"{}",name);
+		}
+	}
+
+	#[test]
+	fn test_verify_seed_x962() {
+		for name in [PRIME256v1_NAME,SECP384r1_NAME,SECT113r1_NAME,SECT193r1_NAME].iter() {
+			let grp :ECGroup = ecc_get_curve_group(name).unwrap();
+			assert!(grp.verify_seed().unwrap(),"{}",name);
+		}
+	}
+
+	#[test]
+	fn test_brainpool_coefficients_tampered() {
+		let grp :ECGroupPrime = ecc_get_curve_group(BRAINPOOLP256r1_NAME).unwrap().get_prime_group();
+		let montv :MontNum = MontNum::new(&grp.p).unwrap();
+		let a :BigInt = montv.mont_from(&grp.a);
+		let b :BigInt = montv.mont_from(&grp.b);
+		let cseed :BigInt = rfc5639_curve_seed(256).unwrap();
+		assert!(rfc5639_check_coefficients(&grp.p,&a,&b));
+		assert!(rfc5639_seed_check_coefficients(&cseed,&grp.p,&a,&b));
+		/*a square B is refused by step 7*/
+		assert!(!rfc5639_check_coefficients(&grp.p,&a,&(&b * &b)));
+		assert!(!rfc5639_seed_check_coefficients(&cseed,&grp.p,&a,&(&b * &b)));
+		/*A * 2^4 and B * 2^2 keep the conditions of section 5 , but not the seed*/
+		let a2 :BigInt = nmod(&(&a * BigInt::from(16)),&grp.p);
+		let b2 :BigInt = nmod(&(&b * BigInt::from(4)),&grp.p);
+		assert!(rfc5639_check_coefficients(&grp.p,&a2,&b));
+		assert!(rfc5639_check_coefficients(&grp.p,&a,&b2));
+		assert!(!rfc5639_seed_check_coefficients(&cseed,&grp.p,&a2,&b));
+		assert!(!rfc5639_seed_check_coefficients(&cseed,&grp.p,&a,&b2));
+		/*the seed of another size*/
+		assert!(!rfc5639_seed_check_coefficients(&rfc5639_curve_seed(384).unwrap(),&grp.p,&a,&b));
+		assert!(rfc5639_curve_seed(200).is_none());
+
+		/*the prime seed of another curve*/
+		let grp384 :ECGroupPrime = ecc_get_curve_group(BRAINPOOLP384r1_NAME).unwrap().get_prime_group();
+		assert!(rfc5639_seed_check_prime(&grp.seed,&grp.p));
+		assert!(!rfc5639_seed_check_prime(&grp384.seed,&grp.p));
+
+		/*t1 B changed*/
+		let tgrp :ECGroupPrime = ecc_get_curve_group(BRAINPOOLP256t1_NAME).unwrap().get_prime_group();
+		let at :BigInt = montv.mont_from(&tgrp.a);
+		let bt :BigInt = montv.mont_from(&tgrp.b);
+		assert!(rfc5639_check_twist(&grp.p,&a,&b,&at,&bt));
+		assert!(!rfc5639_check_twist(&grp.p,&a,&b,&at,&nmod(&(&bt * BigInt::from(4)),&grp.p)));
+		assert!(!rfc5639_check_twist(&grp.p,&a2,&b,&at,&bt));
+	}
+
+
+	#[test]
+	fn test_brainpool_der_seed() {
+		let grp :ECGroup = ecc_get_curve_group(BRAINPOOLP256r1_NAME).unwrap();
+		let der :Vec<u8> = grp.to_der(EC_UNCOMPRESSED,EC_PARAMS_EXLICIT).unwrap();
+		assert!(ECGroup::from_der_canonical(&der,false).unwrap().get_seed().is_none());
+		let der :Vec<u8> = grp.to_der(EC_UNCOMPRESSED,EC_PARAMS_EXLICIT_SEED).unwrap();
+		assert_eq!(ECGroup::from_der_canonical(&der,false).unwrap().get_seed(),grp.get_seed());
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let der :Vec<u8> = grp.to_der(EC_UNCOMPRESSED,EC_PARAMS_EXLICIT).unwrap();
+		assert_eq!(ECGroup::from_der_canonical(&der,false).unwrap().get_seed(),grp.get_seed());
+	}
+}
@@ -1,33 +1,16 @@
 
 use num_bigint::{BigInt,Sign,BigUint};
-use asn1obj_codegen::{asn1_sequence};
-use asn1obj::base::{Asn1BigNum};
-use asn1obj::complex::{Asn1Seq};
-use asn1obj::{asn1obj_error_class,asn1obj_new_error};
 use asn1obj::asn1impl::Asn1Op;
-use asn1obj::strop::asn1_format_line;
-use std::io::Write;
+use crate::ecasn1::*;
 
 
 use std::error::Error;
+#[allow(unused_imports)]
 use crate::*;
+use crate::utils::bigint_to_fixed_bytes;
 
 ecsimple_error_class!{ECSignatureError}
 
-#[derive(Clone)]
-#[asn1_sequence()]
-struct Asn1ECSignatureElem {
-	pub r :Asn1BigNum,
-	pub s :Asn1BigNum,
-}
-
-#[derive(Clone)]
-#[asn1_sequence()]
-struct Asn1ECSignature {
-	pub elem :Asn1Seq<Asn1ECSignatureElem>,
-}
-
-
 pub struct ECSignature {
 	pub r : BigInt,
 	pub s : BigInt,
@@ -68,6 +51,23 @@ impl ECSignature {
 		let rdata = objec.encode_asn1()?;
 		Ok(rdata)
 	}
+
+	/*r || s each of size bytes , as JOSE COSE and DNSSEC use*/
+	pub fn to_fixed_bytes(&self,size :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = bigint_to_fixed_bytes(&self.r,size)?;
+		retv.extend_from_slice(&bigint_to_fixed_bytes(&self.s,size)?);
+		Ok(retv)
+	}
+
+	pub fn from_fixed_bytes(data :&[u8]) -> Result<ECSignature,Box<dyn Error>> {
+		if data.is_empty() || (data.len() % 2) != 0 {
+			ecsimple_new_error!{ECSignatureError,"fixed signature len {} not even",data.len()}
+		}
+		let half :usize = data.len() / 2;
+		let r :BigInt = BigInt::from_bytes_be(Sign::Plus,&data[..half]);
+		let s :BigInt = BigInt::from_bytes_be(Sign::Plus,&data[half..]);
+		Ok(ECSignature::new(&r,&s))
+	}
 }
 
 impl std::fmt::Display for ECSignature {
@@ -89,8 +89,4 @@ impl std::cmp::PartialEq for ECSignature {
 		}
 		retv
 	}
-
-	fn ne(&self,other :&Self) -> bool {
-		return ! self.eq(other);
-	}
-}
\ No newline at end of file
+}
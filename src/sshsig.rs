@@ -0,0 +1,500 @@
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::signature::*;
+use crate::ssh::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use chrono::{Local,NaiveDateTime,TimeZone,Utc};
+use std::error::Error;
+
+ecsimple_error_class!{ECSshSigError}
+
+pub const PEM_SSH_SIGNATURE :&str = "SSH SIGNATURE";
+const SSHSIG_MAGIC :&[u8] = b"SSHSIG";
+const SSHSIG_VERSION :u32 = 1;
+/*ecdsa-sha2-nistp256-cert-v01@openssh.com and the like , certificates not supported*/
+const SSH_CERT_SUFFIX :&str = "-cert-v01@openssh.com";
+
+/*PROTOCOL.sshsig allows only these two , ssh-keygen signs with sha512*/
+pub fn sshsig_hash_name(htype :ECHashType) -> Result<&'static str,Box<dyn Error>> {
+	match htype {
+		ECHashType::Sha256 => Ok("sha256"),
+		ECHashType::Sha512 => Ok("sha512"),
+		_ => {
+			ecsimple_new_error!{ECSshSigError,"hash [{}] not for sshsig",htype.name()}
+		}
+	}
+}
+
+pub fn sshsig_hash_from_name(name :&str) -> Result<ECHashType,Box<dyn Error>> {
+	match name {
+		"sha256" => Ok(ECHashType::Sha256),
+		"sha512" => Ok(ECHashType::Sha512),
+		_ => {
+			ecsimple_new_error!{ECSshSigError,"unsupported sshsig hash [{}]",name}
+		}
+	}
+}
+
+/*RFC 5656 3.1.2 , string key type and string of mpint r and mpint s*/
+pub fn ssh_ecdsa_signature_encode(pubkey :&ECPublicKey,sig :&ECSignature) -> Result<Vec<u8>,Box<dyn Error>> {
+	let sname :String = ssh_curve_from_group(&pubkey.get_group())?;
+	let mut rs :Vec<u8> = Vec::new();
+	ssh_put_mpint(&mut rs,&sig.r);
+	ssh_put_mpint(&mut rs,&sig.s);
+	let mut retv :Vec<u8> = Vec::new();
+	ssh_put_string(&mut retv,format!("{}{}",SSH_ECDSA_PREFIX,sname).as_bytes());
+	ssh_put_string(&mut retv,&rs);
+	Ok(retv)
+}
+
+pub fn ssh_ecdsa_signature_decode(pubkey :&ECPublicKey,data :&[u8]) -> Result<ECSignature,Box<dyn Error>> {
+	let sname :String = ssh_curve_from_group(&pubkey.get_group())?;
+	let mut r :SshReader = SshReader::new(data);
+	let stype :String = r.read_str()?;
+	if stype != format!("{}{}",SSH_ECDSA_PREFIX,sname) {
+		ecsimple_new_error!{ECSshSigError,"signature type [{}] not for curve [{}]",stype,sname}
+	}
+	let rs :Vec<u8> = r.read_string()?;
+	if r.remain() != 0 {
+		ecsimple_new_error!{ECSshSigError,"{} bytes left after signature",r.remain()}
+	}
+	let mut rr :SshReader = SshReader::new(&rs);
+	let sr = rr.read_mpint()?;
+	let ss = rr.read_mpint()?;
+	if rr.remain() != 0 {
+		ecsimple_new_error!{ECSshSigError,"{} bytes left after r s",rr.remain()}
+	}
+	Ok(ECSignature::new(&sr,&ss))
+}
+
+pub struct ECSshSig {
+	pub pubkey :ECPublicKey,
+	pub namespace :String,
+	pub reserved :Vec<u8>,
+	pub hash_alg :ECHashType,
+	pub signature :ECSignature,
+}
+
+/*the blob that is really signed , the message hashed by hash_alg first*/
+fn sshsig_signed_data(namespace :&str,reserved :&[u8],htype :ECHashType,message :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let mut retv :Vec<u8> = SSHSIG_MAGIC.to_vec();
+	ssh_put_string(&mut retv,namespace.as_bytes());
+	ssh_put_string(&mut retv,reserved);
+	ssh_put_string(&mut retv,sshsig_hash_name(htype)?.as_bytes());
+	ssh_put_string(&mut retv,&htype.digest(message));
+	Ok(retv)
+}
+
+impl ECSshSig {
+	pub fn sign(privkey :&ECPrivateKey,namespace :&str,message :&[u8],htype :ECHashType) -> Result<ECSshSig,Box<dyn Error>> {
+		if namespace.is_empty() {
+			ecsimple_new_error!{ECSshSigError,"empty namespace"}
+		}
+		let pubkey :ECPublicKey = privkey.export_pubkey();
+		let sdata :Vec<u8> = sshsig_signed_data(namespace,&[],htype,message)?;
+		let hashv :Vec<u8> = ECHashType::from_order_bits(pubkey.get_group().info().order_bits).digest(&sdata);
+		let sig :ECSignature = privkey.sign_base(&hashv)?;
+		Ok(ECSshSig {
+			pubkey,
+			namespace : namespace.to_string(),
+			reserved : Vec::new(),
+			hash_alg : htype,
+			signature : sig,
+		})
+	}
+
+	pub fn from_blob(data :&[u8]) -> Result<ECSshSig,Box<dyn Error>> {
+		if !data.starts_with(SSHSIG_MAGIC) {
+			ecsimple_new_error!{ECSshSigError,"no SSHSIG magic"}
+		}
+		let mut r :SshReader = SshReader::new(&data[SSHSIG_MAGIC.len()..]);
+		let version :u32 = r.read_u32()?;
+		if version != SSHSIG_VERSION {
+			ecsimple_new_error!{ECSshSigError,"sshsig version {} != {}",version,SSHSIG_VERSION}
+		}
+		let pubkey :ECPublicKey = ECPublicKey::from_ssh_blob(&r.read_string()?)?;
+		let namespace :String = r.read_str()?;
+		let reserved :Vec<u8> = r.read_string()?;
+		let htype :ECHashType = sshsig_hash_from_name(&r.read_str()?)?;
+		let sig :ECSignature = ssh_ecdsa_signature_decode(&pubkey,&r.read_string()?)?;
+		if r.remain() != 0 {
+			ecsimple_new_error!{ECSshSigError,"{} bytes left after sshsig",r.remain()}
+		}
+		Ok(ECSshSig {
+			pubkey,
+			namespace,
+			reserved,
+			hash_alg : htype,
+			signature : sig,
+		})
+	}
+
+	pub fn to_blob(&self) -> Result<Vec<u8>,Box<dyn Error>> {
+		let mut retv :Vec<u8> = SSHSIG_MAGIC.to_vec();
+		ssh_put_u32(&mut retv,SSHSIG_VERSION);
+		ssh_put_string(&mut retv,&self.pubkey.to_ssh_blob()?);
+		ssh_put_string(&mut retv,self.namespace.as_bytes());
+		ssh_put_string(&mut retv,&self.reserved);
+		ssh_put_string(&mut retv,sshsig_hash_name(self.hash_alg)?.as_bytes());
+		ssh_put_string(&mut retv,&ssh_ecdsa_signature_encode(&self.pubkey,&self.signature)?);
+		Ok(retv)
+	}
+
+	pub fn from_armored(ins :&str) -> Result<ECSshSig,Box<dyn Error>> {
+		let b :PemBlock = pem_find_block(ins,&[PEM_SSH_SIGNATURE])?;
+		ECSshSig::from_blob(&b.data)
+	}
+
+	/*-----BEGIN SSH SIGNATURE----- with 70 chars a line as ssh-keygen -Y sign*/
+	pub fn to_armored(&self) -> Result<String,Box<dyn Error>> {
+		Ok(der_to_pem_width(&self.to_blob()?,PEM_SSH_SIGNATURE,SSH_ARMOR_LINE_LENGTH))
+	}
+
+	/*the key inside the signature is used , check it is the trusted one with the allowed signers*/
+	pub fn verify(&self,namespace :&str,message :&[u8]) -> Result<bool,Box<dyn Error>> {
+		if self.namespace != namespace {
+			ecsimple_log_trace!("namespace [{}] != [{}]",self.namespace,namespace);
+			return Ok(false);
+		}
+		let sdata :Vec<u8> = sshsig_signed_data(&self.namespace,&self.reserved,self.hash_alg,message)?;
+		let hashv :Vec<u8> = ECHashType::from_order_bits(self.pubkey.get_group().info().order_bits).digest(&sdata);
+		self.pubkey.verify_base(&self.signature,&hashv)
+	}
+}
+
+pub fn sshsig_sign(privkey :&ECPrivateKey,namespace :&str,message :&[u8]) -> Result<String,Box<dyn Error>> {
+	let sig :ECSshSig = ECSshSig::sign(privkey,namespace,message,ECHashType::Sha512)?;
+	sig.to_armored()
+}
+
+/*one line of the ssh-keygen ALLOWED SIGNERS file*/
+#[derive(Clone)]
+pub struct ECSshAllowedSigner {
+	/*comma list of patterns*/
+	pub principals :String,
+	pub namespaces :Option<String>,
+	pub cert_authority :bool,
+	/*unix seconds*/
+	pub valid_after :Option<i64>,
+	pub valid_before :Option<i64>,
+	pub pubkey :ECPublicKey,
+}
+
+/*the ssh match_pattern with * and ? , backtracking only to the last * so no exponential time*/
+fn ssh_match_pattern(s :&[u8],pat :&[u8]) -> bool {
+	let mut si :usize = 0;
+	let mut pi :usize = 0;
+	let mut star :Option<usize> = None;
+	let mut mark :usize = 0;
+	while si < s.len() {
+		if pi < pat.len() && pat[pi] == b'*' {
+			star = Some(pi);
+			mark = si;
+			pi += 1;
+		} else if pi < pat.len() && (pat[pi] == b'?' || pat[pi] == s[si]) {
+			si += 1;
+			pi += 1;
+		} else if let Some(st) = star {
+			/*let the last * take one more char*/
+			pi = st + 1;
+			mark += 1;
+			si = mark;
+		} else {
+			return false;
+		}
+	}
+	while pi < pat.len() && pat[pi] == b'*' {
+		pi += 1;
+	}
+	pi == pat.len()
+}
+
+/*negated pattern match denies at once as match_pattern_list does*/
+fn ssh_match_pattern_list(s :&str,patlist :&str) -> bool {
+	let mut matched :bool = false;
+	for p in patlist.split(",") {
+		let p :&str = p.trim();
+		if let Some(np) = p.strip_prefix('!') {
+			if ssh_match_pattern(s.as_bytes(),np.as_bytes()) {
+				return false;
+			}
+		} else if ssh_match_pattern(s.as_bytes(),p.as_bytes()) {
+			matched = true;
+		}
+	}
+	matched
+}
+
+/*YYYYMMDD[HHMM[SS]] with Z for UTC , local time otherwise*/
+fn sshsig_parse_time(s :&str) -> Result<i64,Box<dyn Error>> {
+	let mut ts :&str = s;
+	let mut utc :bool = false;
+	if ts.ends_with("Z") || ts.ends_with("z") {
+		utc = true;
+		ts = &ts[..(ts.len() - 1)];
+	}
+	if !ts.bytes().all(|c| c.is_ascii_digit()) || (ts.len() != 8 && ts.len() != 12 && ts.len() != 14) {
+		ecsimple_new_error!{ECSshSigError,"time [{}] not valid",s}
+	}
+	let full :String = format!("{:0<14}",ts);
+	let ndt :NaiveDateTime = match NaiveDateTime::parse_from_str(&full,"%Y%m%d%H%M%S") {
+		Ok(v) => v,
+		Err(e) => {
+			ecsimple_new_error!{ECSshSigError,"time [{}] not valid {:?}",s,e}
+		}
+	};
+	if utc {
+		return Ok(Utc.from_utc_datetime(&ndt).timestamp());
+	}
+	match Local.from_local_datetime(&ndt).earliest() {
+		Some(v) => Ok(v.timestamp()),
+		None => {
+			ecsimple_new_error!{ECSshSigError,"time [{}] not in local time",s}
+		}
+	}
+}
+
+/*split at the first blank outside double quotes*/
+fn sshsig_next_token(s :&str) -> (String,String) {
+	let mut inquote :bool = false;
+	let mut idx :usize = s.len();
+	for (i,c) in s.char_indices() {
+		if c == '"' {
+			inquote = !inquote;
+		} else if !inquote && (c == ' ' || c == '\t') {
+			idx = i;
+			break;
+		}
+	}
+	(s[..idx].to_string(),s[idx..].trim_start().to_string())
+}
+
+fn sshsig_unquote(s :&str) -> String {
+	if s.len() >= 2 && s.starts_with("\"") && s.ends_with("\"") {
+		return s[1..(s.len() - 1)].to_string();
+	}
+	s.to_string()
+}
+
+fn sshsig_parse_options(signer :&mut ECSshAllowedSigner,opts :&str) -> Result<(),Box<dyn Error>> {
+	let mut inquote :bool = false;
+	let mut cur :String = "".to_string();
+	let mut items :Vec<String> = Vec::new();
+	for c in opts.chars() {
+		if c == '"' {
+			inquote = !inquote;
+		}
+		if c == ',' && !inquote {
+			items.push(cur.clone());
+			cur = "".to_string();
+		} else {
+			cur.push(c);
+		}
+	}
+	items.push(cur);
+	for o in items.iter() {
+		let (name,val) = match o.find("=") {
+			Some(i) => (o[..i].to_ascii_lowercase(),Some(sshsig_unquote(&o[(i + 1)..]))),
+			None => (o.to_ascii_lowercase(),None),
+		};
+		match (name.as_str(),val) {
+			("cert-authority",None) => {
+				signer.cert_authority = true;
+			},
+			("namespaces",Some(v)) => {
+				signer.namespaces = Some(v);
+			},
+			("valid-after",Some(v)) => {
+				signer.valid_after = Some(sshsig_parse_time(&v)?);
+			},
+			("valid-before",Some(v)) => {
+				signer.valid_before = Some(sshsig_parse_time(&v)?);
+			},
+			_ => {
+				ecsimple_new_error!{ECSshSigError,"unknown option [{}]",o}
+			},
+		}
+
+	}
+	Ok(())
+}
+
+/*
+	"principals [options] keytype base64 [comment]" lines ,
+	keys other than ecdsa and the certificate key types skipped
+*/
+pub fn sshsig_parse_allowed_signers(ins :&str) -> Result<Vec<ECSshAllowedSigner>,Box<dyn Error>> {
+	let mut retv :Vec<ECSshAllowedSigner> = Vec::new();
+	for (lineno,l) in ins.lines().enumerate() {
+		let l :&str = l.trim();
+		if l.is_empty() || l.starts_with("#") {
+			continue;
+		}
+		let (principals,rest) = sshsig_next_token(l);
+		let (mut tok,mut rest) = sshsig_next_token(&rest);
+		let mut opts :Option<String> = None;
+		if !tok.starts_with("ssh-") && !tok.starts_with("ecdsa-") && !tok.starts_with("sk-") {
+			opts = Some(tok);
+			let (t,r) = sshsig_next_token(&rest);
+			tok = t;
+			rest = r;
+		}
+		if !tok.starts_with(SSH_ECDSA_PREFIX) || tok.ends_with(SSH_CERT_SUFFIX) {
+			ecsimple_log_trace!("line {} skip key type [{}]",lineno + 1,tok);
+			continue;
+		}
+		let pubkey :ECPublicKey = match ssh_parse_public_line(&format!("{} {}",tok,rest)) {
+			Ok((k,_)) => k,
+			Err(e) => {
+				ecsimple_new_error!{ECSshSigError,"line {} key error {:?}",lineno + 1,e}
+			}
+		};
+		let mut signer :ECSshAllowedSigner = ECSshAllowedSigner {
+			principals : sshsig_unquote(&principals),
+			namespaces : None,
+			cert_authority : false,
+			valid_after : None,
+			valid_before : None,
+			pubkey,
+		};
+		if let Some(o) = opts.as_ref() {
+			sshsig_parse_options(&mut signer,o)?;
+		}
+
+		retv.push(signer);
+	}
+	Ok(retv)
+}
+
+impl ECSshAllowedSigner {
+	/*cert-authority lines are for certificates , never a plain key*/
+	pub fn allows(&self,principal :&str,namespace :&str,pubkey :&ECPublicKey,now :i64) -> Result<bool,Box<dyn Error>> {
+		if self.cert_authority {
+			return Ok(false);
+		}
+		if self.pubkey.to_ssh_blob()? != pubkey.to_ssh_blob()? {
+			return Ok(false);
+		}
+		if !ssh_match_pattern_list(principal,&self.principals) {
+			return Ok(false);
+		}
+		if self.namespaces.is_some() && !ssh_match_pattern_list(namespace,self.namespaces.as_ref().unwrap()) {
+			return Ok(false);
+		}
+		if self.valid_after.is_some() && now < self.valid_after.unwrap() {
+			return Ok(false);
+		}
+		if self.valid_before.is_some() && now > self.valid_before.unwrap() {
+			return Ok(false);
+		}
+		Ok(true)
+	}
+}
+
+/*ssh-keygen -Y verify -f allowed_signers -I principal -n namespace -s sigfile*/
+pub fn sshsig_verify(allowed :&[ECSshAllowedSigner],principal :&str,namespace :&str,armored :&str,message :&[u8]) -> Result<bool,Box<dyn Error>> {
+	let sig :ECSshSig = ECSshSig::from_armored(armored)?;
+	if !sig.verify(namespace,message)? {
+		return Ok(false);
+	}
+	let now :i64 = Utc::now().timestamp();
+	for a in allowed.iter() {
+		if a.allows(principal,namespace,&sig.pubkey,now)? {
+			return Ok(true);
+		}
+	}
+	ecsimple_log_trace!("no allowed signer for [{}] namespace [{}]",principal,namespace);
+	Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SSHSIG_MESSAGE :&[u8] = b"hello ecsimple\n";
+	/*ssh-keygen -Y sign -n file of nistp256 , sha512 as default*/
+	const SSH_KEYGEN_SIG_P256 :&str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EEYQWwgdMbmrAqwJ4g3iyLysJ1fJ83fKw98kzT0KUATCeoJTeVdmS2w8eBv/qWoDJVXDA/
+EMOk/P5dyRj2WZLCCAAAAARmaWxlAAAAAAAAAAZzaGE1MTIAAABkAAAAE2VjZHNhLXNoYT
+ItbmlzdHAyNTYAAABJAAAAIQDi35rVE4QcyZeyXTcx3vrp67xNaCxzBVQ7MkAAxksUNgAA
+ACBMAU6/BV1qe+96DmbF43JOMFXDQWeMRVXhoD/ityeZ0w==
+-----END SSH SIGNATURE-----
+";
+	/*the same with -O hashalg=sha256*/
+	const SSH_KEYGEN_SIG_P256_SHA256 :&str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EEYQWwgdMbmrAqwJ4g3iyLysJ1fJ83fKw98kzT0KUATCeoJTeVdmS2w8eBv/qWoDJVXDA/
+EMOk/P5dyRj2WZLCCAAAAARmaWxlAAAAAAAAAAZzaGEyNTYAAABkAAAAE2VjZHNhLXNoYT
+ItbmlzdHAyNTYAAABJAAAAIFsh9/7CwfxeURku0PKyj4Cv1Xm9RBgodUgPItfkhrthAAAA
+IQCUfnbXbbGewNGxR9KvQAa3bFyqlqya3xtewF7HTUELCQ==
+-----END SSH SIGNATURE-----
+";
+	/*nistp384*/
+	const SSH_KEYGEN_SIG_P384 :&str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAIgAAAATZWNkc2Etc2hhMi1uaXN0cDM4NAAAAAhuaXN0cDM4NAAAAG
+EEGFNSb9GKH1XCz6oNUM+ytQX+8FceV3tliF+WfqdUgSiEP4JkpxjgeDfeBy5U8YKdMBhb
+I/uvsDfwXtOvHlGF8w/tIHeNP3PXcoYZGltIw7JZ1BeVLpr6imWfh4ttpm2rAAAABGZpbG
+UAAAAAAAAABnNoYTUxMgAAAIQAAAATZWNkc2Etc2hhMi1uaXN0cDM4NAAAAGkAAAAxAMaZ
+TAyfJYfaxHp3P4xzoUvMJnNrbCdgHPWLbYhyfLNXGIDR7hBZXqrgzgQY10dxuAAAADB1Up
+5sY1X+T3hQhaSUJyQhseTF6P4+MP9evaWPTWr0j/wXYoyrJbe3mIPLdBNVGwk=
+-----END SSH SIGNATURE-----
+";
+	/*nistp521*/
+	const SSH_KEYGEN_SIG_P521 :&str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAKwAAAATZWNkc2Etc2hhMi1uaXN0cDUyMQAAAAhuaXN0cDUyMQAAAI
+UEAJvTWG9PRWy+Sf+4zawV3XlrPwO1Qwgo9/mQooy+mkxONqKZygFlHoQ4B5dvdmFvWPs5
+BTsw1Oqgq7HuqwxsO6MxAX/axjs5ySa5c1zBUjM572kFTG0ZEbw+R9VJGD1L1urS8QcGOl
+9KnnQBr5kymIhDK/vrrqH3kJBxJhAmCrY3WBaSAAAABGZpbGUAAAAAAAAABnNoYTUxMgAA
+AKYAAAATZWNkc2Etc2hhMi1uaXN0cDUyMQAAAIsAAABCANL0u5KTj9gY/mP0f6U5ehWwmf
+Ggq2n9A5iF4YosdfI04zWhLP7ioq+acA23fmcvk9dSb4nOcMwnEOMzA7KDniUTAAAAQWtc
+WmaEjHwhUbCmY/iT6XeoxhBywy5fFBop8/5zoO49I0JrmrF9rh6j59CQqCYm/UwqAro0Cy
+GMICVSZ0c+k5w9
+-----END SSH SIGNATURE-----
+";
+	const SSH_KEYGEN_PUB_P256 :&str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBGEFsIHTG5qwKsCeIN4si8rCdXyfN3ysPfJM09ClAEwnqCU3lXZktsPHgb/6lqAyVVwwPxDDpPz+XckY9lmSwgg=";
+	const SSH_KEYGEN_PUB_P384 :&str = "ecdsa-sha2-nistp384 AAAAE2VjZHNhLXNoYTItbmlzdHAzODQAAAAIbmlzdHAzODQAAABhBBhTUm/Rih9Vws+qDVDPsrUF/vBXHld7ZYhfln6nVIEohD+CZKcY4Hg33gcuVPGCnTAYWyP7r7A38F7Trx5RhfMP7SB3jT9z13KGGRpbSMOyWdQXlS6a+opln4eLbaZtqw==";
+	const SSH_KEYGEN_PUB_P521 :&str = "ecdsa-sha2-nistp521 AAAAE2VjZHNhLXNoYTItbmlzdHA1MjEAAAAIbmlzdHA1MjEAAACFBACb01hvT0Vsvkn/uM2sFd15az8DtUMIKPf5kKKMvppMTjaimcoBZR6EOAeXb3Zhb1j7OQU7MNTqoKux7qsMbDujMQF/2sY7OckmuXNcwVIzOe9pBUxtGRG8PkfVSRg9S9bq0vEHBjpfSp50Aa+ZMpiIQyv7666h95CQcSYQJgq2N1gWkg==";
+
+	fn allowed_signers() -> Vec<ECSshAllowedSigner> {
+		let s :String = format!("# cert lines are skipped not refused\nca@example.com cert-authority ecdsa-sha2-nistp256-cert-v01@openssh.com AAAAKGVjZHNh\nsigner@example.com namespaces=\"file\" {}\n*@example.com {}\nsigner@example.com,!bad@example.com {}\n",
+			SSH_KEYGEN_PUB_P256,SSH_KEYGEN_PUB_P384,SSH_KEYGEN_PUB_P521);
+		sshsig_parse_allowed_signers(&s).unwrap()
+	}
+
+	#[test]
+	fn test_ssh_keygen_sign() {
+		let allowed :Vec<ECSshAllowedSigner> = allowed_signers();
+		assert_eq!(allowed.len(),3);
+		for sig in [SSH_KEYGEN_SIG_P256,SSH_KEYGEN_SIG_P256_SHA256,SSH_KEYGEN_SIG_P384,SSH_KEYGEN_SIG_P521].iter() {
+			assert!(sshsig_verify(&allowed,"signer@example.com","file",sig,SSHSIG_MESSAGE).unwrap());
+			assert!(!sshsig_verify(&allowed,"signer@example.com","git",sig,SSHSIG_MESSAGE).unwrap());
+			assert!(!sshsig_verify(&allowed,"signer@example.com","file",sig,b"hello ecsimple").unwrap());
+			assert!(!sshsig_verify(&allowed,"other@example.org","file",sig,SSHSIG_MESSAGE).unwrap());
+		}
+		assert_eq!(ECSshSig::from_armored(SSH_KEYGEN_SIG_P256).unwrap().hash_alg,ECHashType::Sha512);
+		assert_eq!(ECSshSig::from_armored(SSH_KEYGEN_SIG_P256_SHA256).unwrap().hash_alg,ECHashType::Sha256);
+		/*the p521 line denies bad@example.com , the p384 one takes any of example.com*/
+		assert!(!sshsig_verify(&allowed,"bad@example.com","file",SSH_KEYGEN_SIG_P521,SSHSIG_MESSAGE).unwrap());
+		assert!(sshsig_verify(&allowed,"bad@example.com","file",SSH_KEYGEN_SIG_P384,SSHSIG_MESSAGE).unwrap());
+		/*same armor as ssh-keygen gives*/
+		let sig :ECSshSig = ECSshSig::from_armored(SSH_KEYGEN_SIG_P384).unwrap();
+		assert_eq!(sig.to_armored().unwrap(),SSH_KEYGEN_SIG_P384);
+	}
+
+	#[test]
+	fn test_match_pattern() {
+		assert!(ssh_match_pattern(b"signer@example.com",b"*@example.com"));
+		assert!(ssh_match_pattern(b"abc",b"a?c"));
+		assert!(ssh_match_pattern(b"",b"*"));
+		assert!(ssh_match_pattern(b"abcbcd",b"a*bcd"));
+		assert!(!ssh_match_pattern(b"abc",b"a?"));
+		assert!(!ssh_match_pattern(b"abcd",b"a*c"));
+		/*exponential with the recursive match , at once here*/
+		let s :Vec<u8> = vec![b'a';4096];
+		assert!(!ssh_match_pattern(&s,b"a*a*a*a*a*a*a*a*a*a*a*a*a*a*a*a*b"));
+		assert!(ssh_match_pattern(&s,b"a*a*a*a*a*a*a*a*a*a*a*a*a*a*a*a*a"));
+	}
+}
@@ -0,0 +1,987 @@
+#[allow(unused_imports)]
+use crate::logger::*;
+use std::error::Error;
+
+ecsimple_error_class!{ECCipherError}
+
+pub const SYM_BLOCK_SIZE :usize = 16;
+pub const GCM_TAG_SIZE :usize = 16;
+
+const AES_SBOX :[u8;256] = [
+	0x63,0x7c,0x77,0x7b,0xf2,0x6b,0x6f,0xc5,0x30,0x01,0x67,0x2b,0xfe,0xd7,0xab,0x76,
+	0xca,0x82,0xc9,0x7d,0xfa,0x59,0x47,0xf0,0xad,0xd4,0xa2,0xaf,0x9c,0xa4,0x72,0xc0,
+	0xb7,0xfd,0x93,0x26,0x36,0x3f,0xf7,0xcc,0x34,0xa5,0xe5,0xf1,0x71,0xd8,0x31,0x15,
+	0x04,0xc7,0x23,0xc3,0x18,0x96,0x05,0x9a,0x07,0x12,0x80,0xe2,0xeb,0x27,0xb2,0x75,
+	0x09,0x83,0x2c,0x1a,0x1b,0x6e,0x5a,0xa0,0x52,0x3b,0xd6,0xb3,0x29,0xe3,0x2f,0x84,
+	0x53,0xd1,0x00,0xed,0x20,0xfc,0xb1,0x5b,0x6a,0xcb,0xbe,0x39,0x4a,0x4c,0x58,0xcf,
+	0xd0,0xef,0xaa,0xfb,0x43,0x4d,0x33,0x85,0x45,0xf9,0x02,0x7f,0x50,0x3c,0x9f,0xa8,
+	0x51,0xa3,0x40,0x8f,0x92,0x9d,0x38,0xf5,0xbc,0xb6,0xda,0x21,0x10,0xff,0xf3,0xd2,
+	0xcd,0x0c,0x13,0xec,0x5f,0x97,0x44,0x17,0xc4,0xa7,0x7e,0x3d,0x64,0x5d,0x19,0x73,
+	0x60,0x81,0x4f,0xdc,0x22,0x2a,0x90,0x88,0x46,0xee,0xb8,0x14,0xde,0x5e,0x0b,0xdb,
+	0xe0,0x32,0x3a,0x0a,0x49,0x06,0x24,0x5c,0xc2,0xd3,0xac,0x62,0x91,0x95,0xe4,0x79,
+	0xe7,0xc8,0x37,0x6d,0x8d,0xd5,0x4e,0xa9,0x6c,0x56,0xf4,0xea,0x65,0x7a,0xae,0x08,
+	0xba,0x78,0x25,0x2e,0x1c,0xa6,0xb4,0xc6,0xe8,0xdd,0x74,0x1f,0x4b,0xbd,0x8b,0x8a,
+	0x70,0x3e,0xb5,0x66,0x48,0x03,0xf6,0x0e,0x61,0x35,0x57,0xb9,0x86,0xc1,0x1d,0x9e,
+	0xe1,0xf8,0x98,0x11,0x69,0xd9,0x8e,0x94,0x9b,0x1e,0x87,0xe9,0xce,0x55,0x28,0xdf,
+	0x8c,0xa1,0x89,0x0d,0xbf,0xe6,0x42,0x68,0x41,0x99,0x2d,0x0f,0xb0,0x54,0xbb,0x16,
+];
+
+const AES_INV_SBOX :[u8;256] = [
+	0x52,0x09,0x6a,0xd5,0x30,0x36,0xa5,0x38,0xbf,0x40,0xa3,0x9e,0x81,0xf3,0xd7,0xfb,
+	0x7c,0xe3,0x39,0x82,0x9b,0x2f,0xff,0x87,0x34,0x8e,0x43,0x44,0xc4,0xde,0xe9,0xcb,
+	0x54,0x7b,0x94,0x32,0xa6,0xc2,0x23,0x3d,0xee,0x4c,0x95,0x0b,0x42,0xfa,0xc3,0x4e,
+	0x08,0x2e,0xa1,0x66,0x28,0xd9,0x24,0xb2,0x76,0x5b,0xa2,0x49,0x6d,0x8b,0xd1,0x25,
+	0x72,0xf8,0xf6,0x64,0x86,0x68,0x98,0x16,0xd4,0xa4,0x5c,0xcc,0x5d,0x65,0xb6,0x92,
+	0x6c,0x70,0x48,0x50,0xfd,0xed,0xb9,0xda,0x5e,0x15,0x46,0x57,0xa7,0x8d,0x9d,0x84,
+	0x90,0xd8,0xab,0x00,0x8c,0xbc,0xd3,0x0a,0xf7,0xe4,0x58,0x05,0xb8,0xb3,0x45,0x06,
+	0xd0,0x2c,0x1e,0x8f,0xca,0x3f,0x0f,0x02,0xc1,0xaf,0xbd,0x03,0x01,0x13,0x8a,0x6b,
+	0x3a,0x91,0x11,0x41,0x4f,0x67,0xdc,0xea,0x97,0xf2,0xcf,0xce,0xf0,0xb4,0xe6,0x73,
+	0x96,0xac,0x74,0x22,0xe7,0xad,0x35,0x85,0xe2,0xf9,0x37,0xe8,0x1c,0x75,0xdf,0x6e,
+	0x47,0xf1,0x1a,0x71,0x1d,0x29,0xc5,0x89,0x6f,0xb7,0x62,0x0e,0xaa,0x18,0xbe,0x1b,
+	0xfc,0x56,0x3e,0x4b,0xc6,0xd2,0x79,0x20,0x9a,0xdb,0xc0,0xfe,0x78,0xcd,0x5a,0xf4,
+	0x1f,0xdd,0xa8,0x33,0x88,0x07,0xc7,0x31,0xb1,0x12,0x10,0x59,0x27,0x80,0xec,0x5f,
+	0x60,0x51,0x7f,0xa9,0x19,0xb5,0x4a,0x0d,0x2d,0xe5,0x7a,0x9f,0x93,0xc9,0x9c,0xef,
+	0xa0,0xe0,0x3b,0x4d,0xae,0x2a,0xf5,0xb0,0xc8,0xeb,0xbb,0x3c,0x83,0x53,0x99,0x61,
+	0x17,0x2b,0x04,0x7e,0xba,0x77,0xd6,0x26,0xe1,0x69,0x14,0x63,0x55,0x21,0x0c,0x7d,
+];
+
+const SM4_SBOX :[u8;256] = [
+	0xd6,0x90,0xe9,0xfe,0xcc,0xe1,0x3d,0xb7,0x16,0xb6,0x14,0xc2,0x28,0xfb,0x2c,0x05,
+	0x2b,0x67,0x9a,0x76,0x2a,0xbe,0x04,0xc3,0xaa,0x44,0x13,0x26,0x49,0x86,0x06,0x99,
+	0x9c,0x42,0x50,0xf4,0x91,0xef,0x98,0x7a,0x33,0x54,0x0b,0x43,0xed,0xcf,0xac,0x62,
+	0xe4,0xb3,0x1c,0xa9,0xc9,0x08,0xe8,0x95,0x80,0xdf,0x94,0xfa,0x75,0x8f,0x3f,0xa6,
+	0x47,0x07,0xa7,0xfc,0xf3,0x73,0x17,0xba,0x83,0x59,0x3c,0x19,0xe6,0x85,0x4f,0xa8,
+	0x68,0x6b,0x81,0xb2,0x71,0x64,0xda,0x8b,0xf8,0xeb,0x0f,0x4b,0x70,0x56,0x9d,0x35,
+	0x1e,0x24,0x0e,0x5e,0x63,0x58,0xd1,0xa2,0x25,0x22,0x7c,0x3b,0x01,0x21,0x78,0x87,
+	0xd4,0x00,0x46,0x57,0x9f,0xd3,0x27,0x52,0x4c,0x36,0x02,0xe7,0xa0,0xc4,0xc8,0x9e,
+	0xea,0xbf,0x8a,0xd2,0x40,0xc7,0x38,0xb5,0xa3,0xf7,0xf2,0xce,0xf9,0x61,0x15,0xa1,
+	0xe0,0xae,0x5d,0xa4,0x9b,0x34,0x1a,0x55,0xad,0x93,0x32,0x30,0xf5,0x8c,0xb1,0xe3,
+	0x1d,0xf6,0xe2,0x2e,0x82,0x66,0xca,0x60,0xc0,0x29,0x23,0xab,0x0d,0x53,0x4e,0x6f,
+	0xd5,0xdb,0x37,0x45,0xde,0xfd,0x8e,0x2f,0x03,0xff,0x6a,0x72,0x6d,0x6c,0x5b,0x51,
+	0x8d,0x1b,0xaf,0x92,0xbb,0xdd,0xbc,0x7f,0x11,0xd9,0x5c,0x41,0x1f,0x10,0x5a,0xd8,
+	0x0a,0xc1,0x31,0x88,0xa5,0xcd,0x7b,0xbd,0x2d,0x74,0xd0,0x12,0xb8,0xe5,0xb4,0xb0,
+	0x89,0x69,0x97,0x4a,0x0c,0x96,0x77,0x7e,0x65,0xb9,0xf1,0x09,0xc5,0x6e,0xc6,0x84,
+	0x18,0xf0,0x7d,0xec,0x3a,0xdc,0x4d,0x20,0x79,0xee,0x5f,0x3e,0xd7,0xcb,0x39,0x48,
+];
+
+const AES_RCON :[u8;11] = [0x00,0x01,0x02,0x04,0x08,0x10,0x20,0x40,0x80,0x1b,0x36];
+
+const SM4_FK :[u32;4] = [0xa3b1bac6,0x56aa3350,0x677d9197,0xb27022dc];
+
+/*hex digits of pi , Schneier 1993*/
+const BLOWFISH_P :[u32;18] = [
+	0x243f6a88,0x85a308d3,0x13198a2e,0x03707344,0xa4093822,0x299f31d0,0x082efa98,0xec4e6c89,
+	0x452821e6,0x38d01377,0xbe5466cf,0x34e90c6c,0xc0ac29b7,0xc97c50dd,0x3f84d5b5,0xb5470917,
+	0x9216d5d9,0x8979fb1b,
+];
+
+const BLOWFISH_S :[[u32;256];4] = [
+	[
+		0xd1310ba6,0x98dfb5ac,0x2ffd72db,0xd01adfb7,0xb8e1afed,0x6a267e96,0xba7c9045,0xf12c7f99,
+		0x24a19947,0xb3916cf7,0x0801f2e2,0x858efc16,0x636920d8,0x71574e69,0xa458fea3,0xf4933d7e,
+		0x0d95748f,0x728eb658,0x718bcd58,0x82154aee,0x7b54a41d,0xc25a59b5,0x9c30d539,0x2af26013,
+		0xc5d1b023,0x286085f0,0xca417918,0xb8db38ef,0x8e79dcb0,0x603a180e,0x6c9e0e8b,0xb01e8a3e,
+		0xd71577c1,0xbd314b27,0x78af2fda,0x55605c60,0xe65525f3,0xaa55ab94,0x57489862,0x63e81440,
+		0x55ca396a,0x2aab10b6,0xb4cc5c34,0x1141e8ce,0xa15486af,0x7c72e993,0xb3ee1411,0x636fbc2a,
+		0x2ba9c55d,0x741831f6,0xce5c3e16,0x9b87931e,0xafd6ba33,0x6c24cf5c,0x7a325381,0x28958677,
+		0x3b8f4898,0x6b4bb9af,0xc4bfe81b,0x66282193,0x61d809cc,0xfb21a991,0x487cac60,0x5dec8032,
+		0xef845d5d,0xe98575b1,0xdc262302,0xeb651b88,0x23893e81,0xd396acc5,0x0f6d6ff3,0x83f44239,
+		0x2e0b4482,0xa4842004,0x69c8f04a,0x9e1f9b5e,0x21c66842,0xf6e96c9a,0x670c9c61,0xabd388f0,
+		0x6a51a0d2,0xd8542f68,0x960fa728,0xab5133a3,0x6eef0b6c,0x137a3be4,0xba3bf050,0x7efb2a98,
+		0xa1f1651d,0x39af0176,0x66ca593e,0x82430e88,0x8cee8619,0x456f9fb4,0x7d84a5c3,0x3b8b5ebe,
+		0xe06f75d8,0x85c12073,0x401a449f,0x56c16aa6,0x4ed3aa62,0x363f7706,0x1bfedf72,0x429b023d,
+		0x37d0d724,0xd00a1248,0xdb0fead3,0x49f1c09b,0x075372c9,0x80991b7b,0x25d479d8,0xf6e8def7,
+		0xe3fe501a,0xb6794c3b,0x976ce0bd,0x04c006ba,0xc1a94fb6,0x409f60c4,0x5e5c9ec2,0x196a2463,
+		0x68fb6faf,0x3e6c53b5,0x1339b2eb,0x3b52ec6f,0x6dfc511f,0x9b30952c,0xcc814544,0xaf5ebd09,
+		0xbee3d004,0xde334afd,0x660f2807,0x192e4bb3,0xc0cba857,0x45c8740f,0xd20b5f39,0xb9d3fbdb,
+		0x5579c0bd,0x1a60320a,0xd6a100c6,0x402c7279,0x679f25fe,0xfb1fa3cc,0x8ea5e9f8,0xdb3222f8,
+		0x3c7516df,0xfd616b15,0x2f501ec8,0xad0552ab,0x323db5fa,0xfd238760,0x53317b48,0x3e00df82,
+		0x9e5c57bb,0xca6f8ca0,0x1a87562e,0xdf1769db,0xd542a8f6,0x287effc3,0xac6732c6,0x8c4f5573,
+		0x695b27b0,0xbbca58c8,0xe1ffa35d,0xb8f011a0,0x10fa3d98,0xfd2183b8,0x4afcb56c,0x2dd1d35b,
+		0x9a53e479,0xb6f84565,0xd28e49bc,0x4bfb9790,0xe1ddf2da,0xa4cb7e33,0x62fb1341,0xcee4c6e8,
+		0xef20cada,0x36774c01,0xd07e9efe,0x2bf11fb4,0x95dbda4d,0xae909198,0xeaad8e71,0x6b93d5a0,
+		0xd08ed1d0,0xafc725e0,0x8e3c5b2f,0x8e7594b7,0x8ff6e2fb,0xf2122b64,0x8888b812,0x900df01c,
+		0x4fad5ea0,0x688fc31c,0xd1cff191,0xb3a8c1ad,0x2f2f2218,0xbe0e1777,0xea752dfe,0x8b021fa1,
+		0xe5a0cc0f,0xb56f74e8,0x18acf3d6,0xce89e299,0xb4a84fe0,0xfd13e0b7,0x7cc43b81,0xd2ada8d9,
+		0x165fa266,0x80957705,0x93cc7314,0x211a1477,0xe6ad2065,0x77b5fa86,0xc75442f5,0xfb9d35cf,
+		0xebcdaf0c,0x7b3e89a0,0xd6411bd3,0xae1e7e49,0x00250e2d,0x2071b35e,0x226800bb,0x57b8e0af,
+		0x2464369b,0xf009b91e,0x5563911d,0x59dfa6aa,0x78c14389,0xd95a537f,0x207d5ba2,0x02e5b9c5,
+		0x83260376,0x6295cfa9,0x11c81968,0x4e734a41,0xb3472dca,0x7b14a94a,0x1b510052,0x9a532915,
+		0xd60f573f,0xbc9bc6e4,0x2b60a476,0x81e67400,0x08ba6fb5,0x571be91f,0xf296ec6b,0x2a0dd915,
+		0xb6636521,0xe7b9f9b6,0xff34052e,0xc5855664,0x53b02d5d,0xa99f8fa1,0x08ba4799,0x6e85076a,
+	],
+	[
+		0x4b7a70e9,0xb5b32944,0xdb75092e,0xc4192623,0xad6ea6b0,0x49a7df7d,0x9cee60b8,0x8fedb266,
+		0xecaa8c71,0x699a17ff,0x5664526c,0xc2b19ee1,0x193602a5,0x75094c29,0xa0591340,0xe4183a3e,
+		0x3f54989a,0x5b429d65,0x6b8fe4d6,0x99f73fd6,0xa1d29c07,0xefe830f5,0x4d2d38e6,0xf0255dc1,
+		0x4cdd2086,0x8470eb26,0x6382e9c6,0x021ecc5e,0x09686b3f,0x3ebaefc9,0x3c971814,0x6b6a70a1,
+		0x687f3584,0x52a0e286,0xb79c5305,0xaa500737,0x3e07841c,0x7fdeae5c,0x8e7d44ec,0x5716f2b8,
+		0xb03ada37,0xf0500c0d,0xf01c1f04,0x0200b3ff,0xae0cf51a,0x3cb574b2,0x25837a58,0xdc0921bd,
+		0xd19113f9,0x7ca92ff6,0x94324773,0x22f54701,0x3ae5e581,0x37c2dadc,0xc8b57634,0x9af3dda7,
+		0xa9446146,0x0fd0030e,0xecc8c73e,0xa4751e41,0xe238cd99,0x3bea0e2f,0x3280bba1,0x183eb331,
+		0x4e548b38,0x4f6db908,0x6f420d03,0xf60a04bf,0x2cb81290,0x24977c79,0x5679b072,0xbcaf89af,
+		0xde9a771f,0xd9930810,0xb38bae12,0xdccf3f2e,0x5512721f,0x2e6b7124,0x501adde6,0x9f84cd87,
+		0x7a584718,0x7408da17,0xbc9f9abc,0xe94b7d8c,0xec7aec3a,0xdb851dfa,0x63094366,0xc464c3d2,
+		0xef1c1847,0x3215d908,0xdd433b37,0x24c2ba16,0x12a14d43,0x2a65c451,0x50940002,0x133ae4dd,
+		0x71dff89e,0x10314e55,0x81ac77d6,0x5f11199b,0x043556f1,0xd7a3c76b,0x3c11183b,0x5924a509,
+		0xf28fe6ed,0x97f1fbfa,0x9ebabf2c,0x1e153c6e,0x86e34570,0xeae96fb1,0x860e5e0a,0x5a3e2ab3,
+		0x771fe71c,0x4e3d06fa,0x2965dcb9,0x99e71d0f,0x803e89d6,0x5266c825,0x2e4cc978,0x9c10b36a,
+		0xc6150eba,0x94e2ea78,0xa5fc3c53,0x1e0a2df4,0xf2f74ea7,0x361d2b3d,0x1939260f,0x19c27960,
+		0x5223a708,0xf71312b6,0xebadfe6e,0xeac31f66,0xe3bc4595,0xa67bc883,0xb17f37d1,0x018cff28,
+		0xc332ddef,0xbe6c5aa5,0x65582185,0x68ab9802,0xeecea50f,0xdb2f953b,0x2aef7dad,0x5b6e2f84,
+		0x1521b628,0x29076170,0xecdd4775,0x619f1510,0x13cca830,0xeb61bd96,0x0334fe1e,0xaa0363cf,
+		0xb5735c90,0x4c70a239,0xd59e9e0b,0xcbaade14,0xeecc86bc,0x60622ca7,0x9cab5cab,0xb2f3846e,
+		0x648b1eaf,0x19bdf0ca,0xa02369b9,0x655abb50,0x40685a32,0x3c2ab4b3,0x319ee9d5,0xc021b8f7,
+		0x9b540b19,0x875fa099,0x95f7997e,0x623d7da8,0xf837889a,0x97e32d77,0x11ed935f,0x16681281,
+		0x0e358829,0xc7e61fd6,0x96dedfa1,0x7858ba99,0x57f584a5,0x1b227263,0x9b83c3ff,0x1ac24696,
+		0xcdb30aeb,0x532e3054,0x8fd948e4,0x6dbc3128,0x58ebf2ef,0x34c6ffea,0xfe28ed61,0xee7c3c73,
+		0x5d4a14d9,0xe864b7e3,0x42105d14,0x203e13e0,0x45eee2b6,0xa3aaabea,0xdb6c4f15,0xfacb4fd0,
+		0xc742f442,0xef6abbb5,0x654f3b1d,0x41cd2105,0xd81e799e,0x86854dc7,0xe44b476a,0x3d816250,
+		0xcf62a1f2,0x5b8d2646,0xfc8883a0,0xc1c7b6a3,0x7f1524c3,0x69cb7492,0x47848a0b,0x5692b285,
+		0x095bbf00,0xad19489d,0x1462b174,0x23820e00,0x58428d2a,0x0c55f5ea,0x1dadf43e,0x233f7061,
+		0x3372f092,0x8d937e41,0xd65fecf1,0x6c223bdb,0x7cde3759,0xcbee7460,0x4085f2a7,0xce77326e,
+		0xa6078084,0x19f8509e,0xe8efd855,0x61d99735,0xa969a7aa,0xc50c06c2,0x5a04abfc,0x800bcadc,
+		0x9e447a2e,0xc3453484,0xfdd56705,0x0e1e9ec9,0xdb73dbd3,0x105588cd,0x675fda79,0xe3674340,
+		0xc5c43465,0x713e38d8,0x3d28f89e,0xf16dff20,0x153e21e7,0x8fb03d4a,0xe6e39f2b,0xdb83adf7,
+	],
+	[
+		0xe93d5a68,0x948140f7,0xf64c261c,0x94692934,0x411520f7,0x7602d4f7,0xbcf46b2e,0xd4a20068,
+		0xd4082471,0x3320f46a,0x43b7d4b7,0x500061af,0x1e39f62e,0x97244546,0x14214f74,0xbf8b8840,
+		0x4d95fc1d,0x96b591af,0x70f4ddd3,0x66a02f45,0xbfbc09ec,0x03bd9785,0x7fac6dd0,0x31cb8504,
+		0x96eb27b3,0x55fd3941,0xda2547e6,0xabca0a9a,0x28507825,0x530429f4,0x0a2c86da,0xe9b66dfb,
+		0x68dc1462,0xd7486900,0x680ec0a4,0x27a18dee,0x4f3ffea2,0xe887ad8c,0xb58ce006,0x7af4d6b6,
+		0xaace1e7c,0xd3375fec,0xce78a399,0x406b2a42,0x20fe9e35,0xd9f385b9,0xee39d7ab,0x3b124e8b,
+		0x1dc9faf7,0x4b6d1856,0x26a36631,0xeae397b2,0x3a6efa74,0xdd5b4332,0x6841e7f7,0xca7820fb,
+		0xfb0af54e,0xd8feb397,0x454056ac,0xba489527,0x55533a3a,0x20838d87,0xfe6ba9b7,0xd096954b,
+		0x55a867bc,0xa1159a58,0xcca92963,0x99e1db33,0xa62a4a56,0x3f3125f9,0x5ef47e1c,0x9029317c,
+		0xfdf8e802,0x04272f70,0x80bb155c,0x05282ce3,0x95c11548,0xe4c66d22,0x48c1133f,0xc70f86dc,
+		0x07f9c9ee,0x41041f0f,0x404779a4,0x5d886e17,0x325f51eb,0xd59bc0d1,0xf2bcc18f,0x41113564,
+		0x257b7834,0x602a9c60,0xdff8e8a3,0x1f636c1b,0x0e12b4c2,0x02e1329e,0xaf664fd1,0xcad18115,
+		0x6b2395e0,0x333e92e1,0x3b240b62,0xeebeb922,0x85b2a20e,0xe6ba0d99,0xde720c8c,0x2da2f728,
+		0xd0127845,0x95b794fd,0x647d0862,0xe7ccf5f0,0x5449a36f,0x877d48fa,0xc39dfd27,0xf33e8d1e,
+		0x0a476341,0x992eff74,0x3a6f6eab,0xf4f8fd37,0xa812dc60,0xa1ebddf8,0x991be14c,0xdb6e6b0d,
+		0xc67b5510,0x6d672c37,0x2765d43b,0xdcd0e804,0xf1290dc7,0xcc00ffa3,0xb5390f92,0x690fed0b,
+		0x667b9ffb,0xcedb7d9c,0xa091cf0b,0xd9155ea3,0xbb132f88,0x515bad24,0x7b9479bf,0x763bd6eb,
+		0x37392eb3,0xcc115979,0x8026e297,0xf42e312d,0x6842ada7,0xc66a2b3b,0x12754ccc,0x782ef11c,
+		0x6a124237,0xb79251e7,0x06a1bbe6,0x4bfb6350,0x1a6b1018,0x11caedfa,0x3d25bdd8,0xe2e1c3c9,
+		0x44421659,0x0a121386,0xd90cec6e,0xd5abea2a,0x64af674e,0xda86a85f,0xbebfe988,0x64e4c3fe,
+		0x9dbc8057,0xf0f7c086,0x60787bf8,0x6003604d,0xd1fd8346,0xf6381fb0,0x7745ae04,0xd736fccc,
+		0x83426b33,0xf01eab71,0xb0804187,0x3c005e5f,0x77a057be,0xbde8ae24,0x55464299,0xbf582e61,
+		0x4e58f48f,0xf2ddfda2,0xf474ef38,0x8789bdc2,0x5366f9c3,0xc8b38e74,0xb475f255,0x46fcd9b9,
+		0x7aeb2661,0x8b1ddf84,0x846a0e79,0x915f95e2,0x466e598e,0x20b45770,0x8cd55591,0xc902de4c,
+		0xb90bace1,0xbb8205d0,0x11a86248,0x7574a99e,0xb77f19b6,0xe0a9dc09,0x662d09a1,0xc4324633,
+		0xe85a1f02,0x09f0be8c,0x4a99a025,0x1d6efe10,0x1ab93d1d,0x0ba5a4df,0xa186f20f,0x2868f169,
+		0xdcb7da83,0x573906fe,0xa1e2ce9b,0x4fcd7f52,0x50115e01,0xa70683fa,0xa002b5c4,0x0de6d027,
+		0x9af88c27,0x773f8641,0xc3604c06,0x61a806b5,0xf0177a28,0xc0f586e0,0x006058aa,0x30dc7d62,
+		0x11e69ed7,0x2338ea63,0x53c2dd94,0xc2c21634,0xbbcbee56,0x90bcb6de,0xebfc7da1,0xce591d76,
+		0x6f05e409,0x4b7c0188,0x39720a3d,0x7c927c24,0x86e3725f,0x724d9db9,0x1ac15bb4,0xd39eb8fc,
+		0xed545578,0x08fca5b5,0xd83d7cd3,0x4dad0fc4,0x1e50ef5e,0xb161e6f8,0xa28514d9,0x6c51133c,
+		0x6fd5c7e7,0x56e14ec4,0x362abfce,0xddc6c837,0xd79a3234,0x92638212,0x670efa8e,0x406000e0,
+	],
+	[
+		0x3a39ce37,0xd3faf5cf,0xabc27737,0x5ac52d1b,0x5cb0679e,0x4fa33742,0xd3822740,0x99bc9bbe,
+		0xd5118e9d,0xbf0f7315,0xd62d1c7e,0xc700c47b,0xb78c1b6b,0x21a19045,0xb26eb1be,0x6a366eb4,
+		0x5748ab2f,0xbc946e79,0xc6a376d2,0x6549c2c8,0x530ff8ee,0x468dde7d,0xd5730a1d,0x4cd04dc6,
+		0x2939bbdb,0xa9ba4650,0xac9526e8,0xbe5ee304,0xa1fad5f0,0x6a2d519a,0x63ef8ce2,0x9a86ee22,
+		0xc089c2b8,0x43242ef6,0xa51e03aa,0x9cf2d0a4,0x83c061ba,0x9be96a4d,0x8fe51550,0xba645bd6,
+		0x2826a2f9,0xa73a3ae1,0x4ba99586,0xef5562e9,0xc72fefd3,0xf752f7da,0x3f046f69,0x77fa0a59,
+		0x80e4a915,0x87b08601,0x9b09e6ad,0x3b3ee593,0xe990fd5a,0x9e34d797,0x2cf0b7d9,0x022b8b51,
+		0x96d5ac3a,0x017da67d,0xd1cf3ed6,0x7c7d2d28,0x1f9f25cf,0xadf2b89b,0x5ad6b472,0x5a88f54c,
+		0xe029ac71,0xe019a5e6,0x47b0acfd,0xed93fa9b,0xe8d3c48d,0x283b57cc,0xf8d56629,0x79132e28,
+		0x785f0191,0xed756055,0xf7960e44,0xe3d35e8c,0x15056dd4,0x88f46dba,0x03a16125,0x0564f0bd,
+		0xc3eb9e15,0x3c9057a2,0x97271aec,0xa93a072a,0x1b3f6d9b,0x1e6321f5,0xf59c66fb,0x26dcf319,
+		0x7533d928,0xb155fdf5,0x03563482,0x8aba3cbb,0x28517711,0xc20ad9f8,0xabcc5167,0xccad925f,
+		0x4de81751,0x3830dc8e,0x379d5862,0x9320f991,0xea7a90c2,0xfb3e7bce,0x5121ce64,0x774fbe32,
+		0xa8b6e37e,0xc3293d46,0x48de5369,0x6413e680,0xa2ae0810,0xdd6db224,0x69852dfd,0x09072166,
+		0xb39a460a,0x6445c0dd,0x586cdecf,0x1c20c8ae,0x5bbef7dd,0x1b588d40,0xccd2017f,0x6bb4e3bb,
+		0xdda26a7e,0x3a59ff45,0x3e350a44,0xbcb4cdd5,0x72eacea8,0xfa6484bb,0x8d6612ae,0xbf3c6f47,
+		0xd29be463,0x542f5d9e,0xaec2771b,0xf64e6370,0x740e0d8d,0xe75b1357,0xf8721671,0xaf537d5d,
+		0x4040cb08,0x4eb4e2cc,0x34d2466a,0x0115af84,0xe1b00428,0x95983a1d,0x06b89fb4,0xce6ea048,
+		0x6f3f3b82,0x3520ab82,0x011a1d4b,0x277227f8,0x611560b1,0xe7933fdc,0xbb3a792b,0x344525bd,
+		0xa08839e1,0x51ce794b,0x2f32c9b7,0xa01fbac9,0xe01cc87e,0xbcc7d1f6,0xcf0111c3,0xa1e8aac7,
+		0x1a908749,0xd44fbd9a,0xd0dadecb,0xd50ada38,0x0339c32a,0xc6913667,0x8df9317c,0xe0b12b4f,
+		0xf79e59b7,0x43f5bb3a,0xf2d519ff,0x27d9459c,0xbf97222c,0x15e6fc2a,0x0f91fc71,0x9b941525,
+		0xfae59361,0xceb69ceb,0xc2a86459,0x12baa8d1,0xb6c1075e,0xe3056a0c,0x10d25065,0xcb03a442,
+		0xe0ec6e0e,0x1698db3b,0x4c98a0be,0x3278e964,0x9f1f9532,0xe0d392df,0xd3a0342b,0x8971f21e,
+		0x1b0a7441,0x4ba3348c,0xc5be7120,0xc37632d8,0xdf359f8d,0x9b992f2e,0xe60b6f47,0x0fe3f11d,
+		0xe54cda54,0x1edad891,0xce6279cf,0xcd3e7e6f,0x1618b166,0xfd2c1d05,0x848fd2c5,0xf6fb2299,
+		0xf523f357,0xa6327623,0x93a83531,0x56cccd02,0xacf08162,0x5a75ebb5,0x6e163697,0x88d273cc,
+		0xde966292,0x81b949d0,0x4c50901b,0x71c65614,0xe6c6c7bd,0x327a140a,0x45e1d006,0xc3f27b9a,
+		0xc9aa53fd,0x62a80f00,0xbb25bfe2,0x35bdd2f6,0x71126905,0xb2040222,0xb6cbcf7c,0xcd769c2b,
+		0x53113ec0,0x1640e3d3,0x38abbd60,0x2547adf0,0xba38209c,0xf746ce76,0x77afa1c5,0x20756060,
+		0x85cbfe4e,0x8ae88dd8,0x7aaaf9b0,0x4cf9aa7e,0x1948c25c,0x02fb8a8c,0x01c36ae4,0xd6ebe1f9,
+		0x90d4f869,0xa65cdea0,0x3f09252d,0xc208e69f,0xb74e6132,0xce77e25b,0x578fdfe3,0x3ac372e6,
+	],
+];
+
+fn xtime(x :u8) -> u8 {
+	if (x & 0x80) != 0 {
+		return (x << 1) ^ 0x1b;
+	}
+	x << 1
+}
+
+fn gf_mul(a :u8,b :u8) -> u8 {
+	let mut r :u8 = 0;
+	let mut x :u8 = a;
+	let mut y :u8 = b;
+	while y != 0 {
+		if (y & 1) != 0 {
+			r ^= x;
+		}
+		x = xtime(x);
+		y >>= 1;
+	}
+	r
+}
+
+pub trait BlockCipher {
+	fn encrypt_block(&self,blk :&mut [u8;16]);
+	fn decrypt_block(&self,blk :&mut [u8;16]);
+}
+
+#[derive(Clone)]
+pub struct AesCipher {
+	rk :Vec<[u8;16]>,
+	nr :usize,
+}
+
+impl AesCipher {
+	/*16 24 or 32 bytes key*/
+	pub fn new(key :&[u8]) -> Result<AesCipher,Box<dyn Error>> {
+		if key.len() != 16 && key.len() != 24 && key.len() != 32 {
+			ecsimple_new_error!{ECCipherError,"aes key len {} not valid",key.len()}
+		}
+		let nk :usize = key.len() / 4;
+		let nr :usize = nk + 6;
+		let mut w :Vec<[u8;4]> = Vec::new();
+		for i in 0..nk {
+			w.push([key[4*i],key[4*i+1],key[4*i+2],key[4*i+3]]);
+		}
+		for i in nk..(4 * (nr + 1)) {
+			let mut t :[u8;4] = w[i-1];
+			if i % nk == 0 {
+				t = [AES_SBOX[t[1] as usize] ^ AES_RCON[i / nk],AES_SBOX[t[2] as usize],AES_SBOX[t[3] as usize],AES_SBOX[t[0] as usize]];
+			} else if nk > 6 && i % nk == 4 {
+				t = [AES_SBOX[t[0] as usize],AES_SBOX[t[1] as usize],AES_SBOX[t[2] as usize],AES_SBOX[t[3] as usize]];
+			}
+			let p :[u8;4] = w[i - nk];
+			w.push([p[0] ^ t[0],p[1] ^ t[1],p[2] ^ t[2],p[3] ^ t[3]]);
+		}
+		let mut rk :Vec<[u8;16]> = Vec::new();
+		for r in 0..(nr + 1) {
+			let mut k :[u8;16] = [0;16];
+			for c in 0..4 {
+				k[4*c..4*c+4].copy_from_slice(&w[4*r + c]);
+			}
+			rk.push(k);
+		}
+		Ok(AesCipher {
+			rk,
+			nr,
+		})
+	}
+}
+
+fn aes_add_round_key(s :&mut [u8;16],k :&[u8;16]) {
+	for i in 0..16 {
+		s[i] ^= k[i];
+	}
+}
+
+impl BlockCipher for AesCipher {
+	fn encrypt_block(&self,blk :&mut [u8;16]) {
+		let mut s :[u8;16] = *blk;
+		aes_add_round_key(&mut s,&self.rk[0]);
+		for round in 1..(self.nr + 1) {
+			let mut t :[u8;16] = [0;16];
+			/*sub bytes and shift rows , byte r + 4c is row r column c*/
+			for c in 0..4 {
+				for r in 0..4 {
+					t[r + 4*c] = AES_SBOX[s[r + 4*((c + r) % 4)] as usize];
+				}
+			}
+			if round != self.nr {
+				for c in 0..4 {
+					let a0 :u8 = t[4*c];
+					let a1 :u8 = t[4*c+1];
+					let a2 :u8 = t[4*c+2];
+					let a3 :u8 = t[4*c+3];
+					t[4*c] = xtime(a0) ^ (xtime(a1) ^ a1) ^ a2 ^ a3;
+					t[4*c+1] = a0 ^ xtime(a1) ^ (xtime(a2) ^ a2) ^ a3;
+					t[4*c+2] = a0 ^ a1 ^ xtime(a2) ^ (xtime(a3) ^ a3);
+					t[4*c+3] = (xtime(a0) ^ a0) ^ a1 ^ a2 ^ xtime(a3);
+				}
+			}
+			aes_add_round_key(&mut t,&self.rk[round]);
+			s = t;
+		}
+		*blk = s;
+	}
+
+	fn decrypt_block(&self,blk :&mut [u8;16]) {
+		let mut s :[u8;16] = *blk;
+		aes_add_round_key(&mut s,&self.rk[self.nr]);
+		let mut round :usize = self.nr;
+		while round > 0 {
+			round -= 1;
+			let mut t :[u8;16] = [0;16];
+			/*inverse shift rows and inverse sub bytes*/
+			for c in 0..4 {
+				for r in 0..4 {
+					t[r + 4*((c + r) % 4)] = AES_INV_SBOX[s[r + 4*c] as usize];
+				}
+			}
+			aes_add_round_key(&mut t,&self.rk[round]);
+			if round != 0 {
+				for c in 0..4 {
+					let a0 :u8 = t[4*c];
+					let a1 :u8 = t[4*c+1];
+					let a2 :u8 = t[4*c+2];
+					let a3 :u8 = t[4*c+3];
+					t[4*c] = gf_mul(a0,14) ^ gf_mul(a1,11) ^ gf_mul(a2,13) ^ gf_mul(a3,9);
+					t[4*c+1] = gf_mul(a0,9) ^ gf_mul(a1,14) ^ gf_mul(a2,11) ^ gf_mul(a3,13);
+					t[4*c+2] = gf_mul(a0,13) ^ gf_mul(a1,9) ^ gf_mul(a2,14) ^ gf_mul(a3,11);
+					t[4*c+3] = gf_mul(a0,11) ^ gf_mul(a1,13) ^ gf_mul(a2,9) ^ gf_mul(a3,14);
+				}
+			}
+			s = t;
+		}
+		*blk = s;
+	}
+}
+
+fn sm4_tau(a :u32) -> u32 {
+	let b :[u8;4] = a.to_be_bytes();
+	u32::from_be_bytes([SM4_SBOX[b[0] as usize],SM4_SBOX[b[1] as usize],SM4_SBOX[b[2] as usize],SM4_SBOX[b[3] as usize]])
+}
+
+fn sm4_ck(i :usize) -> u32 {
+	let mut b :[u8;4] = [0;4];
+	for (j,v) in b.iter_mut().enumerate() {
+		*v = (((4 * i + j) * 7) & 0xff) as u8;
+	}
+	u32::from_be_bytes(b)
+}
+
+/*GB/T 32907-2016*/
+#[derive(Clone)]
+pub struct Sm4Cipher {
+	rk :[u32;32],
+}
+
+impl Sm4Cipher {
+	pub fn new(key :&[u8]) -> Result<Sm4Cipher,Box<dyn Error>> {
+		if key.len() != 16 {
+			ecsimple_new_error!{ECCipherError,"sm4 key len {} != 16",key.len()}
+		}
+		let mut k :[u32;36] = [0;36];
+		for i in 0..4 {
+			k[i] = u32::from_be_bytes([key[4*i],key[4*i+1],key[4*i+2],key[4*i+3]]) ^ SM4_FK[i];
+		}
+		let mut rk :[u32;32] = [0;32];
+		for i in 0..32 {
+			let t :u32 = sm4_tau(k[i+1] ^ k[i+2] ^ k[i+3] ^ sm4_ck(i));
+			k[i+4] = k[i] ^ t ^ t.rotate_left(13) ^ t.rotate_left(23);
+			rk[i] = k[i+4];
+		}
+		Ok(Sm4Cipher {
+			rk,
+		})
+	}
+
+	fn crypt(&self,blk :&mut [u8;16],decrypt :bool) {
+		let mut x :[u32;36] = [0;36];
+		for i in 0..4 {
+			x[i] = u32::from_be_bytes([blk[4*i],blk[4*i+1],blk[4*i+2],blk[4*i+3]]);
+		}
+		for i in 0..32 {
+			let rk :u32 = if decrypt { self.rk[31 - i] } else { self.rk[i] };
+			let t :u32 = sm4_tau(x[i+1] ^ x[i+2] ^ x[i+3] ^ rk);
+			x[i+4] = x[i] ^ t ^ t.rotate_left(2) ^ t.rotate_left(10) ^ t.rotate_left(18) ^ t.rotate_left(24);
+		}
+		for i in 0..4 {
+			blk[4*i..4*i+4].copy_from_slice(&x[35 - i].to_be_bytes());
+		}
+	}
+}
+
+impl BlockCipher for Sm4Cipher {
+	fn encrypt_block(&self,blk :&mut [u8;16]) {
+		self.crypt(blk,false);
+	}
+
+	fn decrypt_block(&self,blk :&mut [u8;16]) {
+		self.crypt(blk,true);
+	}
+}
+
+pub const DES_BLOCK_SIZE :usize = 8;
+
+const DES_IP :[u8;64] = [
+	58,50,42,34,26,18,10,2,60,52,44,36,28,20,12,4,
+	62,54,46,38,30,22,14,6,64,56,48,40,32,24,16,8,
+	57,49,41,33,25,17,9,1,59,51,43,35,27,19,11,3,
+	61,53,45,37,29,21,13,5,63,55,47,39,31,23,15,7,
+];
+
+const DES_FP :[u8;64] = [
+	40,8,48,16,56,24,64,32,39,7,47,15,55,23,63,31,
+	38,6,46,14,54,22,62,30,37,5,45,13,53,21,61,29,
+	36,4,44,12,52,20,60,28,35,3,43,11,51,19,59,27,
+	34,2,42,10,50,18,58,26,33,1,41,9,49,17,57,25,
+];
+
+const DES_E :[u8;48] = [
+	32,1,2,3,4,5,4,5,6,7,8,9,8,9,10,11,
+	12,13,12,13,14,15,16,17,16,17,18,19,20,21,20,21,
+	22,23,24,25,24,25,26,27,28,29,28,29,30,31,32,1,
+];
+
+const DES_P :[u8;32] = [
+	16,7,20,21,29,12,28,17,1,15,23,26,5,18,31,10,
+	2,8,24,14,32,27,3,9,19,13,30,6,22,11,4,25,
+];
+
+const DES_PC1 :[u8;56] = [
+	57,49,41,33,25,17,9,1,58,50,42,34,26,18,
+	10,2,59,51,43,35,27,19,11,3,60,52,44,36,
+	63,55,47,39,31,23,15,7,62,54,46,38,30,22,
+	14,6,61,53,45,37,29,21,13,5,28,20,12,4,
+];
+
+const DES_PC2 :[u8;48] = [
+	14,17,11,24,1,5,3,28,15,6,21,10,
+	23,19,12,4,26,8,16,7,27,20,13,2,
+	41,52,31,37,47,55,30,40,51,45,33,48,
+	44,49,39,56,34,53,46,42,50,36,29,32,
+];
+
+const DES_SHIFTS :[u32;16] = [1,1,2,2,2,2,2,2,1,2,2,2,2,2,2,1];
+
+const DES_SBOX :[[u8;64];8] = [
+	[14,4,13,1,2,15,11,8,3,10,6,12,5,9,0,7,
+	0,15,7,4,14,2,13,1,10,6,12,11,9,5,3,8,
+	4,1,14,8,13,6,2,11,15,12,9,7,3,10,5,0,
+	15,12,8,2,4,9,1,7,5,11,3,14,10,0,6,13],
+	[15,1,8,14,6,11,3,4,9,7,2,13,12,0,5,10,
+	3,13,4,7,15,2,8,14,12,0,1,10,6,9,11,5,
+	0,14,7,11,10,4,13,1,5,8,12,6,9,3,2,15,
+	13,8,10,1,3,15,4,2,11,6,7,12,0,5,14,9],
+	[10,0,9,14,6,3,15,5,1,13,12,7,11,4,2,8,
+	13,7,0,9,3,4,6,10,2,8,5,14,12,11,15,1,
+	13,6,4,9,8,15,3,0,11,1,2,12,5,10,14,7,
+	1,10,13,0,6,9,8,7,4,15,14,3,11,5,2,12],
+	[7,13,14,3,0,6,9,10,1,2,8,5,11,12,4,15,
+	13,8,11,5,6,15,0,3,4,7,2,12,1,10,14,9,
+	10,6,9,0,12,11,7,13,15,1,3,14,5,2,8,4,
+	3,15,0,6,10,1,13,8,9,4,5,11,12,7,2,14],
+	[2,12,4,1,7,10,11,6,8,5,3,15,13,0,14,9,
+	14,11,2,12,4,7,13,1,5,0,15,10,3,9,8,6,
+	4,2,1,11,10,13,7,8,15,9,12,5,6,3,0,14,
+	11,8,12,7,1,14,2,13,6,15,0,9,10,4,5,3],
+	[12,1,10,15,9,2,6,8,0,13,3,4,14,7,5,11,
+	10,15,4,2,7,12,9,5,6,1,13,14,0,11,3,8,
+	9,14,15,5,2,8,12,3,7,0,4,10,1,13,11,6,
+	4,3,2,12,9,5,15,10,11,14,1,7,6,0,8,13],
+	[4,11,2,14,15,0,8,13,3,12,9,7,5,10,6,1,
+	13,0,11,7,4,9,1,10,14,3,5,12,2,15,8,6,
+	1,4,11,13,12,3,7,14,10,15,6,8,0,5,9,2,
+	6,11,13,8,1,4,10,7,9,5,0,15,14,2,3,12],
+	[13,2,8,4,6,15,11,1,10,9,3,14,5,0,12,7,
+	1,15,13,8,10,3,7,4,12,5,6,11,0,14,9,2,
+	7,11,4,1,9,12,14,2,0,6,10,13,15,3,5,8,
+	2,1,14,7,4,10,8,13,15,12,9,0,3,5,6,11],
+];
+
+/*table entries count from 1 at the most significant of inbits*/
+fn des_permute(v :u64,inbits :u32,table :&[u8]) -> u64 {
+	let mut retv :u64 = 0;
+	for t in table.iter() {
+		retv = (retv << 1) | ((v >> (inbits - (*t as u32))) & 0x1);
+	}
+	retv
+}
+
+fn des_f(r :u32,k :u64) -> u32 {
+	let x :u64 = des_permute(r as u64,32,&DES_E) ^ k;
+	let mut s :u32 = 0;
+	for (i,sbox) in DES_SBOX.iter().enumerate() {
+		let b :usize = ((x >> (42 - 6 * i)) & 0x3f) as usize;
+		let row :usize = ((b >> 4) & 0x2) | (b & 0x1);
+		let col :usize = (b >> 1) & 0xf;
+		s = (s << 4) | (sbox[row * 16 + col] as u32);
+	}
+
+	des_permute(s as u64,32,&DES_P) as u32
+}
+
+fn des_key_schedule(key :&[u8]) -> [u64;16] {
+	let mut kb :[u8;8] = [0;8];
+	kb.copy_from_slice(key);
+	let cd :u64 = des_permute(u64::from_be_bytes(kb),64,&DES_PC1);
+	let mut c :u32 = ((cd >> 28) & 0xfffffff) as u32;
+	let mut d :u32 = (cd & 0xfffffff) as u32;
+	let mut rk :[u64;16] = [0;16];
+	for i in 0..16 {
+		c = ((c << DES_SHIFTS[i]) | (c >> (28 - DES_SHIFTS[i]))) & 0xfffffff;
+		d = ((d << DES_SHIFTS[i]) | (d >> (28 - DES_SHIFTS[i]))) & 0xfffffff;
+		rk[i] = des_permute(((c as u64) << 28) | (d as u64),56,&DES_PC2);
+	}
+	rk
+}
+
+fn des_crypt(rk :&[u64;16],v :u64,decrypt :bool) -> u64 {
+	let x :u64 = des_permute(v,64,&DES_IP);
+	let mut l :u32 = (x >> 32) as u32;
+	let mut r :u32 = x as u32;
+	for i in 0..16 {
+		let k :u64 = if decrypt { rk[15 - i] } else { rk[i] };
+		let t :u32 = l ^ des_f(r,k);
+		l = r;
+		r = t;
+	}
+	des_permute(((r as u64) << 32) | (l as u64),64,&DES_FP)
+}
+
+/*
+	FIPS 46-3 triple DES in EDE mode with three keys , only for the
+	DES-EDE3-CBC of legacy openssl pem , 64 bit block so no BlockCipher for it
+*/
+#[derive(Clone)]
+pub struct Des3Cipher {
+	rk :[[u64;16];3],
+}
+
+impl Des3Cipher {
+	pub fn new(key :&[u8]) -> Result<Des3Cipher,Box<dyn Error>> {
+		if key.len() != 24 {
+			ecsimple_new_error!{ECCipherError,"des-ede3 key len {} != 24",key.len()}
+		}
+		Ok(Des3Cipher {
+			rk : [des_key_schedule(&key[0..8]),des_key_schedule(&key[8..16]),des_key_schedule(&key[16..24])],
+		})
+	}
+
+	pub fn encrypt_block(&self,blk :&mut [u8;8]) {
+		let mut v :u64 = u64::from_be_bytes(*blk);
+		v = des_crypt(&self.rk[0],v,false);
+		v = des_crypt(&self.rk[1],v,true);
+		v = des_crypt(&self.rk[2],v,false);
+		*blk = v.to_be_bytes();
+	}
+
+	pub fn decrypt_block(&self,blk :&mut [u8;8]) {
+		let mut v :u64 = u64::from_be_bytes(*blk);
+		v = des_crypt(&self.rk[2],v,true);
+		v = des_crypt(&self.rk[1],v,false);
+		v = des_crypt(&self.rk[0],v,true);
+		*blk = v.to_be_bytes();
+	}
+}
+
+/*
+	Blowfish with 64 bit block as two words , kept only for the
+	bcrypt_pbkdf key schedule so no BlockCipher for it
+*/
+#[derive(Clone)]
+pub(crate) struct BlowfishState {
+	p :[u32;18],
+	s :[[u32;256];4],
+}
+
+fn blowfish_stream_word(data :&[u8],off :&mut usize) -> u32 {
+	let mut retv :u32 = 0;
+	for _ in 0..4 {
+		if *off >= data.len() {
+			*off = 0;
+		}
+		retv = (retv << 8) | (data[*off] as u32);
+		*off += 1;
+	}
+	retv
+}
+
+impl BlowfishState {
+	pub(crate) fn new() -> BlowfishState {
+		BlowfishState {
+			p : BLOWFISH_P,
+			s : BLOWFISH_S,
+		}
+	}
+
+	fn round_f(&self,x :u32) -> u32 {
+		let a :u32 = self.s[0][(x >> 24) as usize];
+		let b :u32 = self.s[1][((x >> 16) & 0xff) as usize];
+		let c :u32 = self.s[2][((x >> 8) & 0xff) as usize];
+		let d :u32 = self.s[3][(x & 0xff) as usize];
+		(a.wrapping_add(b) ^ c).wrapping_add(d)
+	}
+
+	pub(crate) fn encrypt(&self,l :&mut u32,r :&mut u32) {
+		let mut xl :u32 = *l;
+		let mut xr :u32 = *r;
+		for i in 0..8 {
+			xl ^= self.p[2*i];
+			xr ^= self.round_f(xl) ^ self.p[2*i+1];
+			xl ^= self.round_f(xr);
+		}
+		xl ^= self.p[16];
+		xr ^= self.p[17];
+		*l = xr;
+		*r = xl;
+	}
+
+	/*the eksblowfish expand , data empty for the plain key schedule*/
+	pub(crate) fn expand_state(&mut self,data :&[u8],key :&[u8]) {
+		let mut koff :usize = 0;
+		for i in 0..18 {
+			self.p[i] ^= blowfish_stream_word(key,&mut koff);
+		}
+		let mut doff :usize = 0;
+		let mut l :u32 = 0;
+		let mut r :u32 = 0;
+		for i in 0..9 {
+			if !data.is_empty() {
+				l ^= blowfish_stream_word(data,&mut doff);
+				r ^= blowfish_stream_word(data,&mut doff);
+			}
+			self.encrypt(&mut l,&mut r);
+			self.p[2*i] = l;
+			self.p[2*i+1] = r;
+		}
+		for i in 0..4 {
+			for k in 0..128 {
+				if !data.is_empty() {
+					l ^= blowfish_stream_word(data,&mut doff);
+					r ^= blowfish_stream_word(data,&mut doff);
+				}
+				self.encrypt(&mut l,&mut r);
+				self.s[i][2*k] = l;
+				self.s[i][2*k+1] = r;
+			}
+		}
+	}
+}
+
+fn cbc_pad(data :&[u8],blksize :usize) -> Vec<u8> {
+	let mut padded :Vec<u8> = data.to_vec();
+	let padlen :usize = blksize - (data.len() % blksize);
+	for _ in 0..padlen {
+		padded.push(padlen as u8);
+	}
+	padded
+}
+
+fn cbc_unpad(retv :&mut Vec<u8>,blksize :usize) -> Result<(),Box<dyn Error>> {
+	/*wrong password mostly comes here , every byte of the last block checked the same way*/
+	let last :u8 = retv[retv.len() - 1];
+	let padlen :usize = last as usize;
+	let mut bad :u8 = ((padlen == 0) as u8) | ((padlen > blksize) as u8);
+	for i in 0..blksize {
+		/*0xff when i < padlen*/
+		let inpad :u8 = ((i as u16).wrapping_sub(padlen as u16) >> 8) as u8;
+		bad |= (retv[retv.len() - 1 - i] ^ last) & inpad;
+	}
+	if bad != 0 {
+		ecsimple_new_error!{ECCipherError,"bad padding"}
+	}
+	retv.truncate(retv.len() - padlen);
+	Ok(())
+}
+
+/*CBC with PKCS#7 padding*/
+pub fn cbc_encrypt<T :BlockCipher>(cipher :&T,iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iv.len() != SYM_BLOCK_SIZE {
+		ecsimple_new_error!{ECCipherError,"iv len {} != {}",iv.len(),SYM_BLOCK_SIZE}
+	}
+	let padded :Vec<u8> = cbc_pad(data,SYM_BLOCK_SIZE);
+	let mut prev :[u8;16] = [0;16];
+	prev.copy_from_slice(iv);
+	let mut retv :Vec<u8> = Vec::new();
+	for c in padded.chunks(SYM_BLOCK_SIZE) {
+		let mut blk :[u8;16] = [0;16];
+		for i in 0..SYM_BLOCK_SIZE {
+			blk[i] = c[i] ^ prev[i];
+		}
+		cipher.encrypt_block(&mut blk);
+		retv.extend_from_slice(&blk);
+		prev = blk;
+	}
+	Ok(retv)
+}
+
+pub fn cbc_decrypt<T :BlockCipher>(cipher :&T,iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iv.len() != SYM_BLOCK_SIZE {
+		ecsimple_new_error!{ECCipherError,"iv len {} != {}",iv.len(),SYM_BLOCK_SIZE}
+	}
+	if data.is_empty() || (data.len() % SYM_BLOCK_SIZE) != 0 {
+		ecsimple_new_error!{ECCipherError,"data len {} not multiple of {}",data.len(),SYM_BLOCK_SIZE}
+	}
+	let mut prev :[u8;16] = [0;16];
+	prev.copy_from_slice(iv);
+	let mut retv :Vec<u8> = Vec::new();
+	for c in data.chunks(SYM_BLOCK_SIZE) {
+		let mut blk :[u8;16] = [0;16];
+		blk.copy_from_slice(c);
+		cipher.decrypt_block(&mut blk);
+		for i in 0..SYM_BLOCK_SIZE {
+			retv.push(blk[i] ^ prev[i]);
+		}
+		prev.copy_from_slice(c);
+	}
+	cbc_unpad(&mut retv,SYM_BLOCK_SIZE)?;
+	Ok(retv)
+}
+
+pub fn des3_cbc_encrypt(cipher :&Des3Cipher,iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iv.len() != DES_BLOCK_SIZE {
+		ecsimple_new_error!{ECCipherError,"iv len {} != {}",iv.len(),DES_BLOCK_SIZE}
+	}
+	let padded :Vec<u8> = cbc_pad(data,DES_BLOCK_SIZE);
+	let mut prev :[u8;8] = [0;8];
+	prev.copy_from_slice(iv);
+	let mut retv :Vec<u8> = Vec::new();
+	for c in padded.chunks(DES_BLOCK_SIZE) {
+		let mut blk :[u8;8] = [0;8];
+		for i in 0..DES_BLOCK_SIZE {
+			blk[i] = c[i] ^ prev[i];
+		}
+		cipher.encrypt_block(&mut blk);
+		retv.extend_from_slice(&blk);
+		prev = blk;
+	}
+	Ok(retv)
+}
+
+pub fn des3_cbc_decrypt(cipher :&Des3Cipher,iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iv.len() != DES_BLOCK_SIZE {
+		ecsimple_new_error!{ECCipherError,"iv len {} != {}",iv.len(),DES_BLOCK_SIZE}
+	}
+	if data.is_empty() || (data.len() % DES_BLOCK_SIZE) != 0 {
+		ecsimple_new_error!{ECCipherError,"data len {} not multiple of {}",data.len(),DES_BLOCK_SIZE}
+	}
+	let mut prev :[u8;8] = [0;8];
+	prev.copy_from_slice(iv);
+	let mut retv :Vec<u8> = Vec::new();
+	for c in data.chunks(DES_BLOCK_SIZE) {
+		let mut blk :[u8;8] = [0;8];
+		blk.copy_from_slice(c);
+		cipher.decrypt_block(&mut blk);
+		for i in 0..DES_BLOCK_SIZE {
+			retv.push(blk[i] ^ prev[i]);
+		}
+		prev.copy_from_slice(c);
+	}
+	cbc_unpad(&mut retv,DES_BLOCK_SIZE)?;
+	Ok(retv)
+}
+
+fn gcm_gf_mul(x :u128,y :u128) -> u128 {
+	let mut z :u128 = 0;
+	let mut v :u128 = y;
+	for i in 0..128 {
+		if ((x >> (127 - i)) & 1) != 0 {
+			z ^= v;
+		}
+		if (v & 1) != 0 {
+			v = (v >> 1) ^ (0xe1u128 << 120);
+		} else {
+			v >>= 1;
+		}
+	}
+	z
+}
+
+fn gcm_ghash(h :u128,aad :&[u8],cdata :&[u8]) -> u128 {
+	let mut y :u128 = 0;
+	for part in [aad,cdata].iter() {
+		for c in part.chunks(SYM_BLOCK_SIZE) {
+			let mut blk :[u8;16] = [0;16];
+			blk[..c.len()].copy_from_slice(c);
+			y = gcm_gf_mul(y ^ u128::from_be_bytes(blk),h);
+		}
+	}
+	let lens :u128 = (((aad.len() as u128) * 8) << 64) | ((cdata.len() as u128) * 8);
+	gcm_gf_mul(y ^ lens,h)
+}
+
+fn gcm_ctr<T :BlockCipher>(cipher :&T,j0 :&[u8;16],data :&[u8]) -> Vec<u8> {
+	let mut retv :Vec<u8> = Vec::new();
+	let mut cb :[u8;16] = *j0;
+	for c in data.chunks(SYM_BLOCK_SIZE) {
+		let ctr :u32 = u32::from_be_bytes([cb[12],cb[13],cb[14],cb[15]]).wrapping_add(1);
+		cb[12..16].copy_from_slice(&ctr.to_be_bytes());
+		let mut ks :[u8;16] = cb;
+		cipher.encrypt_block(&mut ks);
+		for i in 0..c.len() {
+			retv.push(c[i] ^ ks[i]);
+		}
+	}
+	retv
+}
+
+fn gcm_j0<T :BlockCipher>(cipher :&T,nonce :&[u8]) -> Result<(u128,[u8;16]),Box<dyn Error>> {
+	if nonce.is_empty() {
+		ecsimple_new_error!{ECCipherError,"empty gcm nonce"}
+	}
+	let mut hblk :[u8;16] = [0;16];
+	cipher.encrypt_block(&mut hblk);
+	let h :u128 = u128::from_be_bytes(hblk);
+	let j0 :[u8;16];
+	if nonce.len() == 12 {
+		let mut b :[u8;16] = [0;16];
+		b[..12].copy_from_slice(nonce);
+		b[15] = 1;
+		j0 = b;
+	} else {
+		j0 = gcm_ghash(h,&[],nonce).to_be_bytes();
+	}
+	Ok((h,j0))
+}
+
+/*NIST SP 800-38D , return ciphertext with the tag appended*/
+pub fn gcm_encrypt<T :BlockCipher>(cipher :&T,nonce :&[u8],aad :&[u8],data :&[u8],taglen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if !(12..=GCM_TAG_SIZE).contains(&taglen) {
+		ecsimple_new_error!{ECCipherError,"gcm tag len {} not valid",taglen}
+	}
+	let (h,j0) = gcm_j0(cipher,nonce)?;
+	let mut retv :Vec<u8> = gcm_ctr(cipher,&j0,data);
+	let s :u128 = gcm_ghash(h,aad,&retv);
+	let mut tag :[u8;16] = j0;
+	cipher.encrypt_block(&mut tag);
+	let tagv :[u8;16] = (u128::from_be_bytes(tag) ^ s).to_be_bytes();
+	retv.extend_from_slice(&tagv[..taglen]);
+	Ok(retv)
+}
+
+pub fn gcm_decrypt<T :BlockCipher>(cipher :&T,nonce :&[u8],aad :&[u8],data :&[u8],taglen :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	if !(12..=GCM_TAG_SIZE).contains(&taglen) {
+		ecsimple_new_error!{ECCipherError,"gcm tag len {} not valid",taglen}
+	}
+	if data.len() < taglen {
+		ecsimple_new_error!{ECCipherError,"gcm data len {} < tag len {}",data.len(),taglen}
+	}
+	let (h,j0) = gcm_j0(cipher,nonce)?;
+	let cdata :&[u8] = &data[..(data.len() - taglen)];
+	let s :u128 = gcm_ghash(h,aad,cdata);
+	let mut tag :[u8;16] = j0;
+	cipher.encrypt_block(&mut tag);
+	let tagv :[u8;16] = (u128::from_be_bytes(tag) ^ s).to_be_bytes();
+	let mut diff :u8 = 0;
+	for i in 0..taglen {
+		diff |= tagv[i] ^ data[data.len() - taglen + i];
+	}
+	if diff != 0 {
+		ecsimple_new_error!{ECCipherError,"gcm tag not match"}
+	}
+	Ok(gcm_ctr(cipher,&j0,cdata))
+}
+
+/*SP 800-38A CTR , the whole iv as a 128 bit big endian counter as ssh aes*-ctr does*/
+pub fn ctr_crypt<T :BlockCipher>(cipher :&T,iv :&[u8],data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if iv.len() != SYM_BLOCK_SIZE {
+		ecsimple_new_error!{ECCipherError,"iv len {} != {}",iv.len(),SYM_BLOCK_SIZE}
+	}
+	let mut cb :[u8;16] = [0;16];
+	cb.copy_from_slice(iv);
+	let mut retv :Vec<u8> = Vec::new();
+	for c in data.chunks(SYM_BLOCK_SIZE) {
+		let mut ks :[u8;16] = cb;
+		cipher.encrypt_block(&mut ks);
+		for i in 0..c.len() {
+			retv.push(c[i] ^ ks[i]);
+		}
+		cb = u128::from_be_bytes(cb).wrapping_add(1).to_be_bytes();
+	}
+	Ok(retv)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+
+	#[test]
+	fn test_aes128_cbc_sp800_38a() {
+		/*SP 800-38A F.2.1 , the padding block follows the four given ones*/
+		let key :Vec<u8> = Vec::from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+		let iv :Vec<u8> = Vec::from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+		let plain :Vec<u8> = Vec::from_hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710").unwrap();
+		let cipher :Vec<u8> = Vec::from_hex("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7").unwrap();
+		let aes :AesCipher = AesCipher::new(&key).unwrap();
+		let encv :Vec<u8> = cbc_encrypt(&aes,&iv,&plain).unwrap();
+		assert_eq!(encv.len(),plain.len() + SYM_BLOCK_SIZE);
+		assert_eq!(&encv[..plain.len()],&cipher[..]);
+		assert_eq!(cbc_decrypt(&aes,&iv,&encv).unwrap(),plain);
+	}
+
+	#[test]
+	fn test_cbc_bad_padding() {
+		let key :Vec<u8> = Vec::from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+		let mut iv :Vec<u8> = vec![0;SYM_BLOCK_SIZE];
+		let aes :AesCipher = AesCipher::new(&key).unwrap();
+		let encv :Vec<u8> = cbc_encrypt(&aes,&iv,b"0123456789").unwrap();
+		/*the last plain byte 0x06 turns 0x07*/
+		iv[SYM_BLOCK_SIZE - 1] ^= 0x1;
+		assert!(cbc_decrypt(&aes,&iv,&encv).is_err());
+		/*0x06 turns 0x00*/
+		iv[SYM_BLOCK_SIZE - 1] ^= 0x7;
+		assert!(cbc_decrypt(&aes,&iv,&encv).is_err());
+	}
+
+	#[test]
+	fn test_sm4_gbt32907() {
+		let key :Vec<u8> = Vec::from_hex("0123456789abcdeffedcba9876543210").unwrap();
+		let sm4 :Sm4Cipher = Sm4Cipher::new(&key).unwrap();
+		let mut blk :[u8;16] = [0;16];
+		blk.copy_from_slice(&key);
+		sm4.encrypt_block(&mut blk);
+		assert_eq!(blk.to_vec(),Vec::from_hex("681edf34d206965e86b3e94f536e4246").unwrap());
+		sm4.decrypt_block(&mut blk);
+		assert_eq!(blk.to_vec(),key);
+	}
+
+	#[test]
+	fn test_aes_gcm_nist() {
+		/*GCM spec test case 2*/
+		let aes :AesCipher = AesCipher::new(&[0;16]).unwrap();
+		let encv :Vec<u8> = gcm_encrypt(&aes,&[0;12],&[],&[0;16],GCM_TAG_SIZE).unwrap();
+		assert_eq!(encv,Vec::from_hex("0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf").unwrap());
+		/*test case 4 with aad*/
+		let key :Vec<u8> = Vec::from_hex("feffe9928665731c6d6a8f9467308308").unwrap();
+		let nonce :Vec<u8> = Vec::from_hex("cafebabefacedbaddecaf888").unwrap();
+		let plain :Vec<u8> = Vec::from_hex("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39").unwrap();
+		let aad :Vec<u8> = Vec::from_hex("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+		let aes :AesCipher = AesCipher::new(&key).unwrap();
+		let encv :Vec<u8> = gcm_encrypt(&aes,&nonce,&aad,&plain,GCM_TAG_SIZE).unwrap();
+		assert_eq!(encv,Vec::from_hex("42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e0915bc94fbc3221a5db94fae95ae7121a47").unwrap());
+		assert_eq!(gcm_decrypt(&aes,&nonce,&aad,&encv,GCM_TAG_SIZE).unwrap(),plain);
+		assert!(gcm_decrypt(&aes,&nonce,&[],&encv,GCM_TAG_SIZE).is_err());
+	}
+
+	#[test]
+	fn test_des_fips46() {
+		/*the classic worked example , one key repeated is single DES*/
+		let key :Vec<u8> = Vec::from_hex("133457799bbcdff1133457799bbcdff1133457799bbcdff1").unwrap();
+		let des :Des3Cipher = Des3Cipher::new(&key).unwrap();
+		let mut blk :[u8;8] = [0x01,0x23,0x45,0x67,0x89,0xab,0xcd,0xef];
+		des.encrypt_block(&mut blk);
+		assert_eq!(blk.to_vec(),Vec::from_hex("85e813540f0ab405").unwrap());
+		des.decrypt_block(&mut blk);
+		assert_eq!(blk.to_vec(),Vec::from_hex("0123456789abcdef").unwrap());
+	}
+
+	#[test]
+	fn test_des3_cbc() {
+		/*openssl enc -des-ede3-cbc*/
+		let key :Vec<u8> = Vec::from_hex("0123456789abcdeff1e0d3c2b5a49786fedcba9876543210").unwrap();
+		let iv :Vec<u8> = Vec::from_hex("1032547698badcfe").unwrap();
+		let plain :&[u8] = b"0123456789abcdef0123456789abcdef01234";
+		let cipher :Vec<u8> = Vec::from_hex("40d7c7ec107bce4cd6e5084819499c88308bd8d3cc6a7c44e864b916f4917d41beb5657839888814").unwrap();
+		let des :Des3Cipher = Des3Cipher::new(&key).unwrap();
+		assert_eq!(des3_cbc_encrypt(&des,&iv,plain).unwrap(),cipher);
+		assert_eq!(des3_cbc_decrypt(&des,&iv,&cipher).unwrap(),plain.to_vec());
+		assert!(des3_cbc_decrypt(&des,&iv,&cipher[..32]).is_err());
+	}
+}
@@ -0,0 +1,537 @@
+use crate::consts::*;
+use crate::curve::*;
+use crate::group::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::signature::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use std::error::Error;
+
+ecsimple_error_class!{ECTlsError}
+
+/*RFC 8446 4.4.3*/
+pub const TLS13_CV_SERVER_CONTEXT :&str = "TLS 1.3, server CertificateVerify";
+pub const TLS13_CV_CLIENT_CONTEXT :&str = "TLS 1.3, client CertificateVerify";
+/*RFC 8998 3.2.1 , the SM2 id for sm2sig_sm3*/
+pub const TLS13_SM2_ID :&[u8] = b"TLSv1.3+GM+Cipher+Suite";
+
+const TLS13_CV_PAD_LEN :usize = 64;
+const TLS13_CV_PAD :u8 = 0x20;
+const TLS13_X25519_KEY_SIZE :usize = 32;
+/*not an ECGroup , so not in the CurveId registry*/
+const TLS13_X25519_GROUP :u16 = 0x001d;
+const TLS13_EC_GROUPS :[ECTlsNamedGroup;4] = [ECTlsNamedGroup::Secp256r1,ECTlsNamedGroup::Secp384r1,ECTlsNamedGroup::Secp521r1,ECTlsNamedGroup::CurveSm2];
+
+/*RFC 8446 4.2.7 and RFC 8998 2*/
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECTlsNamedGroup {
+	Secp256r1,
+	Secp384r1,
+	Secp521r1,
+	X25519,
+	CurveSm2,
+}
+
+impl ECTlsNamedGroup {
+	/*the codepoints from CurveId::tls_group , the built-in curves here always have one*/
+	pub fn code(&self) -> u16 {
+		match self.curve_name() {
+			Some(n) => CurveId::from_name(n).unwrap().tls_group().unwrap(),
+			None => TLS13_X25519_GROUP,
+		}
+	}
+
+	pub fn from_code(code :u16) -> Result<ECTlsNamedGroup,Box<dyn Error>> {
+		if code == TLS13_X25519_GROUP {
+			return Ok(ECTlsNamedGroup::X25519);
+		}
+		if let Ok(id) = CurveId::from_tls_group(code) {
+			for g in TLS13_EC_GROUPS.iter() {
+				if g.curve_name() == Some(id.name().as_str()) {
+					return Ok(*g);
+				}
+			}
+		}
+		ecsimple_new_error!{ECTlsError,"unknown named group 0x{:04x}",code}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			ECTlsNamedGroup::Secp256r1 => "secp256r1",
+			ECTlsNamedGroup::Secp384r1 => "secp384r1",
+			ECTlsNamedGroup::Secp521r1 => "secp521r1",
+			ECTlsNamedGroup::X25519 => "x25519",
+			ECTlsNamedGroup::CurveSm2 => "curveSM2",
+		}
+	}
+
+	/*None for x25519 , which ECGroup does not cover*/
+	pub fn curve_name(&self) -> Option<&'static str> {
+		match self {
+			ECTlsNamedGroup::Secp256r1 => Some(PRIME256v1_NAME),
+			ECTlsNamedGroup::Secp384r1 => Some(SECP384r1_NAME),
+			ECTlsNamedGroup::Secp521r1 => Some(SECP521r1_NAME),
+			ECTlsNamedGroup::X25519 => None,
+			ECTlsNamedGroup::CurveSm2 => Some(SM2_NAME),
+		}
+	}
+
+	/*RFC 8446 4.2.8.2 UncompressedPointRepresentation , or the x25519 u*/
+	pub fn key_exchange_size(&self) -> usize {
+		match self {
+			ECTlsNamedGroup::Secp256r1 | ECTlsNamedGroup::CurveSm2 => 65,
+			ECTlsNamedGroup::Secp384r1 => 97,
+			ECTlsNamedGroup::Secp521r1 => 133,
+			ECTlsNamedGroup::X25519 => TLS13_X25519_KEY_SIZE,
+		}
+	}
+
+	pub fn from_group(grp :&ECGroup) -> Result<ECTlsNamedGroup,Box<dyn Error>> {
+		for g in TLS13_EC_GROUPS.iter() {
+			let ggrp :ECGroup = ecc_get_curve_group(g.curve_name().unwrap())?;
+			if grp.eq_params(&ggrp) {
+				return Ok(*g);
+			}
+		}
+		ecsimple_new_error!{ECTlsError,"no named group for curve [{}]",grp.info().curvename}
+	}
+}
+
+struct TlsReader<'a> {
+	data :&'a [u8],
+	off :usize,
+}
+
+impl<'a> TlsReader<'a> {
+	fn new(data :&'a [u8]) -> TlsReader<'a> {
+		TlsReader {
+			data,
+			off : 0,
+		}
+	}
+
+	fn read_bytes(&mut self,n :usize) -> Result<&'a [u8],Box<dyn Error>> {
+		if self.data.len() - self.off < n {
+			ecsimple_new_error!{ECTlsError,"no {} bytes at offset {}",n,self.off}
+		}
+		let retv :&'a [u8] = &self.data[self.off..(self.off+n)];
+		self.off += n;
+		Ok(retv)
+	}
+
+	fn read_u16(&mut self) -> Result<u16,Box<dyn Error>> {
+		let b :&[u8] = self.read_bytes(2)?;
+		Ok(u16::from_be_bytes([b[0],b[1]]))
+	}
+
+	fn read_vec16(&mut self) -> Result<&'a [u8],Box<dyn Error>> {
+		let n :usize = self.read_u16()? as usize;
+		self.read_bytes(n)
+	}
+
+	fn remain(&self) -> usize {
+		self.data.len() - self.off
+	}
+}
+
+fn tls_put_vec16(v :&mut Vec<u8>,data :&[u8]) {
+	v.extend_from_slice(&(data.len() as u16).to_be_bytes());
+	v.extend_from_slice(data);
+}
+
+/*KeyShareEntry , key_exchange checked on decode*/
+#[derive(Clone,Debug)]
+pub struct ECTlsKeyShare {
+	pub group :ECTlsNamedGroup,
+	pub key_exchange :Vec<u8>,
+}
+
+impl ECTlsKeyShare {
+	pub fn new(pubkey :&ECPublicKey) -> Result<ECTlsKeyShare,Box<dyn Error>> {
+		Ok(ECTlsKeyShare {
+			group : ECTlsNamedGroup::from_group(&pubkey.get_group())?,
+			key_exchange : pubkey.to_bin(EC_UNCOMPRESSED)?,
+		})
+	}
+
+	/*length , 0x04 form and point on the curve*/
+	pub fn check(&self) -> Result<(),Box<dyn Error>> {
+		if self.key_exchange.len() != self.group.key_exchange_size() {
+			ecsimple_new_error!{ECTlsError,"{} key_exchange of {} bytes",self.group.name(),self.key_exchange.len()}
+		}
+		if self.group.curve_name().is_some() {
+			let _ = self.pubkey()?;
+		}
+		Ok(())
+	}
+
+	pub fn pubkey(&self) -> Result<ECPublicKey,Box<dyn Error>> {
+		let cname :&str = match self.group.curve_name() {
+			Some(v) => v,
+			None => {
+				ecsimple_new_error!{ECTlsError,"{} not an ECGroup",self.group.name()}
+			}
+		};
+		if self.key_exchange.len() != self.group.key_exchange_size() || self.key_exchange[0] != EC_CODE_UNCOMPRESSED {
+			ecsimple_new_error!{ECTlsError,"{} key_exchange not an uncompressed point",self.group.name()}
+		}
+		let grp :ECGroup = ecc_get_curve_group(cname)?;
+		ECPublicKey::from_bin(&grp,&self.key_exchange)
+	}
+
+	/*RFC 8446 7.4.2 , the x coordinate as the shared secret*/
+	pub fn shared_secret(&self,privkey :&ECPrivateKey) -> Result<Vec<u8>,Box<dyn Error>> {
+		let peer :ECPublicKey = self.pubkey()?;
+		if ECTlsNamedGroup::from_group(&privkey.get_group())? != self.group {
+			ecsimple_new_error!{ECTlsError,"private key not for {}",self.group.name()}
+		}
+		privkey.ecdh(&peer)
+	}
+
+	pub fn encode(&self) -> Vec<u8> {
+		let mut retv :Vec<u8> = self.group.code().to_be_bytes().to_vec();
+		tls_put_vec16(&mut retv,&self.key_exchange);
+		retv
+	}
+
+	/*the group code and None for a group not known here , which a server ignores*/
+	fn decode_entry(r :&mut TlsReader) -> Result<(u16,Option<ECTlsKeyShare>),Box<dyn Error>> {
+		let code :u16 = r.read_u16()?;
+		let kex :&[u8] = r.read_vec16()?;
+		let group :ECTlsNamedGroup = match ECTlsNamedGroup::from_code(code) {
+			Ok(g) => g,
+			Err(_e) => {
+				ecsimple_log_trace!("skip key share {:?}",_e);
+				return Ok((code,None));
+			}
+		};
+		let retv :ECTlsKeyShare = ECTlsKeyShare {
+			group,
+			key_exchange : kex.to_vec(),
+		};
+		retv.check()?;
+		Ok((code,Some(retv)))
+	}
+
+	pub fn decode(data :&[u8]) -> Result<ECTlsKeyShare,Box<dyn Error>> {
+		let mut r :TlsReader = TlsReader::new(data);
+		let (code,retv) = ECTlsKeyShare::decode_entry(&mut r)?;
+		if r.remain() != 0 {
+			ecsimple_new_error!{ECTlsError,"{} bytes after KeyShareEntry",r.remain()}
+		}
+		match retv {
+			Some(v) => Ok(v),
+			None => {
+				ecsimple_new_error!{ECTlsError,"unknown named group 0x{:04x}",code}
+			}
+		}
+	}
+}
+
+/*KeyShareClientHello extension_data*/
+pub fn tls13_key_share_client_encode(shares :&[ECTlsKeyShare]) -> Vec<u8> {
+	let mut entries :Vec<u8> = Vec::new();
+	for s in shares.iter() {
+		entries.extend_from_slice(&s.encode());
+	}
+	let mut retv :Vec<u8> = Vec::new();
+	tls_put_vec16(&mut retv,&entries);
+	retv
+}
+
+/*unknown groups skipped , a repeated group is an error as RFC 8446 4.2.8 says*/
+pub fn tls13_key_share_client_decode(data :&[u8]) -> Result<Vec<ECTlsKeyShare>,Box<dyn Error>> {
+	let mut r :TlsReader = TlsReader::new(data);
+	let entries :&[u8] = r.read_vec16()?;
+	if r.remain() != 0 {
+		ecsimple_new_error!{ECTlsError,"{} bytes after client_shares",r.remain()}
+	}
+	let mut retv :Vec<ECTlsKeyShare> = Vec::new();
+	let mut er :TlsReader = TlsReader::new(entries);
+	let mut codes :Vec<u16> = Vec::new();
+	while er.remain() > 0 {
+		let (code,s) = ECTlsKeyShare::decode_entry(&mut er)?;
+		if codes.contains(&code) {
+			ecsimple_new_error!{ECTlsError,"named group 0x{:04x} offered twice",code}
+		}
+		codes.push(code);
+		if let Some(ks) = s {
+			retv.push(ks);
+		}
+
+	}
+	Ok(retv)
+}
+
+/*KeyShareServerHello extension_data*/
+pub fn tls13_key_share_server_encode(share :&ECTlsKeyShare) -> Vec<u8> {
+	share.encode()
+}
+
+pub fn tls13_key_share_server_decode(data :&[u8]) -> Result<ECTlsKeyShare,Box<dyn Error>> {
+	ECTlsKeyShare::decode(data)
+}
+
+/*KeyShareHelloRetryRequest extension_data*/
+pub fn tls13_key_share_hrr_encode(group :ECTlsNamedGroup) -> Vec<u8> {
+	group.code().to_be_bytes().to_vec()
+}
+
+pub fn tls13_key_share_hrr_decode(data :&[u8]) -> Result<ECTlsNamedGroup,Box<dyn Error>> {
+	if data.len() != 2 {
+		ecsimple_new_error!{ECTlsError,"selected_group of {} bytes",data.len()}
+	}
+	ECTlsNamedGroup::from_code(u16::from_be_bytes([data[0],data[1]]))
+}
+
+/*RFC 8446 4.2.3 ECDSA schemes and RFC 8998 sm2sig_sm3*/
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECTlsSigScheme {
+	EcdsaSecp256r1Sha256,
+	EcdsaSecp384r1Sha384,
+	EcdsaSecp521r1Sha512,
+	Sm2sigSm3,
+}
+
+impl ECTlsSigScheme {
+	pub fn code(&self) -> u16 {
+		match self {
+			ECTlsSigScheme::EcdsaSecp256r1Sha256 => 0x0403,
+			ECTlsSigScheme::EcdsaSecp384r1Sha384 => 0x0503,
+			ECTlsSigScheme::EcdsaSecp521r1Sha512 => 0x0603,
+			ECTlsSigScheme::Sm2sigSm3 => 0x0708,
+		}
+	}
+
+	pub fn from_code(code :u16) -> Result<ECTlsSigScheme,Box<dyn Error>> {
+		for s in [ECTlsSigScheme::EcdsaSecp256r1Sha256,ECTlsSigScheme::EcdsaSecp384r1Sha384,
+			ECTlsSigScheme::EcdsaSecp521r1Sha512,ECTlsSigScheme::Sm2sigSm3].iter() {
+			if s.code() == code {
+				return Ok(*s);
+			}
+		}
+		ecsimple_new_error!{ECTlsError,"unsupported signature scheme 0x{:04x}",code}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			ECTlsSigScheme::EcdsaSecp256r1Sha256 => "ecdsa_secp256r1_sha256",
+			ECTlsSigScheme::EcdsaSecp384r1Sha384 => "ecdsa_secp384r1_sha384",
+			ECTlsSigScheme::EcdsaSecp521r1Sha512 => "ecdsa_secp521r1_sha512",
+			ECTlsSigScheme::Sm2sigSm3 => "sm2sig_sm3",
+		}
+	}
+
+	pub fn hash_type(&self) -> ECHashType {
+		match self {
+			ECTlsSigScheme::EcdsaSecp256r1Sha256 => ECHashType::Sha256,
+			ECTlsSigScheme::EcdsaSecp384r1Sha384 => ECHashType::Sha384,
+			ECTlsSigScheme::EcdsaSecp521r1Sha512 => ECHashType::Sha512,
+			ECTlsSigScheme::Sm2sigSm3 => ECHashType::Sm3,
+		}
+	}
+
+	/*TLS 1.3 binds the curve to the scheme*/
+	pub fn named_group(&self) -> ECTlsNamedGroup {
+		match self {
+			ECTlsSigScheme::EcdsaSecp256r1Sha256 => ECTlsNamedGroup::Secp256r1,
+			ECTlsSigScheme::EcdsaSecp384r1Sha384 => ECTlsNamedGroup::Secp384r1,
+			ECTlsSigScheme::EcdsaSecp521r1Sha512 => ECTlsNamedGroup::Secp521r1,
+			ECTlsSigScheme::Sm2sigSm3 => ECTlsNamedGroup::CurveSm2,
+		}
+	}
+
+	pub fn from_key(pubkey :&ECPublicKey) -> Result<ECTlsSigScheme,Box<dyn Error>> {
+		let g :ECTlsNamedGroup = ECTlsNamedGroup::from_group(&pubkey.get_group())?;
+		for s in [ECTlsSigScheme::EcdsaSecp256r1Sha256,ECTlsSigScheme::EcdsaSecp384r1Sha384,
+			ECTlsSigScheme::EcdsaSecp521r1Sha512,ECTlsSigScheme::Sm2sigSm3].iter() {
+			if s.named_group() == g {
+				return Ok(*s);
+			}
+		}
+		ecsimple_new_error!{ECTlsError,"no signature scheme for {}",g.name()}
+	}
+
+	fn check_key(&self,pubkey :&ECPublicKey) -> Result<(),Box<dyn Error>> {
+		if ECTlsNamedGroup::from_group(&pubkey.get_group())? != self.named_group() {
+			ecsimple_new_error!{ECTlsError,"{} key not for {}",pubkey.get_group().info().curvename,self.name()}
+		}
+		Ok(())
+	}
+
+	/*SM3(Z || content) with the RFC 8998 id for SM2*/
+	fn content_hash(&self,pubkey :&ECPublicKey,content :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+		if *self == ECTlsSigScheme::Sm2sigSm3 {
+			let mut zdata :Vec<u8> = pubkey.get_sm3_hashcode(TLS13_SM2_ID)?;
+			zdata.extend_from_slice(content);
+			return Ok(ECHashType::Sm3.digest(&zdata));
+		}
+		Ok(self.hash_type().digest(content))
+	}
+}
+
+/*64 spaces , context string , 0 and the transcript hash*/
+pub fn tls13_certificate_verify_input(server :bool,transcript_hash :&[u8]) -> Vec<u8> {
+	let mut retv :Vec<u8> = vec![TLS13_CV_PAD;TLS13_CV_PAD_LEN];
+	if server {
+		retv.extend_from_slice(TLS13_CV_SERVER_CONTEXT.as_bytes());
+	} else {
+		retv.extend_from_slice(TLS13_CV_CLIENT_CONTEXT.as_bytes());
+	}
+	retv.push(0);
+	retv.extend_from_slice(transcript_hash);
+	retv
+}
+
+/*DER ECDSA-Sig-Value for the CertificateVerify signature field*/
+pub fn tls13_certificate_verify_sign(scheme :ECTlsSigScheme,privkey :&ECPrivateKey,server :bool,transcript_hash :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let pubkey :ECPublicKey = privkey.export_pubkey();
+	scheme.check_key(&pubkey)?;
+	let hashv :Vec<u8> = scheme.content_hash(&pubkey,&tls13_certificate_verify_input(server,transcript_hash))?;
+	let sig :ECSignature = if scheme == ECTlsSigScheme::Sm2sigSm3 {
+		privkey.sign_sm2_base(&hashv)?
+	} else {
+		privkey.sign_base(&hashv)?
+	};
+	sig.encode_asn1()
+}
+
+/*false on a bad signature , error on a scheme not fit for the key*/
+pub fn tls13_certificate_verify_verify(scheme :ECTlsSigScheme,pubkey :&ECPublicKey,server :bool,transcript_hash :&[u8],sigder :&[u8]) -> Result<bool,Box<dyn Error>> {
+	scheme.check_key(pubkey)?;
+	let hashv :Vec<u8> = scheme.content_hash(pubkey,&tls13_certificate_verify_input(server,transcript_hash))?;
+	let sig :ECSignature = match ECSignature::decode_asn1(sigder) {
+		Ok(v) => v,
+		Err(_e) => {
+			ecsimple_log_trace!("signature not decoded {:?}",_e);
+			return Ok(false);
+		}
+	};
+	if scheme == ECTlsSigScheme::Sm2sigSm3 {
+		return pubkey.verify_sm2_base(&sig,&hashv);
+	}
+	pubkey.verify_base(&sig,&hashv)
+}
+
+/*the CertificateVerify handshake body , algorithm and signature*/
+pub fn tls13_certificate_verify_encode(scheme :ECTlsSigScheme,sigder :&[u8]) -> Vec<u8> {
+	let mut retv :Vec<u8> = scheme.code().to_be_bytes().to_vec();
+	tls_put_vec16(&mut retv,sigder);
+	retv
+}
+
+pub fn tls13_certificate_verify_decode(data :&[u8]) -> Result<(ECTlsSigScheme,Vec<u8>),Box<dyn Error>> {
+	let mut r :TlsReader = TlsReader::new(data);
+	let scheme :ECTlsSigScheme = ECTlsSigScheme::from_code(r.read_u16()?)?;
+	let sig :Vec<u8> = r.read_vec16()?.to_vec();
+	if r.remain() != 0 {
+		ecsimple_new_error!{ECTlsError,"{} bytes after CertificateVerify",r.remain()}
+	}
+	Ok((scheme,sig))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex::FromHex;
+	use num_bigint::{BigInt,Sign};
+
+	/*SHA-256("ecsimple tls13 transcript") as the transcript hash*/
+	const CV_TRANSCRIPT_HEX :&str = "c61a8ae62982acb790cbd57143e3460859ec06f2b3041504864009945408e495";
+	const CV_SERVER_INPUT_HEX :&str = "20202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020202020544c5320312e332c2073657276657220436572746966696361746556657269667900c61a8ae62982acb790cbd57143e3460859ec06f2b3041504864009945408e495";
+
+	/*openssl dgst -sign over the server input , (curve , scheme , private , public , signature)*/
+	const CV_OPENSSL_VECTORS :[(&str,ECTlsSigScheme,&str,&str,&str);2] = [
+		(PRIME256v1_NAME,ECTlsSigScheme::EcdsaSecp256r1Sha256,"d1fca701b4fe9a763875def1a0b02d1c5f38d81b254aba3f1562c6bd8f8d25ae",
+			"048e392e4fa9b6c254f7944079de3c62fae4e6e7f005b98cbcad8cf464a735845a34ebee28ff90905b442aed3b772b6f84ea8541ac5ae84b2642648785b9ebb51d",
+			"3045022100e8f522622557c0252942b97721feaf94d84ada501276bc8b2a2028e7059f6b1f02202d31c6f80987872ec1454efe4258b13c754d5b1fee6596b2f2fd97be7ff23711"),
+		/*-sm3 -sigopt distid:TLSv1.3+GM+Cipher+Suite , RFC 8998 3.2.1*/
+		(SM2_NAME,ECTlsSigScheme::Sm2sigSm3,"495c9a0eee8a24f2087dc2707a389abeb4dfd8c7fd024c6ddea54be9cd67c456",
+			"04648a26c368defba21674923c47d848215370a8730c1326e398fdc02e79ae6922fae481dc57695e13afbc77a8e330f327d04727898b583b8335f86db4edfc50ca",
+			"304402207808402008589e5e3fa411f67af9f07b50b1588127f15d90492bbd2ef11b1e28022010829895bc7a73a8f95a6abca0502652f3108d2eed62192379f153a0b4b4d3c5"),
+	];
+
+	#[test]
+	fn test_certificate_verify_input() {
+		let th :Vec<u8> = Vec::from_hex(CV_TRANSCRIPT_HEX).unwrap();
+		assert_eq!(tls13_certificate_verify_input(true,&th),Vec::from_hex(CV_SERVER_INPUT_HEX).unwrap());
+		let client :Vec<u8> = tls13_certificate_verify_input(false,&th);
+		assert_eq!(&client[..64],&[0x20u8;64][..]);
+		assert_eq!(&client[64..97],TLS13_CV_CLIENT_CONTEXT.as_bytes());
+		assert_eq!(client[97],0);
+		assert_eq!(&client[98..],&th[..]);
+	}
+
+	#[test]
+	fn test_certificate_verify_openssl() {
+		let th :Vec<u8> = Vec::from_hex(CV_TRANSCRIPT_HEX).unwrap();
+		for (cname,scheme,privhex,pubhex,sighex) in CV_OPENSSL_VECTORS.iter() {
+			let grp :ECGroup = ecc_get_curve_group(cname).unwrap();
+			let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&Vec::from_hex(pubhex).unwrap()).unwrap();
+			let privkey :ECPrivateKey = ECPrivateKey::new(&grp,&BigInt::from_bytes_be(Sign::Plus,&Vec::from_hex(privhex).unwrap()));
+			let sig :Vec<u8> = Vec::from_hex(sighex).unwrap();
+			assert_eq!(ECTlsSigScheme::from_key(&pubkey).unwrap(),*scheme);
+			assert!(tls13_certificate_verify_verify(*scheme,&pubkey,true,&th,&sig).unwrap());
+			assert!(!tls13_certificate_verify_verify(*scheme,&pubkey,false,&th,&sig).unwrap());
+			let body :Vec<u8> = tls13_certificate_verify_encode(*scheme,&sig);
+			let (s2,sig2) = tls13_certificate_verify_decode(&body).unwrap();
+			assert_eq!(s2,*scheme);
+			assert_eq!(sig2,sig);
+			let mysig :Vec<u8> = tls13_certificate_verify_sign(*scheme,&privkey,false,&th).unwrap();
+			assert!(tls13_certificate_verify_verify(*scheme,&pubkey,false,&th,&mysig).unwrap());
+			assert!(!tls13_certificate_verify_verify(*scheme,&pubkey,true,&th,&mysig).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_sm2sig_sm3_id() {
+		let (cname,_,_,pubhex,sighex) = CV_OPENSSL_VECTORS[1];
+		let grp :ECGroup = ecc_get_curve_group(cname).unwrap();
+		let pubkey :ECPublicKey = ECPublicKey::from_bin(&grp,&Vec::from_hex(pubhex).unwrap()).unwrap();
+		let sig :ECSignature = ECSignature::decode_asn1(&Vec::from_hex(sighex).unwrap()).unwrap();
+		let input :Vec<u8> = Vec::from_hex(CV_SERVER_INPUT_HEX).unwrap();
+		let mut zdata :Vec<u8> = pubkey.get_sm3_hashcode(TLS13_SM2_ID).unwrap();
+		zdata.extend_from_slice(&input);
+		assert!(pubkey.verify_sm2_base(&sig,&ECHashType::Sm3.digest(&zdata)).unwrap());
+		/*the GB/T default id is not what RFC 8998 signs with*/
+		let mut zdef :Vec<u8> = pubkey.get_sm3_hashcode(b"1234567812345678").unwrap();
+		zdef.extend_from_slice(&input);
+		assert!(!pubkey.verify_sm2_base(&sig,&ECHashType::Sm3.digest(&zdef)).unwrap());
+		let p256 :ECPrivateKey = ECPrivateKey::generate(&ecc_get_curve_group(PRIME256v1_NAME).unwrap());
+		assert!(tls13_certificate_verify_sign(ECTlsSigScheme::Sm2sigSm3,&p256,true,&[0u8;32]).is_err());
+	}
+
+	#[test]
+	fn test_named_group_codes() {
+		let codes :[(ECTlsNamedGroup,u16);5] = [(ECTlsNamedGroup::Secp256r1,0x0017),(ECTlsNamedGroup::Secp384r1,0x0018),
+			(ECTlsNamedGroup::Secp521r1,0x0019),(ECTlsNamedGroup::X25519,0x001d),(ECTlsNamedGroup::CurveSm2,0x0029)];
+		for (g,c) in codes.iter() {
+			assert_eq!(g.code(),*c);
+			assert_eq!(ECTlsNamedGroup::from_code(*c).unwrap(),*g);
+			assert_eq!(tls13_key_share_hrr_decode(&tls13_key_share_hrr_encode(*g)).unwrap(),*g);
+		}
+		/*registered tls groups without a variant here*/
+		assert!(ECTlsNamedGroup::from_code(0x0015).is_err());
+		assert!(ECTlsNamedGroup::from_code(0x001f).is_err());
+		assert!(ECTlsNamedGroup::from_code(0x0100).is_err());
+	}
+
+	#[test]
+	fn test_key_share_ecdh() {
+		for g in [ECTlsNamedGroup::Secp256r1,ECTlsNamedGroup::Secp384r1,ECTlsNamedGroup::CurveSm2].iter() {
+			let grp :ECGroup = ecc_get_curve_group(g.curve_name().unwrap()).unwrap();
+			let a :ECPrivateKey = ECPrivateKey::generate(&grp);
+			let b :ECPrivateKey = ECPrivateKey::generate(&grp);
+			let sa :ECTlsKeyShare = ECTlsKeyShare::new(&a.export_pubkey()).unwrap();
+			let sb :ECTlsKeyShare = tls13_key_share_server_decode(&tls13_key_share_server_encode(&ECTlsKeyShare::new(&b.export_pubkey()).unwrap())).unwrap();
+			assert_eq!(sa.group,*g);
+			assert_eq!(sb.shared_secret(&a).unwrap(),sa.shared_secret(&b).unwrap());
+			let mut bad :ECTlsKeyShare = sa.clone();
+			let l :usize = bad.key_exchange.len();
+			bad.key_exchange[l - 1] ^= 1;
+			assert!(bad.check().is_err());
+			assert!(bad.shared_secret(&b).is_err());
+		}
+	}
+}
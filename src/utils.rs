@@ -1,5 +1,7 @@
 use num_bigint::{BigInt,Sign};
-use num_traits::{zero,one};
+use num_traits::{zero,one,Signed};
+
+#[allow(unused_imports)]
 use crate::*;
 #[allow(unused_imports)]
 use crate::logger::*;
@@ -17,14 +19,26 @@ fn bn_is_odd(a :&BigInt) -> bool {
     if (a % &tv) == zv {
         return false;
     }
-    return true;
+    true
+}
+
+/*big endian of exactly size bytes with leading zeros , as the fixed width JOSE COSE and DNSSEC fields*/
+pub fn bigint_to_fixed_bytes(v :&BigInt,size :usize) -> Result<Vec<u8>,Box<dyn Error>> {
+	let (_,mut retv) = v.to_bytes_be();
+	if retv.len() > size {
+		ecsimple_new_error!{ECUtilsError,"value {} bytes > {}",retv.len(),size}
+	}
+	while retv.len() < size {
+		retv.insert(0,0x0);
+	}
+	Ok(retv)
 }
 
 pub (crate) fn format_bigint_as_order(bn2 :&[u8], order :&BigInt) -> BigInt {
 	let obits :i64 = get_max_bits(order);
     ecsimple_log_trace!("order bits 0x{:x}",obits);
 	let mut bs :Vec<u8>;
-	bs = bn2.clone().to_vec();
+	bs = bn2.to_vec();
 	if (8 * bs.len()) > (obits as usize) {
 		bs = bs[0..(((obits as usize) +7) >> 3)].to_vec();
 	}
@@ -34,9 +48,9 @@ pub (crate) fn format_bigint_as_order(bn2 :&[u8], order :&BigInt) -> BigInt {
 	let mut retv :BigInt = BigInt::from_bytes_be(Sign::Plus,&bs);
     ecsimple_log_trace!("dgst_len 0x{:x} 0x{:X}",bs.len(),retv);
 	if bs.len() * 8 > (obits as usize) {
-		retv = retv >> (8 - obits & 0x7);
+		retv >>= (8 - obits) & 0x7 ;
 	}
-	return retv;
+	retv
 }
 
 pub (crate) fn get_bit_set(bn :&BigInt,i :i32) -> i32 {
@@ -46,7 +60,7 @@ pub (crate) fn get_bit_set(bn :&BigInt,i :i32) -> i32 {
 	if (bn & (ov << i)) != zv {
 		retv = 1;
 	}
-	return retv;
+	retv
 }
 
 pub (crate) fn nmod(a :&BigInt,m :&BigInt) -> BigInt {
@@ -56,16 +70,16 @@ pub (crate) fn nmod(a :&BigInt,m :&BigInt) -> BigInt {
 	if retv < zv {
 		retv += m;
 	}
-	return retv;
+	retv
 }
 
 fn verify_mod_sqrt(retv :&BigInt,ac :&BigInt,p :&BigInt) -> Result<BigInt,Box<dyn Error>> {
-    let cv :BigInt;
-    cv = retv.clone() * retv.clone() % p.clone();
+    
+    let cv :BigInt = retv.clone() * retv.clone() % p.clone();
     if cv != ac.clone() {
         ecsimple_new_error!{ECUtilsError,"check 0x{:X} for mod_sqrt(0x{:X},0x{:X}) error",retv,ac,p}
     }
-    return Ok(retv.clone());
+    Ok(retv.clone())
 }
 
 
@@ -85,7 +99,7 @@ fn get_lsw(v :&BigInt, mask :u32) -> u32 {
         retv |= (vecs[idx] as u32) << (idx * 8);
         idx += 1;
     }
-    return retv & mask;
+    retv & mask
 }
 
 #[allow(unused_variables)]
@@ -168,7 +182,7 @@ fn kronecker_value(a :&BigInt,b :&BigInt) -> i32 {
 
         if (i & 1) != 0 {
             ecsimple_log_trace!("ret {} = ret {} * tab[BN_lsw(0x{:X})&7 = 0x{:x}]",ret * KRONTAB[get_lsw(&B,7) as usize], ret,B,get_lsw(&B,7));
-            ret = ret * KRONTAB[get_lsw(&B,7) as usize];
+            ret *= KRONTAB[get_lsw(&B,7) as usize];
         }
 
         let mut rv :u32;
@@ -181,9 +195,9 @@ fn kronecker_value(a :&BigInt,b :&BigInt) -> i32 {
         }
 
         if rv != 0 {
-            let mut negv :String=format!("0");
+            let mut negv :String="0".to_string();
             if A < zv {
-                negv = format!("1");
+                negv = "1".to_string();
             }
             ecsimple_log_trace!("A->neg {} A 0x{:X} B 0x{:X}",negv,A,B);
             ret = -ret;
@@ -208,7 +222,7 @@ pub fn mod_sqrt(ac :&BigInt,p :&BigInt) -> Result<BigInt,Box<dyn Error>> {
     let ov :BigInt = one();
     let zv :BigInt = zero();
     let tv :BigInt = ov.clone() + ov.clone();
-    let A :BigInt;
+    
     let mut b :BigInt;
     let mut q :BigInt;
     let mut t :BigInt;
@@ -236,7 +250,7 @@ pub fn mod_sqrt(ac :&BigInt,p :&BigInt) -> Result<BigInt,Box<dyn Error>> {
         return Ok(retv);
     }
 
-    A = nmod(ac,p);
+    let A :BigInt = nmod(ac,p);
     ecsimple_log_trace!("BN_nnmod(A 0x{:X},a 0x{:X},p 0x{:X})",A,ac,p);
     e = 1;
     while get_bit_set(p,e) == 0 {
@@ -327,15 +341,13 @@ pub fn mod_sqrt(ac :&BigInt,p :&BigInt) -> Result<BigInt,Box<dyn Error>> {
      i = 2;
      loop {
         if i < 22 {
-            vecs = Vec::new();
-            vecs.push(i as u8);
+            vecs = vec![i as u8];
             y = BigInt::from_bytes_le(Sign::Plus,&vecs);
         } else {
             y = ecsimple_rand_bits(get_max_bits(p) as u64,0,0);
             y = nmod(&y,p);
             if y == zv {
-                vecs = Vec::new();
-                vecs.push(i as u8);
+                vecs = vec![i as u8];
                 y = BigInt::from_bytes_le(Sign::Plus,&vecs);                
             }
         }
@@ -488,14 +500,14 @@ pub (crate) fn get_wnaf_bits(bn :&BigInt) -> i32 {
     } else if bbits >= 20 {
         return 2;
     }
-    return 1;
+    1
 }
 
 pub (crate) fn wnaf_value(bn :&BigInt,w :i32) -> Result<Vec<u8>,Box<dyn Error>> {
     let mut retv :Vec<u8> = Vec::new();
     let zv :BigInt = zero();
     let ov :BigInt = one();
-    if w < 1 || w > 7 {
+    if !(1..=7).contains(&w) {
         ecsimple_new_error!{ECUtilsError,"w {} < 1 || > 7", w};
     }
     let bit :BigInt = ov.clone() << w;
@@ -503,9 +515,9 @@ pub (crate) fn wnaf_value(bn :&BigInt,w :i32) -> Result<Vec<u8>,Box<dyn Error>>
     let mask :BigInt = next_bit.clone() - ov.clone();
     let mut window_val :BigInt;
     let mut j :i32 = 0;
-    let lenv :i32;
+    
     window_val = bn.clone() & mask.clone();
-    lenv = get_max_bits(bn) as i32;
+    let lenv :i32 = get_max_bits(bn) as i32;
     while window_val != zv || (j+ w + 1) < lenv {
         let mut digit : BigInt = zv.clone();
         if (window_val.clone() & ov.clone()) != zv {
@@ -526,7 +538,7 @@ pub (crate) fn wnaf_value(bn :&BigInt,w :i32) -> Result<Vec<u8>,Box<dyn Error>>
         if digit >= zv {
             retv.push(vecs[0]);     
         } else {
-            retv.push((0xff - vecs[0] + 1) as u8);
+            retv.push(0xff - vecs[0] + 1  );
         }
         
         j += 1;
@@ -574,5 +586,230 @@ pub (crate) fn bnusub(anum :&BigInt,bnum :&BigInt) -> BigInt {
         retv = BigInt::from_bytes_le(Sign::Plus,&resvecs);
     }
 
-    return retv;
+    retv
+}
+
+const SMALL_PRIMES :[u32;12] = [2,3,5,7,11,13,17,19,23,29,31,37];
+
+fn bn_mod_pos(a :&BigInt,n :&BigInt) -> BigInt {
+	let r :BigInt = a % n;
+	if r.sign() == Sign::Minus {
+		return r + n;
+	}
+	r
+}
+
+/*jacobi symbol (a/n) for odd positive n*/
+pub (crate) fn bn_jacobi(a :&BigInt,n :&BigInt) -> i32 {
+	let zv :BigInt = zero();
+	let ov :BigInt = one();
+	let mut x :BigInt = bn_mod_pos(a,n);
+	let mut y :BigInt = n.clone();
+	let mut retv :i32 = 1;
+	while x != zv {
+		while !bn_is_odd(&x) {
+			x >>= 1;
+			let r8 :BigInt = &y % BigInt::from(8u32);
+			if r8 == BigInt::from(3u32) || r8 == BigInt::from(5u32) {
+				retv = -retv;
+			}
+		}
+		std::mem::swap(&mut x,&mut y);
+		if (&x % BigInt::from(4u32)) == BigInt::from(3u32) && (&y % BigInt::from(4u32)) == BigInt::from(3u32) {
+			retv = -retv;
+		}
+		x = &x % &y;
+	}
+	if y == ov {
+		return retv;
+	}
+	0
+}
+
+/*n - 1 = d * 2^s , a the witness*/
+fn bn_miller_rabin_round(n :&BigInt,a :&BigInt,d :&BigInt,s :u32) -> bool {
+	let ov :BigInt = one();
+	let nm1 :BigInt = n - &ov;
+	let mut x :BigInt = a.modpow(d,n);
+	if x == ov || x == nm1 {
+		return true;
+	}
+	let mut r :u32 = 1;
+	while r < s {
+		x = (&x * &x) % n;
+		if x == nm1 {
+			return true;
+		}
+		r += 1;
+	}
+	false
+}
+
+/*strong lucas probable prime test with the selfridge parameters , FIPS 186-4 C.3.3*/
+fn bn_is_strong_lucas_prp(n :&BigInt) -> bool {
+	let zv :BigInt = zero();
+	let ov :BigInt = one();
+	let sq :BigInt = n.sqrt();
+	if &sq * &sq == *n {
+		return false;
+	}
+	/*D in 5 , -7 , 9 , -11 ... with (D/n) = -1*/
+	let mut dv :BigInt = BigInt::from(5i32);
+	loop {
+		let j :i32 = bn_jacobi(&dv,n);
+		if j == -1 {
+			break;
+		}
+		if j == 0 && bn_mod_pos(&dv,n) != zv {
+			return false;
+		}
+		if dv.sign() == Sign::Minus {
+			dv = -dv + 2;
+		} else {
+			dv = -dv - 2;
+		}
+	}
+	/*P = 1 , Q = (1 - D) / 4*/
+	let qv :BigInt = bn_mod_pos(&((&ov - &dv) / 4),n);
+	let dm :BigInt = bn_mod_pos(&dv,n);
+	let half = |v :BigInt| -> BigInt {
+		if bn_is_odd(&v) {
+			(v + n) >> 1
+		} else {
+			v >> 1
+		}
+	};
+
+	let mut d :BigInt = n + &ov;
+	let mut s :u32 = 0;
+	while !bn_is_odd(&d) {
+		d >>= 1;
+		s += 1;
+	}
+	let mut u :BigInt = ov.clone();
+	let mut v :BigInt = ov.clone();
+	let mut qk :BigInt = qv.clone();
+	let bits :i64 = get_max_bits(&d);
+	let mut i :i64 = bits - 2;
+	while i >= 0 {
+		u = (&u * &v) % n;
+		v = bn_mod_pos(&(&v * &v - &qk - &qk),n);
+		qk = (&qk * &qk) % n;
+		if d.bit(i as u64) {
+			let nu :BigInt = half(&u + &v);
+			let nv :BigInt = half((&dm * &u + &v) % n);
+			u = nu % n;
+			v = nv % n;
+			qk = (&qk * &qv) % n;
+		}
+		i -= 1;
+	}
+	if u == zv || v == zv {
+		return true;
+	}
+	let mut r :u32 = 1;
+	while r < s {
+		v = bn_mod_pos(&(&v * &v - &qk - &qk),n);
+		if v == zv {
+			return true;
+		}
+		qk = (&qk * &qk) % n;
+		r += 1;
+	}
+	false
+}
+
+/*baillie-psw , then rounds more miller-rabin with random witnesses*/
+pub (crate) fn bn_is_probable_prime(n :&BigInt,rounds :usize) -> bool {
+	let zv :BigInt = zero();
+	let ov :BigInt = one();
+	let tv :BigInt = BigInt::from(2u32);
+
+	if n <= &ov {
+		return false;
+	}
+	for p in SMALL_PRIMES.iter() {
+		let bp :BigInt = BigInt::from(*p);
+		if n == &bp {
+			return true;
+		}
+		if (n % &bp) == zv {
+			return false;
+		}
+	}
+
+	let mut d :BigInt = n - &ov;
+	let mut s :u32 = 0;
+	while !bn_is_odd(&d) {
+		d >>= 1;
+		s += 1;
+	}
+	if !bn_miller_rabin_round(n,&tv,&d,s) {
+		return false;
+	}
+	if !bn_is_strong_lucas_prp(n) {
+		return false;
+	}
+	/*witness in [2 , n - 2]*/
+	let range :BigInt = n - BigInt::from(3u32);
+	let mut idx :usize = 0;
+	while idx < rounds {
+		let a :BigInt = ecsimple_rand_range(&range) + &tv;
+		if !bn_miller_rabin_round(n,&a,&d,s) {
+			return false;
+		}
+		idx += 1;
+	}
+	true
+}
+
+/*log2 of the absolute value, good enough for security estimation*/
+pub (crate) fn bn_log2(n :&BigInt) -> f64 {
+	let absn :BigInt = n.abs();
+	let bits :i64 = get_max_bits(&absn);
+	if bits <= 0 {
+		return 0.0;
+	}
+	let shift :i64 = if bits > 52 { bits - 52 } else { 0 };
+
+	let top :BigInt = absn >> shift;
+	let (_,vecs) = top.to_bytes_be();
+	let mut fv :f64 = 0.0;
+	for b in vecs.iter() {
+		fv = fv * 256.0 + (*b as f64);
+	}
+	fv.log2() + (shift as f64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_probable_prime() {
+		for v in ["2","41","65537","170141183460469231731687303715884105727","115792089210356248762697446949407573530086143415290314195533631308867097853951"].iter() {
+			assert!(bn_is_probable_prime(&BigInt::parse_bytes(v.as_bytes(),10).unwrap(),4),"{} prime",v);
+		}
+		/*strong pseudoprimes to every base from 2 to 37 , and carmichael numbers*/
+		for v in ["318665857834031151167461","3317044064679887385961981","3825123056546413051","561","41041","1729","1681"].iter() {
+			assert!(!bn_is_probable_prime(&BigInt::parse_bytes(v.as_bytes(),10).unwrap(),0),"{} composite",v);
+		}
+	}
+
+	#[test]
+	fn test_strong_lucas() {
+		/*the smallest strong lucas pseudoprimes with the selfridge parameters*/
+		for v in [5459u32,5777,10877,16109,18971].iter() {
+			assert!(bn_is_strong_lucas_prp(&BigInt::from(*v)));
+		}
+		for v in [101u32,65537,1000003].iter() {
+			assert!(bn_is_strong_lucas_prp(&BigInt::from(*v)));
+		}
+		for v in [561u32,2047,3277,4033,1681].iter() {
+			assert!(!bn_is_strong_lucas_prp(&BigInt::from(*v)));
+		}
+		assert_eq!(bn_jacobi(&BigInt::from(1001u32),&BigInt::from(9907u32)),-1);
+		assert_eq!(bn_jacobi(&BigInt::from(-7i32),&BigInt::from(15u32)),1);
+		assert_eq!(bn_jacobi(&BigInt::from(5u32),&BigInt::from(15u32)),0);
+	}
 }
@@ -0,0 +1,991 @@
+use crate::consts::*;
+use crate::ecasn1::*;
+use crate::hashop::*;
+use crate::keys::*;
+use crate::pem::*;
+use crate::signature::*;
+use crate::randop::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use asn1obj::base::{Asn1Any,Asn1Object,Asn1Integer,Asn1Boolean,Asn1OctData,Asn1BitDataFlag,Asn1Time};
+use asn1obj::complex::{Asn1Imp,Asn1Ndef};
+use asn1obj::asn1impl::Asn1Op;
+use chrono::{DateTime,Utc,Datelike,Duration};
+use num_bigint::{BigInt,Sign};
+use num_traits::Zero;
+use std::error::Error;
+
+ecsimple_error_class!{ECX509Error}
+
+pub const PEM_CERTIFICATE :&str = "CERTIFICATE";
+/*GM/T 0009 default user id for SM2 signatures*/
+pub const SM2_DEFAULT_ID :&[u8] = b"1234567812345678";
+
+pub const KEY_USAGE_DIGITAL_SIGNATURE :u16 = 1 << 0;
+pub const KEY_USAGE_NON_REPUDIATION :u16 = 1 << 1;
+pub const KEY_USAGE_KEY_ENCIPHERMENT :u16 = 1 << 2;
+pub const KEY_USAGE_DATA_ENCIPHERMENT :u16 = 1 << 3;
+pub const KEY_USAGE_KEY_AGREEMENT :u16 = 1 << 4;
+pub const KEY_USAGE_KEY_CERT_SIGN :u16 = 1 << 5;
+pub const KEY_USAGE_CRL_SIGN :u16 = 1 << 6;
+pub const KEY_USAGE_ENCIPHER_ONLY :u16 = 1 << 7;
+pub const KEY_USAGE_DECIPHER_ONLY :u16 = 1 << 8;
+
+const ASN1_UTF8STRING_TAG :u64 = 0x0c;
+const ASN1_PRINTABLESTRING_TAG :u64 = 0x13;
+const ASN1_T61STRING_TAG :u64 = 0x14;
+const ASN1_IA5STRING_TAG :u64 = 0x16;
+const ASN1_UNIVERSALSTRING_TAG :u64 = 0x1c;
+const ASN1_BMPSTRING_TAG :u64 = 0x1e;
+const ASN1_INTEGER_TAG :u64 = 0x02;
+const ASN1_BITSTRING_TAG :u8 = 0x03;
+const ASN1_GENERALIZEDTIME_TAG :u8 = 0x18;
+const GENERAL_NAME_EMAIL_TAG :u64 = 0x81;
+const GENERAL_NAME_DNS_TAG :u64 = 0x82;
+const GENERAL_NAME_URI_TAG :u64 = 0x86;
+const GENERAL_NAME_IP_TAG :u64 = 0x87;
+
+/*signatureAlgorithm of the certificate , SM2 hashes SM3 with Z first*/
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum ECX509SigAlg {
+	EcdsaSha1,
+	EcdsaSha224,
+	EcdsaSha256,
+	EcdsaSha384,
+	EcdsaSha512,
+	Sm2Sm3,
+}
+
+impl ECX509SigAlg {
+	pub fn oid(&self) -> &'static str {
+		match self {
+			ECX509SigAlg::EcdsaSha1 => ECDSA_WITH_SHA1_OID,
+			ECX509SigAlg::EcdsaSha224 => ECDSA_WITH_SHA224_OID,
+			ECX509SigAlg::EcdsaSha256 => ECDSA_WITH_SHA256_OID,
+			ECX509SigAlg::EcdsaSha384 => ECDSA_WITH_SHA384_OID,
+			ECX509SigAlg::EcdsaSha512 => ECDSA_WITH_SHA512_OID,
+			ECX509SigAlg::Sm2Sm3 => SM2_WITH_SM3_OID,
+		}
+	}
+
+	pub fn from_oid(oid :&str) -> Result<ECX509SigAlg,Box<dyn Error>> {
+		for a in [ECX509SigAlg::EcdsaSha1,ECX509SigAlg::EcdsaSha224,ECX509SigAlg::EcdsaSha256,
+			ECX509SigAlg::EcdsaSha384,ECX509SigAlg::EcdsaSha512,ECX509SigAlg::Sm2Sm3].iter() {
+			if a.oid() == oid {
+				return Ok(*a);
+			}
+		}
+		ecsimple_new_error!{ECX509Error,"unsupported signature algorithm [{}]",oid}
+	}
+
+	pub fn hash_type(&self) -> ECHashType {
+		match self {
+			ECX509SigAlg::EcdsaSha1 => ECHashType::Sha1,
+			ECX509SigAlg::EcdsaSha224 => ECHashType::Sha224,
+			ECX509SigAlg::EcdsaSha256 => ECHashType::Sha256,
+			ECX509SigAlg::EcdsaSha384 => ECHashType::Sha384,
+			ECX509SigAlg::EcdsaSha512 => ECHashType::Sha512,
+			ECX509SigAlg::Sm2Sm3 => ECHashType::Sm3,
+		}
+	}
+
+	/*SM2-with-SM3 for SM2 keys , otherwise the sha of the curve size*/
+	pub fn from_key(pubkey :&ECPublicKey) -> ECX509SigAlg {
+		if pubkey.is_sm2() {
+			return ECX509SigAlg::Sm2Sm3;
+		}
+		match ECHashType::from_order_bits(pubkey.get_group().info().order_bits) {
+			ECHashType::Sha256 => ECX509SigAlg::EcdsaSha256,
+			ECHashType::Sha384 => ECX509SigAlg::EcdsaSha384,
+			_ => ECX509SigAlg::EcdsaSha512,
+		}
+	}
+}
+
+/*the hash to sign or verify , SM2 gets SM3(Z || data) with the default id*/
+pub(crate) fn x509_sig_hash(alg :ECX509SigAlg,pubkey :&ECPublicKey,data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	if alg == ECX509SigAlg::Sm2Sm3 {
+		if !pubkey.is_sm2() {
+			ecsimple_new_error!{ECX509Error,"SM2-with-SM3 for a not SM2 key"}
+		}
+		let mut zdata :Vec<u8> = pubkey.get_sm3_hashcode(SM2_DEFAULT_ID)?;
+		zdata.extend_from_slice(data);
+		return Ok(ECHashType::Sm3.digest(&zdata));
+	}
+	Ok(alg.hash_type().digest(data))
+}
+
+/*verify DER ECDSA-Sig-Value over data , false on bad signature*/
+pub(crate) fn x509_verify_data(alg :ECX509SigAlg,pubkey :&ECPublicKey,data :&[u8],sigder :&[u8]) -> Result<bool,Box<dyn Error>> {
+	let hashv :Vec<u8> = x509_sig_hash(alg,pubkey,data)?;
+	let sig :ECSignature = match ECSignature::decode_asn1(sigder) {
+		Ok(v) => v,
+		Err(_e) => {
+			ecsimple_log_trace!("signature not decoded {:?}",_e);
+			return Ok(false);
+		}
+	};
+	if alg == ECX509SigAlg::Sm2Sm3 {
+		return pubkey.verify_sm2_base(&sig,&hashv);
+	}
+	pubkey.verify_base(&sig,&hashv)
+}
+
+const X509_NAME_SHORTS :[(&str,&str);9] = [
+	(X509_COMMON_NAME_OID,"CN"),
+	(X509_SERIAL_NUMBER_OID,"serialNumber"),
+	(X509_COUNTRY_OID,"C"),
+	(X509_LOCALITY_OID,"L"),
+	(X509_STATE_OID,"ST"),
+	(X509_ORGANIZATION_OID,"O"),
+	(X509_ORG_UNIT_OID,"OU"),
+	(X509_EMAIL_OID,"emailAddress"),
+	(X509_DOMAIN_COMPONENT_OID,"DC"),
+];
+
+/*DER ECDSA-Sig-Value over data*/
+pub(crate) fn x509_sign_data(alg :ECX509SigAlg,privkey :&ECPrivateKey,data :&[u8]) -> Result<Vec<u8>,Box<dyn Error>> {
+	let hashv :Vec<u8> = x509_sig_hash(alg,&privkey.export_pubkey(),data)?;
+	let sig :ECSignature = if alg == ECX509SigAlg::Sm2Sm3 {
+		privkey.sign_sm2_base(&hashv)?
+	} else {
+		privkey.sign_base(&hashv)?
+	};
+	sig.encode_asn1()
+}
+
+/*RFC 5280 4.1.2.5 , UTCTime through 2049 and GeneralizedTime otherwise*/
+pub(crate) fn x509_time_asn1(t :&DateTime<Utc>) -> Result<Asn1Time,Box<dyn Error>> {
+	let mut retv :Asn1Time = Asn1Time::init_asn1();
+	if t.year() >= 1950 && t.year() < 2050 {
+		retv.set_value_time(t)?;
+	} else {
+		let s :String = t.format("%Y%m%d%H%M%SZ").to_string();
+		let mut code :Vec<u8> = vec![ASN1_GENERALIZEDTIME_TAG,s.len() as u8];
+		code.extend_from_slice(s.as_bytes());
+		let _ = retv.decode_asn1(&code)?;
+	}
+	Ok(retv)
+}
+
+/*RFC 5280 4.2.1.2 method 1 , SHA-1 of the subjectPublicKey bits*/
+pub fn x509_key_id(pubkey :&ECPublicKey) -> Result<Vec<u8>,Box<dyn Error>> {
+	return Ok(ECHashType::Sha1.digest(&pubkey.to_bin(EC_UNCOMPRESSED)?));
+}
+
+fn x509_name_short(oid :&str) -> String {
+	for (o,n) in X509_NAME_SHORTS.iter() {
+		if *o == oid {
+			return n.to_string();
+		}
+	}
+	oid.to_string()
+}
+
+fn x509_name_oid(short :&str) -> Result<String,Box<dyn Error>> {
+	for (o,n) in X509_NAME_SHORTS.iter() {
+		if n.eq_ignore_ascii_case(short) {
+			return Ok(o.to_string());
+		}
+	}
+	if !short.is_empty() && short.chars().all(|c| c.is_ascii_digit() || c == '.') {
+		return Ok(short.to_string());
+	}
+	ecsimple_new_error!{ECX509Error,"unknown name attribute [{}]",short}
+}
+
+/*C and serialNumber are PrintableString , emailAddress and DC IA5String*/
+fn x509_name_value_any(oid :&str,value :&str) -> Result<Asn1Any,Box<dyn Error>> {
+	let mut retv :Asn1Any = Asn1Any::init_asn1();
+	match oid {
+		X509_COUNTRY_OID | X509_SERIAL_NUMBER_OID => {
+			for c in value.chars() {
+				if !(c.is_ascii_alphanumeric() || " '()+,-./:=?".contains(c)) {
+					ecsimple_new_error!{ECX509Error,"[{}] not printable for [{}]",value,x509_name_short(oid)}
+				}
+			}
+			retv.tag = ASN1_PRINTABLESTRING_TAG;
+		},
+		X509_EMAIL_OID | X509_DOMAIN_COMPONENT_OID => {
+			if !value.is_ascii() {
+				ecsimple_new_error!{ECX509Error,"[{}] not ascii for [{}]",value,x509_name_short(oid)}
+			}
+			retv.tag = ASN1_IA5STRING_TAG;
+		},
+		_ => {
+			retv.tag = ASN1_UTF8STRING_TAG;
+		}
+	}
+	retv.content = value.as_bytes().to_vec();
+	Ok(retv)
+}
+
+/*the directory string types , others given in hex*/
+fn x509_any_to_string(v :&Asn1Any) -> String {
+	match v.tag {
+		ASN1_UTF8STRING_TAG | ASN1_PRINTABLESTRING_TAG | ASN1_IA5STRING_TAG => {
+			String::from_utf8_lossy(&v.content).to_string()
+		},
+		ASN1_T61STRING_TAG => {
+			v.content.iter().map(|c| *c as char).collect()
+		},
+		ASN1_BMPSTRING_TAG => {
+			let mut u :Vec<u16> = Vec::new();
+			for c in v.content.chunks(2) {
+				if c.len() == 2 {
+					u.push(((c[0] as u16) << 8) | (c[1] as u16));
+				}
+			}
+			String::from_utf16_lossy(&u)
+		},
+		ASN1_UNIVERSALSTRING_TAG => {
+			let mut rets :String = "".to_string();
+			for c in v.content.chunks(4) {
+				if c.len() == 4 {
+					let cv :u32 = u32::from_be_bytes([c[0],c[1],c[2],c[3]]);
+					rets.push(char::from_u32(cv).unwrap_or('?'));
+				}
+			}
+			rets
+		},
+		_ => {
+			format!("#{}",hex::encode(&v.content))
+		}
+	}
+}
+
+#[derive(Clone,Debug)]
+pub struct ECX509Name {
+	/*oid and value in the order of the rdns*/
+	pub entries :Vec<(String,String)>,
+	pub der :Vec<u8>,
+}
+
+impl ECX509Name {
+	pub fn from_der(der :&[u8]) -> Result<ECX509Name,Box<dyn Error>> {
+		let mut name :Asn1X509Name = Asn1X509Name::init_asn1();
+		let used :usize = name.decode_asn1(der)?;
+		if used != der.len() {
+			ecsimple_new_error!{ECX509Error,"name {} bytes left",der.len() - used}
+		}
+		let mut entries :Vec<(String,String)> = Vec::new();
+		for rdn in name.rdns.val.iter() {
+			for e in rdn.entries.val.iter() {
+				let ev :&Asn1X509NameEntryElem = &e.elem.val[0];
+				entries.push((ev.object.get_value(),x509_any_to_string(&ev.value)));
+			}
+		}
+		Ok(ECX509Name {
+			entries,
+			der : der.to_vec(),
+		})
+	}
+
+	/*the first value of the attribute , CN with X509_COMMON_NAME_OID*/
+	pub fn get(&self,oid :&str) -> Option<String> {
+		for (k,v) in self.entries.iter() {
+			if k == oid {
+				return Some(v.clone());
+			}
+		}
+		None
+	}
+
+	pub fn common_name(&self) -> Option<String> {
+		self.get(X509_COMMON_NAME_OID)
+	}
+
+	/*(oid , value) each in its own RDN in the order given*/
+	pub fn new(entries :&[(&str,&str)]) -> Result<ECX509Name,Box<dyn Error>> {
+		let mut name :Asn1X509Name = Asn1X509Name::init_asn1();
+		for (oid,value) in entries.iter() {
+			let mut ev :Asn1X509NameEntryElem = Asn1X509NameEntryElem::init_asn1();
+			let _ = ev.object.set_value(oid)?;
+			ev.value = x509_name_value_any(oid,value)?;
+			let mut e :Asn1X509NameEntry = Asn1X509NameEntry::init_asn1();
+			e.elem.val.push(ev);
+			let mut rdn :Asn1X509Rdn = Asn1X509Rdn::init_asn1();
+			rdn.entries.val.push(e);
+			name.rdns.val.push(rdn);
+		}
+		return ECX509Name::from_der(&name.encode_asn1()?);
+	}
+
+	/*"CN=example.com, O=Example" as Display gives , backslash escapes a comma*/
+	pub fn parse(s :&str) -> Result<ECX509Name,Box<dyn Error>> {
+		let mut parts :Vec<String> = Vec::new();
+		let mut cur :String = "".to_string();
+		let mut escaped :bool = false;
+		for c in s.chars() {
+			if escaped {
+				cur.push(c);
+				escaped = false;
+			} else if c == '\\' {
+				escaped = true;
+			} else if c == ',' {
+				parts.push(cur.clone());
+				cur = "".to_string();
+			} else {
+				cur.push(c);
+			}
+		}
+		if !cur.trim().is_empty() || !parts.is_empty() {
+			parts.push(cur);
+		}
+		let mut entries :Vec<(String,String)> = Vec::new();
+		for p in parts.iter() {
+			let idx = p.find('=');
+			if idx.is_none() {
+				ecsimple_new_error!{ECX509Error,"no = in [{}]",p}
+			}
+			let idx :usize = idx.unwrap();
+			entries.push((x509_name_oid(p[..idx].trim())?,p[(idx+1)..].trim().to_string()));
+		}
+		let refs :Vec<(&str,&str)> = entries.iter().map(|(k,v)| (k.as_str(),v.as_str())).collect();
+		ECX509Name::new(&refs)
+	}
+}
+
+impl PartialEq for ECX509Name {
+	fn eq(&self,other :&Self) -> bool {
+		self.der == other.der
+	}
+}
+
+impl std::fmt::Display for ECX509Name {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut parts :Vec<String> = Vec::new();
+		for (k,v) in self.entries.iter() {
+			parts.push(format!("{}={}",x509_name_short(k),v.replace("\\","\\\\").replace(",","\\,")));
+		}
+		write!(f,"{}",parts.join(", "))
+	}
+}
+
+/*(cA , pathLenConstraint)*/
+
+pub type ECX509BasicConstraints = (bool,Option<i64>);
+
+#[derive(Clone,Debug)]
+pub struct ECX509Extension {
+	pub oid :String,
+	pub critical :bool,
+	/*the content of extnValue OCTET STRING*/
+	pub value :Vec<u8>,
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum ECX509GeneralName {
+	Dns(String),
+	Email(String),
+	Uri(String),
+	Ip(std::net::IpAddr),
+}
+
+impl ECX509Extension {
+	pub fn new(oid :&str,critical :bool,value :&[u8]) -> ECX509Extension {
+		ECX509Extension {
+			oid : oid.to_string(),
+			critical,
+			value : value.to_vec(),
+		}
+	}
+
+	pub fn basic_constraints(ca :bool,pathlen :Option<i64>) -> Result<ECX509Extension,Box<dyn Error>> {
+		if pathlen.is_some() && (!ca || pathlen.unwrap() < 0) {
+			ecsimple_new_error!{ECX509Error,"pathlen {:?} for ca {}",pathlen,ca}
+		}
+		let mut elem :Asn1X509BasicConstraintsElem = Asn1X509BasicConstraintsElem::init_asn1();
+		if ca {
+			let mut b :Asn1Boolean = Asn1Boolean::init_asn1();
+			b.val = true;
+			elem.ca.val = Some(b);
+		}
+		if let Some(pl) = pathlen {
+			let mut n :Asn1Integer = Asn1Integer::init_asn1();
+			n.val = pl;
+			elem.pathlen.val = Some(n);
+		}
+		let mut bc :Asn1X509BasicConstraints = Asn1X509BasicConstraints::init_asn1();
+		bc.elem.val.push(elem);
+		Ok(ECX509Extension::new(X509_BASIC_CONSTRAINTS_OID,true,&bc.encode_asn1()?))
+	}
+
+	/*KEY_USAGE_* bits as a DER named bit list without trailing zero bits*/
+	pub fn key_usage(bits :u16) -> Result<ECX509Extension,Box<dyn Error>> {
+		if bits == 0 || bits >= (1 << 9) {
+			ecsimple_new_error!{ECX509Error,"key usage bits 0x{:x} not valid",bits}
+		}
+		let mut data :Vec<u8> = vec![0,0];
+		for i in 0..9 {
+			if (bits & (1 << i)) != 0 {
+				data[i / 8] |= 0x80 >> (i % 8);
+			}
+		}
+		if data[1] == 0 {
+			data.pop();
+		}
+		let unused :u8 = data[data.len() - 1].trailing_zeros() as u8;
+		let mut value :Vec<u8> = vec![ASN1_BITSTRING_TAG,(data.len() + 1) as u8,unused];
+		value.extend_from_slice(&data);
+		Ok(ECX509Extension::new(X509_KEY_USAGE_OID,true,&value))
+	}
+
+	/*X509_EKU_*_OID values*/
+	pub fn ext_key_usage(oids :&[&str]) -> Result<ECX509Extension,Box<dyn Error>> {
+		if oids.is_empty() {
+			ecsimple_new_error!{ECX509Error,"empty extended key usage"}
+		}
+		let mut eku :Asn1X509ExtKeyUsage = Asn1X509ExtKeyUsage::init_asn1();
+		for o in oids.iter() {
+			let mut obj :Asn1Object = Asn1Object::init_asn1();
+			let _ = obj.set_value(o)?;
+			eku.oids.val.push(obj);
+		}
+		Ok(ECX509Extension::new(X509_EXT_KEY_USAGE_OID,false,&eku.encode_asn1()?))
+	}
+
+	pub fn subject_alt_name(names :&[ECX509GeneralName]) -> Result<ECX509Extension,Box<dyn Error>> {
+		if names.is_empty() {
+			ecsimple_new_error!{ECX509Error,"empty subject alt name"}
+		}
+		let mut gn :Asn1X509GeneralNames = Asn1X509GeneralNames::init_asn1();
+		for n in names.iter() {
+			let mut any :Asn1Any = Asn1Any::init_asn1();
+			match n {
+				ECX509GeneralName::Dns(v) | ECX509GeneralName::Email(v) | ECX509GeneralName::Uri(v) => {
+					if !v.is_ascii() {
+						ecsimple_new_error!{ECX509Error,"[{}] not IA5String",v}
+					}
+					any.content = v.as_bytes().to_vec();
+					any.tag = match n {
+						ECX509GeneralName::Dns(_) => GENERAL_NAME_DNS_TAG,
+						ECX509GeneralName::Email(_) => GENERAL_NAME_EMAIL_TAG,
+						_ => GENERAL_NAME_URI_TAG,
+					};
+				},
+				ECX509GeneralName::Ip(ip) => {
+					any.content = match ip {
+						std::net::IpAddr::V4(v) => v.octets().to_vec(),
+						std::net::IpAddr::V6(v) => v.octets().to_vec(),
+					};
+					any.tag = GENERAL_NAME_IP_TAG;
+				},
+			}
+			gn.names.val.push(any);
+		}
+		Ok(ECX509Extension::new(X509_SUBJECT_ALT_NAME_OID,false,&gn.encode_asn1()?))
+	}
+
+	pub fn subject_key_id(pubkey :&ECPublicKey) -> Result<ECX509Extension,Box<dyn Error>> {
+		let mut oct :Asn1OctData = Asn1OctData::init_asn1();
+		oct.data = x509_key_id(pubkey)?;
+		Ok(ECX509Extension::new(X509_SUBJECT_KEY_ID_OID,false,&oct.encode_asn1()?))
+	}
+
+	/*keyIdentifier only , the subjectKeyIdentifier of the issuer*/
+	pub fn authority_key_id(keyid :&[u8]) -> Result<ECX509Extension,Box<dyn Error>> {
+		let mut elem :Asn1X509AuthKeyIdElem = Asn1X509AuthKeyIdElem::init_asn1();
+		let mut kid :Asn1Imp<Asn1OctData,0> = Asn1Imp::init_asn1();
+		kid.val.data = keyid.to_vec();
+		elem.keyid.val = Some(kid);
+		let mut aki :Asn1X509AuthKeyId = Asn1X509AuthKeyId::init_asn1();
+		aki.elem.val.push(elem);
+		Ok(ECX509Extension::new(X509_AUTHORITY_KEY_ID_OID,false,&aki.encode_asn1()?))
+	}
+
+	pub(crate) fn to_asn1(&self) -> Result<Asn1X509Extension,Box<dyn Error>> {
+		let mut elem :Asn1X509ExtensionElem = Asn1X509ExtensionElem::init_asn1();
+		let _ = elem.extnid.set_value(&self.oid)?;
+		if self.critical {
+			let mut b :Asn1Boolean = Asn1Boolean::init_asn1();
+			b.val = true;
+			elem.critical.val = Some(b);
+		}
+		elem.extnvalue.data = self.value.clone();
+		let mut retv :Asn1X509Extension = Asn1X509Extension::init_asn1();
+		retv.elem.val.push(elem);
+		Ok(retv)
+	}
+}
+
+pub(crate) fn x509_extensions_from_asn1(exts :&Asn1X509Extensions) -> Result<Vec<ECX509Extension>,Box<dyn Error>> {
+	let mut retv :Vec<ECX509Extension> = Vec::new();
+	for e in exts.exts.val.iter() {
+		let ev :&Asn1X509ExtensionElem = &e.elem.val[0];
+		let oid :String = ev.extnid.get_value();
+		for o in retv.iter() {
+			if o.oid == oid {
+				ecsimple_new_error!{ECX509Error,"duplicate extension [{}]",oid}
+			}
+		}
+		let mut critical :bool = false;
+		if let Some(c) = ev.critical.val.as_ref() {
+			critical = c.val;
+		}
+		retv.push(ECX509Extension {
+			oid,
+			critical,
+			value : ev.extnvalue.data.clone(),
+		});
+	}
+	Ok(retv)
+}
+
+pub(crate) fn x509_extensions_to_asn1(exts :&[ECX509Extension]) -> Result<Asn1X509Extensions,Box<dyn Error>> {
+	let mut retv :Asn1X509Extensions = Asn1X509Extensions::init_asn1();
+	for e in exts.iter() {
+		retv.exts.val.push(e.to_asn1()?);
+	}
+	Ok(retv)
+}
+
+/*replace the one of the same oid*/
+pub(crate) fn x509_put_extension(exts :&mut Vec<ECX509Extension>,ext :ECX509Extension) {
+	for e in exts.iter_mut() {
+		if e.oid == ext.oid {
+			*e = ext;
+			return;
+		}
+	}
+	exts.push(ext);
+}
+
+
+#[derive(Clone)]
+pub struct ECX509Cert {
+	pub der :Vec<u8>,
+	/*the TBSCertificate bytes as signed*/
+	pub tbs :Vec<u8>,
+	/*1 2 or 3*/
+	pub version :i64,
+	pub serial :BigInt,
+	pub issuer :ECX509Name,
+	pub subject :ECX509Name,
+	pub not_before :DateTime<Utc>,
+	pub not_after :DateTime<Utc>,
+	pub spki :Vec<u8>,
+	pub pubkey :ECPublicKey,
+	pub extensions :Vec<ECX509Extension>,
+	pub sigalg :ECX509SigAlg,
+	pub signature :Vec<u8>,
+}
+
+impl ECX509Cert {
+	pub fn from_der(der :&[u8]) -> Result<ECX509Cert,Box<dyn Error>> {
+		let mut cert :Asn1X509Cert = Asn1X509Cert::init_asn1();
+		let used :usize = cert.decode_asn1(der)?;
+		if used != der.len() || cert.elem.val.len() != 1 {
+			ecsimple_new_error!{ECX509Error,"not one certificate in {} bytes",der.len()}
+		}
+		let celem :&Asn1X509CertElem = &cert.elem.val[0];
+		let tbs :Vec<u8> = celem.tbs.encode_asn1()?;
+		let mut tbsasn1 :Asn1X509TbsCert = Asn1X509TbsCert::init_asn1();
+		let tused :usize = tbsasn1.decode_asn1(&tbs)?;
+		if tused != tbs.len() || tbsasn1.elem.val.len() != 1 {
+			ecsimple_new_error!{ECX509Error,"TBSCertificate not parsed"}
+		}
+		let telem :&Asn1X509TbsCertElem = &tbsasn1.elem.val[0];
+
+		let sigoid :String = celem.sigalg.elem.val[0].algorithm.get_value();
+		if telem.signature.elem.val[0].algorithm.get_value() != sigoid {
+			ecsimple_new_error!{ECX509Error,"tbs signature [{}] != [{}]",telem.signature.elem.val[0].algorithm.get_value(),sigoid}
+		}
+		let sigalg :ECX509SigAlg = ECX509SigAlg::from_oid(&sigoid)?;
+
+		let mut version :i64 = 1;
+		if let Some(vn) = telem.version.val.as_ref() {
+			if let Some(v) = vn.val.as_ref() {
+				version = v.val + 1;
+			}
+		}
+		if !(1..=3).contains(&version) {
+			ecsimple_new_error!{ECX509Error,"certificate version {} not valid",version}
+		}
+
+		let spki :Vec<u8> = telem.spki.encode_asn1()?;
+		let pubkey :ECPublicKey = ECPublicKey::from_der(&spki)?;
+		let mut extensions :Vec<ECX509Extension> = Vec::new();
+		if let Some(exts) = telem.extensions.val.as_ref() {
+			if version != 3 {
+				ecsimple_new_error!{ECX509Error,"extensions in v{} certificate",version}
+			}
+			if let Some(ev) = exts.val.as_ref() {
+				extensions = x509_extensions_from_asn1(ev)?;
+			}
+		}
+		let validity :&Asn1X509ValidityElem = &telem.validity.elem.val[0];
+		if telem.serial.tag != ASN1_INTEGER_TAG || telem.serial.content.is_empty() {
+			ecsimple_new_error!{ECX509Error,"serial not INTEGER"}
+		}
+		Ok(ECX509Cert {
+			der : der.to_vec(),
+			tbs : tbs.clone(),
+			version,
+			serial : BigInt::from_signed_bytes_be(&telem.serial.content),
+			issuer : ECX509Name::from_der(&telem.issuer.encode_asn1()?)?,
+			subject : ECX509Name::from_der(&telem.subject.encode_asn1()?)?,
+			not_before : validity.notbefore.get_value_time()?,
+			not_after : validity.notafter.get_value_time()?,
+			spki,
+			pubkey,
+			extensions,
+			sigalg,
+			signature : celem.signature.data.clone(),
+		})
+	}
+
+	pub fn from_pem(ins :&str) -> Result<ECX509Cert,Box<dyn Error>> {
+		let b :PemBlock = pem_find_block(ins,&[PEM_CERTIFICATE])?;
+		ECX509Cert::from_der(&b.data)
+	}
+
+	/*every CERTIFICATE block of a bundle*/
+	pub fn from_pem_all(ins :&str) -> Result<Vec<ECX509Cert>,Box<dyn Error>> {
+		let mut retv :Vec<ECX509Cert> = Vec::new();
+		for b in pem_to_blocks(ins)?.iter() {
+			if b.label == PEM_CERTIFICATE {
+				retv.push(ECX509Cert::from_der(&b.data)?);
+			}
+		}
+		Ok(retv)
+	}
+
+	pub fn to_der(&self) -> Vec<u8> {
+		self.der.clone()
+	}
+
+	pub fn to_pem(&self) -> String {
+		der_to_pem(&self.der,PEM_CERTIFICATE)
+	}
+
+	/*check the signature with the issuer public key*/
+	pub fn verify(&self,issuer_pubkey :&ECPublicKey) -> Result<bool,Box<dyn Error>> {
+		x509_verify_data(self.sigalg,issuer_pubkey,&self.tbs,&self.signature)
+	}
+
+	pub fn verify_signed_by(&self,issuer :&ECX509Cert) -> Result<bool,Box<dyn Error>> {
+		if self.issuer != issuer.subject {
+			ecsimple_log_trace!("issuer [{}] != [{}]",self.issuer,issuer.subject);
+			return Ok(false);
+		}
+		self.verify(&issuer.pubkey)
+	}
+
+	pub fn is_self_signed(&self) -> Result<bool,Box<dyn Error>> {
+		if self.issuer != self.subject {
+			return Ok(false);
+		}
+		self.verify(&self.pubkey)
+	}
+
+	pub fn is_valid_at(&self,t :&DateTime<Utc>) -> bool {
+		*t >= self.not_before && *t <= self.not_after
+	}
+
+	pub fn get_extension(&self,oid :&str) -> Option<&ECX509Extension> {
+		self.extensions.iter().find(|&e| e.oid == oid).map(|v| v as _)
+	}
+
+	/*(cA , pathLenConstraint) , None when no extension*/
+	pub fn basic_constraints(&self) -> Result<Option<ECX509BasicConstraints>,Box<dyn Error>> {
+		let e :&ECX509Extension = match self.get_extension(X509_BASIC_CONSTRAINTS_OID) {
+			Some(e) => e,
+			None => return Ok(None),
+		};
+		let mut bc :Asn1X509BasicConstraints = Asn1X509BasicConstraints::init_asn1();
+		let _ = bc.decode_asn1(&e.value)?;
+		let mut ca :bool = false;
+		let mut pathlen :Option<i64> = None;
+		if !bc.elem.val.is_empty() {
+			let bv :&Asn1X509BasicConstraintsElem = &bc.elem.val[0];
+			if let Some(c) = bv.ca.val.as_ref() {
+				ca = c.val;
+			}
+			if let Some(pl) = bv.pathlen.val.as_ref() {
+				pathlen = Some(pl.val);
+			}
+		}
+		Ok(Some((ca,pathlen)))
+	}
+
+	/*KEY_USAGE_* bits , None when no extension*/
+	pub fn key_usage(&self) -> Result<Option<u16>,Box<dyn Error>> {
+		let e = self.get_extension(X509_KEY_USAGE_OID);
+		if e.is_none() {
+			return Ok(None);
+		}
+		let v :&Vec<u8> = &e.unwrap().value;
+		if v.len() < 3 || v[0] != 0x03 || (v[1] as usize) != v.len() - 2 {
+			ecsimple_new_error!{ECX509Error,"key usage not bit string"}
+		}
+		let mut retv :u16 = 0;
+		for i in 0..9 {
+			let idx :usize = 3 + i / 8;
+			if idx < v.len() && (v[idx] & (0x80 >> (i % 8))) != 0 {
+				retv |= 1 << i;
+			}
+		}
+		Ok(Some(retv))
+	}
+
+	pub fn subject_key_id(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		let e = self.get_extension(X509_SUBJECT_KEY_ID_OID);
+		if e.is_none() {
+			return Ok(None);
+		}
+		let mut any :Asn1Any = Asn1Any::init_asn1();
+		let _ = any.decode_asn1(&e.unwrap().value)?;
+		Ok(Some(any.content.clone()))
+	}
+
+	pub fn authority_key_id(&self) -> Result<Option<Vec<u8>>,Box<dyn Error>> {
+		let e = self.get_extension(X509_AUTHORITY_KEY_ID_OID);
+		if e.is_none() {
+			return Ok(None);
+		}
+		let mut aki :Asn1X509AuthKeyId = Asn1X509AuthKeyId::init_asn1();
+		let _ = aki.decode_asn1(&e.unwrap().value)?;
+		if !aki.elem.val.is_empty() && aki.elem.val[0].keyid.val.is_some() {
+			return Ok(Some(aki.elem.val[0].keyid.val.as_ref().unwrap().val.data.clone()));
+		}
+		Ok(None)
+	}
+
+	/*dNSName entries of subjectAltName*/
+	pub fn dns_names(&self) -> Result<Vec<String>,Box<dyn Error>> {
+		let mut retv :Vec<String> = Vec::new();
+		if let Some(e) = self.get_extension(X509_SUBJECT_ALT_NAME_OID) {
+			let mut names :Asn1X509GeneralNames = Asn1X509GeneralNames::init_asn1();
+			let _ = names.decode_asn1(&e.value)?;
+
+			for n in names.names.val.iter() {
+				if n.tag == GENERAL_NAME_DNS_TAG {
+					retv.push(String::from_utf8_lossy(&n.content).to_string());
+				}
+			}
+		}
+		Ok(retv)
+	}
+	/*the KeyPurposeId oids , None when no extension*/
+	pub fn ext_key_usage(&self) -> Result<Option<Vec<String>>,Box<dyn Error>> {
+		let e = self.get_extension(X509_EXT_KEY_USAGE_OID);
+		if e.is_none() {
+			return Ok(None);
+		}
+		let mut eku :Asn1X509ExtKeyUsage = Asn1X509ExtKeyUsage::init_asn1();
+		let _ = eku.decode_asn1(&e.unwrap().value)?;
+		Ok(Some(eku.oids.val.iter().map(|o| o.get_value()).collect()))
+	}
+}
+
+/*v3 certificate , subjectKeyIdentifier and authorityKeyIdentifier added at sign when not given*/
+#[derive(Clone)]
+pub struct ECX509CertBuilder {
+	pub serial :BigInt,
+	pub issuer :ECX509Name,
+	pub subject :ECX509Name,
+	pub not_before :DateTime<Utc>,
+	pub not_after :DateTime<Utc>,
+	pub pubkey :ECPublicKey,
+	pub extensions :Vec<ECX509Extension>,
+	/*None for ECX509SigAlg::from_key of the signing key*/
+	pub sigalg :Option<ECX509SigAlg>,
+}
+
+impl ECX509CertBuilder {
+	/*self issued with a random 127 bit serial , valid for 365 days from now*/
+	pub fn new(subject :&ECX509Name,pubkey :&ECPublicKey) -> ECX509CertBuilder {
+		let mut sn :Vec<u8> = ecsimple_rand_bytes(16);
+		sn[0] = (sn[0] & 0x7f) | 0x40;
+		let now :DateTime<Utc> = Utc::now();
+		ECX509CertBuilder {
+			serial : BigInt::from_bytes_be(Sign::Plus,&sn),
+			issuer : subject.clone(),
+			subject : subject.clone(),
+			not_before : now,
+			not_after : now + Duration::days(365),
+			pubkey : pubkey.clone(),
+			extensions : Vec::new(),
+			sigalg : None,
+		}
+	}
+
+	/*issuer name and authorityKeyIdentifier from the CA certificate*/
+	pub fn issued_by(&mut self,ca :&ECX509Cert) -> Result<(),Box<dyn Error>> {
+		self.issuer = ca.subject.clone();
+		let mut keyid :Option<Vec<u8>> = ca.subject_key_id()?;
+		if keyid.is_none() {
+			keyid = Some(x509_key_id(&ca.pubkey)?);
+		}
+		self.add_extension(ECX509Extension::authority_key_id(&keyid.unwrap())?);
+		Ok(())
+	}
+
+	pub fn add_extension(&mut self,ext :ECX509Extension) {
+		x509_put_extension(&mut self.extensions,ext);
+	}
+
+	pub fn sign(&self,privkey :&ECPrivateKey) -> Result<ECX509Cert,Box<dyn Error>> {
+		if self.serial <= BigInt::zero() {
+			ecsimple_new_error!{ECX509Error,"serial {} not positive",self.serial}
+		}
+		if self.not_after < self.not_before {
+			ecsimple_new_error!{ECX509Error,"not_after {} before not_before {}",self.not_after,self.not_before}
+		}
+		let signpub :ECPublicKey = privkey.export_pubkey();
+		let sigalg :ECX509SigAlg = match self.sigalg {
+			Some(a) => a,
+			None => ECX509SigAlg::from_key(&signpub),
+		};
+		let mut exts :Vec<ECX509Extension> = self.extensions.clone();
+		for e in exts.iter_mut() {
+			/*RFC 5280 4.2.1.6 , critical when the subject is empty*/
+			if e.oid == X509_SUBJECT_ALT_NAME_OID && self.subject.entries.is_empty() {
+				e.critical = true;
+			}
+		}
+		if exts.iter().all(|e| e.oid != X509_SUBJECT_KEY_ID_OID) {
+			exts.push(ECX509Extension::subject_key_id(&self.pubkey)?);
+		}
+		if exts.iter().all(|e| e.oid != X509_AUTHORITY_KEY_ID_OID) {
+			exts.push(ECX509Extension::authority_key_id(&x509_key_id(&signpub)?)?);
+		}
+
+		let mut telem :Asn1X509TbsCertElem = Asn1X509TbsCertElem::init_asn1();
+		let mut vn :Asn1Ndef<Asn1Integer,0> = Asn1Ndef::init_asn1();
+		let mut vi :Asn1Integer = Asn1Integer::init_asn1();
+		vi.val = 2;
+		vn.val = Some(vi);
+		telem.version.val = Some(vn);
+		telem.serial = Asn1Any::init_asn1();
+		telem.serial.tag = ASN1_INTEGER_TAG;
+		telem.serial.content = self.serial.to_signed_bytes_be();
+		telem.signature = form_algor(sigalg.oid(),None)?;
+		telem.issuer = der_to_any(&self.issuer.der)?;
+		let mut velem :Asn1X509ValidityElem = Asn1X509ValidityElem::init_asn1();
+		velem.notbefore = x509_time_asn1(&self.not_before)?;
+		velem.notafter = x509_time_asn1(&self.not_after)?;
+		telem.validity.elem.val.push(velem);
+		telem.subject = der_to_any(&self.subject.der)?;
+		telem.spki = der_to_any(&self.pubkey.to_der(EC_UNCOMPRESSED,"")?)?;
+		let mut extn :Asn1Ndef<Asn1X509Extensions,3> = Asn1Ndef::init_asn1();
+		extn.val = Some(x509_extensions_to_asn1(&exts)?);
+		telem.extensions.val = Some(extn);
+		let mut tbsasn1 :Asn1X509TbsCert = Asn1X509TbsCert::init_asn1();
+		tbsasn1.elem.val.push(telem);
+		let tbs :Vec<u8> = tbsasn1.encode_asn1()?;
+
+		let mut celem :Asn1X509CertElem = Asn1X509CertElem::init_asn1();
+		celem.tbs = der_to_any(&tbs)?;
+		celem.sigalg = form_algor(sigalg.oid(),None)?;
+		celem.signature = Asn1BitDataFlag::init_asn1();
+		celem.signature.data = x509_sign_data(sigalg,privkey,&tbs)?;
+		let mut cert :Asn1X509Cert = Asn1X509Cert::init_asn1();
+		cert.elem.val.push(celem);
+		return ECX509Cert::from_der(&cert.encode_asn1()?);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use num_bigint::Sign;
+	use hex::FromHex;
+
+	/*openssl req -x509 -sha256 -set_serial 0x8a1b2c3d -addext authorityKeyIdentifier=keyid:always,issuer:always*/
+	const OPENSSL_P256_CERT :&str = "
+-----BEGIN CERTIFICATE-----
+MIIBnDCCAUGgAwIBAgIFAIobLD0wCgYIKoZIzj0EAwIwGDEWMBQGA1UEAwwNZWNz
+aW1wbGUgcDI1NjAeFw0yNjEwMTkwOTQ1MDhaFw0zNjEwMTYwOTQ1MDhaMBgxFjAU
+BgNVBAMMDWVjc2ltcGxlIHAyNTYwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAATI
+NPm6R4Q+Kp2wa3xL6Xwxz780lWlmC4p/TFlVq/hZSe36V0Q3p+TznQW/NByzFr1S
+03Duos6Aq3+QwBTSvhl7o3gwdjAdBgNVHQ4EFgQUog+CGRHDXoFBTVAwPogg8E+f
+vFIwDwYDVR0TAQH/BAUwAwEB/zBEBgNVHSMEPTA7gBSiD4IZEcNegUFNUDA+iCDw
+T5+8UqEcpBowGDEWMBQGA1UEAwwNZWNzaW1wbGUgcDI1NoIFAIobLD0wCgYIKoZI
+zj0EAwIDSQAwRgIhAM0YljGkjB67vu1/Zuwgk8Fpcd7mhpvPRz2FOkD4aLnMAiEA
++613Q6zzAnr0FOlS7dqNr1cl560gisSwHR9vVMF5zGs=
+-----END CERTIFICATE-----
+";
+
+	/*openssl req -x509 -set_serial -1234 with the same key*/
+	const OPENSSL_NEG_SERIAL_CERT :&str = "
+-----BEGIN CERTIFICATE-----
+MIIBcTCCARegAwIBAgIC+y4wCgYIKoZIzj0EAwIwFzEVMBMGA1UEAwwMZWNzaW1w
+bGUgbmVnMB4XDTI2MTAxOTA5NDUxMVoXDTM2MTAxNjA5NDUxMVowFzEVMBMGA1UE
+AwwMZWNzaW1wbGUgbmVnMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEyDT5ukeE
+PiqdsGt8S+l8Mc+/NJVpZguKf0xZVav4WUnt+ldEN6fk850FvzQcsxa9UtNw7qLO
+gKt/kMAU0r4Ze6NTMFEwHQYDVR0OBBYEFKIPghkRw16BQU1QMD6IIPBPn7xSMB8G
+A1UdIwQYMBaAFKIPghkRw16BQU1QMD6IIPBPn7xSMA8GA1UdEwEB/wQFMAMBAf8w
+CgYIKoZIzj0EAwIDSAAwRQIhAOZ4DB4BcW1IKr1htHk8LsQZea++QAO4MWcfT3bk
+6xI4AiB7vxk/beJsV0qHiiQTbbJuzIiiTaFjMxuVOMkqhCgK4A==
+-----END CERTIFICATE-----
+";
+
+	/*openssl req -x509 -sm3 -sigopt distid:1234567812345678*/
+	const OPENSSL_SM2_CERT :&str = "
+-----BEGIN CERTIFICATE-----
+MIIBcDCCARegAwIBAgICEjQwCgYIKoEcz1UBg3UwFzEVMBMGA1UEAwwMZWNzaW1w
+bGUgc20yMB4XDTI2MTAxOTA5NDUwOFoXDTM2MTAxNjA5NDUwOFowFzEVMBMGA1UE
+AwwMZWNzaW1wbGUgc20yMFkwEwYHKoZIzj0CAQYIKoEcz1UBgi0DQgAEFWEJbqeu
+dSx8b8ggalcvq3GtssJC/Fddw6Vw/w7jWAQq2hx044RIKhwMGK27x4O2q3Fr1n+Z
+YEPzsiCW1Enh9aNTMFEwHQYDVR0OBBYEFMu2lab6eYHO1VqJJ5yOa4RcV7lJMB8G
+A1UdIwQYMBaAFMu2lab6eYHO1VqJJ5yOa4RcV7lJMA8GA1UdEwEB/wQFMAMBAf8w
+CgYIKoEcz1UBg3UDRwAwRAIgFU1cQR2AH0BIF9jTjwalaWiLScFojtwSPnTmOHoI
+LtUCIAqexDYsRIGCQJJ0pOVlErNbvCaflASXhzoTUWYhjiLc
+-----END CERTIFICATE-----
+";
+
+	#[test]
+	fn test_openssl_p256_cert() {
+		let cert :ECX509Cert = ECX509Cert::from_pem(OPENSSL_P256_CERT).unwrap();
+		assert_eq!(cert.version,3);
+		assert_eq!(cert.serial,BigInt::from(0x8a1b2c3du64));
+		assert_eq!(cert.sigalg,ECX509SigAlg::EcdsaSha256);
+		assert_eq!(cert.subject.common_name(),Some("ecsimple p256".to_string()));
+		assert!(cert.is_self_signed().unwrap());
+		assert!(cert.verify(&cert.pubkey).unwrap());
+		let skid :Vec<u8> = Vec::from_hex("A20F821911C35E81414D50303E8820F04F9FBC52").unwrap();
+		assert_eq!(cert.subject_key_id().unwrap(),Some(skid.clone()));
+		assert_eq!(cert.authority_key_id().unwrap(),Some(skid));
+		assert_eq!(cert.basic_constraints().unwrap(),Some((true,None)));
+
+		/*authorityCertIssuer is IMPLICIT [1] GeneralNames holding the directoryName*/
+		let ext :&ECX509Extension = cert.get_extension(X509_AUTHORITY_KEY_ID_OID).unwrap();
+		let mut aki :Asn1X509AuthKeyId = Asn1X509AuthKeyId::init_asn1();
+		let used :usize = aki.decode_asn1(&ext.value).unwrap();
+		assert_eq!(used,ext.value.len());
+		let issuer = aki.elem.val[0].issuer.val.as_ref().unwrap();
+		assert_eq!(issuer.val.len(),1);
+		assert_eq!(issuer.val[0].tag,0xa4);
+		assert_eq!(aki.elem.val[0].serial.val.as_ref().unwrap().val.val,num_bigint::BigUint::from(0x8a1b2c3du64));
+		assert_eq!(aki.encode_asn1().unwrap(),ext.value);
+
+		let mut der :Vec<u8> = cert.to_der();
+		let l :usize = der.len();
+		der[l - 1] ^= 1;
+		let bad :ECX509Cert = ECX509Cert::from_der(&der).unwrap();
+		assert!(!bad.verify(&bad.pubkey).unwrap_or(false));
+	}
+
+	#[test]
+	fn test_negative_serial() {
+		let cert :ECX509Cert = ECX509Cert::from_pem(OPENSSL_NEG_SERIAL_CERT).unwrap();
+		assert_eq!(cert.serial,BigInt::from(-1234));
+		assert!(cert.verify(&cert.pubkey).unwrap());
+		let p256 :ECX509Cert = ECX509Cert::from_pem(OPENSSL_P256_CERT).unwrap();
+		assert_eq!(p256.serial.sign(),Sign::Plus);
+	}
+
+	#[test]
+	fn test_openssl_sm2_cert() {
+		let cert :ECX509Cert = ECX509Cert::from_pem(OPENSSL_SM2_CERT).unwrap();
+		assert_eq!(cert.serial,BigInt::from(4660));
+		assert_eq!(cert.sigalg,ECX509SigAlg::Sm2Sm3);
+		assert_eq!(cert.subject.common_name(),Some("ecsimple sm2".to_string()));
+		assert!(cert.is_self_signed().unwrap());
+		assert!(cert.verify(&cert.pubkey).unwrap());
+		let p256 :ECX509Cert = ECX509Cert::from_pem(OPENSSL_P256_CERT).unwrap();
+		assert!(!cert.verify(&p256.pubkey).unwrap_or(false));
+	}
+}
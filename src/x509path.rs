@@ -0,0 +1,570 @@
+use crate::consts::*;
+use crate::x509::*;
+#[allow(unused_imports)]
+use crate::logger::*;
+use chrono::{DateTime,Utc};
+use std::error::Error;
+
+/*intermediates between the leaf and the anchor*/
+pub const X509_PATH_MAX_INTERMEDIATES :usize = 8;
+pub const X509_EKU_ANY_OID :&str = "2.5.29.37.0";
+
+/*extensions checked here , any other critical one fails the path*/
+const X509_PATH_KNOWN_EXTENSIONS :[&str;6] = [
+	X509_BASIC_CONSTRAINTS_OID,
+	X509_KEY_USAGE_OID,
+	X509_EXT_KEY_USAGE_OID,
+	X509_SUBJECT_ALT_NAME_OID,
+	X509_SUBJECT_KEY_ID_OID,
+	X509_AUTHORITY_KEY_ID_OID,
+];
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum ECX509PathErrorKind {
+	Decode(String),
+	NoIssuer,
+	BadSignature,
+	NotYetValid,
+	Expired,
+	NotCa,
+	PathLenExceeded,
+	NoKeyCertSign,
+	UnhandledCriticalExtension(String),
+	ExtKeyUsage(String),
+	TooLong,
+}
+
+/*depth 0 for the leaf and up to the trust anchor*/
+#[derive(Clone,Debug)]
+pub struct ECX509PathError {
+	pub depth :usize,
+	pub subject :String,
+	pub kind :ECX509PathErrorKind,
+}
+
+impl std::fmt::Display for ECX509PathError {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s :String = match &self.kind {
+			ECX509PathErrorKind::Decode(e) => format!("not decoded {}",e),
+			ECX509PathErrorKind::NoIssuer => "no issuer found".to_string(),
+			ECX509PathErrorKind::BadSignature => "signature not valid".to_string(),
+			ECX509PathErrorKind::NotYetValid => "not yet valid".to_string(),
+			ECX509PathErrorKind::Expired => "expired".to_string(),
+			ECX509PathErrorKind::NotCa => "issuer not a CA".to_string(),
+			ECX509PathErrorKind::PathLenExceeded => "pathLenConstraint exceeded".to_string(),
+			ECX509PathErrorKind::NoKeyCertSign => "issuer without keyCertSign".to_string(),
+			ECX509PathErrorKind::UnhandledCriticalExtension(o) => format!("unhandled critical extension [{}]",o),
+			ECX509PathErrorKind::ExtKeyUsage(o) => format!("extended key usage [{}] not allowed",o),
+			ECX509PathErrorKind::TooLong => "path too long".to_string(),
+		};
+		write!(f,"depth {} [{}] {}",self.depth,self.subject,s)
+	}
+}
+
+impl Error for ECX509PathError {}
+
+fn path_error(depth :usize,cert :Option<&ECX509Cert>,kind :ECX509PathErrorKind) -> ECX509PathError {
+	let mut subject :String = "".to_string();
+	if let Some(c) = cert {
+		subject = format!("{}",c.subject);
+	}
+	ECX509PathError {
+		depth,
+		subject,
+		kind,
+	}
+}
+
+fn path_is_self_issued(cert :&ECX509Cert) -> bool {
+	cert.issuer == cert.subject
+}
+
+/*subject names the issuer , keyids compared when both given*/
+fn path_may_issue(issuer :&ECX509Cert,cert :&ECX509Cert) -> bool {
+	if issuer.subject != cert.issuer {
+		return false;
+	}
+	let aki = cert.authority_key_id().unwrap_or(None);
+	let ski = issuer.subject_key_id().unwrap_or(None);
+	if aki.is_some() && ski.is_some() && aki != ski {
+		return false;
+	}
+	true
+}
+
+fn path_check_extensions(depth :usize,cert :&ECX509Cert) -> Result<(),ECX509PathError> {
+	for e in cert.extensions.iter() {
+		if e.critical && !X509_PATH_KNOWN_EXTENSIONS.contains(&e.oid.as_str()) {
+			return Err(path_error(depth,Some(cert),ECX509PathErrorKind::UnhandledCriticalExtension(e.oid.clone())));
+		}
+	}
+	Ok(())
+}
+
+fn path_check_time(depth :usize,cert :&ECX509Cert,at :&DateTime<Utc>) -> Result<(),ECX509PathError> {
+	if *at < cert.not_before {
+		return Err(path_error(depth,Some(cert),ECX509PathErrorKind::NotYetValid));
+	}
+	if *at > cert.not_after {
+		return Err(path_error(depth,Some(cert),ECX509PathErrorKind::Expired));
+	}
+	Ok(())
+}
+
+/*RFC 5280 6.1.4 (k) and (n) , returns the pathLenConstraint*/
+fn path_check_ca(depth :usize,cert :&ECX509Cert) -> Result<Option<i64>,ECX509PathError> {
+	let bc = match cert.basic_constraints() {
+		Ok(v) => v,
+		Err(e) => {
+			return Err(path_error(depth,Some(cert),ECX509PathErrorKind::Decode(format!("basicConstraints {}",e))));
+		}
+	};
+	if cert.version != 3 || bc.is_none() || !bc.unwrap().0 {
+		return Err(path_error(depth,Some(cert),ECX509PathErrorKind::NotCa));
+	}
+	let ku = match cert.key_usage() {
+		Ok(v) => v,
+		Err(e) => {
+			return Err(path_error(depth,Some(cert),ECX509PathErrorKind::Decode(format!("keyUsage {}",e))));
+		}
+	};
+	if ku.is_some() && (ku.unwrap() & KEY_USAGE_KEY_CERT_SIGN) == 0 {
+		return Err(path_error(depth,Some(cert),ECX509PathErrorKind::NoKeyCertSign));
+	}
+	Ok(bc.unwrap().1)
+}
+
+/*RFC 5280 6 subset : names , validity , basicConstraints , keyUsage and signatures , no policies or name constraints*/
+#[derive(Clone)]
+pub struct ECX509Validator {
+	pub anchors :Vec<ECX509Cert>,
+	pub max_intermediates :usize,
+	/*when set the leaf extKeyUsage , if any , must have it or anyExtendedKeyUsage*/
+	pub leaf_eku :Option<String>,
+	/*RFC 5280 leaves anchors as trusted input , true to check their validity and pathLenConstraint too*/
+	pub check_anchors :bool,
+}
+
+impl ECX509Validator {
+	pub fn new(anchors :&[ECX509Cert]) -> ECX509Validator {
+		ECX509Validator {
+			anchors : anchors.to_vec(),
+			max_intermediates : X509_PATH_MAX_INTERMEDIATES,
+			leaf_eku : None,
+			check_anchors : true,
+		}
+	}
+
+	pub fn from_der_anchors(anchors :&[Vec<u8>]) -> Result<ECX509Validator,Box<dyn Error>> {
+		let mut certs :Vec<ECX509Cert> = Vec::new();
+		for a in anchors.iter() {
+			certs.push(ECX509Cert::from_der(a)?);
+		}
+		Ok(ECX509Validator::new(&certs))
+	}
+
+	/*the path from the leaf to the anchor on success*/
+	pub fn validate(&self,leaf :&[u8],intermediates :&[Vec<u8>],at :&DateTime<Utc>) -> Result<Vec<ECX509Cert>,ECX509PathError> {
+		let leafcert :ECX509Cert = match ECX509Cert::from_der(leaf) {
+			Ok(c) => c,
+			Err(e) => {
+				return Err(path_error(0,None,ECX509PathErrorKind::Decode(format!("{}",e))));
+			}
+		};
+		let mut pool :Vec<ECX509Cert> = Vec::new();
+		for c in intermediates.iter() {
+			match ECX509Cert::from_der(c) {
+				Ok(v) => {
+					pool.push(v);
+				},
+				Err(_e) => {
+					/*a bad extra certificate does not break the path*/
+					ecsimple_log_trace!("intermediate not decoded {:?}",_e);
+				}
+			}
+		}
+		let mut path :Vec<ECX509Cert> = vec![leafcert];
+		let mut used :Vec<bool> = vec![false;pool.len()];
+		self.build_path(&mut path,&pool,&mut used,at)?;
+		Ok(path)
+	}
+
+	/*depth first from the leaf , an anchor before the pool , each issuer whose signature verifies is tried
+	  and a complete path failing check_path backtracks to the next , the first error is returned*/
+	fn build_path(&self,path :&mut Vec<ECX509Cert>,pool :&[ECX509Cert],used :&mut Vec<bool>,at :&DateTime<Utc>) -> Result<(),ECX509PathError> {
+		let depth :usize = path.len() - 1;
+		for a in self.anchors.iter() {
+			if a.der == path[depth].der {
+				return self.check_path(path,at);
+			}
+		}
+		let mut named :bool = false;
+		let mut firsterr :Option<ECX509PathError> = None;
+		for a in self.anchors.iter() {
+			if path_may_issue(a,&path[depth]) {
+				named = true;
+				if path[depth].verify(&a.pubkey).unwrap_or(false) {
+					path.push(a.clone());
+					match self.check_path(path,at) {
+						Ok(_) => {
+							return Ok(());
+						},
+						Err(e) => {
+							if firsterr.is_none() {
+								firsterr = Some(e);
+							}
+						}
+					}
+					path.pop();
+				}
+			}
+		}
+		if depth >= self.max_intermediates {
+			if let Some(e) = firsterr {
+				return Err(e);
+			}
+			return Err(path_error(depth,Some(&path[depth]),ECX509PathErrorKind::TooLong));
+		}
+		for (i,c) in pool.iter().enumerate() {
+			if used[i] || !path_may_issue(c,&path[depth]) {
+				continue;
+			}
+			named = true;
+			if !path[depth].verify(&c.pubkey).unwrap_or(false) {
+				continue;
+			}
+			used[i] = true;
+			path.push(c.clone());
+			match self.build_path(path,pool,used,at) {
+				Ok(_) => {
+					return Ok(());
+				},
+				Err(e) => {
+					if firsterr.is_none() {
+						firsterr = Some(e);
+					}
+				}
+			}
+			path.pop();
+			used[i] = false;
+		}
+		if let Some(e) = firsterr {
+			return Err(e);
+		}
+		if named {
+			return Err(path_error(depth,Some(&path[depth]),ECX509PathErrorKind::BadSignature));
+		}
+		Err(path_error(depth,Some(&path[depth]),ECX509PathErrorKind::NoIssuer))
+	}
+
+	/*from the anchor down as RFC 5280 6.1 does*/
+	fn check_path(&self,path :&[ECX509Cert],at :&DateTime<Utc>) -> Result<(),ECX509PathError> {
+		let adepth :usize = path.len() - 1;
+		let anchor :&ECX509Cert = &path[adepth];
+		let mut remain :Option<i64> = None;
+		if self.check_anchors {
+			path_check_time(adepth,anchor,at)?;
+			if adepth > 0 {
+				remain = path_check_ca(adepth,anchor)?;
+			}
+		}
+		let mut depth :usize = adepth;
+		while depth > 0 {
+			depth -= 1;
+			let cert :&ECX509Cert = &path[depth];
+			path_check_time(depth,cert,at)?;
+			path_check_extensions(depth,cert)?;
+			if depth == 0 {
+				break;
+			}
+			let pathlen :Option<i64> = path_check_ca(depth,cert)?;
+			if !path_is_self_issued(cert) {
+				if remain == Some(0) {
+					return Err(path_error(depth,Some(cert),ECX509PathErrorKind::PathLenExceeded));
+				}
+				remain = remain.map(|r| r - 1);
+			}
+			if let Some(pl) = pathlen {
+				if remain.map_or(true,|r| pl < r) {
+					remain = Some(pl);
+				}
+			}
+		}
+
+		if let (Some(want),true) = (self.leaf_eku.as_ref(),adepth > 0) {
+			let eku = match path[0].ext_key_usage() {
+				Ok(v) => v,
+				Err(e) => {
+					return Err(path_error(0,Some(&path[0]),ECX509PathErrorKind::Decode(format!("extKeyUsage {}",e))));
+				}
+			};
+			if let Some(oids) = eku {
+				if !oids.iter().any(|o| o == want || o == X509_EKU_ANY_OID) {
+
+					return Err(path_error(0,Some(&path[0]),ECX509PathErrorKind::ExtKeyUsage(want.clone())));
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::group::*;
+	use crate::keys::*;
+
+	fn path_time(s :&str) -> DateTime<Utc> {
+		DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+	}
+
+	/*index is the depth , the leaf first and the self signed anchor last , adjust called before each sign*/
+	fn path_chain<F>(inters :usize,mut adjust :F) -> (Vec<ECX509Cert>,Vec<ECPrivateKey>)
+		where F : FnMut(usize,&mut ECX509CertBuilder) {
+		let grp :ECGroup = ecc_get_curve_group(PRIME256v1_NAME).unwrap();
+		let top :usize = inters + 1;
+		let mut keys :Vec<ECPrivateKey> = Vec::new();
+		for _ in 0..(top + 1) {
+			keys.push(ECPrivateKey::generate(&grp));
+		}
+		let mut certs :Vec<Option<ECX509Cert>> = vec![None;top + 1];
+		let mut depth :usize = top + 1;
+		while depth > 0 {
+			depth -= 1;
+			let name :String = if depth == 0 {
+				"CN=path leaf".to_string()
+			} else if depth == top {
+				"CN=path anchor".to_string()
+			} else {
+				format!("CN=path ca {}",depth)
+			};
+			let mut b :ECX509CertBuilder = ECX509CertBuilder::new(&ECX509Name::parse(&name).unwrap(),&keys[depth].export_pubkey());
+			b.not_before = path_time("2026-01-01T00:00:00Z");
+			b.not_after = path_time("2027-01-01T00:00:00Z");
+			if depth == 0 {
+				b.add_extension(ECX509Extension::key_usage(KEY_USAGE_DIGITAL_SIGNATURE).unwrap());
+			} else {
+				b.add_extension(ECX509Extension::basic_constraints(true,None).unwrap());
+				b.add_extension(ECX509Extension::key_usage(KEY_USAGE_KEY_CERT_SIGN | KEY_USAGE_CRL_SIGN).unwrap());
+			}
+			let mut signer :usize = depth;
+			if depth < top {
+				b.issued_by(certs[depth + 1].as_ref().unwrap()).unwrap();
+				signer = depth + 1;
+			}
+			adjust(depth,&mut b);
+			certs[depth] = Some(b.sign(&keys[signer]).unwrap());
+		}
+		(certs.into_iter().map(|c| c.unwrap()).collect(),keys)
+	}
+
+	fn path_validate(v :&ECX509Validator,certs :&[ECX509Cert]) -> Result<Vec<ECX509Cert>,ECX509PathError> {
+		let inters :Vec<Vec<u8>> = certs[1..(certs.len() - 1)].iter().map(|c| c.der.clone()).collect();
+		v.validate(&certs[0].der,&inters,&path_time("2026-06-01T00:00:00Z"))
+	}
+
+	fn path_expect(certs :&[ECX509Cert],depth :usize,kind :ECX509PathErrorKind) {
+		let v :ECX509Validator = ECX509Validator::new(&certs[(certs.len() - 1)..]);
+		let e :ECX509PathError = path_validate(&v,certs).err().unwrap();
+		assert_eq!(e.kind,kind,"{}",e);
+		assert_eq!(e.depth,depth,"{}",e);
+	}
+
+	#[test]
+	fn test_path_valid() {
+		let (certs,_) = path_chain(1,|_,_| {});
+		let v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		let path :Vec<ECX509Cert> = path_validate(&v,&certs).unwrap();
+		assert_eq!(path.len(),3);
+		for (i,c) in path.iter().enumerate() {
+			assert_eq!(c.der,certs[i].der);
+		}
+		/*no intermediates given*/
+		let e :ECX509PathError = v.validate(&certs[0].der,&[],&path_time("2026-06-01T00:00:00Z")).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::NoIssuer);
+		/*the anchor alone*/
+		assert_eq!(v.validate(&certs[2].der,&[],&path_time("2026-06-01T00:00:00Z")).unwrap().len(),1);
+	}
+
+	#[test]
+	fn test_path_validity_each_depth() {
+		for depth in 0..3 {
+			let (certs,_) = path_chain(1,|d,b| {
+				if d == depth {
+					b.not_before = path_time("2026-07-01T00:00:00Z");
+				}
+			});
+			path_expect(&certs,depth,ECX509PathErrorKind::NotYetValid);
+			let (certs,_) = path_chain(1,|d,b| {
+				if d == depth {
+					b.not_after = path_time("2026-05-01T00:00:00Z");
+				}
+			});
+			path_expect(&certs,depth,ECX509PathErrorKind::Expired);
+		}
+		/*the anchor validity only checked when asked*/
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 2 {
+				b.not_after = path_time("2026-05-01T00:00:00Z");
+			}
+		});
+		let mut v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		v.check_anchors = false;
+		assert!(path_validate(&v,&certs).is_ok());
+	}
+
+	#[test]
+	fn test_path_len_constraint() {
+		/*pathLenConstraint 0 allows the leaf below*/
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 1 {
+				b.add_extension(ECX509Extension::basic_constraints(true,Some(0)).unwrap());
+			}
+		});
+		let v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		assert!(path_validate(&v,&certs).is_ok());
+		/*but not one more non self issued CA*/
+		let (certs,_) = path_chain(2,|d,b| {
+			if d == 2 {
+				b.add_extension(ECX509Extension::basic_constraints(true,Some(0)).unwrap());
+			}
+		});
+		path_expect(&certs,1,ECX509PathErrorKind::PathLenExceeded);
+		/*the anchor pathLenConstraint too*/
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 2 {
+				b.add_extension(ECX509Extension::basic_constraints(true,Some(0)).unwrap());
+			}
+		});
+		path_expect(&certs,1,ECX509PathErrorKind::PathLenExceeded);
+	}
+
+	#[test]
+	fn test_path_ca_checks() {
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 1 {
+				b.add_extension(ECX509Extension::key_usage(KEY_USAGE_DIGITAL_SIGNATURE | KEY_USAGE_CRL_SIGN).unwrap());
+			}
+		});
+		path_expect(&certs,1,ECX509PathErrorKind::NoKeyCertSign);
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 1 {
+				b.add_extension(ECX509Extension::basic_constraints(false,None).unwrap());
+			}
+		});
+		path_expect(&certs,1,ECX509PathErrorKind::NotCa);
+	}
+
+	#[test]
+	fn test_path_critical_extension() {
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 0 {
+				b.add_extension(ECX509Extension::new("1.3.6.1.4.1.99999.1",true,&[0x05,0x00]));
+			}
+		});
+		path_expect(&certs,0,ECX509PathErrorKind::UnhandledCriticalExtension("1.3.6.1.4.1.99999.1".to_string()));
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 1 {
+				b.add_extension(ECX509Extension::new("1.3.6.1.4.1.99999.1",true,&[0x05,0x00]));
+			}
+		});
+		path_expect(&certs,1,ECX509PathErrorKind::UnhandledCriticalExtension("1.3.6.1.4.1.99999.1".to_string()));
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 0 {
+				b.add_extension(ECX509Extension::new("1.3.6.1.4.1.99999.1",false,&[0x05,0x00]));
+			}
+		});
+		let v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		assert!(path_validate(&v,&certs).is_ok());
+	}
+
+	#[test]
+	fn test_path_leaf_eku() {
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 0 {
+				b.add_extension(ECX509Extension::ext_key_usage(&[X509_EKU_CLIENT_AUTH_OID]).unwrap());
+			}
+		});
+		let mut v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		assert!(path_validate(&v,&certs).is_ok());
+		v.leaf_eku = Some(X509_EKU_SERVER_AUTH_OID.to_string());
+		let e :ECX509PathError = path_validate(&v,&certs).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::ExtKeyUsage(X509_EKU_SERVER_AUTH_OID.to_string()));
+		assert_eq!(e.depth,0);
+
+		let (certs,_) = path_chain(1,|d,b| {
+			if d == 0 {
+				b.add_extension(ECX509Extension::ext_key_usage(&[X509_EKU_CLIENT_AUTH_OID,X509_EKU_ANY_OID]).unwrap());
+			}
+		});
+		let mut v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		v.leaf_eku = Some(X509_EKU_SERVER_AUTH_OID.to_string());
+		assert!(path_validate(&v,&certs).is_ok());
+		/*no extKeyUsage is any usage*/
+		let (certs,_) = path_chain(1,|_,_| {});
+		let mut v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		v.leaf_eku = Some(X509_EKU_SERVER_AUTH_OID.to_string());
+		assert!(path_validate(&v,&certs).is_ok());
+	}
+
+	#[test]
+	fn test_path_no_issuer_bad_signature() {
+		let (certs,_) = path_chain(1,|_,_| {});
+		let (other,okeys) = path_chain(1,|_,_| {});
+		let v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		let at :DateTime<Utc> = path_time("2026-06-01T00:00:00Z");
+		/*no certificate names the issuer*/
+		let e :ECX509PathError = v.validate(&certs[0].der,&[other[0].der.clone()],&at).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::NoIssuer);
+		assert_eq!(e.depth,0);
+		/*same name and keyid but another key*/
+		let mut b :ECX509CertBuilder = ECX509CertBuilder::new(&certs[1].subject,&okeys[1].export_pubkey());
+		b.not_before = certs[1].not_before;
+		b.not_after = certs[1].not_after;
+		b.add_extension(ECX509Extension::basic_constraints(true,None).unwrap());
+		b.add_extension(ECX509Extension::subject_key_id(&certs[1].pubkey).unwrap());
+		b.issued_by(&certs[2]).unwrap();
+		let fake :ECX509Cert = b.sign(&okeys[2]).unwrap();
+		let e :ECX509PathError = v.validate(&certs[0].der,std::slice::from_ref(&fake.der),&at).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::BadSignature);
+		assert_eq!(e.depth,0);
+		/*the intermediate signed by a key other than the anchor*/
+		let e :ECX509PathError = v.validate(&other[0].der,&[other[1].der.clone()],&at).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::NoIssuer);
+		assert_eq!(e.depth,1);
+	}
+
+	#[test]
+	fn test_path_too_long() {
+		let (certs,_) = path_chain(2,|_,_| {});
+		let mut v :ECX509Validator = ECX509Validator::new(&certs[3..]);
+		v.max_intermediates = 2;
+		assert_eq!(path_validate(&v,&certs).unwrap().len(),4);
+		v.max_intermediates = 1;
+		let e :ECX509PathError = path_validate(&v,&certs).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::TooLong);
+		assert_eq!(e.depth,1);
+	}
+
+	#[test]
+	fn test_path_backtrack() {
+		/*two intermediates for the same name and key , the first expired*/
+		let mut inter :Option<ECX509CertBuilder> = None;
+		let (certs,keys) = path_chain(1,|d,b| {
+			if d == 1 {
+				inter = Some(b.clone());
+			}
+		});
+		let mut b :ECX509CertBuilder = inter.unwrap();
+		b.not_after = path_time("2026-05-01T00:00:00Z");
+		let expired :ECX509Cert = b.sign(&keys[2]).unwrap();
+		let v :ECX509Validator = ECX509Validator::new(&certs[2..]);
+		let at :DateTime<Utc> = path_time("2026-06-01T00:00:00Z");
+		let path :Vec<ECX509Cert> = v.validate(&certs[0].der,&[expired.der.clone(),certs[1].der.clone()],&at).unwrap();
+		assert_eq!(path[1].der,certs[1].der);
+		let e :ECX509PathError = v.validate(&certs[0].der,std::slice::from_ref(&expired.der),&at).err().unwrap();
+		assert_eq!(e.kind,ECX509PathErrorKind::Expired);
+		assert_eq!(e.depth,1);
+	}
+}